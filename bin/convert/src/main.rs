@@ -3,11 +3,17 @@
 //! Provides an interactive command-line interface for:
 //! - Type conversions (Path, Edge, Abstraction, Observation, Isomorphism)
 //! - Database queries (equity, distance, population, similarity, etc.)
+//!
+//! With arguments, runs that one command and exits, e.g.
+//! `convert chart --depth 3 --blueprint blueprint.bin`.
 
 #[tokio::main]
 async fn main() {
     let _telemetry = vitals::init();
     pokerkit::kys();
     pokerkit::brb();
-    portal::CLI::run().await;
+    match std::env::args().len() {
+        1 => portal::CLI::run().await,
+        _ => portal::CLI::once(std::env::args()).await,
+    }
 }
@@ -0,0 +1,132 @@
+//! Trains the learned frontier value model and benchmarks it against
+//! Monte Carlo rollouts.
+//!
+//! Training labels blueprint self-play frontier states with rollout
+//! payoffs, fits a `nlhe::LeafModel`, and reports held-out error:
+//!
+//! ```text
+//! leaf --samples 65536 --rollouts 16 --out leaf.json
+//! ```
+//!
+//! `--bench` compares the model with rollouts of several depths on fresh
+//! frontier states, scoring each evaluator by its mean squared error
+//! against a deep rollout reference and by its time per frontier:
+//!
+//! ```text
+//! leaf --bench --model leaf.json --states 512
+//! ```
+//!
+//! Load the result for play with `slumbot --frontier learned --leaf-model leaf.json`.
+
+use clap::Parser;
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// Rollout depths compared against the model in `--bench`.
+const DEPTHS: [usize; 4] = [1, 4, 16, 64];
+
+#[derive(Parser, Debug)]
+#[command(name = "leaf", about = "Learned frontier values for depth-limited search.")]
+struct Cli {
+    /// Abstraction version whose blueprint generates the self-play data.
+    #[arg(long, value_enum, default_value_t)]
+    version: pokerkit::Version,
+
+    /// Frontier states to label for training.
+    #[arg(long, default_value_t = 1 << 14)]
+    samples: usize,
+
+    /// Rollouts per continuation pair when labeling training states.
+    #[arg(long, default_value_t = 16)]
+    rollouts: usize,
+
+    /// Passes over the training set.
+    #[arg(long, default_value_t = 64)]
+    epochs: usize,
+
+    /// Adam step size.
+    #[arg(long, default_value_t = 1e-3)]
+    rate: f32,
+
+    /// Where to write the trained model.
+    #[arg(long, default_value = "leaf.json")]
+    out: PathBuf,
+
+    /// Benchmark an existing model instead of training one.
+    #[arg(long)]
+    bench: bool,
+
+    /// Model to benchmark.
+    #[arg(long, default_value = "leaf.json")]
+    model: PathBuf,
+
+    /// Frontier states scored in `--bench`.
+    #[arg(long, default_value_t = 256)]
+    states: usize,
+
+    /// Rollouts per continuation pair for the `--bench` reference values.
+    #[arg(long, default_value_t = 1024)]
+    reference: usize,
+}
+
+#[tokio::main(flavor = "multi_thread")]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+    let cli = Cli::parse();
+    pokerkit::init_version(cli.version);
+    let client = daybook::db().await;
+    let blueprint: nlhe::Flagship = daybook::Hydrate::hydrate(client).await;
+    if cli.bench { bench(&cli, &blueprint) } else { train(&cli, &blueprint) }
+}
+
+fn train(cli: &Cli, blueprint: &nlhe::Flagship) -> anyhow::Result<()> {
+    let start = Instant::now();
+    let mut samples = blueprint.leaf_samples(cli.samples, cli.rollouts);
+    eprintln!("labeled {} frontier states in {:.1?}", samples.len(), start.elapsed());
+    let holdout = samples.split_off(samples.len() - samples.len() / 10);
+    let mut model = nlhe::LeafModel::new(0);
+    for (epoch, loss) in model.fit(&samples, cli.epochs, cli.rate).into_iter().enumerate() {
+        eprintln!("epoch {:>4}  train mse {:>10.3}  holdout mse {:>10.3}", epoch + 1, loss, model.loss(&holdout));
+    }
+    model.save(&cli.out)?;
+    eprintln!("wrote {}", cli.out.display());
+    Ok(())
+}
+
+fn bench(cli: &Cli, blueprint: &nlhe::Flagship) -> anyhow::Result<()> {
+    let model = nlhe::LeafModel::load(&cli.model)?;
+    let states = blueprint
+        .frontier_states(cli.states)
+        .into_iter()
+        .map(|state| {
+            let truth = blueprint.rollout_payoffs(&state.prefix, &state.game, state.internal, cli.reference);
+            (state, truth)
+        })
+        .collect::<Vec<_>>();
+    let n = states.len().max(1) as f32;
+    println!("{:>12} {:>12} {:>12} {:>12}", "evaluator", "mse", "rmse", "µs/frontier");
+    for rollouts in DEPTHS {
+        let start = Instant::now();
+        let mse = states
+            .iter()
+            .map(|(state, truth)| {
+                blueprint
+                    .rollout_payoffs(&state.prefix, &state.game, state.internal, rollouts)
+                    .error(truth)
+            })
+            .sum::<f32>()
+            / n;
+        let micros = start.elapsed().as_micros() as f32 / n;
+        let label = format!("rollout×{rollouts}");
+        println!("{label:>12} {mse:>12.3} {:>12.3} {micros:>12.1}", mse.sqrt());
+    }
+    let start = Instant::now();
+    let mse = states
+        .iter()
+        .map(|(state, truth)| blueprint.leaf_payoffs(&model, state).error(truth))
+        .sum::<f32>()
+        / n;
+    let micros = start.elapsed().as_micros() as f32 / n;
+    println!("{:>12} {mse:>12.3} {:>12.3} {micros:>12.1}", "learned", mse.sqrt());
+    Ok(())
+}
@@ -2,6 +2,18 @@
 //!
 //! Parses CLI args, wires strategy/training APIs, runs the catalog,
 //! writes a markdown report.
+//!
+//! To compare abstraction versions, run once with `--save` under the
+//! baseline version and again with `--baseline` under the candidate:
+//!
+//! ```text
+//! litmus --version v3 --save v3.json
+//! litmus --version v5 --baseline v3.json --out v5.md
+//! ```
+//!
+//! With `--endgame river` (or `turn`) the blueprint is loaded into memory
+//! and decisions on those streets are answered by exact-card subgame
+//! solves, which is what the `river_blockers` category exercises.
 
 use clap::Parser;
 use std::path::PathBuf;
@@ -16,17 +28,39 @@ struct Cli {
     /// Where to write the markdown report. If omitted, prints to stdout.
     #[arg(long)]
     out: Option<PathBuf>,
+
+    /// Abstraction version whose tables are evaluated.
+    #[arg(long, value_enum, default_value_t)]
+    version: pokerkit::Version,
+
+    /// Write per-case statuses as JSON, for use as a later `--baseline`.
+    #[arg(long)]
+    save: Option<PathBuf>,
+
+    /// Per-case statuses from an earlier `--save`; appends a comparison.
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// Streets whose decisions are resolved on exact hole cards.
+    #[arg(long, value_enum, default_value_t)]
+    endgame: pokerkit::Endgame,
 }
 
 #[tokio::main(flavor = "multi_thread")]
 async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt::init();
     let cli = Cli::parse();
+    pokerkit::init_version(cli.version);
+    pokerkit::init_endgame(cli.endgame);
 
     let scenarios = litmus::load(&cli.scenarios)?;
     let client = daybook::db().await;
-    let backend =
-        portal::litmus::Backend::new(portal::StrategyAPI::new(client.clone()), portal::TrainingAPI::new(client));
+    let blueprint: Option<&'static nlhe::Flagship> = match cli.endgame {
+        pokerkit::Endgame::Abstract => None,
+        _ => Some(Box::leak(Box::new(daybook::Hydrate::hydrate(client.clone()).await))),
+    };
+    let strategy = portal::StrategyAPI::new(client.clone()).with_blueprint(blueprint);
+    let backend = portal::litmus::Backend::new(strategy, portal::TrainingAPI::new(client));
     let litmus = litmus::Litmus::new(backend);
 
     let outcomes = litmus.run(&scenarios).await?;
@@ -47,7 +81,15 @@ async fn main() -> anyhow::Result<()> {
     let api_label = format!("rbp-{} {}", pokerkit::regime(), pokerkit::version());
     let status = litmus.status().await.ok();
     let grid_usage = litmus.grid_usage().await.ok();
-    let report = litmus::render(&api_label, status.as_ref(), &scenarios, &outcomes, grid_usage.as_deref());
+    let mut report = litmus::render(&api_label, status.as_ref(), &scenarios, &outcomes, grid_usage.as_deref());
+    if let Some(path) = &cli.baseline {
+        let baseline = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        report.push_str(&litmus::versus(&path.display().to_string(), &baseline, &outcomes));
+    }
+    if let Some(path) = &cli.save {
+        std::fs::write(path, serde_json::to_string_pretty(&litmus::statuses(&outcomes))?)?;
+        eprintln!("saved {}", path.display());
+    }
 
     if let Some(path) = &cli.out {
         if let Some(parent) = path.parent() {
@@ -7,6 +7,25 @@
 //! Per-variant session override: append `*N` to any variant token to set its
 //! concurrent session count, overriding `--sessions` for that variant only.
 //! e.g. `--variants base*1,dirac*1,depth+dirac*4,depth+world*4`.
+//! Per-variant solve shape: `@WxL` before any `*N` sets the subgame world
+//! and frontier-leaf counts, so one run can sweep them side by side,
+//! e.g. `--variants depth+world@4x4*2,depth+world@8x2*2`.
+//!
+//! `--translation exact|epsilon-prune|epsilon-harmonic` leaves off-grid
+//! Slumbot raises off-tree, so subgame variants re-solve them as real edges.
+//!
+//! `--endgame abstract|river|turn` makes `world` variants resolve those
+//! streets on exact hole cards instead of abstraction buckets.
+//!
+//! `--frontier learned --leaf-model leaf.json` makes `depth` variants value
+//! their frontiers with a model trained by the `leaf` binary instead of
+//! biased rollouts.
+//!
+//! `--cache solutions.json` turns on the subgame solution cache, backed by
+//! a file loaded at startup and rewritten as it fills, so recurring spots
+//! keep their solves across runs. A file made under another abstraction
+//! version or blueprint is refused. Without `--cache`, every decision
+//! solves fresh.
 
 use clap::Parser;
 
@@ -23,13 +42,34 @@ struct Cli {
     throttle: usize,
     #[arg(long, default_value_t = 1)]
     sessions: usize,
+    #[arg(long, value_enum, default_value_t)]
+    translation: pokerkit::Translation,
+    #[arg(long, value_enum, default_value_t)]
+    endgame: pokerkit::Endgame,
+    #[arg(long, value_enum, default_value_t)]
+    frontier: pokerkit::Frontier,
+    #[arg(long)]
+    leaf_model: Option<std::path::PathBuf>,
+    #[arg(long)]
+    cache: Option<std::path::PathBuf>,
 }
 
 #[tokio::main]
 async fn main() {
     tracing_subscriber::fmt::init();
     let cli = Cli::parse();
+    pokerkit::init_translation(cli.translation);
+    pokerkit::init_endgame(cli.endgame);
+    pokerkit::init_frontier(cli.frontier);
+    match (cli.frontier, cli.leaf_model) {
+        (pokerkit::Frontier::Learned, Some(path)) => {
+            nlhe::init_leaf_model(nlhe::LeafModel::load(&path).expect("load leaf model"));
+        }
+        (pokerkit::Frontier::Learned, None) => panic!("--frontier learned requires --leaf-model"),
+        (pokerkit::Frontier::Rollout, _) => {}
+    }
     spar::Runtime::new(&cli.variants, cli.hands, cli.continuous, cli.throttle, cli.sessions)
+        .cache(cli.cache)
         .run()
         .await;
 }
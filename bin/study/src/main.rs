@@ -0,0 +1,63 @@
+//! Thin CLI wrapper around `study::Study`.
+//!
+//! Reads a spot.json (board, pot, stacks, ranges, bet sizes), runs
+//! range-vs-range CFR over exact combos, prints a summary and optionally
+//! writes the full per-combo solution as JSON:
+//!
+//! ```text
+//! study --spot bin/study/spot.json --iterations 500 --every 50 --out solution.json
+//! ```
+//!
+//! Flop spots enumerate every turn and river card, so they take far longer
+//! per iteration than turn or river spots.
+
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(name = "study", about = "Range-vs-range postflop solver.")]
+struct Cli {
+    /// Path to spot.json.
+    #[arg(long, default_value = "bin/study/spot.json")]
+    spot: PathBuf,
+
+    /// CFR iterations to run.
+    #[arg(long, default_value_t = 1000)]
+    iterations: usize,
+
+    /// Print exploitability every N iterations (0 disables).
+    #[arg(long, default_value_t = 0)]
+    every: usize,
+
+    /// Where to write the JSON solution. If omitted, only the summary prints.
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let spot: study::Spot = serde_json::from_str(&std::fs::read_to_string(&cli.spot)?)?;
+    let mut study = study::Study::try_from(spot)?;
+    let start = std::time::Instant::now();
+    while study.epoch() < cli.iterations {
+        study.step();
+        if cli.every > 0 && study.epoch().is_multiple_of(cli.every) {
+            let exploitability = study.exploitability();
+            let percent = 100. * exploitability / f32::from(study.spot().pot);
+            eprintln!(
+                "{:>6}  {:>8.3} chips  {:>6.2}% pot  {:>6.1}s",
+                study.epoch(),
+                exploitability,
+                percent,
+                start.elapsed().as_secs_f32()
+            );
+        }
+    }
+    let solution = study.solution();
+    println!("{solution}");
+    if let Some(path) = &cli.out {
+        std::fs::write(path, serde_json::to_string_pretty(&solution)?)?;
+        eprintln!("saved {}", path.display());
+    }
+    Ok(())
+}
@@ -4,6 +4,13 @@
 //!
 //! Mode (exactly one of):
 //!   --status, --fast, --slow, --cluster, --reset, --forget
+//!
+//! `--version` selects the abstraction version (table suffixes, and the
+//! river layer used by `--cluster`). Defaults to v3.
+//!
+//! `--artifacts <dir>` with `--cluster` also saves each clustered street's
+//! lookup, metric, and future files into `dir`, readable by
+//! `topology diagnose --artifacts` and file-based `compare`.
 
 use clap::ArgGroup;
 use clap::Parser;
@@ -24,6 +31,11 @@ struct Cli {
     reset: bool,
     #[arg(long)]
     forget: bool,
+    #[arg(long, value_enum, default_value_t)]
+    version: pokerkit::Version,
+    /// Directory to save clustered artifacts into.
+    #[arg(long, requires = "cluster")]
+    artifacts: Option<std::path::PathBuf>,
 }
 
 impl Cli {
@@ -33,7 +45,7 @@ impl Cli {
         } else if self.slow {
             forge::Mode::Slow
         } else if self.cluster {
-            forge::Mode::Cluster
+            forge::Mode::Cluster(self.artifacts.clone())
         } else if self.reset {
             forge::Mode::Reset
         } else if self.forget {
@@ -50,5 +62,6 @@ impl Cli {
 async fn main() {
     tracing_subscriber::fmt::init();
     let cli = Cli::parse();
+    pokerkit::init_version(cli.version);
     cli.mode().run().await;
 }
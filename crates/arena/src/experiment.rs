@@ -0,0 +1,230 @@
+//! Heads-up matches between in-process players, off the database.
+//!
+//! [`Match`] deals hands on a bare [`Game`], asks the seat to act through
+//! [`Player::decide`] and tells both seats what happened through
+//! [`Player::notify`]: [`Event::Waiting`] while the other seat is on the
+//! clock and [`Event::Concluded`] once the hand settles — the same events
+//! the live [`Engine`](parlor::Engine) sends. The button alternates and
+//! stacks reset every hand, so each hand is an independent sample of the
+//! hero's winnings.
+//!
+//! The experiments below pit the opponent-modeling
+//! [`Variant::Exploit`](pokerkit::Variant::Exploit) bot and its plain
+//! blueprint counterpart against [`Fish`] and the [`Biased`] bots, and
+//! check the exploiter wins without falling behind the blueprint. Against
+//! the blueprint itself, which it has no edge to find, it must not lose
+//! what its deviations give away. They need a hydrated
+//! blueprint (`DB_URL`), so they are `#[ignore]`d; run them with
+//! `cargo test -p arena --release -- --ignored --nocapture`. Stored hands
+//! can warm an exploiter first: feed each [`witness`](crate::witness) to
+//! it as [`Event::Concluded`].
+use kicker::*;
+use parlor::Event;
+use parlor::Player;
+use pokerkit::*;
+
+/// Two seats and the hands they've played. Seat 0 is the hero.
+pub struct Match {
+    seats: [Box<dyn Player>; N],
+    outcome: Outcome,
+}
+
+impl Match {
+    pub fn new(hero: Box<dyn Player>, villain: Box<dyn Player>) -> Self {
+        Self {
+            seats: [hero, villain],
+            outcome: Outcome::default(),
+        }
+    }
+
+    pub fn outcome(&self) -> &Outcome {
+        &self.outcome
+    }
+
+    /// Plays `hands` more hands and returns the running outcome.
+    pub async fn play(&mut self, hands: usize) -> &Outcome {
+        for _ in 0..hands {
+            let dealer = self.outcome.hands() % N;
+            let won = self.hand(dealer).await;
+            self.outcome.add(won);
+        }
+        &self.outcome
+    }
+
+    /// One hand from fresh stacks; the hero's net chips.
+    async fn hand(&mut self, dealer: Position) -> Chips {
+        let mut game = Game::from_start(dealer, [STACK; N]);
+        let mut actions = Vec::new();
+        loop {
+            match game.turn() {
+                Turn::Terminal => break,
+                Turn::Chance => {
+                    let draw = game.reveal();
+                    actions.push(draw);
+                    game = game.apply(draw);
+                }
+                Turn::Choice(pos) => {
+                    for other in (0..N).filter(|other| *other != pos) {
+                        let recall = Self::recall(&game, &actions, dealer, other);
+                        self.seats[other].notify(&Event::Waiting(recall)).await;
+                    }
+                    let recall = Self::recall(&game, &actions, dealer, pos);
+                    let action = self.seats[pos].decide(&recall).await;
+                    let action = if game.is_allowed(&action) { action } else { game.passive() };
+                    actions.push(action);
+                    game = game.apply(action);
+                }
+            }
+        }
+        for pos in 0..N {
+            let recall = Self::recall(&game, &actions, dealer, pos);
+            self.seats[pos].notify(&Event::Concluded(recall)).await;
+        }
+        game.settlements()[0].pnl().won()
+    }
+
+    /// What `pos` has seen: its own hole, the board so far and every
+    /// choice, replayed the way the engine builds a seat's recall.
+    fn recall(game: &Game, actions: &[Action], dealer: Position, pos: Position) -> Witness {
+        let cards = game.seats()[pos]
+            .cards()
+            .into_iter()
+            .chain(actions.iter().filter_map(Action::hand).flatten())
+            .collect::<Vec<_>>();
+        actions.iter().filter(|a| a.is_choice()).copied().fold(
+            Witness::initial_with(Turn::Choice(pos), Arrangement::from(cards), [STACK; N], dealer),
+            |recall, action| recall.push(action),
+        )
+    }
+}
+
+/// The hero's per-hand winnings, summarized.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Outcome {
+    hands: usize,
+    sum: i64,
+    squares: i64,
+}
+
+impl Outcome {
+    pub fn hands(&self) -> usize {
+        self.hands
+    }
+
+    /// Net chips over every hand.
+    pub fn net(&self) -> i64 {
+        self.sum
+    }
+
+    /// Mean winnings in big blinds per hundred hands.
+    pub fn bb100(&self) -> f64 {
+        self.sum as f64 / self.hands.max(1) as f64 / B_BLIND as f64 * 100.
+    }
+
+    /// Standard error of [`bb100`](Self::bb100).
+    pub fn stderr(&self) -> f64 {
+        let n = self.hands.max(2) as f64;
+        let mean = self.sum as f64 / n;
+        let variance = (self.squares as f64 / n - mean * mean).max(0.) * n / (n - 1.);
+        (variance / n).sqrt() / B_BLIND as f64 * 100.
+    }
+
+    fn add(&mut self, won: Chips) {
+        self.hands += 1;
+        self.sum += i64::from(won);
+        self.squares += i64::from(won) * i64::from(won);
+    }
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:+.1} ± {:.1} bb/100 over {} hands", self.bb100(), self.stderr(), self.hands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parlor::*;
+
+    const HANDS: usize = 2000;
+
+    /// The exploiter's solver without the opponent model.
+    const BLUEPRINT: Variant = Variant::Bot {
+        depth: true,
+        world: true,
+        dirac: false,
+    };
+
+    #[tokio::test]
+    async fn maniac_runs_over_nit() {
+        let mut arena = Match::new(Box::new(Biased::Maniac), Box::new(Biased::Nit));
+        let outcome = arena.play(200).await;
+        assert_eq!(outcome.hands(), 200);
+        assert!(outcome.net() > 0, "{outcome}");
+    }
+
+    #[tokio::test]
+    async fn fish_pays_itself() {
+        let outcome = *Match::new(Box::new(Fish), Box::new(Fish)).play(200).await;
+        assert_eq!(outcome.hands(), 200);
+        assert!(outcome.stderr() > 0.);
+    }
+
+    async fn model() -> &'static nlhe::Flagship {
+        hydrate_blueprint(daybook::db().await).await
+    }
+
+    /// Blueprint and exploiter, same solver, against the same villain:
+    /// the exploiter has to beat the villain, and by no less than the
+    /// blueprint does, give or take two standard errors.
+    async fn versus(villain: fn() -> Box<dyn Player>) {
+        let model = model().await;
+        let baseline = *Match::new(BLUEPRINT.into_player(Some(model)), villain())
+            .play(HANDS)
+            .await;
+        let exploit = *Match::new(Variant::Exploit.into_player(Some(model)), villain())
+            .play(HANDS)
+            .await;
+        let margin = 2. * baseline.stderr().hypot(exploit.stderr());
+        assert!(exploit.bb100() > 0., "exploit {exploit}");
+        assert!(exploit.bb100() > baseline.bb100() - margin, "exploit {exploit} trails blueprint {baseline}");
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a hydrated blueprint"]
+    async fn exploit_vs_fish() {
+        versus(|| Box::new(Fish)).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a hydrated blueprint"]
+    async fn exploit_vs_station() {
+        versus(|| Box::new(Biased::Station)).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a hydrated blueprint"]
+    async fn exploit_vs_nit() {
+        versus(|| Box::new(Biased::Nit)).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a hydrated blueprint"]
+    async fn exploit_vs_maniac() {
+        versus(|| Box::new(Biased::Maniac)).await;
+    }
+
+    /// Against the blueprint it would deviate from, the exploiter only
+    /// chases noise, so it must come out no worse than even, give or
+    /// take two standard errors.
+    #[tokio::test]
+    #[ignore = "needs a hydrated blueprint"]
+    async fn exploit_vs_blueprint() {
+        let model = model().await;
+        let outcome = *Match::new(Variant::Exploit.into_player(Some(model)), BLUEPRINT.into_player(Some(model)))
+            .play(HANDS)
+            .await;
+        assert!(outcome.bb100() > -2. * outcome.stderr(), "exploit loses to blueprint: {outcome}");
+    }
+}
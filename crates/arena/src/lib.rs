@@ -9,13 +9,16 @@
 //! - `metrics` — Aggregate statistics and derived poker metrics
 //! - `aivat` — AIVAT variance reduction estimator
 //! - `repository` — Bulk database queries for evaluation
+//! - `experiment` — Heads-up matches between in-process players (exploit vs fish / biased bots)
 mod aivat;
 mod correction;
+mod experiment;
 mod metrics;
 mod replay;
 mod repository;
 pub use aivat::*;
 pub use correction::*;
+pub use experiment::*;
 pub use metrics::*;
 pub use replay::*;
 pub use repository::*;
@@ -184,15 +184,7 @@ pub fn replay(
         .iter()
         .find(|p| p.seat() == seat)
         .ok_or_else(|| anyhow::anyhow!("seat {seat} not found"))?;
-    let reveals = plays_arrangement(participant.hole(), hand.board(), plays);
-    let witness = plays
-        .iter()
-        .filter(|p| !p.action().is_blind())
-        .filter(|p| !p.action().is_chance())
-        .try_fold(
-            Witness::initial_with(Turn::Choice(seat), reveals, stacks(participants)?, hand.dealer()),
-            |r, p| r.try_push(p.action()),
-        )?;
+    let witness = witness(hand, participants, plays, seat)?;
     let actions = witness
         .plays()
         .into_iter()
@@ -220,6 +212,29 @@ pub fn replay(
     })
 }
 
+/// A stored hand as `seat` saw it: own hole, the board as dealt and every
+/// choice. Fed to a bot as [`Event::Concluded`](parlor::Event::Concluded)
+/// to seed its opponent model from history.
+pub fn witness(
+    hand: &HandRecord,
+    participants: &[Participant],
+    plays: &[Play],
+    seat: Position,
+) -> anyhow::Result<Witness> {
+    let participant = participants
+        .iter()
+        .find(|p| p.seat() == seat)
+        .ok_or_else(|| anyhow::anyhow!("seat {seat} not found"))?;
+    let reveals = plays_arrangement(participant.hole(), hand.board(), plays);
+    plays
+        .iter()
+        .filter(|p| !p.action().is_blind())
+        .filter(|p| !p.action().is_chance())
+        .try_fold(Witness::initial_with(Turn::Choice(seat), reveals, stacks(participants)?, hand.dealer()), |r, p| {
+            r.try_push(p.action())
+        })
+}
+
 /// Build the stacks array from participants.
 pub fn stacks(participants: &[Participant]) -> anyhow::Result<[Chips; N]> {
     participants.iter().try_fold([0i16; N], |mut acc, p| {
@@ -0,0 +1,25 @@
+//! Generates `cactus.h` from the `extern "C"` surface into `OUT_DIR`.
+//!
+//! The checked-in `include/cactus.h` is only rewritten when
+//! `CACTUS_WRITE_HEADER` is set (`make header`); otherwise a stale copy
+//! is reported as a build warning.
+fn main() {
+    let dir = std::env::var("CARGO_MANIFEST_DIR").expect("cargo sets CARGO_MANIFEST_DIR");
+    let out = std::env::var("OUT_DIR").expect("cargo sets OUT_DIR");
+    let config = cbindgen::Config::from_file(format!("{dir}/cbindgen.toml")).expect("valid cbindgen.toml");
+    let generated = format!("{out}/cactus.h");
+    cbindgen::generate_with_config(&dir, config)
+        .expect("cbindgen parses the crate")
+        .write_to_file(&generated);
+    let checked = format!("{dir}/include/cactus.h");
+    let fresh = std::fs::read(&generated).expect("header was just written");
+    if std::env::var_os("CACTUS_WRITE_HEADER").is_some() {
+        std::fs::write(&checked, &fresh).expect("include/ is writable");
+    } else if std::fs::read(&checked).ok().as_deref() != Some(fresh.as_slice()) {
+        println!("cargo:warning=include/cactus.h is stale; regenerate it with `make header`");
+    }
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=include/cactus.h");
+    println!("cargo:rerun-if-env-changed=CACTUS_WRITE_HEADER");
+}
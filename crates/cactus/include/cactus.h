@@ -0,0 +1,142 @@
+/* Generated by cbindgen from crates/cactus. Do not edit. */
+
+#ifndef CACTUS_H
+#define CACTUS_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Result code returned by every exported function.
+ */
+typedef enum CactusStatus {
+  CACTUS_STATUS_OK = 0,
+  /**
+   * A required pointer argument was null.
+   */
+  CACTUS_STATUS_NULL_POINTER = 1,
+  /**
+   * Malformed text, wrong card count, or overlapping card sets.
+   */
+  CACTUS_STATUS_INVALID_ARGUMENT = 2,
+  /**
+   * The caller's output buffer cannot hold the result.
+   */
+  CACTUS_STATUS_BUFFER_TOO_SMALL = 3,
+} CactusStatus;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Parses a card such as `"As"` into its index `rank * 4 + suit`.
+ *
+ * # Safety
+ *
+ * `text` must be a valid NUL-terminated string; `out` must be valid for
+ * one `uint8_t` write.
+ */
+enum CactusStatus cactus_card_parse(const char *text, uint8_t *out);
+
+/**
+ * Parses whitespace-tolerant card text such as `"AsKd 7h"` into a
+ * bitmask. Duplicate cards are rejected.
+ *
+ * # Safety
+ *
+ * `text` must be a valid NUL-terminated string; `out` must be valid for
+ * one `uint64_t` write.
+ */
+enum CactusStatus cactus_hand_parse(const char *text, uint64_t *out);
+
+/**
+ * Formats a bitmask as concatenated cards (`"2c7hAs"`, low to high)
+ * into `buffer`, NUL-terminated. `capacity` must be at least
+ * `2 * popcount(hand) + 1`.
+ *
+ * # Safety
+ *
+ * `buffer` must be valid for `capacity` byte writes.
+ */
+enum CactusStatus cactus_hand_format(uint64_t hand, char *buffer, size_t capacity);
+
+/**
+ * Exact river equity of `hole` against every opponent holding on a
+ * five-card `board`, ties excluded.
+ *
+ * # Safety
+ *
+ * `out` must be valid for one `float` write.
+ */
+enum CactusStatus cactus_equity(uint64_t hole, uint64_t board, float *out);
+
+/**
+ * Equity of `hero` against one `villain` holding, ties counting half.
+ * Exact on the river; otherwise averaged over `trials` random runouts.
+ *
+ * # Safety
+ *
+ * `out` must be valid for one `float` write.
+ */
+enum CactusStatus cactus_equity_vs(uint64_t hero,
+                                   uint64_t villain,
+                                   uint64_t board,
+                                   size_t trials,
+                                   float *out);
+
+/**
+ * Equity of `hero` against a weighted list of `n` villain holdings.
+ * Holdings that collide with hero's cards or the board are skipped and
+ * the remaining weights renormalized; a null `weights` means uniform.
+ *
+ * # Safety
+ *
+ * `villains` must be valid for `n` reads, `weights` null or valid for
+ * `n` reads, and `out` valid for one `float` write.
+ */
+enum CactusStatus cactus_equity_vs_range(uint64_t hero,
+                                         uint64_t board,
+                                         const uint64_t *villains,
+                                         const float *weights,
+                                         size_t n,
+                                         size_t trials,
+                                         float *out);
+
+/**
+ * Evaluates a 5–7 card bitmask into a strength rank. Larger ranks win
+ * at showdown; equal ranks split.
+ *
+ * # Safety
+ *
+ * `out` must be valid for one `uint32_t` write.
+ */
+enum CactusStatus cactus_evaluate(uint64_t hand, uint32_t *out);
+
+/**
+ * Evaluates `n` bitmasks into `out[0..n]`. Stops at the first invalid
+ * hand and reports it; earlier entries are already written.
+ *
+ * # Safety
+ *
+ * `hands` must be valid for `n` reads and `out` for `n` writes.
+ */
+enum CactusStatus cactus_evaluate_batch(const uint64_t *hands, size_t n, uint32_t *out);
+
+/**
+ * Hand category of a strength rank: 0 = high card … 8 = straight flush.
+ *
+ * # Safety
+ *
+ * `out` must be valid for one `uint8_t` write.
+ */
+enum CactusStatus cactus_category(uint32_t rank, uint8_t *out);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* CACTUS_H */
@@ -0,0 +1,120 @@
+use super::CactusStatus;
+use super::emit;
+use deuce::Card;
+use deuce::Hand;
+use std::ffi::CStr;
+use std::ffi::c_char;
+
+/// Parses a card such as `"As"` into its index `rank * 4 + suit`.
+///
+/// # Safety
+///
+/// `text` must be a valid NUL-terminated string; `out` must be valid for
+/// one `uint8_t` write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cactus_card_parse(text: *const c_char, out: *mut u8) -> CactusStatus {
+    let card = unsafe { read(text) }.and_then(|s| Card::try_from(s).map_err(|_| CactusStatus::InvalidArgument));
+    unsafe { emit(out, card.map(u8::from)) }
+}
+
+/// Parses whitespace-tolerant card text such as `"AsKd 7h"` into a
+/// bitmask. Duplicate cards are rejected.
+///
+/// # Safety
+///
+/// `text` must be a valid NUL-terminated string; `out` must be valid for
+/// one `uint64_t` write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cactus_hand_parse(text: *const c_char, out: *mut u64) -> CactusStatus {
+    let hand = unsafe { read(text) }.and_then(|s| {
+        let cards = Card::parse(s).map_err(|_| CactusStatus::InvalidArgument)?;
+        let hand = Hand::from(cards.clone());
+        if hand.size() == cards.len() {
+            Ok(u64::from(hand))
+        } else {
+            Err(CactusStatus::InvalidArgument)
+        }
+    });
+    unsafe { emit(out, hand) }
+}
+
+/// Formats a bitmask as concatenated cards (`"2c7hAs"`, low to high)
+/// into `buffer`, NUL-terminated. `capacity` must be at least
+/// `2 * popcount(hand) + 1`.
+///
+/// # Safety
+///
+/// `buffer` must be valid for `capacity` byte writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cactus_hand_format(hand: u64, buffer: *mut c_char, capacity: usize) -> CactusStatus {
+    if buffer.is_null() {
+        return CactusStatus::NullPointer;
+    }
+    if hand & !Hand::mask() != 0 {
+        return CactusStatus::InvalidArgument;
+    }
+    let text = Hand::from(hand).to_string();
+    if text.len() >= capacity {
+        return CactusStatus::BufferTooSmall;
+    }
+    unsafe {
+        std::ptr::copy_nonoverlapping(text.as_ptr().cast::<c_char>(), buffer, text.len());
+        buffer.add(text.len()).write(0);
+    }
+    CactusStatus::Ok
+}
+
+/// Borrows a C string as UTF-8.
+///
+/// # Safety
+///
+/// `text` must be null or a valid NUL-terminated string.
+unsafe fn read<'a>(text: *const c_char) -> Result<&'a str, CactusStatus> {
+    if text.is_null() {
+        Err(CactusStatus::NullPointer)
+    } else {
+        unsafe { CStr::from_ptr(text) }
+            .to_str()
+            .map_err(|_| CactusStatus::InvalidArgument)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_format_round_trip() {
+        let mut hand = 0u64;
+        let mut text = [0 as c_char; 16];
+        unsafe {
+            assert_eq!(cactus_hand_parse(c"As Kd 7h".as_ptr(), &raw mut hand), CactusStatus::Ok);
+            assert_eq!(cactus_hand_format(hand, text.as_mut_ptr(), text.len()), CactusStatus::Ok);
+            assert_eq!(CStr::from_ptr(text.as_ptr()).to_str(), Ok("7hKdAs"));
+        }
+    }
+
+    #[test]
+    fn parse_card_index() {
+        let mut card = 0u8;
+        unsafe {
+            assert_eq!(cactus_card_parse(c"2c".as_ptr(), &raw mut card), CactusStatus::Ok);
+            assert_eq!(card, 0);
+            assert_eq!(cactus_card_parse(c"As".as_ptr(), &raw mut card), CactusStatus::Ok);
+            assert_eq!(card, 51);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        let mut hand = 0u64;
+        let mut tiny = [0 as c_char; 2];
+        unsafe {
+            assert_eq!(cactus_hand_parse(c"As As".as_ptr(), &raw mut hand), CactusStatus::InvalidArgument);
+            assert_eq!(cactus_hand_parse(c"Xx".as_ptr(), &raw mut hand), CactusStatus::InvalidArgument);
+            assert_eq!(cactus_hand_parse(std::ptr::null(), &raw mut hand), CactusStatus::NullPointer);
+            assert_eq!(cactus_hand_parse(c"As".as_ptr(), std::ptr::null_mut()), CactusStatus::NullPointer);
+            assert_eq!(cactus_hand_format(1, tiny.as_mut_ptr(), tiny.len()), CactusStatus::BufferTooSmall);
+        }
+    }
+}
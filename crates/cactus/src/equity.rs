@@ -0,0 +1,171 @@
+use super::CactusStatus;
+use super::emit;
+use deuce::Hand;
+use deuce::Observation;
+
+/// Exact river equity of `hole` against every opponent holding on a
+/// five-card `board`, ties excluded.
+///
+/// # Safety
+///
+/// `out` must be valid for one `float` write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cactus_equity(hole: u64, board: u64, out: *mut f32) -> CactusStatus {
+    let equity = observe(hole, board).and_then(|obs| match obs.public().size() {
+        5 => Ok(obs.equity()),
+        _ => Err(CactusStatus::InvalidArgument),
+    });
+    unsafe { emit(out, equity) }
+}
+
+/// Equity of `hero` against one `villain` holding, ties counting half.
+/// Exact on the river; otherwise averaged over `trials` random runouts.
+///
+/// # Safety
+///
+/// `out` must be valid for one `float` write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cactus_equity_vs(
+    hero: u64,
+    villain: u64,
+    board: u64,
+    trials: usize,
+    out: *mut f32,
+) -> CactusStatus {
+    let equity = observe(hero, board).and_then(|obs| versus(&obs, villain, trials));
+    unsafe { emit(out, equity) }
+}
+
+/// Equity of `hero` against a weighted list of `n` villain holdings.
+/// Holdings that collide with hero's cards or the board are skipped and
+/// the remaining weights renormalized; a null `weights` means uniform.
+///
+/// # Safety
+///
+/// `villains` must be valid for `n` reads, `weights` null or valid for
+/// `n` reads, and `out` valid for one `float` write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cactus_equity_vs_range(
+    hero: u64,
+    board: u64,
+    villains: *const u64,
+    weights: *const f32,
+    n: usize,
+    trials: usize,
+    out: *mut f32,
+) -> CactusStatus {
+    if villains.is_null() && n > 0 {
+        return CactusStatus::NullPointer;
+    }
+    let villains = match n {
+        0 => &[][..],
+        _ => unsafe { std::slice::from_raw_parts(villains, n) },
+    };
+    let weights = if weights.is_null() {
+        vec![1.; n]
+    } else {
+        unsafe { std::slice::from_raw_parts(weights, n) }.to_vec()
+    };
+    let equity = observe(hero, board).and_then(|obs| {
+        let dead = u64::from(Hand::from(obs));
+        let (sum, mass) = villains
+            .iter()
+            .zip(weights)
+            .filter(|(villain, weight)| *villain & dead == 0 && *weight > 0.)
+            .try_fold((0., 0.), |(sum, mass), (villain, weight)| {
+                versus(&obs, *villain, trials).map(|e| (sum + e * weight, mass + weight))
+            })?;
+        if mass > 0. { Ok(sum / mass) } else { Err(CactusStatus::InvalidArgument) }
+    });
+    unsafe { emit(out, equity) }
+}
+
+/// Validates a two-card hole against a 0, 3, 4, or 5 card board.
+fn observe(hole: u64, board: u64) -> Result<Observation, CactusStatus> {
+    let (pocket, public) = (Hand::from(hole), Hand::from(board));
+    let valid = (hole | board) & !Hand::mask() == 0
+        && hole & board == 0
+        && pocket.size() == 2
+        && matches!(public.size(), 0 | 3 | 4 | 5);
+    if valid {
+        Ok(Observation::from((pocket, public)))
+    } else {
+        Err(CactusStatus::InvalidArgument)
+    }
+}
+
+fn versus(obs: &Observation, villain: u64, trials: usize) -> Result<f32, CactusStatus> {
+    let dead = u64::from(Hand::from(*obs));
+    let hand = Hand::from(villain);
+    let valid = villain & !Hand::mask() == 0 && villain & dead == 0 && hand.size() == 2;
+    match (valid, obs.public().size(), trials) {
+        (false, _, _) => Err(CactusStatus::InvalidArgument),
+        (true, 5, _) => Ok(obs.equity_vs(hand, 1)),
+        (true, _, 0) => Err(CactusStatus::InvalidArgument),
+        (true, _, _) => Ok(obs.equity_vs(hand, trials)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand(s: &str) -> u64 {
+        u64::from(Hand::try_from(s).unwrap())
+    }
+
+    #[test]
+    fn river_equity_matches_observation() {
+        let mut out = 0.;
+        let obs = Observation::try_from("AsKs ~ QsJsTs2c3d").unwrap();
+        let status = unsafe { cactus_equity(hand("As Ks"), hand("Qs Js Ts 2c 3d"), &raw mut out) };
+        assert_eq!(status, CactusStatus::Ok);
+        assert_eq!(out, obs.equity());
+    }
+
+    #[test]
+    fn river_versus_is_exact() {
+        let mut out = 0.;
+        let board = hand("Ac Kd 7h 4s 2c");
+        unsafe {
+            assert_eq!(cactus_equity_vs(hand("Ah Ad"), hand("Kc Ks"), board, 0, &raw mut out), CactusStatus::Ok);
+            assert_eq!(out, 1.);
+            assert_eq!(cactus_equity_vs(hand("9h 8h"), hand("9d 8d"), board, 0, &raw mut out), CactusStatus::Ok);
+            assert_eq!(out, 0.5);
+        }
+    }
+
+    #[test]
+    fn range_skips_blocked_combos() {
+        let mut out = 0.;
+        let board = hand("Ac Kd 7h 4s 2c");
+        let villains = [hand("Ah As"), hand("Kc Ks"), hand("Qc Qs")];
+        let weights = [1., 1., 3.];
+        let status = unsafe {
+            cactus_equity_vs_range(
+                hand("Ad Ah"),
+                board,
+                villains.as_ptr(),
+                weights.as_ptr(),
+                villains.len(),
+                0,
+                &raw mut out,
+            )
+        };
+        assert_eq!(status, CactusStatus::Ok);
+        assert_eq!(out, 1.);
+    }
+
+    #[test]
+    fn rejects_bad_boards() {
+        let mut out = 0.;
+        unsafe {
+            assert_eq!(cactus_equity(hand("As Ks"), hand("Qs Js"), &raw mut out), CactusStatus::InvalidArgument);
+            assert_eq!(cactus_equity(hand("As Ks"), hand("As Js Ts"), &raw mut out), CactusStatus::InvalidArgument);
+            assert_eq!(
+                cactus_equity_vs(hand("As Ks"), hand("Ks Qd"), 0, 100, &raw mut out),
+                CactusStatus::InvalidArgument
+            );
+        }
+    }
+}
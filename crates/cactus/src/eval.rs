@@ -0,0 +1,112 @@
+use super::CactusStatus;
+use super::emit;
+use deuce::Hand;
+use deuce::Strength;
+
+/// Evaluates a 5–7 card bitmask into a strength rank. Larger ranks win
+/// at showdown; equal ranks split.
+///
+/// # Safety
+///
+/// `out` must be valid for one `uint32_t` write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cactus_evaluate(hand: u64, out: *mut u32) -> CactusStatus {
+    unsafe { emit(out, strength(hand).map(u32::from)) }
+}
+
+/// Evaluates `n` bitmasks into `out[0..n]`. Stops at the first invalid
+/// hand and reports it; earlier entries are already written.
+///
+/// # Safety
+///
+/// `hands` must be valid for `n` reads and `out` for `n` writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cactus_evaluate_batch(hands: *const u64, n: usize, out: *mut u32) -> CactusStatus {
+    if n == 0 {
+        return CactusStatus::Ok;
+    }
+    if hands.is_null() || out.is_null() {
+        return CactusStatus::NullPointer;
+    }
+    let hands = unsafe { std::slice::from_raw_parts(hands, n) };
+    let out = unsafe { std::slice::from_raw_parts_mut(out, n) };
+    for (hand, slot) in hands.iter().zip(out.iter_mut()) {
+        match strength(*hand) {
+            Ok(rank) => *slot = u32::from(rank),
+            Err(status) => return status,
+        }
+    }
+    CactusStatus::Ok
+}
+
+/// Hand category of a strength rank: 0 = high card … 8 = straight flush.
+///
+/// # Safety
+///
+/// `out` must be valid for one `uint8_t` write.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cactus_category(rank: u32, out: *mut u8) -> CactusStatus {
+    let category = (rank >> 21) as u8;
+    match category {
+        0..=8 => unsafe { emit(out, Ok(category)) },
+        _ => CactusStatus::InvalidArgument,
+    }
+}
+
+fn strength(hand: u64) -> Result<Strength, CactusStatus> {
+    match hand & !Hand::mask() {
+        0 => match Hand::from(hand).size() {
+            5..=7 => Ok(Strength::from(Hand::from(hand))),
+            _ => Err(CactusStatus::InvalidArgument),
+        },
+        _ => Err(CactusStatus::InvalidArgument),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rank(cards: &str) -> u32 {
+        let mut out = 0;
+        let hand = u64::from(Hand::try_from(cards).unwrap());
+        assert_eq!(unsafe { cactus_evaluate(hand, &raw mut out) }, CactusStatus::Ok);
+        out
+    }
+
+    #[test]
+    fn ranks_follow_showdown_order() {
+        assert!(rank("As Ks Qs Js Ts") > rank("Ac Ad Ah As Kc"));
+        assert!(rank("Ac Ad Ah As Kc") > rank("Ac Ad Ah Kc Kd"));
+        assert!(rank("Ac Ad 2h 7s 9c") > rank("Kc Kd 2h 7s 9c"));
+        assert_eq!(rank("Ac 2d 3h 4s 5c"), rank("Ad 2c 3s 4h 5d"));
+    }
+
+    #[test]
+    fn batch_matches_single() {
+        let hands = ["As Ks Qs Js Ts 2c", "2c 3d 4h 5s 7c 9d Jh", "Ac Ad Ah 2c 2d"]
+            .map(|s| u64::from(Hand::try_from(s).unwrap()));
+        let mut out = [0u32; 3];
+        assert_eq!(unsafe { cactus_evaluate_batch(hands.as_ptr(), hands.len(), out.as_mut_ptr()) }, CactusStatus::Ok);
+        for (hand, rank) in hands.iter().zip(out) {
+            assert_eq!(rank, u32::from(Strength::from(Hand::from(*hand))));
+        }
+    }
+
+    #[test]
+    fn categories() {
+        let mut category = 0u8;
+        assert_eq!(unsafe { cactus_category(rank("As Ks Qs Js Ts"), &raw mut category) }, CactusStatus::Ok);
+        assert_eq!(category, 8);
+        assert_eq!(unsafe { cactus_category(rank("2c 3d 4h 5s 7c 9d Jh"), &raw mut category) }, CactusStatus::Ok);
+        assert_eq!(category, 0);
+    }
+
+    #[test]
+    fn rejects_wrong_sizes() {
+        let mut out = 0;
+        let four = u64::from(Hand::try_from("As Ks Qs Js").unwrap());
+        assert_eq!(unsafe { cactus_evaluate(four, &raw mut out) }, CactusStatus::InvalidArgument);
+        assert_eq!(unsafe { cactus_evaluate(u64::MAX, &raw mut out) }, CactusStatus::InvalidArgument);
+    }
+}
@@ -0,0 +1,56 @@
+//! C ABI for the hand evaluator and equity calculator.
+//!
+//! Exposes [`deuce`]'s bitwise [`Evaluator`](deuce::Evaluator) and
+//! [`Observation`](deuce::Observation) equity to C and C++ callers as a
+//! `cdylib` / `staticlib` with a generated header (`include/cactus.h`).
+//!
+//! ## Conventions
+//!
+//! - Card sets cross the boundary as `uint64_t` bitmasks with the same
+//!   layout as [`Hand`](deuce::Hand): bit `rank * 4 + suit`, ranks `2..A`
+//!   as `0..12`, suits `c d h s` as `0..3`.
+//! - Every function returns a [`CactusStatus`] and writes its result
+//!   through an out-pointer; nothing panics across the boundary.
+//! - Strength ranks are `uint32_t`s whose integer order is showdown
+//!   order (see `From<Strength> for u32` in `deuce`).
+//!
+//! ## Testing
+//!
+//! `make -C crates/cactus test` builds the library, compiles
+//! `tests/smoke.c` against the header, and runs it.
+mod card;
+mod equity;
+mod eval;
+
+pub use card::*;
+pub use equity::*;
+pub use eval::*;
+
+/// Result code returned by every exported function.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CactusStatus {
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = 1,
+    /// Malformed text, wrong card count, or overlapping card sets.
+    InvalidArgument = 2,
+    /// The caller's output buffer cannot hold the result.
+    BufferTooSmall = 3,
+}
+
+/// Writes `value` through `out`, or reports a null out-pointer.
+///
+/// # Safety
+///
+/// `out` must be null or valid for a write of `T`.
+unsafe fn emit<T>(out: *mut T, value: Result<T, CactusStatus>) -> CactusStatus {
+    match (out.is_null(), value) {
+        (true, _) => CactusStatus::NullPointer,
+        (false, Err(status)) => status,
+        (false, Ok(value)) => {
+            unsafe { out.write(value) };
+            CactusStatus::Ok
+        }
+    }
+}
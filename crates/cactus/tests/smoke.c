@@ -0,0 +1,80 @@
+/* Exercises the C ABI end to end: parse, evaluate, format, equity.
+ * Every call runs outside assert() and only its stored result is
+ * checked, so the calls still happen if NDEBUG strips the checks. */
+#include <assert.h>
+#include <math.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "cactus.h"
+
+static uint64_t hand(const char *text) {
+  uint64_t out = 0;
+  CactusStatus status = cactus_hand_parse(text, &out);
+  assert(status == CACTUS_STATUS_OK);
+  (void)status;
+  return out;
+}
+
+static uint32_t rank(const char *text) {
+  uint32_t out = 0;
+  CactusStatus status = cactus_evaluate(hand(text), &out);
+  assert(status == CACTUS_STATUS_OK);
+  (void)status;
+  return out;
+}
+
+int main(void) {
+  CactusStatus status;
+
+  uint8_t card = 0;
+  status = cactus_card_parse("As", &card);
+  assert(status == CACTUS_STATUS_OK && card == 51);
+  status = cactus_card_parse("Zz", &card);
+  assert(status == CACTUS_STATUS_INVALID_ARGUMENT);
+
+  char text[16];
+  status = cactus_hand_format(hand("Kd As 7h"), text, sizeof text);
+  assert(status == CACTUS_STATUS_OK);
+  assert(strcmp(text, "7hKdAs") == 0);
+  status = cactus_hand_format(hand("Kd As 7h"), text, 4);
+  assert(status == CACTUS_STATUS_BUFFER_TOO_SMALL);
+
+  assert(rank("As Ks Qs Js Ts") > rank("Ac Ad Ah As Kc"));
+  assert(rank("Ac Ad 2h 7s 9c 3d") > rank("Kc Kd 2h 7s 9c 3d"));
+  assert(rank("Ac 2d 3h 4s 5c") == rank("Ad 2c 3s 4h 5d"));
+
+  uint8_t category = 0;
+  status = cactus_category(rank("As Ks Qs Js Ts"), &category);
+  assert(status == CACTUS_STATUS_OK && category == 8);
+
+  uint64_t batch[2] = {hand("2c 3d 4h 5s 7c 9d Jh"), hand("Ac Ad Ah 2c 2d")};
+  uint32_t ranks[2] = {0, 0};
+  status = cactus_evaluate_batch(batch, 2, ranks);
+  assert(status == CACTUS_STATUS_OK);
+  assert(ranks[0] < ranks[1]);
+
+  float equity = 0;
+  uint64_t board = hand("Ac Kd 7h 4s 2c");
+  status = cactus_equity(hand("Ah Ad"), board, &equity);
+  assert(status == CACTUS_STATUS_OK && equity > 0.95f);
+  status = cactus_equity_vs(hand("Ah Ad"), hand("Kc Ks"), board, 0, &equity);
+  assert(status == CACTUS_STATUS_OK && equity == 1.0f);
+  status = cactus_equity_vs(hand("Ah Ad"), hand("Kc Ks"), 0, 2000, &equity);
+  assert(status == CACTUS_STATUS_OK);
+  assert(fabsf(equity - 0.82f) < 0.05f);
+
+  uint64_t villains[2] = {hand("Ah Ac"), hand("Qc Qs")};
+  status = cactus_equity_vs_range(hand("Ad Ac"), hand("Kd 7h 4s 2c 3d"), villains, NULL, 2, 0, &equity);
+  assert(status == CACTUS_STATUS_OK);
+  assert(equity == 1.0f);
+
+  status = cactus_evaluate(hand("As Ks"), NULL);
+  assert(status == CACTUS_STATUS_NULL_POINTER);
+  status = cactus_equity(hand("As Ks"), hand("As Qs Js Ts 9s"), &equity);
+  assert(status == CACTUS_STATUS_INVALID_ARGUMENT);
+  (void)status;
+
+  puts("cactus: ok");
+  return 0;
+}
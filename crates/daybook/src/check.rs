@@ -92,8 +92,12 @@ impl Check for Client {
     }
 
     async fn clustered(&self, street: Street) -> bool {
-        let sql = format!("SELECT 1 FROM {t} WHERE obs = $1", t = isomorphism());
-        let obs = i64::from(Isomorphism::from(Observation::from(street)));
+        let (t, obs) = if pokerkit::version().recall() {
+            (recall(), i64::from(Waugh::from((street, 0))))
+        } else {
+            (isomorphism(), i64::from(Isomorphism::from(Observation::from(street))))
+        };
+        let sql = format!("SELECT 1 FROM {t} WHERE obs = $1");
         measure("check.clustered", self.query_opt(&sql, &[&obs]))
             .await
             .ok()
@@ -137,8 +137,15 @@ table!(users, "users", "Table for registered user accounts and identity.");
 // ── Versioned tables (abstraction-derived — depend on K-means params) ───────
 versioned!(abstraction, "abstraction", "Table for abstraction bucket definitions.");
 versioned!(isomorphism, "isomorphism", "Table for isomorphism → abstraction mappings.");
+versioned!(recall, "recall", "Table for perfect-recall index → abstraction mappings.");
 versioned!(street, "street", "Table for street-specific metadata.");
 versioned!(transitions, "transitions", "Table for abstraction transition probabilities.");
+versioned!(
+    centroid,
+    "centroid",
+    "Table for OCHS river bucket strengths, written when the river is \
+     clustered so later runs can give those buckets an equity."
+);
 versioned!(
     metric,
     "metric",
@@ -146,6 +153,12 @@ versioned!(
      between buckets is meaningless across abstractions with different K."
 );
 
+/// [`isomorphism`] as named under another version, for tooling that
+/// reads several abstraction families side by side.
+pub fn isomorphism_of(version: pokerkit::Version) -> String {
+    format!("isomorphism{}", version.clustering_suffix())
+}
+
 // ── Regime × Version tables (training-derived — depend on K-means × bet sizing)
 regime!(
     blueprint,
@@ -95,11 +95,11 @@ impl Schema for Abstraction {
             $$ BEGIN RETURN (SELECT COUNT(*) FROM {} e WHERE e.abs = xxx); END; $$
             LANGUAGE plpgsql;
             CREATE OR REPLACE FUNCTION get_street_abs(abs SMALLINT) RETURNS SMALLINT AS
-            $$ BEGIN RETURN ((abs >> 8) & 255)::SMALLINT; END; $$
+            $$ BEGIN RETURN ((abs >> 8) & 3)::SMALLINT; END; $$
             LANGUAGE plpgsql;
             CREATE OR REPLACE FUNCTION get_equity(parent SMALLINT) RETURNS REAL AS
             $$ BEGIN RETURN CASE WHEN get_street_abs(parent) = 3
-                THEN (parent & 255)::REAL / 100
+                THEN {}
                 ELSE (
                     SELECT COALESCE(SUM(t.dx * r.equity) / NULLIF(SUM(t.dx), 0), 0)
                     FROM {} t
@@ -109,6 +109,7 @@ impl Schema for Abstraction {
                 abstraction(),
                 abstraction(),
                 isomorphism(),
+                river_equity(),
                 transitions(),
                 abstraction()
             ))
@@ -154,6 +155,17 @@ impl Schema for Abstraction {
     }
 }
 
+/// SQL for a river bucket's equity inside `get_equity`: the ladder step
+/// `index / 100`, or under OCHS (`Version::V5`) whatever the row holds,
+/// since the trainer writes centroid strengths there after deriving.
+fn river_equity() -> String {
+    if pokerkit::version().ochs() {
+        format!("(SELECT a.equity FROM {} a WHERE a.abs = parent)", abstraction())
+    } else {
+        "(parent & 255)::REAL / 100".to_string()
+    }
+}
+
 impl Derive for Abstraction {
     fn exhaust() -> Vec<Self> {
         Street::all().iter().rev().copied().flat_map(Abstraction::all).collect()
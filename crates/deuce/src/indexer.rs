@@ -0,0 +1,289 @@
+use super::*;
+use std::sync::OnceLock;
+
+/// Dense suit-symmetric ranking of cards dealt in rounds (Waugh 2013).
+///
+/// A deal is a sequence of rounds, each an unordered set of cards. Two
+/// deals are equivalent when a suit relabeling maps every round of one onto
+/// the same round of the other. The indexer ranks those classes densely
+/// into `0..size()` and unranks them back, in time linear in the cards
+/// dealt, using only the handful of per-layout configurations as state.
+///
+/// - [`Waugh`] uses one round per street (pocket, flop, turn, river)
+/// - [`Isomorphism`] uses two rounds (pocket, merged board)
+///
+/// # Method
+///
+/// Every suit contributes one *shape*, its card count per round. The
+/// sorted shapes of the four suits form a *configuration*. A suit's rank
+/// sets are ranked round by round in colexicographic order among the ranks
+/// still unused in that suit. Suits sharing a shape are interchangeable,
+/// so their indices form a multiset. The dense index is the configuration
+/// offset plus the mixed-radix combination of those multiset ranks.
+pub(crate) struct Indexer {
+    configs: Vec<Config>,
+}
+
+/// Cards per round, either held in one suit or dealt in total.
+type Shape = [u8; 4];
+
+/// Per-round rank masks of one suit.
+type Masks = [u16; 4];
+
+/// One configuration: suit shapes sorted descending, with its dense range.
+struct Config {
+    shapes: [Shape; 4],
+    offset: u64,
+    size: u64,
+}
+
+#[cfg(not(feature = "shortdeck"))]
+const RANKS: u32 = 13;
+#[cfg(feature = "shortdeck")]
+const RANKS: u32 = 9;
+/// Ranks below the deck's lowest rank are shifted out of suit masks.
+const LOWEST: u32 = 13 - RANKS;
+
+impl Indexer {
+    /// One round per street dealt so far: pocket, flop, turn, river.
+    pub(crate) fn perfect(street: Street) -> &'static Self {
+        static INDEXERS: [OnceLock<Indexer>; 4] = [const { OnceLock::new() }; 4];
+        INDEXERS[street as usize].get_or_init(|| {
+            let mut rounds = [0; 4];
+            for (r, s) in Street::all().iter().enumerate().take(street as usize + 1) {
+                rounds[r] = s.n_revealed() as u8;
+            }
+            Self::new(rounds)
+        })
+    }
+    /// Two rounds: the pocket, then the whole board of the street.
+    pub(crate) fn merged(street: Street) -> &'static Self {
+        static INDEXERS: [OnceLock<Indexer>; 4] = [const { OnceLock::new() }; 4];
+        INDEXERS[street as usize].get_or_init(|| Self::new([2, street.n_board() as u8, 0, 0]))
+    }
+
+    /// Number of classes.
+    pub(crate) fn size(&self) -> u64 {
+        self.configs.last().map_or(0, |c| c.offset + c.size)
+    }
+
+    /// Ranks a deal given as one card set per round.
+    pub(crate) fn rank(&self, rounds: &[Hand; 4]) -> u64 {
+        let mut suits = Suit::all().map(|suit| rounds.map(|round| round.of(&suit).ranks() >> LOWEST));
+        suits.sort_unstable_by_key(|m| std::cmp::Reverse(Self::shape(m)));
+        let shapes = suits.map(|m| Self::shape(&m));
+        let config = &self.configs[self
+            .configs
+            .binary_search_by(|c| c.shapes.cmp(&shapes))
+            .expect("configuration of a legal deal")];
+        let mut index = 0;
+        let mut radix = 1;
+        let mut slot = 0;
+        for (shape, g) in Self::groups(&config.shapes) {
+            let mut xs = [0; 4];
+            for (x, masks) in xs.iter_mut().zip(&suits[slot..slot + g as usize]) {
+                *x = Self::encode(masks);
+            }
+            let xs = &mut xs[..g as usize];
+            xs.sort_unstable();
+            let rank = xs
+                .iter()
+                .enumerate()
+                .map(|(i, x)| choose(x + i as u64, i as u64 + 1))
+                .sum::<u64>();
+            index += rank * radix;
+            radix *= multichoose(Self::volume(&shape), g);
+            slot += g as usize;
+        }
+        config.offset + index
+    }
+
+    /// Unranks to a representative deal, one card set per round.
+    pub(crate) fn unrank(&self, index: u64) -> [Hand; 4] {
+        debug_assert!(index < self.size());
+        let config = &self.configs[self.configs.partition_point(|c| c.offset <= index) - 1];
+        let mut index = index - config.offset;
+        let mut suits = [[0u16; 4]; 4];
+        let mut slot = 0;
+        for (shape, g) in Self::groups(&config.shapes) {
+            let volume = Self::volume(&shape);
+            let radix = multichoose(volume, g);
+            let mut rank = index % radix;
+            index /= radix;
+            for i in (1..=g).rev() {
+                let y = Self::largest(i - 1, volume + g - 1, i, rank);
+                rank -= choose(y, i);
+                suits[slot + i as usize - 1] = Self::decode(y - (i - 1), &shape);
+            }
+            slot += g as usize;
+        }
+        std::array::from_fn(|r| {
+            Suit::all()
+                .into_iter()
+                .zip(suits)
+                .flat_map(|(suit, masks)| {
+                    (0..RANKS)
+                        .filter(move |bit| masks[r] & (1 << bit) != 0)
+                        .map(move |bit| Card::from((Rank::from((bit + LOWEST) as u8), suit)))
+                })
+                .collect::<Hand>()
+        })
+    }
+
+    fn new(rounds: Shape) -> Self {
+        let mut shapes = Vec::new();
+        Self::enumerate(0, rounds, [u8::MAX; 4], &mut [[0; 4]; 4], &mut shapes);
+        shapes.sort_unstable();
+        let mut offset = 0;
+        let configs = shapes
+            .into_iter()
+            .map(|shapes| {
+                let size = Self::groups(&shapes)
+                    .map(|(shape, g)| multichoose(Self::volume(&shape), g))
+                    .product::<u64>();
+                let config = Config { shapes, offset, size };
+                offset += size;
+                config
+            })
+            .collect();
+        Self { configs }
+    }
+
+    /// Shape of one suit's round masks.
+    fn shape(masks: &Masks) -> Shape {
+        masks.map(|mask| mask.count_ones() as u8)
+    }
+    /// Number of distinct rank assignments for one suit of this shape.
+    fn volume(shape: &Shape) -> u64 {
+        let mut used = 0;
+        let mut volume = 1;
+        for &n in shape {
+            volume *= choose(u64::from(RANKS - used), u64::from(n));
+            used += u32::from(n);
+        }
+        volume
+    }
+    /// Ranks one suit's round masks within its shape.
+    fn encode(masks: &Masks) -> u64 {
+        let mut used = 0u16;
+        let mut index = 0;
+        let mut radix = 1;
+        for &mask in masks {
+            let mut rank = 0;
+            let mut rest = mask;
+            let mut i = 0;
+            while rest != 0 {
+                let bit = rest.trailing_zeros();
+                i += 1;
+                rank += choose(u64::from((!used & ((1 << bit) - 1)).count_ones()), i);
+                rest &= rest - 1;
+            }
+            index += rank * radix;
+            radix *= choose(u64::from(RANKS - used.count_ones()), u64::from(mask.count_ones()));
+            used |= mask;
+        }
+        index
+    }
+    /// Inverts [`Self::encode`] for a suit of the given shape.
+    fn decode(mut index: u64, shape: &Shape) -> Masks {
+        let mut used = 0u16;
+        let mut masks = [0; 4];
+        for (mask, &n) in masks.iter_mut().zip(shape) {
+            let free = u64::from(RANKS - used.count_ones());
+            let radix = choose(free, u64::from(n));
+            let mut rank = index % radix;
+            index /= radix;
+            for i in (1..=u64::from(n)).rev() {
+                let pos = Self::largest(0, free - 1, i, rank);
+                rank -= choose(pos, i);
+                let bit = (0..RANKS)
+                    .filter(|bit| used & (1 << bit) == 0)
+                    .nth(pos as usize)
+                    .expect("free rank");
+                *mask |= 1 << bit;
+            }
+            used |= *mask;
+        }
+        masks
+    }
+    /// Largest `y` in `lo..=hi` with `choose(y, k) <= rank`, by bisection.
+    fn largest(mut lo: u64, mut hi: u64, k: u64, rank: u64) -> u64 {
+        while lo < hi {
+            let mid = lo + (hi - lo).div_ceil(2);
+            if choose(mid, k) <= rank { lo = mid } else { hi = mid - 1 }
+        }
+        lo
+    }
+
+    /// Recursively assigns non-increasing shapes to suits until the
+    /// round budget is spent exactly.
+    fn enumerate(slot: usize, budget: Shape, bound: Shape, current: &mut [Shape; 4], out: &mut Vec<[Shape; 4]>) {
+        if slot == 4 {
+            if budget == [0; 4] {
+                out.push(*current);
+            }
+            return;
+        }
+        for a in 0..=budget[0] {
+            for b in 0..=budget[1] {
+                for c in 0..=budget[2] {
+                    for d in 0..=budget[3] {
+                        let shape = [a, b, c, d];
+                        if shape <= bound {
+                            current[slot] = shape;
+                            let rest = [budget[0] - a, budget[1] - b, budget[2] - c, budget[3] - d];
+                            Self::enumerate(slot + 1, rest, shape, current, out);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    /// Runs of equal shapes as `(shape, count)`, in slot order.
+    fn groups(shapes: &[Shape; 4]) -> impl Iterator<Item = (Shape, u64)> + '_ {
+        shapes.chunk_by(|a, b| a == b).map(|run| (run[0], run.len() as u64))
+    }
+}
+
+/// Binomial coefficient, zero when `k > n`.
+fn choose(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+    (0..k.min(n - k)).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+/// Multisets of size `k` drawn from `n` items.
+fn multichoose(n: u64, k: u64) -> u64 {
+    choose(n + k - 1, k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_match_street() {
+        for street in Street::all() {
+            assert_eq!(Indexer::perfect(street).size(), street.n_recalls() as u64);
+            assert_eq!(Indexer::merged(street).size(), street.n_isomorphisms() as u64);
+        }
+    }
+    #[test]
+    fn binomials() {
+        assert_eq!(choose(13, 2), 78);
+        assert_eq!(choose(2, 3), 0);
+        assert_eq!(multichoose(13, 2), 91);
+        assert_eq!(Indexer::largest(0, 12, 2, 77), 12);
+        assert_eq!(Indexer::largest(0, 12, 2, 78), 12);
+        assert_eq!(Indexer::largest(0, 12, 2, 0), 1);
+    }
+    #[test]
+    fn suit_masks_round_trip() {
+        let shape = [2, 3, 1, 1];
+        for index in [0, 1, 777, Indexer::volume(&shape) - 1] {
+            let masks = Indexer::decode(index, &shape);
+            assert_eq!(Indexer::shape(&masks), shape);
+            assert_eq!(Indexer::encode(&masks), index);
+        }
+    }
+}
@@ -1,5 +1,8 @@
+use super::hand::Hand;
+use super::indexer::Indexer;
 use super::observation::Observation;
 use super::permutation::Permutation;
+use super::street::Street;
 use pokerkit::Arbitrary;
 
 /// A canonical representative of a suit-isomorphic observation class.
@@ -18,7 +21,9 @@ use pokerkit::Arbitrary;
 ///
 /// Unlike some approaches, we ignore which cards arrived on which street.
 /// This is a form of imperfect recall that trades some strategic precision
-/// for dramatic space savings.
+/// for dramatic space savings. [`Waugh`] is the perfect-recall alternative.
+///
+/// [`Waugh`]: super::waugh::Waugh
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Debug, PartialOrd, Ord)]
 pub struct Isomorphism(pub Observation);
 
@@ -62,6 +67,23 @@ impl Isomorphism {
     pub fn is_canonical(observation: &Observation) -> bool {
         Permutation::from(observation) == Permutation::identity()
     }
+    /// Dense index within `0..street.n_isomorphisms()`.
+    ///
+    /// Computed from the cards alone in O(cards), so a flat array indexed
+    /// this way replaces an ordered map keyed by isomorphism. Inverted by
+    /// `Isomorphism::from((street, index))`.
+    pub fn index(&self) -> usize {
+        let rounds = [*self.0.pocket(), *self.0.public(), Hand::empty(), Hand::empty()];
+        Indexer::merged(self.0.street()).rank(&rounds) as usize
+    }
+}
+
+/// Unranks a dense index of a street back to its canonical isomorphism.
+impl From<(Street, usize)> for Isomorphism {
+    fn from((street, index): (Street, usize)) -> Self {
+        let [pocket, public, ..] = Indexer::merged(street).unrank(index as u64);
+        Self::from(Observation::from((pocket, public)))
+    }
 }
 
 impl std::fmt::Display for Isomorphism {
@@ -72,11 +94,49 @@ impl std::fmt::Display for Isomorphism {
 
 #[cfg(test)]
 mod tests {
-    use super::super::hand::Hand;
-    use super::super::permutation::Permutation;
-    use super::super::street::Street;
+    use super::super::isomorphism_iter::IsomorphismIterator;
     use super::*;
 
+    #[test]
+    fn index_is_dense_on_preflop() {
+        let mut indices = IsomorphismIterator::from(Street::Pref)
+            .map(|iso| iso.index())
+            .collect::<Vec<usize>>();
+        indices.sort_unstable();
+        assert_eq!(indices, (0..Street::Pref.n_isomorphisms()).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn index_is_dense_on_flop() {
+        let mut seen = vec![false; Street::Flop.n_isomorphisms()];
+        for iso in IsomorphismIterator::from(Street::Flop) {
+            assert!(!std::mem::replace(&mut seen[iso.index()], true));
+        }
+        assert!(seen.into_iter().all(|x| x));
+    }
+
+    #[test]
+    fn index_round_trip() {
+        for _ in 0..1000 {
+            let iso = Isomorphism::random();
+            let street = iso.0.street();
+            assert!(iso.index() < street.n_isomorphisms());
+            assert_eq!(Isomorphism::from((street, iso.index())), iso);
+        }
+    }
+
+    #[test]
+    fn index_ignores_suit_labels() {
+        let observation = Observation::from(Street::Turn);
+        let index = Isomorphism::from(observation).index();
+        assert!(
+            Permutation::exhaust()
+                .iter()
+                .map(|p| p.permute(observation))
+                .all(|o| Isomorphism(o).index() == index)
+        );
+    }
+
     #[test]
     fn false_positives() {
         let observation = Observation::from(Street::Rive);
@@ -18,11 +18,17 @@
 //! - [`Strength`] — Evaluated hand ranking with deuce resolution
 //! - [`Ranking`] — Hand category (high card through straight flush)
 //!
+//! ## Equity
+//!
+//! - [`Range`] — A weighted set of holdings, parsed from `AKs, TT+, A5s-A2s`
+//! - [`Matchup`] — Range-vs-range and multiway equity, exact or Monte Carlo
+//!
 //! ## Abstraction
 //!
 //! - [`Observation`] — A strategically-equivalent game state (hole + board + street)
 //! - [`Isomorphism`] — Canonical representative under suit permutation
 //! - [`Permutation`] — Suit relabeling for equivalence class reduction
+//! - [`Waugh`] — Dense perfect-recall index that remembers which round dealt each card
 //!
 //! ## Street Progression
 //!
@@ -37,19 +43,23 @@ mod hand;
 mod hand_iter;
 mod hand_seq;
 mod hole;
+mod indexer;
 mod isomorphism;
 mod isomorphism_iter;
 mod kicks;
 mod lehmer;
+mod matchup;
 mod observation;
 mod observation_iter;
 mod observation_seq;
 mod permutation;
+mod range;
 mod rank;
 mod ranking;
 mod street;
 mod strength;
 mod suit;
+mod waugh;
 
 pub use board::*;
 pub use card::*;
@@ -64,12 +74,15 @@ pub use isomorphism::*;
 pub use isomorphism_iter::*;
 pub use kicks::*;
 pub use lehmer::*;
+pub use matchup::*;
 pub use observation::*;
 pub use observation_iter::*;
 pub use observation_seq::*;
 pub use permutation::*;
+pub use range::*;
 pub use rank::*;
 pub use ranking::*;
 pub use street::*;
 pub use strength::*;
 pub use suit::*;
+pub use waugh::*;
@@ -0,0 +1,388 @@
+use super::card::Card;
+use super::hand::Hand;
+use super::hand_iter::HandIterator;
+use super::hole::Hole;
+use super::range::Range;
+use super::strength::Strength;
+use pokerkit::Probability;
+
+/// Showdown equity of two or more [`Range`]s on a shared board.
+///
+/// Each player's holding is drawn from their range in proportion to its
+/// weight, conditioned on no card being dealt twice; the board is then
+/// completed uniformly from the remaining deck. Equity is the expected
+/// share of the pot at showdown, with ties split evenly.
+///
+/// # Methods
+///
+/// - [`Matchup::exact`] — enumerates every disjoint combination of holdings
+///   and every runout; exact, but exponential in players and streets
+/// - [`Matchup::simulate`] — Monte Carlo over sampled deals, reporting a
+///   standard error alongside each equity
+/// - [`Matchup::equity`] — picks `exact` when it [fits](Matchup::fits)
+///   under [`Matchup::EXACT_LIMIT`] and [`Matchup::DEAL_LIMIT`], `simulate`
+///   otherwise
+///
+/// Both methods spread their work across threads with the `server` feature.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matchup {
+    board: Hand,
+    ranges: Vec<Range>,
+}
+
+/// Per-player equities from a [`Matchup`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Estimate {
+    /// Expected pot share per player; sums to one.
+    pub equity: Vec<Probability>,
+    /// Standard error of each equity; zero when exact.
+    pub error: Vec<Probability>,
+    /// Showdowns evaluated: deals × runouts when exact, trials otherwise.
+    pub samples: usize,
+    pub exact: bool,
+}
+
+impl Matchup {
+    /// Most players a single deck can deal to with a full board.
+    pub const MAX_PLAYERS: usize = 9;
+    /// Largest [`Matchup::workload`] solved by enumeration.
+    pub const EXACT_LIMIT: usize = 1 << 24;
+    /// Most holding combinations [`Matchup::exact`] materializes.
+    pub const DEAL_LIMIT: usize = 1 << 16;
+    /// Default Monte Carlo trial count.
+    pub const TRIALS: usize = 1 << 18;
+    /// Trials per parallel work unit.
+    const CHUNK: usize = 1 << 12;
+    /// Rejected deals tolerated per trial before giving up on the ranges.
+    const PATIENCE: usize = 1 << 12;
+
+    /// Validates the board and drops holdings it blocks.
+    pub fn new(board: Hand, ranges: Vec<Range>) -> Result<Self, String> {
+        if !matches!(board.size(), 0 | 3 | 4 | 5) {
+            return Err(format!("board must have 0, 3, 4, or 5 cards: {board}"));
+        }
+        if !(2..=Self::MAX_PLAYERS).contains(&ranges.len()) {
+            return Err(format!("need 2 to {} ranges, got {}", Self::MAX_PLAYERS, ranges.len()));
+        }
+        let ranges = ranges.iter().map(|range| range.blocked(board)).collect::<Vec<_>>();
+        match ranges.iter().position(Range::is_empty) {
+            Some(i) => Err(format!("range {i} is empty once the board is removed")),
+            None => Ok(Self { board, ranges }),
+        }
+    }
+    pub fn board(&self) -> Hand {
+        self.board
+    }
+    pub fn ranges(&self) -> &[Range] {
+        &self.ranges
+    }
+    /// Upper bound on showdowns [`Matchup::exact`] would evaluate.
+    pub fn workload(&self) -> usize {
+        self.ranges
+            .iter()
+            .map(Range::len)
+            .fold(self.runouts(), usize::saturating_mul)
+    }
+    /// Upper bound on disjoint holding combinations.
+    pub fn combinations(&self) -> usize {
+        self.ranges.iter().map(Range::len).fold(1, usize::saturating_mul)
+    }
+    /// Whether [`Matchup::exact`] will accept this matchup.
+    pub fn fits(&self) -> bool {
+        self.workload() <= Self::EXACT_LIMIT && self.combinations() <= Self::DEAL_LIMIT
+    }
+    /// Board completions per deal.
+    fn runouts(&self) -> usize {
+        let dead = self.board.size() + 2 * self.ranges.len();
+        let live = Hand::from(Hand::mask()).size() - dead;
+        let need = self.need();
+        (0..need).fold(1, |n, i| n * (live - i) / (i + 1))
+    }
+    fn need(&self) -> usize {
+        5 - self.board.size()
+    }
+
+    /// Exact when cheap enough, otherwise `trials` Monte Carlo deals.
+    pub fn equity(&self, trials: usize) -> Result<Estimate, String> {
+        if self.fits() { self.exact() } else { self.simulate(trials) }
+    }
+
+    /// Enumerates every disjoint deal and every runout. Refuses matchups
+    /// that don't [fit](Matchup::fits).
+    pub fn exact(&self) -> Result<Estimate, String> {
+        if !self.fits() {
+            return Err(format!(
+                "too large to enumerate: {} deals, {} showdowns",
+                self.combinations(),
+                self.workload()
+            ));
+        }
+        let deals = self.deals();
+        #[cfg(feature = "server")]
+        let tally = {
+            use rayon::iter::IntoParallelRefIterator;
+            use rayon::iter::ParallelIterator;
+            deals
+                .par_iter()
+                .map(|(holes, weight)| self.enumerate(holes, *weight))
+                .reduce(|| Tally::new(self.ranges.len()), Tally::merge)
+        };
+        #[cfg(not(feature = "server"))]
+        let tally = deals
+            .iter()
+            .map(|(holes, weight)| self.enumerate(holes, *weight))
+            .fold(Tally::new(self.ranges.len()), Tally::merge);
+        tally.estimate(true)
+    }
+
+    /// Samples `trials` weighted deals and runouts.
+    pub fn simulate(&self, trials: usize) -> Result<Estimate, String> {
+        if trials == 0 {
+            return Err("simulation needs at least one trial".into());
+        }
+        let sampler = Sampler::from(self);
+        let chunks = trials.div_ceil(Self::CHUNK);
+        let sizes = (0..chunks).map(|i| Self::CHUNK.min(trials - i * Self::CHUNK));
+        #[cfg(feature = "server")]
+        let tally = {
+            use rayon::iter::IntoParallelIterator;
+            use rayon::iter::ParallelIterator;
+            sizes
+                .collect::<Vec<_>>()
+                .into_par_iter()
+                .map(|n| sampler.sample(self, n))
+                .reduce(|| Some(Tally::new(self.ranges.len())), Tally::merge_some)
+        };
+        #[cfg(not(feature = "server"))]
+        let tally = sizes
+            .map(|n| sampler.sample(self, n))
+            .fold(Some(Tally::new(self.ranges.len())), Tally::merge_some);
+        tally
+            .ok_or_else(|| "ranges almost never deal without card collisions".to_string())?
+            .estimate(false)
+    }
+
+    /// Every combination of pairwise-disjoint holdings with its joint weight.
+    fn deals(&self) -> Vec<(Vec<Hole>, f64)> {
+        let mut deals = Vec::new();
+        let mut stack = Vec::with_capacity(self.ranges.len());
+        self.descend(&mut stack, self.board, 1., &mut deals);
+        deals
+    }
+    fn descend(&self, stack: &mut Vec<Hole>, dead: Hand, weight: f64, deals: &mut Vec<(Vec<Hole>, f64)>) {
+        match self.ranges.get(stack.len()) {
+            None => deals.push((stack.clone(), weight)),
+            Some(range) => {
+                for (hole, w) in range.iter() {
+                    let hand = Hand::from(hole);
+                    if !Hand::overlaps(&hand, &dead) {
+                        stack.push(hole);
+                        self.descend(stack, Hand::add(dead, hand), weight * f64::from(w), deals);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+    }
+    /// Settles one deal against every completion of the board.
+    fn enumerate(&self, holes: &[Hole], weight: f64) -> Tally {
+        let mut tally = Tally::new(holes.len());
+        let dead = holes.iter().copied().map(Hand::from).fold(self.board, Hand::add);
+        match self.need() {
+            0 => tally.record(&self.settle(holes, self.board), weight),
+            n => HandIterator::from((n, dead))
+                .map(|runout| Hand::add(self.board, runout))
+                .for_each(|board| tally.record(&self.settle(holes, board), weight)),
+        }
+        tally
+    }
+    /// Pot shares on a complete board, ties split evenly.
+    fn settle(&self, holes: &[Hole], board: Hand) -> Vec<f64> {
+        let strengths = holes
+            .iter()
+            .map(|hole| Strength::from(Hand::add(Hand::from(*hole), board)))
+            .collect::<Vec<_>>();
+        let best = strengths.iter().max().expect("at least two players");
+        let winners = strengths.iter().filter(|s| *s == best).count();
+        strengths
+            .iter()
+            .map(|s| if s == best { 1. / winners as f64 } else { 0. })
+            .collect()
+    }
+}
+
+/// Weighted holding samplers, one per range.
+struct Sampler(Vec<(Vec<Hole>, rand::distr::weighted::WeightedIndex<Probability>)>);
+
+impl From<&Matchup> for Sampler {
+    fn from(matchup: &Matchup) -> Self {
+        Self(
+            matchup
+                .ranges
+                .iter()
+                .map(|range| {
+                    let (holes, weights): (Vec<Hole>, Vec<Probability>) = range.iter().unzip();
+                    let index = rand::distr::weighted::WeightedIndex::new(weights).expect("positive weights");
+                    (holes, index)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Sampler {
+    /// Runs `n` trials, or `None` if deals keep colliding.
+    fn sample(&self, matchup: &Matchup, n: usize) -> Option<Tally> {
+        use rand::distr::Distribution;
+        use rand::seq::IndexedRandom;
+        let ref mut rng = rand::rng();
+        let mut tally = Tally::new(self.0.len());
+        let mut holes = Vec::with_capacity(self.0.len());
+        for _ in 0..n {
+            let dead = (0..Matchup::PATIENCE).find_map(|_| {
+                holes.clear();
+                self.0.iter().try_fold(matchup.board, |dead, (range, index)| {
+                    let hole = range[index.sample(rng)];
+                    let hand = Hand::from(hole);
+                    holes.push(hole);
+                    (!Hand::overlaps(&hand, &dead)).then(|| Hand::add(dead, hand))
+                })
+            })?;
+            let deck = dead.complement().collect::<Vec<Card>>();
+            let runout = deck.choose_multiple(rng, matchup.need()).copied().collect::<Hand>();
+            tally.record(&matchup.settle(&holes, Hand::add(matchup.board, runout)), 1.);
+        }
+        Some(tally)
+    }
+}
+
+/// Weighted sums of pot shares, mergeable across threads.
+struct Tally {
+    sum: Vec<f64>,
+    sqr: Vec<f64>,
+    mass: f64,
+    count: usize,
+}
+
+impl Tally {
+    fn new(players: usize) -> Self {
+        Self {
+            sum: vec![0.; players],
+            sqr: vec![0.; players],
+            mass: 0.,
+            count: 0,
+        }
+    }
+    fn record(&mut self, shares: &[f64], weight: f64) {
+        for (i, share) in shares.iter().enumerate() {
+            self.sum[i] += weight * share;
+            self.sqr[i] += weight * share * share;
+        }
+        self.mass += weight;
+        self.count += 1;
+    }
+    fn merge(mut self, other: Self) -> Self {
+        for i in 0..self.sum.len() {
+            self.sum[i] += other.sum[i];
+            self.sqr[i] += other.sqr[i];
+        }
+        self.mass += other.mass;
+        self.count += other.count;
+        self
+    }
+    fn merge_some(a: Option<Self>, b: Option<Self>) -> Option<Self> {
+        Some(a?.merge(b?))
+    }
+    /// Means and, for sampled tallies, standard errors of the mean.
+    fn estimate(self, exact: bool) -> Result<Estimate, String> {
+        if self.mass <= 0. {
+            return Err("no deal gives every player disjoint cards".into());
+        }
+        let n = self.count as f64;
+        let equity = self.sum.iter().map(|s| s / self.mass).collect::<Vec<_>>();
+        let error = equity
+            .iter()
+            .zip(&self.sqr)
+            .map(|(mean, sqr)| if exact { 0. } else { ((sqr / self.mass - mean * mean).max(0.) / n).sqrt() })
+            .collect::<Vec<_>>();
+        Ok(Estimate {
+            equity: equity.into_iter().map(|e| e as Probability).collect(),
+            error: error.into_iter().map(|e| e as Probability).collect(),
+            samples: self.count,
+            exact,
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "shortdeck"))]
+mod tests {
+    use super::*;
+
+    fn matchup(board: &str, ranges: &[&str]) -> Matchup {
+        let board = Hand::try_from(board).unwrap();
+        let ranges = ranges.iter().map(|r| Range::try_from(*r).unwrap()).collect();
+        Matchup::new(board, ranges).unwrap()
+    }
+
+    #[test]
+    fn river_lock() {
+        let estimate = matchup("Ac Kd 7h 4s 2c", &["AhAd", "KcKs"]).exact().unwrap();
+        assert_eq!(estimate.equity, vec![1., 0.]);
+        assert_eq!(estimate.samples, 1);
+    }
+    #[test]
+    fn river_chop() {
+        let estimate = matchup("As Ks Qs Js Ts", &["AA", "KK", "22"]).exact().unwrap();
+        assert_eq!(estimate.equity.len(), 3);
+        assert!(estimate.equity.iter().all(|e| (e - 1. / 3.).abs() < 1e-6));
+    }
+    #[test]
+    fn turn_counts_outs() {
+        let estimate = matchup("Kd 7h 4s 2c", &["AhAd", "KcKs"]).exact().unwrap();
+        assert_eq!(estimate.samples, 44);
+        assert!((estimate.equity[0] - 2. / 44.).abs() < 1e-6);
+    }
+    #[test]
+    fn equities_sum_to_one() {
+        let estimate = matchup("Qh 8d 3c", &["TT+, AKs", "A5s-A2s, KQo", "87s"])
+            .equity(1 << 12)
+            .unwrap();
+        let total = estimate.equity.iter().sum::<Probability>();
+        assert!((total - 1.).abs() < 1e-4, "{total}");
+    }
+    #[test]
+    fn simulate_agrees_with_exact() {
+        let matchup = matchup("Qh 8d 3c", &["AA", "KQs"]);
+        let exact = matchup.exact().unwrap();
+        let guess = matchup.simulate(1 << 16).unwrap();
+        assert!(exact.exact && !guess.exact);
+        assert_eq!(guess.samples, 1 << 16);
+        for i in 0..2 {
+            assert!(guess.error[i] > 0.);
+            assert!((guess.equity[i] - exact.equity[i]).abs() < 5. * guess.error[i]);
+        }
+    }
+    #[test]
+    fn preflop_aces_vs_kings() {
+        let estimate = matchup("", &["AA", "KK"]).equity(1 << 16).unwrap();
+        assert!(!estimate.exact);
+        assert!((estimate.equity[0] - 0.82).abs() < 0.01, "{estimate:?}");
+    }
+    #[test]
+    fn blocked_ranges_fail() {
+        let board = Hand::try_from("As Ah Ad").unwrap();
+        let aces = vec![Range::try_from("AA").unwrap(), Range::full()];
+        assert!(Matchup::new(board, aces).is_err());
+        let impossible = matchup("", &["AsAh", "AsAd"]);
+        assert!(impossible.exact().is_err());
+        assert!(impossible.simulate(16).is_err());
+    }
+    #[test]
+    fn oversized_exact_fails() {
+        let wide = matchup("Ac Kd 7h 4s 2c", &["22+, A2+, K2+", "22+, A2+, K2+"]);
+        assert!(!wide.fits());
+        assert!(wide.exact().is_err());
+        assert!(!wide.equity(1 << 10).unwrap().exact);
+    }
+}
@@ -12,7 +12,7 @@ use std::cmp::Ordering;
 /// # Operations
 ///
 /// - [`Observation::children`] — Iterate over all possible next-street continuations
-/// - [`Observation::simulate`] — Compute showdown win rate against random hands
+/// - [`Observation::simulate`] — Estimate showdown equity against random hands on any street
 /// - [`Observation::equity`] — Compute showdown win rate against exhaustive opponent hands
 /// - [`Observation::street`] — Infer the current street from card counts
 ///
@@ -60,9 +60,17 @@ impl Observation {
             _ => won as Probability / sum as Probability,
         }
     }
-    /// Monte Carlo equity estimation (not yet implemented).
-    pub fn simulate(&self, _: usize) -> Probability {
-        todo!("run out some number of simulations and take equity as average")
+    /// Monte Carlo equity against one uniformly random opponent.
+    ///
+    /// Unlike [`Observation::equity`], valid on any street and counts ties
+    /// as half a win. Panics if `trials` is zero.
+    pub fn simulate(&self, trials: usize) -> Probability {
+        assert!(trials > 0, "simulation needs at least one trial");
+        let hero = Range::from(Hole::from(*self));
+        Matchup::new(self.public, vec![hero, Range::full()])
+            .and_then(|matchup| matchup.simulate(trials))
+            .map(|estimate| estimate.equity[0])
+            .expect("one hole deals against the full range on any board")
     }
     /// Equity of `self` vs a specific villain pocket on the shared board.
     ///
@@ -0,0 +1,482 @@
+use super::card::Card;
+use super::hand::Hand;
+use super::hand_iter::HandIterator;
+use super::hole::Hole;
+use super::rank::Rank;
+use super::suit::Suit;
+use pokerkit::Probability;
+use std::collections::BTreeMap;
+
+/// A weighted set of [`Hole`]s: one player's distribution over holdings.
+///
+/// Weights are relative, not normalized — a range of `{AA: 1, KK: 1}` and
+/// `{AA: 2, KK: 2}` describe the same distribution. Holdings with zero
+/// weight are never stored.
+///
+/// # Notation
+///
+/// Parses the comma-separated shorthand used by every equity calculator:
+///
+/// - `AA`, `AKs`, `AKo`, `AK` — a pair, suited, offsuit, or all 16 combos
+/// - `TT+`, `ATs+` — a pair and every higher pair, or a kicker climbing to
+///   one below the top card
+/// - `99-66`, `A5s-A2s` — an inclusive span with a fixed top card
+/// - `AsKd` — a single explicit combination
+/// - `AKs:0.5`, `TT+:0.25` — any of the above at a non-unit weight
+///
+/// Later tokens override earlier ones, so `22+:0.5, AA` plays every pair
+/// at half weight except aces.
+///
+/// # Formatting
+///
+/// [`Range::notation`] (and `Display`) collapse the 1326 combos back onto
+/// the 169 starting-hand cells: a cell whose live combos all share one
+/// weight prints as `AKs`, adjacent equal cells merge into `+` and `-`
+/// spans, and anything left over prints combo by combo. Parsing the
+/// output reproduces the range exactly.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Range(BTreeMap<Hole, Probability>);
+
+impl Range {
+    /// The range containing no holdings.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+    /// Every two-card holding with equal weight.
+    pub fn full() -> Self {
+        HandIterator::from((2, Hand::empty())).map(Hole::from).collect()
+    }
+    /// The 169 starting-hand cells in chart order: a 13×13 grid from aces
+    /// down, pairs on the diagonal, suited above it and offsuit below.
+    pub fn grid() -> impl Iterator<Item = String> {
+        Self::descending(Rank::Two).flat_map(|row| {
+            Self::descending(Rank::Two).map(move |col| match row.cmp(&col) {
+                std::cmp::Ordering::Equal => format!("{row}{col}"),
+                std::cmp::Ordering::Greater => format!("{row}{col}s"),
+                std::cmp::Ordering::Less => format!("{col}{row}o"),
+            })
+        })
+    }
+    /// Sets the weight of one holding, removing it if non-positive.
+    pub fn insert(&mut self, hole: Hole, weight: Probability) {
+        if weight > 0. {
+            self.0.insert(hole, weight);
+        } else {
+            self.0.remove(&hole);
+        }
+    }
+    /// Relative weight of a holding, zero if absent.
+    pub fn weight(&self, hole: &Hole) -> Probability {
+        self.0.get(hole).copied().unwrap_or_default()
+    }
+    /// Number of holdings with positive weight.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Sum of all weights.
+    pub fn mass(&self) -> Probability {
+        self.0.values().sum()
+    }
+    /// Holdings and their weights in canonical order.
+    pub fn iter(&self) -> impl Iterator<Item = (Hole, Probability)> + '_ {
+        self.0.iter().map(|(hole, weight)| (*hole, *weight))
+    }
+    /// Drops every holding that shares a card with `dead`, e.g. the board.
+    pub fn blocked(&self, dead: Hand) -> Self {
+        self.iter()
+            .filter(|(hole, _)| !Hand::overlaps(&Hand::from(*hole), &dead))
+            .collect()
+    }
+    /// Keeps only holdings also present in `other`, at this range's weight.
+    pub fn within(&self, other: &Self) -> Self {
+        self.iter().filter(|(hole, _)| other.0.contains_key(hole)).collect()
+    }
+    /// Rescales so the heaviest holding has weight one.
+    pub fn scaled(&self) -> Self {
+        let max = self.0.values().copied().fold(0., Probability::max);
+        self.iter().map(|(hole, weight)| (hole, weight / max)).collect()
+    }
+    /// Rounds every weight to the nearest multiple of `1 / levels`,
+    /// dropping holdings that round to zero. Posteriors rarely share
+    /// exact weights within a cell; quantizing first lets
+    /// [`Range::notation`] collapse them.
+    pub fn quantized(&self, levels: u16) -> Self {
+        let n = Probability::from(levels);
+        self.iter()
+            .map(|(hole, weight)| (hole, (weight * n).round() / n))
+            .collect()
+    }
+
+    /// Canonical 169-cell notation, treating holdings blocked by `dead`
+    /// as present so that board cards don't break up their cells.
+    pub fn notation(&self, dead: Hand) -> String {
+        let mut tokens = Vec::new();
+        let mut loose = Vec::new();
+        let pairs = Self::descending(Rank::Two)
+            .map(|r| (r, self.uniform(Self::combos(r, r, None), dead, &mut loose)))
+            .collect::<Vec<_>>();
+        for (top, bottom, weight) in Self::runs(&pairs) {
+            let token = match (top == Rank::Ace, top == bottom) {
+                (_, true) => format!("{top}{top}"),
+                (true, false) => format!("{bottom}{bottom}+"),
+                (false, false) => format!("{top}{top}-{bottom}{bottom}"),
+            };
+            tokens.push(Self::weighted(token, weight));
+        }
+        for hi in Self::descending(Rank::Three) {
+            let mut any = Vec::new();
+            let mut suited = Vec::new();
+            let mut offsuit = Vec::new();
+            for lo in Self::descending(Rank::Two).filter(|lo| *lo < hi) {
+                let mut stray = Vec::new();
+                let s = self.uniform(Self::combos(hi, lo, Some(true)), dead, &mut stray);
+                let o = self.uniform(Self::combos(hi, lo, Some(false)), dead, &mut stray);
+                if s.is_some() && s == o {
+                    any.push((lo, s));
+                    suited.push((lo, None));
+                    offsuit.push((lo, None));
+                } else {
+                    any.push((lo, None));
+                    suited.push((lo, s));
+                    offsuit.push((lo, o));
+                }
+                loose.extend(stray);
+            }
+            for (cells, suffix) in [(any, ""), (suited, "s"), (offsuit, "o")] {
+                for (top, bottom, weight) in Self::runs(&cells) {
+                    let token = match (u8::from(top) + 1 == u8::from(hi), top == bottom) {
+                        (_, true) => format!("{hi}{top}{suffix}"),
+                        (true, false) => format!("{hi}{bottom}{suffix}+"),
+                        (false, false) => format!("{hi}{top}{suffix}-{hi}{bottom}{suffix}"),
+                    };
+                    tokens.push(Self::weighted(token, weight));
+                }
+            }
+        }
+        loose.sort();
+        loose.dedup();
+        tokens.extend(
+            loose
+                .into_iter()
+                .map(|hole| Self::weighted(Self::combo(hole), self.weight(&hole))),
+        );
+        tokens.join(", ")
+    }
+    /// The shared weight of a cell whose live combos are all present and
+    /// equal. Otherwise `None`, with its present combos pushed to `loose`.
+    fn uniform(&self, combos: impl Iterator<Item = Hole>, dead: Hand, loose: &mut Vec<Hole>) -> Option<Probability> {
+        let live = combos
+            .filter(|hole| !Hand::overlaps(&Hand::from(*hole), &dead))
+            .collect::<Vec<_>>();
+        let first = live.first().map(|hole| self.weight(hole)).filter(|w| *w > 0.);
+        match first {
+            Some(w) if live.iter().all(|hole| self.weight(hole) == w) => Some(w),
+            _ => {
+                loose.extend(live.into_iter().filter(|hole| self.0.contains_key(hole)));
+                None
+            }
+        }
+    }
+    /// Maximal spans of adjacent cells sharing a weight, as (top, bottom, weight).
+    fn runs(cells: &[(Rank, Option<Probability>)]) -> Vec<(Rank, Rank, Probability)> {
+        let mut runs: Vec<(Rank, Rank, Probability)> = Vec::new();
+        let mut prev = None;
+        for (rank, weight) in cells {
+            match (runs.last_mut(), weight) {
+                (Some(run), Some(w)) if prev == Some(*w) => run.1 = *rank,
+                (_, Some(w)) => runs.push((*rank, *rank, *w)),
+                (_, None) => {}
+            }
+            prev = *weight;
+        }
+        runs
+    }
+    fn descending(floor: Rank) -> impl Iterator<Item = Rank> {
+        Self::ranks(floor, Rank::Ace).rev()
+    }
+    /// Explicit combo, high card first: `AsKd`.
+    fn combo(hole: Hole) -> String {
+        Vec::<Card>::from(Hand::from(hole))
+            .iter()
+            .rev()
+            .map(Card::to_string)
+            .collect()
+    }
+    fn weighted(token: String, weight: Probability) -> String {
+        if weight == 1. { token } else { format!("{token}:{weight}") }
+    }
+
+    /// Expands one comma-separated token of range notation.
+    fn token(token: &str) -> Result<(Vec<Hole>, Probability), String> {
+        if !token.is_ascii() {
+            return Err(format!("invalid range token {token}"));
+        }
+        let (token, weight) = match token.trim().split_once(':') {
+            None => (token.trim(), 1.),
+            Some((token, weight)) => (token.trim(), Self::parse_weight(weight.trim())?),
+        };
+        let holes = if let Some((hi, lo)) = token.split_once('-') {
+            Self::span(hi.trim(), lo.trim())?
+        } else if let Some(cell) = token.strip_suffix('+') {
+            Self::plus(cell.trim())?
+        } else if token.len() == 4 {
+            vec![Hole::try_from(token)?]
+        } else {
+            let (hi, lo, suited) = Self::cell(token)?;
+            Self::combos(hi, lo, suited).collect()
+        };
+        Ok((holes, weight))
+    }
+    fn parse_weight(s: &str) -> Result<Probability, String> {
+        match s.parse::<Probability>() {
+            Ok(w) if w.is_finite() && w >= 0. => Ok(w),
+            _ => Err(format!("invalid weight {s}")),
+        }
+    }
+    /// `TT+` climbs the pair; `ATs+` climbs the kicker below the top card.
+    fn plus(cell: &str) -> Result<Vec<Hole>, String> {
+        let (hi, lo, suited) = Self::cell(cell)?;
+        if hi == lo {
+            Ok(Self::ranks(lo, Rank::Ace)
+                .flat_map(|r| Self::combos(r, r, None))
+                .collect())
+        } else {
+            let top = Rank::from(u8::from(hi) - 1);
+            Ok(Self::ranks(lo, top).flat_map(|r| Self::combos(hi, r, suited)).collect())
+        }
+    }
+    /// `99-66` spans pairs; `A5s-A2s` spans kickers under a shared top card.
+    fn span(a: &str, b: &str) -> Result<Vec<Hole>, String> {
+        let (a_hi, a_lo, a_suited) = Self::cell(a)?;
+        let (b_hi, b_lo, b_suited) = Self::cell(b)?;
+        let pairs = a_hi == a_lo && b_hi == b_lo;
+        if pairs {
+            let (lo, hi) = (a_lo.min(b_lo), a_lo.max(b_lo));
+            Ok(Self::ranks(lo, hi).flat_map(|r| Self::combos(r, r, None)).collect())
+        } else if a_hi == b_hi && a_suited == b_suited && a_hi != a_lo && b_hi != b_lo {
+            let (lo, hi) = (a_lo.min(b_lo), a_lo.max(b_lo));
+            Ok(Self::ranks(lo, hi)
+                .flat_map(|r| Self::combos(a_hi, r, a_suited))
+                .collect())
+        } else {
+            Err(format!("invalid span {a}-{b}"))
+        }
+    }
+    /// Parses `AA`, `AKs`, `AKo`, `AK` into (high, low, suitedness).
+    fn cell(cell: &str) -> Result<(Rank, Rank, Option<bool>), String> {
+        let (ranks, suited) = match cell.len() {
+            2 => (cell, None),
+            3 if cell.ends_with('s') => (&cell[..2], Some(true)),
+            3 if cell.ends_with('o') => (&cell[..2], Some(false)),
+            _ => return Err(format!("invalid range token {cell}")),
+        };
+        let a = Rank::try_from(&ranks[0..1])?;
+        let b = Rank::try_from(&ranks[1..2])?;
+        match (a == b, suited) {
+            (true, Some(_)) => Err(format!("pairs cannot be suited or offsuit: {cell}")),
+            _ => Ok((a.max(b), a.min(b), suited)),
+        }
+    }
+    /// Inclusive rank span, low to high.
+    fn ranks(lo: Rank, hi: Rank) -> impl DoubleEndedIterator<Item = Rank> {
+        (u8::from(lo)..=u8::from(hi)).map(Rank::from)
+    }
+    /// Every live combination of two ranks under a suitedness constraint.
+    fn combos(hi: Rank, lo: Rank, suited: Option<bool>) -> impl Iterator<Item = Hole> {
+        Suit::all()
+            .into_iter()
+            .flat_map(move |a| Suit::all().into_iter().map(move |b| (a, b)))
+            .filter(move |(a, b)| match (hi == lo, suited) {
+                (true, _) => a < b,
+                (false, None) => true,
+                (false, Some(suited)) => (a == b) == suited,
+            })
+            .map(move |(a, b)| (Card::from((hi, a)), Card::from((lo, b))))
+            .filter(|(a, b)| Hand::from(u64::from(*a) | u64::from(*b)).size() == 2)
+            .map(Hole::from)
+    }
+}
+
+impl From<Hole> for Range {
+    fn from(hole: Hole) -> Self {
+        std::iter::once(hole).collect()
+    }
+}
+
+/// Unit weight for each holding.
+impl FromIterator<Hole> for Range {
+    fn from_iter<I: IntoIterator<Item = Hole>>(iter: I) -> Self {
+        iter.into_iter().map(|hole| (hole, 1.)).collect()
+    }
+}
+impl FromIterator<(Hole, Probability)> for Range {
+    fn from_iter<I: IntoIterator<Item = (Hole, Probability)>>(iter: I) -> Self {
+        let mut range = Self::empty();
+        iter.into_iter().for_each(|(hole, weight)| range.insert(hole, weight));
+        range
+    }
+}
+
+/// str isomorphism
+impl TryFrom<&str> for Range {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let tokens = s
+            .split(',')
+            .filter(|token| !token.trim().is_empty())
+            .map(Self::token)
+            .collect::<Result<Vec<_>, _>>()?;
+        let range = tokens
+            .into_iter()
+            .flat_map(|(holes, weight)| holes.into_iter().map(move |hole| (hole, weight)))
+            .collect::<Self>();
+        if range.is_empty() { Err(format!("empty range: {s:?}")) } else { Ok(range) }
+    }
+}
+
+/// Canonical 169-cell notation.
+impl std::fmt::Display for Range {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.notation(Hand::empty()))
+    }
+}
+
+impl serde::Serialize for Range {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        s.serialize_str(&self.to_string())
+    }
+}
+impl<'de> serde::Deserialize<'de> for Range {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s: String = serde::Deserialize::deserialize(d)?;
+        Self::try_from(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "shortdeck"))]
+mod tests {
+    use super::*;
+
+    fn size(s: &str) -> usize {
+        Range::try_from(s).unwrap().len()
+    }
+    fn canonical(s: &str) -> String {
+        Range::try_from(s).unwrap().to_string()
+    }
+
+    #[test]
+    fn cells() {
+        assert_eq!(size("AA"), 6);
+        assert_eq!(size("AKs"), 4);
+        assert_eq!(size("AKo"), 12);
+        assert_eq!(size("AK"), 16);
+        assert_eq!(size("KA"), 16);
+        assert_eq!(size("AsKd"), 1);
+    }
+    #[test]
+    fn plus() {
+        assert_eq!(size("TT+"), 6 * 5);
+        assert_eq!(size("22+"), 6 * 13);
+        assert_eq!(size("ATs+"), 4 * 4);
+        assert_eq!(size("K9o+"), 12 * 4);
+    }
+    #[test]
+    fn spans() {
+        assert_eq!(size("A5s-A2s"), 4 * 4);
+        assert_eq!(size("A2s-A5s"), 4 * 4);
+        assert_eq!(size("99-66"), 6 * 4);
+        assert!(Range::try_from("A5s-K2s").is_err());
+        assert!(Range::try_from("A5s-A2o").is_err());
+    }
+    #[test]
+    fn unions_dedupe() {
+        assert_eq!(size("AKs, AK, AsKs"), 16);
+        assert_eq!(size("AKs, TT+, A5s-A2s"), 4 + 30 + 16);
+    }
+    #[test]
+    fn weights_override_left_to_right() {
+        let range = Range::try_from("22+:0.5, AA, KK:0").unwrap();
+        assert_eq!(range.len(), 6 * 12);
+        assert_eq!(range.weight(&Hole::try_from("AsAh").unwrap()), 1.);
+        assert_eq!(range.weight(&Hole::try_from("QsQh").unwrap()), 0.5);
+        assert_eq!(range.weight(&Hole::try_from("KsKh").unwrap()), 0.);
+    }
+    #[test]
+    fn rejects_garbage() {
+        assert!(Range::try_from("AAs").is_err());
+        assert!(Range::try_from("XY").is_err());
+        assert!(Range::try_from("AKx").is_err());
+        assert!(Range::try_from("Aé").is_err());
+        assert!(Range::try_from("Kéo").is_err());
+        assert!(Range::try_from("A♠K♠").is_err());
+        assert!(Range::try_from("AKs:-1").is_err());
+        assert!(Range::try_from("AKs:lots").is_err());
+        assert!(Range::try_from("").is_err());
+    }
+    #[test]
+    fn full_and_blocked() {
+        let full = Range::full();
+        assert_eq!(full.len(), 1326);
+        assert_eq!(full.blocked(Hand::try_from("As Kd 7h").unwrap()).len(), 1176);
+        let aces = Range::try_from("AA").unwrap();
+        assert_eq!(aces.blocked(Hand::try_from("As").unwrap()).len(), 3);
+    }
+    #[test]
+    fn grid_partitions_every_combo() {
+        let cells = Range::grid().collect::<Vec<_>>();
+        assert_eq!(cells.len(), 169);
+        assert_eq!(&cells[..3], ["AA", "AKs", "AQs"]);
+        assert_eq!(cells[13], "AKo");
+        assert_eq!(cells[168], "22");
+        let union = Range::try_from(cells.join(", ").as_str()).unwrap();
+        assert_eq!(union, Range::full());
+    }
+    #[test]
+    fn canonical_cells() {
+        assert_eq!(canonical("AA, KK, QQ"), "QQ+");
+        assert_eq!(canonical("99, 88, 77"), "99-77");
+        assert_eq!(canonical("AKs, AKo"), "AK");
+        assert_eq!(canonical("AKs, AQs, AJs, A5s, A4s"), "AJs+, A5s-A4s");
+        assert_eq!(canonical("K9o+, KQs"), "KQ, KJo-K9o");
+        assert_eq!(canonical("TT+:0.5, AKs"), "TT+:0.5, AKs");
+        assert_eq!(canonical("AsKs, AhKh"), "AhKh, AsKs");
+        assert_eq!(Range::full().to_string().split(", ").count(), 13);
+    }
+    #[test]
+    fn notation_ignores_dead_cards() {
+        let board = Hand::try_from("As 7d 2c").unwrap();
+        let range = Range::try_from("AA, AK").unwrap().blocked(board);
+        assert_eq!(range.notation(board), "AA, AK");
+        assert_ne!(range.notation(Hand::empty()), "AA, AK");
+    }
+    #[test]
+    fn notation_round_trips() {
+        use rand::Rng;
+        let ref mut rng = rand::rng();
+        let range = Range::full()
+            .iter()
+            .map(|(hole, _)| (hole, Probability::from(rng.random_range(0..=4u8)) / 4.))
+            .collect::<Range>();
+        assert_eq!(Range::try_from(range.to_string().as_str()).unwrap(), range);
+        for s in ["QQ+, AKs", "TT+:0.5, AJs+, KQo:0.25, 76s, AsKd:0.125", "22+:0.5, AA"] {
+            let range = Range::try_from(s).unwrap();
+            assert_eq!(Range::try_from(range.to_string().as_str()).unwrap(), range);
+        }
+    }
+    #[test]
+    fn scaled_and_quantized() {
+        let range = Range::try_from("AA:0.2, KK:0.1, QQ:0.001").unwrap();
+        assert_eq!(range.scaled().quantized(4).to_string(), "AA, KK:0.5");
+    }
+}
@@ -44,6 +44,29 @@ pub enum Ranking {
 }
 
 impl Ranking {
+    /// Position of this category in the hand-strength order, from 0
+    /// (high card) to 8 (straight flush). Follows the variant order, so
+    /// short deck swaps flush and full house.
+    pub fn ordinal(&self) -> u8 {
+        match self {
+            Ranking::HighCard(_) => 0,
+            Ranking::OnePair(_) => 1,
+            Ranking::TwoPair(_, _) => 2,
+            Ranking::ThreeOAK(_) => 3,
+            Ranking::Straight(_) => 4,
+            #[cfg(not(feature = "shortdeck"))]
+            Ranking::FullHouse(_, _) => 5,
+            #[cfg(not(feature = "shortdeck"))]
+            Ranking::Flush(_) => 6,
+            #[cfg(feature = "shortdeck")]
+            Ranking::Flush(_) => 5,
+            #[cfg(feature = "shortdeck")]
+            Ranking::FullHouse(_, _) => 6,
+            Ranking::FourOAK(_) => 7,
+            Ranking::StraightFlush(_) => 8,
+            Ranking::MAX => 9,
+        }
+    }
     /// Number of deuce cards used for this hand type.
     pub fn n_kickers(&self) -> usize {
         match self {
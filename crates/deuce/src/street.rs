@@ -134,6 +134,18 @@ impl Street {
             Self::Rive => 0_123_156_254,
         }
     }
+    /// Strategically-distinct situations under perfect recall, where the
+    /// round each card arrived on still distinguishes deals. See [`Waugh`].
+    ///
+    /// [`Waugh`]: super::waugh::Waugh
+    pub const fn n_recalls(&self) -> usize {
+        match self {
+            Self::Pref => 0___________169,
+            Self::Flop => 0_____1_286_792,
+            Self::Turn => 0____55_190_538,
+            Self::Rive => 2_428_287_420,
+        }
+    }
     /// Total (hole, board) combinations without suit reduction.
     pub const fn n_observations(&self) -> usize {
         match self {
@@ -165,6 +177,15 @@ impl Street {
         }
     }
 
+    pub const fn n_recalls(&self) -> usize {
+        match self {
+            Self::Pref => 0__________81,
+            Self::Flop => 0_____186_696,
+            Self::Turn => 0___5_266_044,
+            Self::Rive => 0_151_065_864,
+        }
+    }
+
     pub const fn n_observations(&self) -> usize {
         match self {
             Self::Pref => 0_________630,
@@ -35,14 +35,56 @@ impl Strength {
         self.value
     }
 }
+
 impl From<(Ranking, Kickers)> for Strength {
     fn from((value, kicks): (Ranking, Kickers)) -> Self {
         Self { value, kicks }
     }
 }
 
+/// u32 rank, order-preserving
+///
+/// [4 bits category][4 bits primary][4 bits secondary][13 bits kickers]
+/// so `u32::from(a).cmp(&u32::from(b)) == a.cmp(&b)`. One-way: a dense
+/// comparable rank for callers outside Rust, not a serialization.
+impl From<Strength> for u32 {
+    fn from(strength: Strength) -> Self {
+        let (hi, lo) = match strength.value {
+            Ranking::HighCard(r)
+            | Ranking::OnePair(r)
+            | Ranking::ThreeOAK(r)
+            | Ranking::Straight(r)
+            | Ranking::Flush(r)
+            | Ranking::FourOAK(r)
+            | Ranking::StraightFlush(r) => (u8::from(r), 0),
+            Ranking::TwoPair(a, b) | Ranking::FullHouse(a, b) => (u8::from(a), u8::from(b)),
+            Ranking::MAX => unreachable!(),
+        };
+        (strength.value.ordinal() as u32) << 21
+            | (hi as u32) << 17
+            | (lo as u32) << 13
+            | u16::from(strength.kicks) as u32
+    }
+}
+
 impl std::fmt::Display for Strength {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "{:<18}{:>5}", self.value, self.kicks)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::observation::Observation;
+    use super::super::street::Street;
+    use super::*;
+
+    #[test]
+    fn u32_preserves_order() {
+        for _ in 0..1000 {
+            let a = Strength::from(Hand::from(Observation::from(Street::Rive)));
+            let b = Strength::from(Hand::from(Observation::from(Street::Rive)));
+            assert_eq!(a.cmp(&b), u32::from(a).cmp(&u32::from(b)), "{a} vs {b}");
+        }
+    }
+}
@@ -0,0 +1,9 @@
+/// Anything that records which centroid a point is assigned to.
+///
+/// Lets [`Elkan::recompute`](crate::Elkan::recompute) rebuild centroids
+/// from any bound scheme: [`Bounds`](crate::Bounds), [`Hamerly`](crate::Hamerly),
+/// or [`Yinyang`](crate::Yinyang).
+pub trait Assignment {
+    /// Currently assigned centroid index.
+    fn j(&self) -> usize;
+}
@@ -97,6 +97,12 @@ impl<const K: usize> Bounds<K> {
     }
 }
 
+impl<const K: usize> super::Assignment for Bounds<K> {
+    fn j(&self) -> usize {
+        self.j
+    }
+}
+
 impl<const K: usize> Default for Bounds<K> {
     fn default() -> Self {
         Self {
@@ -26,6 +26,28 @@ impl<const K: usize> Drift<K> {
         self.0.iter().copied().fold(0.0, Energy::max)
     }
 
+    /// Largest movement among clusters other than `j`. Hamerly's single
+    /// lower bound covers every centroid but the assigned one.
+    pub fn except(&self, j: usize) -> Energy {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != j)
+            .map(|(_, d)| *d)
+            .fold(0.0, Energy::max)
+    }
+
+    /// Largest movement within each of `G` groups, given each cluster's
+    /// group. Yinyang shrinks each group's lower bound by this much.
+    pub fn grouped<const G: usize>(&self, groups: &[usize; K]) -> [Energy; G] {
+        let mut result = [0.0; G];
+        self.0
+            .iter()
+            .zip(groups.iter())
+            .for_each(|(d, &g)| result[g] = Energy::max(result[g], *d));
+        result
+    }
+
     /// Smallest single-cluster centroid movement.
     pub fn min(&self) -> Energy {
         self.0.iter().copied().fold(Energy::INFINITY, Energy::min)
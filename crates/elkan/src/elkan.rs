@@ -17,6 +17,8 @@ use rayon::prelude::*;
 /// # Implementation
 ///
 /// - `step_elkan()` — Single iteration with bound maintenance
+/// - `step_hamerly()` — Same, with one lower bound per point ([`Hamerly`])
+/// - `step_yinyang()` — Same, with one lower bound per centroid group ([`Yinyang`])
 /// - `step_naive()` — Reference implementation for verification
 /// - `init_centroids()` — K-means++ initialization for better convergence
 ///
@@ -122,20 +124,27 @@ pub trait Elkan<const K: usize, const N: usize>: Sync {
         }
     }
 
+    /// Builds centroid `j` from the points assigned to it. Defaults to
+    /// absorbing them into the running mean; override for centroids that
+    /// are not arithmetic means, such as Wasserstein barycenters.
+    fn aggregate<'a>(&'a self, j: usize, members: impl Iterator<Item = &'a Self::P>) -> Self::P {
+        members.fold(self.centroid(j).identity(), Self::P::absorb)
+    }
+
     /// Computes new centroids from current assignments. Each new
-    /// centroid is the result of absorbing every point assigned to
-    /// that cluster into the running mean.
-    fn recompute(&self, bounds: &[Bounds<K>]) -> [Self::P; K] {
+    /// centroid is the [`aggregate`](Elkan::aggregate) of every point
+    /// assigned to that cluster.
+    fn recompute<B: Assignment + Sync>(&self, bounds: &[B]) -> [Self::P; K] {
         use rayon::prelude::*;
         let centroids = (0..K)
             .into_par_iter()
             .map(|j| {
-                bounds
+                let members = bounds
                     .iter()
                     .enumerate()
                     .filter(|(_, b)| b.j() == j)
-                    .map(|(i, _)| self.point(i))
-                    .fold(self.centroid(j).identity(), Self::P::absorb)
+                    .map(|(i, _)| self.point(i));
+                self.aggregate(j, members)
             })
             .collect::<Vec<_>>();
         std::array::from_fn(|j| centroids[j])
@@ -167,20 +176,178 @@ pub trait Elkan<const K: usize, const N: usize>: Sync {
         (centroids, drift)
     }
 
+    /// Distances from a point to every centroid (O(K) distance calls).
+    fn distances(&self, i: usize) -> [f32; K] {
+        let x = self.point(i);
+        std::array::from_fn(|j| self.distance(self.centroid(j), x))
+    }
+
+    /// Nearest centroid and the distance to the second nearest, with ties
+    /// resolved toward the lower index as in [`neighbor`](Elkan::neighbor).
+    fn runner_up(&self, i: usize) -> ((usize, f32), f32) {
+        let distances = self.distances(i);
+        let (j, nearest) = distances
+            .iter()
+            .copied()
+            .enumerate()
+            .inspect(|(_, d)| debug_assert!(d.is_finite()))
+            .min_by(|(_, d1), (_, d2)| d1.partial_cmp(d2).unwrap())
+            .expect("K > 0");
+        let second = distances
+            .iter()
+            .enumerate()
+            .filter(|&(k, _)| k != j)
+            .map(|(_, d)| *d)
+            .fold(f32::MAX, f32::min);
+        ((j, nearest), second)
+    }
+
+    /// Initializes Hamerly bounds with a full scan of every point.
+    fn init_hamerly(&self) -> Box<[Hamerly; N]> {
+        (0..N)
+            .into_par_iter()
+            .map(|i| self.runner_up(i))
+            .map(Hamerly::from)
+            .collect::<Vec<_>>()
+            .try_into()
+            .expect("bounds.len() == N")
+    }
+
+    /// Executes one Hamerly iteration with bound maintenance.
+    ///
+    /// Same contract as [`step_elkan`](Elkan::step_elkan), with O(N)
+    /// rather than O(N·K) bound memory.
+    fn step_hamerly(&self, bounds: &mut [Hamerly; N]) -> ([Self::P; K], Drift<K>) {
+        let pairwise = self.pairwises();
+        let midpoints = self.midpoints(&pairwise);
+        bounds
+            .par_iter_mut()
+            .enumerate()
+            .filter(|(_, b)| b.is_loose(&midpoints))
+            .for_each(|(i, b)| {
+                b.refresh(self.distance(self.point(i), self.centroid(b.j())));
+                if b.is_loose(&midpoints) {
+                    *b = Hamerly::from(self.runner_up(i));
+                }
+            });
+        let centroids = self.recompute(bounds);
+        let drift = self.drift(&centroids);
+        bounds.par_iter_mut().for_each(|b| b.update(&drift));
+        (centroids, drift)
+    }
+
+    /// Partitions centroids into `G` groups for [`Yinyang`] bounds:
+    /// farthest-first seeds among the centroids, then each centroid joins
+    /// its nearest seed. Any partition keeps the bounds valid; tight
+    /// groups only make the group filter prune more.
+    fn groups<const G: usize>(&self) -> [usize; K] {
+        assert!(0 < G && G <= K, "1 <= G <= K");
+        let pairwise = self.pairwises();
+        let mut seeds = vec![0];
+        while seeds.len() < G {
+            let next = (0..K)
+                .map(|j| (j, seeds.iter().map(|&s| pairwise[s][j]).fold(f32::MAX, f32::min)))
+                .max_by(|(_, d1), (_, d2)| d1.partial_cmp(d2).unwrap())
+                .map(|(j, _)| j)
+                .expect("K > 0");
+            seeds.push(next);
+        }
+        std::array::from_fn(|j| {
+            seeds
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| pairwise[**a][j].partial_cmp(&pairwise[**b][j]).unwrap())
+                .map(|(g, _)| g)
+                .expect("G > 0")
+        })
+    }
+
+    /// Initializes Yinyang bounds with a full scan of every point.
+    fn init_yinyang<const G: usize>(&self, groups: &[usize; K]) -> Box<[Yinyang<G>; N]> {
+        (0..N)
+            .into_par_iter()
+            .map(|i| {
+                let distances = self.distances(i);
+                let (j, nearest) = self.runner_up(i).0;
+                let mut lower = [f32::MAX; G];
+                distances
+                    .iter()
+                    .enumerate()
+                    .filter(|&(k, _)| k != j)
+                    .for_each(|(k, d)| lower[groups[k]] = lower[groups[k]].min(*d));
+                Yinyang::from(((j, nearest), lower))
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .expect("bounds.len() == N")
+    }
+
+    /// Scans the groups a Yinyang bound could not exclude and reassigns.
+    ///
+    /// Every centroid outside the scanned groups is strictly farther than
+    /// the tightened upper bound, so the nearest among the scanned ones
+    /// (ties to the lower index) is the naive assignment.
+    fn regroup<const G: usize>(&self, b: &mut Yinyang<G>, x: &Self::P, groups: &[usize; K]) {
+        let (j, u) = (b.j(), b.u());
+        let scanned = std::array::from_fn::<bool, G, _>(|g| b.l(g) <= u);
+        let seen = (0..K)
+            .filter(|&k| scanned[groups[k]] || k == j)
+            .map(|k| (k, if k == j { u } else { self.distance(self.centroid(k), x) }))
+            .collect::<Vec<(usize, f32)>>();
+        let best = seen
+            .iter()
+            .copied()
+            .min_by(|(_, d1), (_, d2)| d1.partial_cmp(d2).unwrap())
+            .expect("assigned centroid seen");
+        let mut lower = std::array::from_fn::<Option<f32>, G, _>(|g| scanned[g].then_some(f32::MAX));
+        seen.iter().filter(|(k, _)| *k != best.0).for_each(|&(k, d)| {
+            let g = groups[k];
+            lower[g] = Some(lower[g].unwrap_or(b.l(g)).min(d));
+        });
+        b.rescan(best, lower);
+    }
+
+    /// Executes one Yinyang iteration with bound maintenance.
+    ///
+    /// Same contract as [`step_elkan`](Elkan::step_elkan), with O(N·G)
+    /// bound memory; `groups` must be the partition the bounds were
+    /// initialized with.
+    fn step_yinyang<const G: usize>(
+        &self,
+        bounds: &mut [Yinyang<G>; N],
+        groups: &[usize; K],
+    ) -> ([Self::P; K], Drift<K>) {
+        bounds
+            .par_iter_mut()
+            .enumerate()
+            .filter(|(_, b)| b.is_loose())
+            .for_each(|(i, b)| {
+                let x = self.point(i);
+                b.refresh(self.distance(x, self.centroid(b.j())));
+                if b.is_loose() {
+                    self.regroup(b, x, groups);
+                }
+            });
+        let centroids = self.recompute(bounds);
+        let drift = self.drift(&centroids);
+        let movement = drift.grouped::<G>(groups);
+        bounds.par_iter_mut().for_each(|b| b.update(&drift, &movement));
+        (centroids, drift)
+    }
+
     /// Executes one naive iteration (for verification/benchmarking).
     fn step_naive(&self) -> [Self::P; K] {
-        let identity = self.centroid(0).identity();
         let assignments = (0..N)
             .into_par_iter()
             .map(|i| self.neighbor(i).0)
             .collect::<Vec<usize>>();
         std::array::from_fn(|j| {
-            assignments
+            let members = assignments
                 .iter()
                 .enumerate()
                 .filter(|(_, k)| k == &&j)
-                .map(|(i, _)| self.point(i))
-                .fold(identity, Self::P::absorb)
+                .map(|(i, _)| self.point(i));
+            self.aggregate(j, members)
         })
     }
 
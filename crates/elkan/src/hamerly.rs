@@ -0,0 +1,70 @@
+/// Per-point metadata for Hamerly's accelerated k-means algorithm.
+///
+/// Where [`Bounds`](crate::Bounds) keeps one lower bound per centroid,
+/// Hamerly (2010) keeps a single lower bound on the distance to the
+/// *second* closest centroid. Memory drops from O(N·K) to O(N), at the
+/// price of a full O(K) scan whenever the one bound fails.
+///
+/// # Algorithm (Hamerly 2010)
+///
+/// A point keeps its assignment without any distance call while
+/// `u(x) < max(l(x), s(c(x)))`, with `s(c)` half the distance from `c`
+/// to its nearest other centroid. Otherwise the upper bound is tightened,
+/// and if the test still fails every centroid is scanned.
+///
+/// # Fields
+///
+/// - `j` — Index of currently assigned centroid
+/// - `upper` — Upper bound u(x) on distance to assigned centroid
+/// - `lower` — Lower bound l(x) on distance to every other centroid
+#[derive(Debug, Clone, Default)]
+pub struct Hamerly {
+    j: usize,
+    upper: f32,
+    lower: f32,
+}
+
+impl Hamerly {
+    /// Currently assigned centroid index.
+    pub fn j(&self) -> usize {
+        self.j
+    }
+    /// Upper bound on distance to assigned centroid.
+    pub fn u(&self) -> f32 {
+        self.upper
+    }
+    /// Lower bound on distance to every other centroid.
+    pub fn l(&self) -> f32 {
+        self.lower
+    }
+    /// Whether another centroid could be at least as close as the
+    /// assigned one. Ties count, so that reassignment breaks them toward
+    /// the lower index exactly as a naive scan does.
+    pub fn is_loose<const K: usize>(&self, midpoints: &[f32; K]) -> bool {
+        self.u() >= self.l().max(midpoints[self.j()])
+    }
+    /// Tightens the upper bound to the exact assigned distance.
+    pub fn refresh(&mut self, distance: f32) {
+        self.upper = distance;
+    }
+    /// Updates bounds after centroids move. The upper bound grows by
+    /// the assigned centroid's drift; the lower bound shrinks by the
+    /// largest drift among the others.
+    pub fn update<const K: usize>(&mut self, drift: &super::Drift<K>) {
+        self.upper += drift.as_array()[self.j()];
+        self.lower = (self.lower - drift.except(self.j())).max(0.0);
+    }
+}
+
+impl super::Assignment for Hamerly {
+    fn j(&self) -> usize {
+        self.j
+    }
+}
+
+/// From a full scan: `(nearest, distance)` and the second-nearest distance.
+impl From<((usize, f32), f32)> for Hamerly {
+    fn from(((j, upper), lower): ((usize, f32), f32)) -> Self {
+        Self { j, upper, lower }
+    }
+}
@@ -8,7 +8,10 @@
 //!
 //! - [`Elkan`] — the accelerated algorithm (with `step_naive` for verification)
 //! - [`Absorb`] — incremental centroid aggregation for the point type
+//! - [`MiniBatch`] — streaming k-means that keeps only centroids and counts
 //! - [`Bounds`] — per-point distance bounds maintained across iterations
+//! - [`Hamerly`] / [`Yinyang`] — one or `G` lower bounds per point, for large K
+//! - [`Assignment`] — the assigned centroid, common to every bound scheme
 //! - [`Drift`] — per-centroid movement between iterations
 //! - [`Step`] / [`Prior`] — iteration bookkeeping helpers
 
@@ -16,15 +19,26 @@
 pub type Energy = f32;
 
 mod absorb;
+mod assignment;
 mod bounds;
 mod drift;
 mod elkan;
+mod hamerly;
+mod minibatch;
 mod prior;
 mod step;
+mod yinyang;
+
+#[cfg(test)]
+mod tests;
 
 pub use absorb::*;
+pub use assignment::*;
 pub use bounds::*;
 pub use drift::*;
 pub use elkan::*;
+pub use hamerly::*;
+pub use minibatch::*;
 pub use prior::*;
 pub use step::*;
+pub use yinyang::*;
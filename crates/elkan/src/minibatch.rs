@@ -0,0 +1,137 @@
+use super::*;
+use crate::Energy;
+use rayon::prelude::*;
+
+/// Mini-batch k-means (Sculley, 2010) over a stream of points.
+///
+/// [`Elkan`] needs every point and its `K` bounds resident at once. This
+/// variant keeps only the centroids and per-centroid counts: each batch
+/// is assigned to its nearest centroids, then every member is absorbed
+/// into the centroid it chose. Since [`Absorb`] keeps a running mean, a
+/// centroid that has absorbed `v` points moved toward the latest one at
+/// rate `1/v`, which is exactly Sculley's per-center learning rate.
+///
+/// Memory is `O(K)` points regardless of how many points stream past,
+/// so callers can page points in from an iterator in fixed-size chunks.
+///
+/// # Type Parameters
+///
+/// - `P` — Point type absorbed into centroids
+/// - `K` — Number of clusters (compile-time constant)
+pub struct MiniBatch<P, const K: usize> {
+    centroids: Box<[P; K]>,
+    counts: [usize; K],
+}
+
+impl<P, const K: usize> MiniBatch<P, K>
+where
+    P: Absorb + Copy + Sync + Send,
+{
+    /// Starts from seeded centroids, each counted as its own first member.
+    pub fn new(centroids: Box<[P; K]>) -> Self {
+        Self {
+            centroids,
+            counts: [1; K],
+        }
+    }
+    /// Current centroid positions.
+    pub fn centroids(&self) -> &[P; K] {
+        &self.centroids
+    }
+    /// Points absorbed into each centroid so far, seeds included.
+    pub fn counts(&self) -> &[usize; K] {
+        &self.counts
+    }
+    /// Nearest centroid to `x` and its distance (O(K) distance calls).
+    pub fn nearest<D>(&self, x: &P, distance: &D) -> (usize, Energy)
+    where
+        D: Fn(&P, &P) -> Energy + Sync,
+    {
+        self.centroids
+            .iter()
+            .enumerate()
+            .map(|(j, c)| (j, distance(c, x)))
+            .inspect(|(_, d)| debug_assert!(d.is_finite()))
+            .min_by(|(_, d1), (_, d2)| d1.partial_cmp(d2).unwrap())
+            .expect("K > 0")
+    }
+    /// Assigns one batch and absorbs its members into their centroids.
+    ///
+    /// Returns how far each centroid moved, so callers can watch
+    /// convergence the same way they would for [`Elkan::step_elkan`].
+    pub fn step<D>(&mut self, batch: &[P], distance: &D) -> Drift<K>
+    where
+        D: Fn(&P, &P) -> Energy + Sync,
+    {
+        let assignments = batch
+            .par_iter()
+            .map(|x| self.nearest(x, distance).0)
+            .collect::<Vec<usize>>();
+        let prior = self.centroids.clone();
+        for (x, &j) in batch.iter().zip(&assignments) {
+            self.centroids[j] = self.centroids[j].absorb(x);
+            self.counts[j] += 1;
+        }
+        Drift::from_array(std::array::from_fn(|j| distance(&prior[j], &self.centroids[j])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Running mean on the line: `(sum, count)`.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Mean(f32, f32);
+    impl Mean {
+        fn at(x: f32) -> Self {
+            Self(x, 1.)
+        }
+        fn value(&self) -> f32 {
+            self.0 / self.1
+        }
+    }
+    impl Absorb for Mean {
+        fn identity(&self) -> Self {
+            Self(0., 0.)
+        }
+        fn absorb(self, other: &Self) -> Self {
+            Self(self.0 + other.0, self.1 + other.1)
+        }
+    }
+
+    fn line(a: &Mean, b: &Mean) -> Energy {
+        (a.value() - b.value()).abs()
+    }
+
+    #[test]
+    fn centroids_track_running_means() {
+        let mut km = MiniBatch::<Mean, 2>::new(Box::new([Mean::at(0.), Mean::at(10.)]));
+        let batch = [1., 2., 9., 11., 12.].map(Mean::at);
+        km.step(&batch, &line);
+        assert_eq!(km.counts(), &[3, 4]);
+        assert!((km.centroids()[0].value() - 1.).abs() < 1e-6);
+        assert!((km.centroids()[1].value() - 10.5).abs() < 1e-6);
+    }
+    #[test]
+    fn drift_vanishes_on_empty_batch() {
+        let mut km = MiniBatch::<Mean, 2>::new(Box::new([Mean::at(0.), Mean::at(10.)]));
+        let drift = km.step(&[], &line);
+        assert_eq!(drift.max(), 0.);
+        assert_eq!(km.counts(), &[1, 1]);
+    }
+    #[test]
+    fn chunking_is_irrelevant_when_separated() {
+        let points = (0..64)
+            .map(|i| Mean::at((i % 4) as f32 + 100. * (i % 2) as f32))
+            .collect::<Vec<_>>();
+        let seeds = Box::new([Mean::at(0.), Mean::at(100.)]);
+        let mut whole = MiniBatch::<Mean, 2>::new(seeds.clone());
+        let mut chunked = MiniBatch::<Mean, 2>::new(seeds);
+        whole.step(&points, &line);
+        points.chunks(5).for_each(|c| {
+            chunked.step(c, &line);
+        });
+        assert_eq!(whole.centroids(), chunked.centroids());
+    }
+}
@@ -0,0 +1,143 @@
+//! Equivalence of every bound scheme with naive k-means.
+//!
+//! Points are blobs in the plane, so ties are measure-zero and any
+//! mismatch against `step_naive` is a pruning bug rather than rounding.
+
+use super::*;
+
+const K: usize = 12;
+const N: usize = 600;
+const G: usize = 3;
+const T: usize = 8;
+
+/// Running mean in the plane: `(Σx, Σy, count)`. Empty means sit at the origin.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Point(f32, f32, f32);
+
+impl Point {
+    fn mean(&self) -> (f32, f32) {
+        (self.0 / self.2.max(1.), self.1 / self.2.max(1.))
+    }
+}
+
+impl Absorb for Point {
+    fn identity(&self) -> Self {
+        Self(0., 0., 0.)
+    }
+    fn absorb(self, other: &Self) -> Self {
+        Self(self.0 + other.0, self.1 + other.1, self.2 + other.2)
+    }
+}
+
+#[derive(Clone)]
+struct Plane {
+    points: Box<[Point; N]>,
+    kmeans: [Point; K],
+    bounds: Box<[Bounds<K>; N]>,
+}
+
+impl Plane {
+    /// Five Gaussian-ish blobs from a fixed linear congruential stream.
+    fn new() -> Self {
+        let mut state = 0x2545_f491_u64;
+        let mut uniform = move || {
+            state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            (state >> 40) as f32 / (1u64 << 24) as f32
+        };
+        let points = (0..N)
+            .map(|i| {
+                let (cx, cy) = ((i % 5) as f32 * 3., (i % 5 * i % 3) as f32 * 2.);
+                let jitter = (0..4).map(|_| uniform()).sum::<f32>() - 2.;
+                Point(cx + jitter, cy + uniform() * 2. - 1., 1.)
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .expect("N");
+        let mut plane = Self {
+            points,
+            kmeans: [Point(0., 0., 0.); K],
+            bounds: vec![Bounds::default(); N].try_into().expect("N"),
+        };
+        plane.kmeans = plane.init_centroids();
+        plane.bounds = plane.init_bounds();
+        plane
+    }
+    fn assignments(&self) -> Vec<usize> {
+        (0..N).map(|i| self.neighbor(i).0).collect()
+    }
+}
+
+impl Elkan<K, N> for Plane {
+    type P = Point;
+    fn points(&self) -> &[Point; N] {
+        &self.points
+    }
+    fn centroids(&self) -> &[Point; K] {
+        &self.kmeans
+    }
+    fn boundings(&self) -> &[Bounds<K>; N] {
+        &self.bounds
+    }
+    fn init_centroids(&self) -> [Point; K] {
+        std::array::from_fn(|j| self.points[j * 7])
+    }
+    fn distance(&self, a: &Point, b: &Point) -> Energy {
+        let ((ax, ay), (bx, by)) = (a.mean(), b.mean());
+        ((ax - bx).powi(2) + (ay - by).powi(2)).sqrt()
+    }
+}
+
+fn js<B: Assignment>(bounds: &[B]) -> Vec<usize> {
+    bounds.iter().map(Assignment::j).collect()
+}
+
+#[test]
+fn elkan_matches_naive() {
+    let mut naive = Plane::new();
+    let mut elkan = naive.clone();
+    let mut bounds = elkan.init_bounds();
+    for _ in 0..T {
+        let expected = naive.assignments();
+        naive.kmeans = naive.step_naive();
+        elkan.kmeans = elkan.step_elkan(&mut bounds).0;
+        assert_eq!(js(&*bounds), expected);
+        assert_eq!(elkan.kmeans, naive.kmeans);
+    }
+}
+
+#[test]
+fn hamerly_matches_naive() {
+    let mut naive = Plane::new();
+    let mut hamerly = naive.clone();
+    let mut bounds = hamerly.init_hamerly();
+    for _ in 0..T {
+        let expected = naive.assignments();
+        naive.kmeans = naive.step_naive();
+        hamerly.kmeans = hamerly.step_hamerly(&mut bounds).0;
+        assert_eq!(js(&*bounds), expected);
+        assert_eq!(hamerly.kmeans, naive.kmeans);
+    }
+}
+
+#[test]
+fn yinyang_matches_naive() {
+    let mut naive = Plane::new();
+    let mut yinyang = naive.clone();
+    let groups = yinyang.groups::<G>();
+    let mut bounds = yinyang.init_yinyang::<G>(&groups);
+    for _ in 0..T {
+        let expected = naive.assignments();
+        naive.kmeans = naive.step_naive();
+        yinyang.kmeans = yinyang.step_yinyang(&mut bounds, &groups).0;
+        assert_eq!(js(&*bounds), expected);
+        assert_eq!(yinyang.kmeans, naive.kmeans);
+    }
+}
+
+#[test]
+fn groups_partition_every_centroid() {
+    let plane = Plane::new();
+    let groups = plane.groups::<G>();
+    assert!(groups.iter().all(|&g| g < G));
+    assert!((0..G).all(|g| groups.contains(&g)));
+}
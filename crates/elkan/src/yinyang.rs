@@ -0,0 +1,83 @@
+/// Per-point metadata for Yinyang accelerated k-means.
+///
+/// Yinyang (Ding et al., 2015) sits between [`Bounds`](crate::Bounds)
+/// and [`Hamerly`](crate::Hamerly): centroids are partitioned into `G`
+/// groups once, and each point keeps one lower bound per group. Memory is
+/// O(N·G), and when the global test fails only the groups whose bound
+/// falls below the upper bound are scanned.
+///
+/// # Algorithm (Ding et al. 2015)
+///
+/// 1. Global filter: skip the point while `u(x) < min_g l(x, g)`
+/// 2. Tighten `u(x)` to the exact assigned distance and test again
+/// 3. Group filter: scan only groups with `l(x, g) ≤ u(x)`
+///
+/// After centroids move, `l(x, g)` shrinks by the largest drift in `g`.
+///
+/// # Fields
+///
+/// - `j` — Index of currently assigned centroid
+/// - `upper` — Upper bound u(x) on distance to assigned centroid
+/// - `lower` — Lower bound l(x, g) on distance to each group's centroids,
+///   the assigned one excluded
+#[derive(Debug, Clone)]
+pub struct Yinyang<const G: usize> {
+    j: usize,
+    upper: f32,
+    lower: [f32; G],
+}
+
+impl<const G: usize> Yinyang<G> {
+    /// Currently assigned centroid index.
+    pub fn j(&self) -> usize {
+        self.j
+    }
+    /// Upper bound on distance to assigned centroid.
+    pub fn u(&self) -> f32 {
+        self.upper
+    }
+    /// Lower bound on distance to centroids of group g.
+    pub fn l(&self, g: usize) -> f32 {
+        self.lower[g]
+    }
+    /// Whether any group could hold a centroid at least as close as the
+    /// assigned one. Ties count, as in [`Hamerly::is_loose`](crate::Hamerly::is_loose).
+    pub fn is_loose(&self) -> bool {
+        self.lower.iter().any(|&l| self.u() >= l)
+    }
+    /// Tightens the upper bound to the exact assigned distance.
+    pub fn refresh(&mut self, distance: f32) {
+        self.upper = distance;
+    }
+    /// Records a rescan: the new assignment and distance, and fresh lower
+    /// bounds for the groups that were scanned (`None` keeps the old one).
+    pub fn rescan(&mut self, (j, upper): (usize, f32), lower: [Option<f32>; G]) {
+        self.j = j;
+        self.upper = upper;
+        self.lower
+            .iter_mut()
+            .zip(lower)
+            .for_each(|(old, new)| *old = new.unwrap_or(*old));
+    }
+    /// Updates bounds after centroids move, given the largest drift per group.
+    pub fn update<const K: usize>(&mut self, drift: &super::Drift<K>, groups: &[f32; G]) {
+        self.upper += drift.as_array()[self.j()];
+        self.lower
+            .iter_mut()
+            .zip(groups.iter())
+            .for_each(|(lower, movement)| *lower = (*lower - movement).max(0.0));
+    }
+}
+
+impl<const G: usize> super::Assignment for Yinyang<G> {
+    fn j(&self) -> usize {
+        self.j
+    }
+}
+
+/// From a full scan: `(nearest, distance)` and per-group lower bounds.
+impl<const G: usize> From<((usize, f32), [f32; G])> for Yinyang<G> {
+    fn from(((j, upper), lower): ((usize, f32), [f32; G])) -> Self {
+        Self { j, upper, lower }
+    }
+}
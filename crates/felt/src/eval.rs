@@ -0,0 +1,86 @@
+use deuce::Hand;
+use deuce::Observation;
+use deuce::Street;
+use deuce::Strength;
+use pokerkit::Probability;
+use wasm_bindgen::prelude::*;
+
+/// Best hand category for 5–7 cards, e.g. `"TwoPair K9"`.
+#[wasm_bindgen]
+pub fn evaluate(cards: &str) -> Result<String, String> {
+    strength(cards).map(|s| s.ranking().to_string().split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Showdown comparison of two 5–7 card hands: `1`, `0`, or `-1`.
+#[wasm_bindgen]
+pub fn compare(hero: &str, villain: &str) -> Result<i8, String> {
+    Ok(strength(hero)?.cmp(&strength(villain)?) as i8)
+}
+
+/// Exact river equity against a uniformly random opponent hand.
+/// Takes an observation such as `"AsKd ~ 2c7h9sTdJd"`.
+#[wasm_bindgen]
+pub fn equity(observation: &str) -> Result<Probability, String> {
+    let observation = Observation::try_from(observation)?;
+    match observation.street() {
+        Street::Rive => Ok(observation.equity()),
+        street => Err(format!("exact equity needs a river board, got {street}")),
+    }
+}
+
+/// Equity of an observation against a specific villain holding, with
+/// `trials` Monte Carlo runouts before the river (exact on the river).
+#[wasm_bindgen]
+pub fn equity_vs(observation: &str, villain: &str, trials: usize) -> Result<Probability, String> {
+    let observation = Observation::try_from(observation)?;
+    let villain = Hand::try_from(villain)?;
+    match villain.size() {
+        2 if !Hand::overlaps(&villain, &Hand::from(observation)) => Ok(observation.equity_vs(villain, trials.max(1))),
+        2 => Err("villain shares cards with the observation".to_string()),
+        n => Err(format!("villain needs 2 cards, got {n}")),
+    }
+}
+
+fn strength(cards: &str) -> Result<Strength, String> {
+    let hand = Hand::try_from(cards)?;
+    match hand.size() {
+        5..=7 => Ok(Strength::from(hand)),
+        n => Err(format!("evaluation needs 5-7 distinct cards, got {n}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn evaluates_categories() {
+        assert!(evaluate("As Ks Qs Js Ts").unwrap().starts_with("StraightFlush"));
+        assert!(evaluate("2c 2d 9h 9s Kc").unwrap().starts_with("TwoPair"));
+        assert!(evaluate("2c 3d").is_err());
+        assert!(evaluate("zz").is_err());
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn compares_showdowns() {
+        assert_eq!(compare("Ac Ad 2h 7s 9c", "Kc Kd 2h 7s 9c").unwrap(), 1);
+        assert_eq!(compare("Kc Kd 2h 7s 9c", "Ac Ad 2h 7s 9c").unwrap(), -1);
+        assert_eq!(compare("Ac 2d 3h 4s 5c", "Ad 2c 3s 4h 5d").unwrap(), 0);
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn river_equity_bounds() {
+        let nuts = equity("AsKs ~ QsJsTs2c3d").unwrap();
+        assert_eq!(nuts, 1.0);
+        assert!(equity("AsKs ~ QsJsTs").is_err());
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn equity_vs_villain() {
+        assert_eq!(equity_vs("AsAh ~ 2c7d9hJsQc", "KsKh", 1).unwrap(), 1.0);
+        let flop = equity_vs("AsAh ~ 2c7d9h", "KsKh", 200).unwrap();
+        assert!((0.0..=1.0).contains(&flop));
+        assert!(equity_vs("AsAh ~ 2c7d9h", "AsKh", 10).is_err());
+    }
+}
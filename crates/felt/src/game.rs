@@ -0,0 +1,178 @@
+use deuce::Hand;
+use kicker::Action;
+use kicker::Turn;
+use pokerkit::Chips;
+use pokerkit::N;
+use wasm_bindgen::prelude::*;
+
+/// The memoryless state of a heads-up hand, as seen from JavaScript.
+///
+/// Thin wrapper over [`kicker::Game`]. Every transition returns a new
+/// `Game`, mirroring the functional `apply` of the engine.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Game(kicker::Game);
+
+#[wasm_bindgen]
+impl Game {
+    /// Blinds posted, random hole cards, default stacks, P0 on the button.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self(kicker::Game::root())
+    }
+    /// Blinds posted with an explicit dealer and starting stacks.
+    pub fn start(dealer: usize, stacks: Vec<Chips>) -> Result<Game, String> {
+        let stacks = <[Chips; N]>::try_from(stacks).map_err(|s| format!("expected {N} stacks, got {}", s.len()))?;
+        if dealer < N {
+            Ok(Self(kicker::Game::from_start(dealer, stacks)))
+        } else {
+            Err(format!("dealer {dealer} out of range"))
+        }
+    }
+    /// `"P0"` / `"P1"` at a decision, `"?"` at a deal, `"-"` when over.
+    pub fn turn(&self) -> String {
+        self.0.turn().to_string()
+    }
+    /// Seat index of the player to act, if any.
+    pub fn actor(&self) -> Option<usize> {
+        self.0.player().map(|turn| turn.position())
+    }
+    pub fn is_terminal(&self) -> bool {
+        self.0.turn().is_terminal()
+    }
+    pub fn is_chance(&self) -> bool {
+        self.0.turn().is_chance()
+    }
+    pub fn street(&self) -> String {
+        self.0.street().label().to_string()
+    }
+    pub fn pot(&self) -> Chips {
+        self.0.pot()
+    }
+    pub fn stacks(&self) -> Vec<Chips> {
+        self.0.stacks().to_vec()
+    }
+    pub fn stakes(&self) -> Vec<Chips> {
+        self.0.stakes().to_vec()
+    }
+    /// Community cards in deal order.
+    pub fn board(&self) -> String {
+        self.0.board().to_string()
+    }
+    /// Hole cards of one seat.
+    pub fn hole(&self, position: usize) -> Result<String, String> {
+        self.0
+            .seats()
+            .get(position)
+            .map(|seat| Hand::from(seat.cards()).to_string())
+            .ok_or_else(|| format!("seat {position} out of range"))
+    }
+    /// Chips the actor must add to call.
+    pub fn to_call(&self) -> Chips {
+        self.0.to_call()
+    }
+    /// Minimum legal raise-to amount for the actor.
+    pub fn to_raise(&self) -> Chips {
+        self.0.to_raise()
+    }
+    /// Legal actions for the player to act, as action tokens.
+    /// Raises are represented by their minimum size.
+    pub fn legal(&self) -> Vec<String> {
+        match self.0.turn() {
+            Turn::Choice(_) => self.0.legal().iter().map(Action::to_string).collect(),
+            _ => Vec::new(),
+        }
+    }
+    /// True if the action token parses and is legal here.
+    pub fn allows(&self, action: &str) -> bool {
+        Action::try_from(action).is_ok_and(|a| self.0.is_allowed(&a))
+    }
+    /// Apply a player action token such as `"RAISE 6"`.
+    pub fn apply(&self, action: &str) -> Result<Game, String> {
+        let action = Action::try_from(action).map_err(String::from)?;
+        self.0.try_apply(action).map(Self).map_err(|e| e.to_string())
+    }
+    /// Deal the next street's cards at a chance node.
+    pub fn deal(&self) -> Result<Game, String> {
+        match self.0.turn() {
+            Turn::Chance => Ok(Self(self.0.apply(self.0.reveal()))),
+            turn => Err(format!("cannot deal at {turn}")),
+        }
+    }
+    /// Per-seat settlements as JSON once the hand is over.
+    pub fn settlements(&self) -> Result<String, String> {
+        match self.0.turn() {
+            Turn::Terminal => serde_json::to_string(&self.0.settlements()).map_err(|e| e.to_string()),
+            turn => Err(format!("hand still live at {turn}")),
+        }
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<kicker::Game> for Game {
+    fn from(game: kicker::Game) -> Self {
+        Self(game)
+    }
+}
+impl From<Game> for kicker::Game {
+    fn from(game: Game) -> Self {
+        game.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn root_is_preflop_decision() {
+        let game = Game::new();
+        assert_eq!(game.street(), "Preflop");
+        assert_eq!(game.pot(), kicker::Game::sblind() + kicker::Game::bblind());
+        assert!(game.actor().is_some());
+        assert!(game.legal().iter().any(|a| a == "FOLD"));
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn every_legal_token_applies() {
+        let game = Game::new();
+        for action in game.legal() {
+            assert!(game.allows(&action));
+            assert!(game.apply(&action).is_ok(), "{action}");
+        }
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn limp_check_reaches_flop() {
+        let game = Game::new().apply("CALL 1").unwrap().apply("CHECK").unwrap();
+        assert!(game.is_chance());
+        assert!(game.legal().is_empty());
+        let flop = game.deal().unwrap();
+        assert_eq!(flop.street(), "Flop");
+        assert_eq!(flop.board().split_whitespace().count(), 3);
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn fold_settles() {
+        let game = Game::new().apply("FOLD").unwrap();
+        assert!(game.is_terminal());
+        assert!(game.settlements().is_ok());
+        assert!(game.apply("CHECK").is_err());
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn rejects_garbage() {
+        let game = Game::new();
+        assert!(game.apply("BET BIG").is_err());
+        assert!(game.deal().is_err());
+        assert!(Game::start(0, vec![100]).is_err());
+        assert!(Game::start(2, vec![100, 100]).is_err());
+        assert!(game.hole(7).is_err());
+    }
+}
@@ -0,0 +1,36 @@
+//! WebAssembly bindings for browser clients.
+//!
+//! The frontend used to re-implement betting rules, legal-action menus,
+//! and showdown evaluation in JavaScript. This crate exposes the real
+//! engine instead, compiled to `wasm32-unknown-unknown` through
+//! `wasm-bindgen`, so the browser and the server can never disagree about
+//! what a legal raise is.
+//!
+//! ## Exports
+//!
+//! - [`Game`] — memoryless [`kicker::Game`] stepping: legal actions, apply, deal
+//! - [`Witness`] — one seat's [`kicker::Witness`] history with JSON round-trip
+//! - [`evaluate`] / [`compare`] — 5–7 card hand evaluation via [`deuce::Evaluator`]
+//! - [`equity`] / [`equity_vs`] — showdown equity via [`deuce::Observation`]
+//!
+//! ## Wire format
+//!
+//! Everything crosses the boundary as strings and numbers. Actions use
+//! the same tokens as [`kicker::Action`]'s `Display` / `TryFrom<&str>`
+//! (`"CALL 1"`, `"RAISE 6"`, `"FOLD"`), cards use `"As Kd"`, and
+//! witnesses use the serde JSON the HTTP API already speaks. Failures
+//! surface as thrown JS strings rather than panics.
+//!
+//! ## Building
+//!
+//! ```text
+//! wasm-pack build crates/felt --target web
+//! wasm-pack test  crates/felt --node
+//! ```
+mod eval;
+mod game;
+mod witness;
+
+pub use eval::*;
+pub use game::*;
+pub use witness::*;
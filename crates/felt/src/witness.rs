@@ -0,0 +1,124 @@
+use super::Game;
+use kicker::Action;
+use kicker::Recall;
+use kicker::Turn;
+use wasm_bindgen::prelude::*;
+
+/// One seat's perfect-recall history of a hand.
+///
+/// Wraps [`kicker::Witness`] and shares its serde JSON encoding, so a
+/// witness built in the browser can be posted to the strategy endpoints
+/// verbatim, and one received from the server can be stepped locally.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Witness(kicker::Witness);
+
+#[wasm_bindgen]
+impl Witness {
+    /// Fresh hand (blinds posted, no decisions) from seat `pov`'s view.
+    #[wasm_bindgen(constructor)]
+    pub fn new(pov: usize) -> Result<Witness, String> {
+        if pov < pokerkit::N {
+            Ok(Self(kicker::Witness::initial(Turn::Choice(pov))))
+        } else {
+            Err(format!("seat {pov} out of range"))
+        }
+    }
+    /// Parse the JSON wire format, validating the action sequence.
+    pub fn parse(json: &str) -> Result<Witness, String> {
+        serde_json::from_str::<kicker::Witness>(json)
+            .map_err(|e| e.to_string())?
+            .validate_observation()
+            .map(Self)
+            .map_err(|e| e.to_string())
+    }
+    /// Serialize to the JSON wire format.
+    pub fn json(&self) -> Result<String, String> {
+        serde_json::to_string(&self.0).map_err(|e| e.to_string())
+    }
+    /// Current game state after replaying every action.
+    pub fn head(&self) -> Game {
+        Game::from(self.0.head())
+    }
+    /// Legal action tokens at the head.
+    pub fn legal(&self) -> Vec<String> {
+        self.head().legal()
+    }
+    /// Recorded actions, excluding blinds.
+    pub fn actions(&self) -> Vec<String> {
+        self.0.actions().iter().map(Action::to_string).collect()
+    }
+    /// Append an action token. Deals are inserted from the arrangement.
+    pub fn push(&self, action: &str) -> Result<Witness, String> {
+        let action = Action::try_from(action).map_err(String::from)?;
+        self.0.try_push(action).map(Self).map_err(|e| e.to_string())
+    }
+    /// Drop the last decision.
+    pub fn undo(&self) -> Result<Witness, String> {
+        if self.0.can_undo() {
+            Ok(Self(self.0.undo()))
+        } else {
+            Err("nothing to undo".to_string())
+        }
+    }
+    /// Hero's observation: `"AsKd ~ 2c7h9s"`.
+    pub fn seen(&self) -> String {
+        self.0.seen().to_string()
+    }
+    /// The boxed text rendering used by the CLI tools.
+    pub fn render(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl From<kicker::Witness> for Witness {
+    fn from(witness: kicker::Witness) -> Self {
+        Self(witness)
+    }
+}
+impl From<Witness> for kicker::Witness {
+    fn from(witness: Witness) -> Self {
+        witness.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn json_round_trip() {
+        let witness = Witness::new(0)
+            .unwrap()
+            .push("CALL 1")
+            .unwrap()
+            .push("RAISE 4")
+            .unwrap();
+        let json = witness.json().unwrap();
+        assert_eq!(Witness::parse(&json).unwrap(), witness);
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn push_undo_inverse() {
+        let witness = Witness::new(1).unwrap();
+        let pushed = witness.push("CALL 1").unwrap();
+        assert_eq!(pushed.actions().len(), 1);
+        assert_eq!(pushed.undo().unwrap(), witness);
+        assert!(witness.undo().is_err());
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn legal_matches_head() {
+        let witness = Witness::new(0).unwrap().push("CALL 1").unwrap();
+        assert_eq!(witness.legal(), witness.head().legal());
+    }
+
+    #[wasm_bindgen_test(unsupported = test)]
+    fn rejects_illegal() {
+        let witness = Witness::new(0).unwrap();
+        assert!(witness.push("CHECK").is_err());
+        assert!(Witness::parse("{}").is_err());
+        assert!(Witness::new(5).is_err());
+    }
+}
@@ -21,7 +21,7 @@ pub struct FastSession {
 
 impl FastSession {
     pub async fn new(client: Arc<Client>) -> Self {
-        PreTraining::run(&client).await;
+        PreTraining::run(&client, None).await;
         crate::ensure_all(&client).await;
         Fingerprint::check(&client).await;
         Self {
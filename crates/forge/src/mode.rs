@@ -3,11 +3,13 @@ use crate::*;
 use daybook::Check;
 use daybook::Schema;
 use nlhe::NlheProfile;
+use std::path::PathBuf;
 
 /// Training mode parsed from command line arguments
 pub enum Mode {
     Status,
-    Cluster,
+    /// Clusters pending streets, optionally saving their artifacts into a directory.
+    Cluster(Option<PathBuf>),
     Fast,
     Slow,
     Reset,
@@ -23,7 +25,7 @@ impl Mode {
             Self::Reset => Self::reset(&client).await,
             Self::Forget => Self::forget(&client).await,
             Self::Status => client.status().await,
-            Self::Cluster => PreTraining::run(&client).await,
+            Self::Cluster(ref artifacts) => PreTraining::run(&client, artifacts.as_deref()).await,
         }
     }
 
@@ -1,20 +1,31 @@
 //! Pretraining - hierarchical clustering pipeline for poker abstractions.
 //!
 //! Manages clustering from scratch to postgres without disk I/O:
-//! 1. River: equity-based abstractions (computed from scratch)
-//! 2. Turn: k-means on river distributions (hydrates river data)
+//! 1. River: equity-based abstractions (computed from scratch), or OCHS
+//!    k-means over sampled river hands when the version asks for it
+//! 2. Turn: k-means on river distributions (hydrates river data), or
+//!    mini-batch k-means streamed over isomorphisms when a batch size is set
 //! 3. Flop: k-means on turn distributions (hydrates turn data)
 //! 4. Preflop: 1:1 isomorphism enumeration (computed from scratch)
+//!
+//! Under a perfect-recall version every step keys hands by [`Waugh`]
+//! index instead of isomorphism and writes the `recall` table.
 use daybook::*;
 use deuce::*;
 use kicker::*;
 use lloyd::*;
+use std::path::Path;
 use std::sync::Arc;
 use tokio_postgres::Client;
 
 type PrefLayer = Layer<{ Street::Pref.k() }, { Street::Pref.n_isomorphisms() }>;
 type FlopLayer = Layer<{ Street::Flop.k() }, { Street::Flop.n_isomorphisms() }>;
 type TurnLayer = Layer<{ Street::Turn.k() }, { Street::Turn.n_isomorphisms() }>;
+type PrefRecall = Layer<{ Street::Pref.k() }, { Street::Pref.n_recalls() }>;
+type FlopRecall = Layer<{ Street::Flop.k() }, { Street::Flop.n_recalls() }>;
+type TurnRecall = Layer<{ Street::Turn.k() }, { Street::Turn.n_recalls() }>;
+type FlopStream = StreamLayer<{ Street::Flop.k() }>;
+type TurnStream = StreamLayer<{ Street::Turn.k() }>;
 
 /// Zero-sized orchestrator for the clustering pipeline.
 /// Encapsulates all clustering logic so Trainer stays clean.
@@ -23,32 +34,69 @@ pub struct PreTraining;
 impl PreTraining {
     /// Run the complete clustering pipeline if needed.
     /// Always runs finalize to ensure derived tables exist.
-    pub async fn run(client: &Arc<Client>) {
+    /// With `artifacts`, each street clustered by this run is also saved
+    /// there via [`Artifacts::save`], for file-based diagnostics.
+    pub async fn run(client: &Arc<Client>, artifacts: Option<&Path>) {
         let streets = Self::pending(client).await;
         for street in streets.iter().copied() {
             tracing::info!(%street, "beginning clustering");
-            Self::cluster(street, client).await.stream(client).await;
+            if pokerkit::version().recall() {
+                Self::persist(street, Self::recollect(street, client).await, client, artifacts).await;
+            } else {
+                Self::persist(street, Self::cluster(street, client).await, client, artifacts).await;
+            }
         }
         if !streets.is_empty() {
             Self::index(client).await;
         }
         Self::derive::<Abstraction>(client).await;
+        if pokerkit::version().ochs() {
+            Self::reequity(client).await;
+        }
         Self::derive::<Street>(client).await;
         tracing::info!("vacuum analyze all tables");
         client.batch_execute("VACUUM ANALYZE;").await.expect("vacuum analyze");
     }
 
     /// Cluster a street via k-means. Dependencies loaded from postgres.
-    /// Dispatches to the appropriate const-generic Layer based on street.
+    /// Dispatches to the appropriate const-generic Layer based on street,
+    /// streaming mini-batches where the hyperparameters set a batch size.
     async fn cluster(street: Street, client: &Arc<Client>) -> Artifacts {
+        let streaming = KmeansHyperParams::DEFAULT.batch(street).is_some();
         match street {
+            Street::Rive if pokerkit::version().ochs() => OchsRiver::cluster_into(client).await,
             Street::Rive => Artifacts::from(Lookup::grow(street)),
+            Street::Turn if streaming => TurnStream::cluster(street, client).await,
+            Street::Flop if streaming => FlopStream::cluster(street, client).await,
             Street::Turn => TurnLayer::cluster(street, client).await,
             Street::Flop => FlopLayer::cluster(street, client).await,
             Street::Pref => PrefLayer::cluster(street, client).await,
         }
     }
 
+    /// Cluster a street's perfect-recall indices. River buckets stay on
+    /// the equity ladder; OCHS is not combined with perfect recall.
+    async fn recollect(street: Street, client: &Arc<Client>) -> Artifacts<RecallLookup> {
+        match street {
+            Street::Rive => Artifacts::from(RecallLookup::grow(street)),
+            Street::Turn => TurnRecall::recollect(street, client).await,
+            Street::Flop => FlopRecall::recollect(street, client).await,
+            Street::Pref => PrefRecall::recollect(street, client).await,
+        }
+    }
+
+    /// Save a street's artifacts into `dir`, if given, then stream them.
+    async fn persist<L>(street: Street, layer: Artifacts<L>, client: &Arc<Client>, dir: Option<&Path>)
+    where
+        L: Assignment + Streamable,
+    {
+        if let Some(dir) = dir {
+            tracing::info!(%street, dir = %dir.display(), "saving artifacts");
+            layer.save(dir).expect("save artifacts");
+        }
+        layer.stream(client).await;
+    }
+
     /// Collect unclustered streets in reverse order (river first).
     async fn pending(client: &Arc<Client>) -> Vec<Street> {
         let mut pending = Vec::new();
@@ -81,11 +129,37 @@ impl PreTraining {
 
     /// Index tables after data is streamed.
     async fn index(client: &Arc<Client>) {
-        Lookup::finalize(client).await;
+        if pokerkit::version().recall() {
+            RecallLookup::finalize(client).await;
+        } else {
+            Lookup::finalize(client).await;
+        }
         Metric::finalize(client).await;
         Future::finalize(client).await;
     }
 
+    /// OCHS river buckets carry no equity in their index, so write each
+    /// bucket's stored centroid strength, then recompute the streets above
+    /// it bottom-up from their transitions.
+    async fn reequity(client: &Arc<Client>) {
+        tracing::info!("writing OCHS river equities");
+        Ochs::install(OchsRiver::stored(client).await);
+        let table = Abstraction::name();
+        let update = format!("UPDATE {table} SET equity = $1 WHERE abs = $2");
+        for abs in Abstraction::all(Street::Rive) {
+            client
+                .execute(&update, &[&Ochs::equity(&abs), &i16::from(abs)])
+                .await
+                .expect("update river equity");
+        }
+        for street in [Street::Turn, Street::Flop, Street::Pref] {
+            client
+                .execute(&format!("UPDATE {table} SET equity = get_equity(abs) WHERE street = $1"), &[&(street as i16)])
+                .await
+                .expect("update equity");
+        }
+    }
+
     /// Derive a table from existing data using SQL functions.
     async fn derive<D>(client: &Arc<Client>)
     where
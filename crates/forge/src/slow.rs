@@ -14,7 +14,7 @@ pub struct SlowSession {
 
 impl SlowSession {
     pub async fn new(client: Arc<Client>) -> Self {
-        PreTraining::run(&client).await;
+        PreTraining::run(&client, None).await;
         crate::ensure_all(&client).await;
         Fingerprint::check(&client).await;
         Self {
@@ -14,14 +14,22 @@ use std::hash::Hash;
 ///
 /// # Encoding
 ///
-/// Packed as `[8 bits street][8 bits index]` in a `u16`, enabling efficient
-/// storage and comparison.
-#[derive(Default, Copy, Clone, Hash, Eq, PartialEq, Debug, PartialOrd, Ord)]
+/// Packed as `[6 bits index hi][2 bits street][8 bits index lo]` in a `u16`,
+/// enabling efficient storage and comparison. The 14-bit index admits up to
+/// 16384 buckets per street; indices below 256 keep the original
+/// `[8 bits street][8 bits index]` layout, so pre-V4 tables decode unchanged.
+/// Ordered by `(street, index)`, not by the packed `u16`: above index 255
+/// the high bits would sort a flop bucket after every turn bucket.
+#[derive(Default, Copy, Clone, Hash, Eq, PartialEq, Debug)]
 pub struct Abstraction(u16);
 
 const INDEX_MASK: u16 = 0xFF;
+const INDEX_HI_BITS: u16 = 10;
 const STREET_BITS: u16 = 8;
-const STREET_MASK: u16 = 0xFF << STREET_BITS;
+const STREET_MASK: u16 = 0x03 << STREET_BITS;
+
+/// Exclusive upper bound on the bucket index.
+pub const INDEX_LIMIT: usize = 1 << 14;
 
 impl Abstraction {
     pub const DELIM: &'static str = "::";
@@ -47,7 +55,7 @@ impl Abstraction {
     }
     /// Extracts the bucket index.
     pub const fn index(&self) -> usize {
-        (self.0 & INDEX_MASK) as usize
+        ((self.0 & INDEX_MASK) | ((self.0 >> INDEX_HI_BITS) << STREET_BITS)) as usize
     }
     /// All abstractions for a given street.
     pub fn all(street: Street) -> Vec<Self> {
@@ -67,11 +75,25 @@ impl Abstraction {
     }
 }
 
+impl Ord for Abstraction {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.street(), self.index()).cmp(&(other.street(), other.index()))
+    }
+}
+
+impl PartialOrd for Abstraction {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl From<(Street, usize)> for Abstraction {
     fn from((street, index): (Street, usize)) -> Self {
-        let hi_bits = (street as u16) << STREET_BITS;
+        debug_assert!(index < INDEX_LIMIT);
+        let hi_bits = (index as u16 >> STREET_BITS) << INDEX_HI_BITS;
+        let st_bits = (street as u16) << STREET_BITS;
         let lo_bits = index as u16 & INDEX_MASK;
-        Self(hi_bits | lo_bits)
+        Self(hi_bits | st_bits | lo_bits)
     }
 }
 
@@ -217,6 +239,28 @@ mod tests {
         assert_eq!(abs, Abstraction::try_from(str.as_str()).unwrap());
     }
     #[test]
+    fn narrow_layout_is_stable() {
+        assert_eq!(u16::from(Abstraction::from((Street::Turn, 0xAB))), 0x02AB);
+        assert_eq!(Abstraction::from(0x03_64u16), Abstraction::from((Street::Rive, 100)));
+    }
+    #[test]
+    fn wide_index_roundtrip() {
+        for street in Street::all() {
+            for i in [255, 256, 1023, 4095, INDEX_LIMIT - 1] {
+                let abs = Abstraction::from((street, i));
+                assert_eq!(abs.street(), street);
+                assert_eq!(abs.index(), i);
+                assert_eq!(abs, Abstraction::from(i16::from(abs)));
+                assert_eq!(abs, Abstraction::try_from(abs.to_string().as_str()).unwrap());
+            }
+        }
+    }
+    #[test]
+    fn orders_by_street_then_index() {
+        assert!(Abstraction::from((Street::Flop, 256)) < Abstraction::from((Street::Turn, 0)));
+        assert!(Abstraction::from((Street::Turn, 255)) < Abstraction::from((Street::Turn, 256)));
+    }
+    #[test]
     fn street_index_roundtrip() {
         for street in Street::all() {
             for i in 0..street.n_abstractions() {
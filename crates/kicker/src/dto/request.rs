@@ -1,6 +1,7 @@
 use crate::*;
 use deuce::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Serialize, Deserialize)]
 pub struct SetStreets {
@@ -67,12 +68,109 @@ impl From<&Witness> for GetPolicy {
     }
 }
 
+/// Node-locked resolve for `/strategy/lock`. `turn`/`seen`/`past` place
+/// hero's decision as in [`GetPolicy`]; each pin continues `past` to one of
+/// the opponent's decisions on the same street and fixes frequencies
+/// there. `kind` is `world`
+/// or `full`. With `exploit`, the opponent plays the blueprint wherever it
+/// isn't pinned and hero best-responds; otherwise the unpinned opponent
+/// nodes re-solve too, for a restricted equilibrium.
+#[derive(Serialize, Deserialize)]
+pub struct GetLock {
+    pub turn: Turn,
+    pub seen: Observation,
+    pub past: Vec<Action>,
+    #[serde(default = "default_lock_kind")]
+    pub kind: Kind,
+    #[serde(default)]
+    pub exploit: bool,
+    pub pins: Vec<ApiPin>,
+}
+fn default_lock_kind() -> Kind {
+    Kind::Full
+}
+
+/// One locked opponent decision: the full action sequence reaching it and
+/// the pinned edge frequencies. Edges left out share the remaining mass in
+/// blueprint proportion, so `{"F": 0}` reads "never folds here".
+#[derive(Serialize, Deserialize)]
+pub struct ApiPin {
+    pub past: Vec<Action>,
+    pub policy: BTreeMap<Edge, f32>,
+}
+
+/// Posterior range query for `/strategy/range` and `/strategy/signalled`.
+/// `within` optionally restricts the answer to a [`Range`] (e.g. `"TT+, AK"`),
+/// renormalizing over the surviving combos.
+#[derive(Serialize, Deserialize)]
+pub struct GetPosterior {
+    pub turn: Turn,
+    pub seen: Observation,
+    pub past: Vec<Action>,
+    #[serde(default)]
+    pub within: Option<Range>,
+}
+
+/// Preflop chart export: every line with at most `depth` bets and
+/// raises, rendered as `format`.
+#[derive(Serialize, Deserialize)]
+pub struct GetChart {
+    #[serde(default = "default_depth")]
+    pub depth: usize,
+    #[serde(default)]
+    pub format: ChartFormat,
+}
+fn default_depth() -> usize {
+    2
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartFormat {
+    #[default]
+    Json,
+    Csv,
+    Html,
+}
+
+/// Clustering diagnostics over `samples` hands per street, rendered
+/// as `format`.
+#[derive(Serialize, Deserialize)]
+pub struct GetDiagnostics {
+    #[serde(default = "default_samples")]
+    pub samples: usize,
+    #[serde(default)]
+    pub format: ReportFormat,
+}
+fn default_samples() -> usize {
+    1024
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[default]
+    Json,
+    Html,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct GetDistance {
     pub a: String,
     pub b: String,
 }
 
+/// Range-vs-range equity on a (possibly empty) board. Ranges use
+/// [`Range`] notation; `trials` bounds Monte Carlo when enumeration
+/// is too expensive.
+#[derive(Serialize, Deserialize)]
+pub struct GetMatchup {
+    pub ranges: Vec<String>,
+    #[serde(default)]
+    pub board: String,
+    pub trials: Option<usize>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct GetSnapshots {
     #[serde(default = "default_limit")]
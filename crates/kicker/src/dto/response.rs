@@ -42,6 +42,11 @@ pub struct ApiStrategy {
 /// | true  | false | `Depth`     | `/strategy/depth`      |
 /// | false | true  | `World`     | `/strategy/world`      |
 /// | true  | true  | `Full`      | `/strategy/full`       |
+///
+/// `Exact` sits outside the cube: the world-partitioned, depth-limited
+/// solve keyed on exact hole cards, served at `/strategy/exact` for
+/// streets [`pokerkit::endgame`] resolves without abstraction.
+/// `/strategy/lock` reuses `World` and `Full` for node-locked resolves.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum Kind {
@@ -49,6 +54,7 @@ pub enum Kind {
     Depth,
     World,
     Full,
+    Exact,
 }
 
 impl Kind {
@@ -61,6 +67,7 @@ impl Kind {
             Self::Depth => "depth",
             Self::World => "world",
             Self::Full => "full",
+            Self::Exact => "exact",
         }
     }
     /// Project a cube [`Config`] onto its route corner. `dirac` is
@@ -108,6 +115,9 @@ pub struct ApiOpponentRange {
     /// the probability that villain holds that exact pocket given the
     /// observed action history under the blueprint.
     pub entries: Vec<ApiRangeEntry>,
+    /// The same posterior in [`Range`] notation, scaled so the likeliest
+    /// combo has weight 1 and rounded to twentieths.
+    pub notation: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -83,6 +83,26 @@ impl<const P: usize> GameN<P> {
         game.act(game.posts());
         game
     }
+    /// Creates a postflop spot: `board` already dealt, `pot` in the middle
+    /// and `stacks` behind, with the first seat to act.
+    ///
+    /// Seats are ordered by postflop action — seat 0 acts first and the
+    /// last seat holds the button. The pot is dead money attributed to no
+    /// seat, so [`settlements`](Self::settlements) only account for chips
+    /// committed after the spot. Hole cards are dealt at random off the
+    /// board; range solvers overwrite them or ignore them.
+    pub fn spot(board: Hand, pot: Chips, stacks: [Chips; P]) -> Self {
+        let mut deck = Deck::from(board.complement());
+        let mut game = Self {
+            pot,
+            board: Board::from(board),
+            seats: std::array::from_fn(|i| Seat::from((deck.hole(), stacks[i]))),
+            dealer: P - 1,
+            ticker: 0,
+        };
+        game.next_player();
+        game
+    }
     /// Sets a specific seat's hole cards.
     pub fn deal(mut self, position: Position, hole: Hole) -> Self {
         self.seats[position].reset_cards(hole);
@@ -809,6 +829,32 @@ impl<const P: usize> GameN<P> {
             }
         }
     }
+    /// A pot-fraction raise edge missing from the abstract grid here,
+    /// nearest in size to `chips`.
+    ///
+    /// Nested subgame solving borrows it to label an off-tree raise as a
+    /// real edge of the re-solved tree. BB-denominated opening grids use
+    /// no pot-fraction sizes, so any [`RAISES`] size can label an off-grid
+    /// open. `None` past the raise cap, or when the grid already uses
+    /// every [`RAISES`] size.
+    pub fn spare(&self, chips: Chips, depth: usize) -> Option<Edge> {
+        let grid = Edge::raises(self.street(), depth);
+        let ratio = chips as Utility / self.pot() as Utility;
+        if grid.is_empty() {
+            return None;
+        }
+        RAISES
+            .iter()
+            .map(|&(n, d)| Edge::Raise(Odds::new(n, d)))
+            .filter(|edge| !grid.contains(edge))
+            .min_by(|a, b| {
+                let distance = |edge: &Edge| match edge {
+                    Edge::Raise(odds) => (Probability::from(*odds) - ratio).abs(),
+                    _ => unreachable!("spare edges are pot-fraction raises"),
+                };
+                distance(a).total_cmp(&distance(b))
+            })
+    }
     /// Snaps a chip amount to the nearest edge in the grid.
     fn snap_to_edge(&self, chips: Chips, depth: usize) -> Edge {
         Edge::raises(self.street(), depth)
@@ -970,6 +1016,22 @@ mod tests {
         assert_eq!(game.turn(), Turn::Choice(game.dealer)); // dealer acts first
     }
 
+    /// seat 0 acts first on every street of a spot, the button closes
+    #[test]
+    fn spot_orders_seats_by_action() {
+        let board = Hand::try_from("Ks7d2c").unwrap();
+        let game = Game::spot(board, 60, [170, 170]);
+        assert_eq!(game.street(), Street::Flop);
+        assert_eq!(game.pot(), 60);
+        assert_eq!(game.turn(), Turn::Choice(0));
+        let game = game.apply(Action::Check);
+        assert_eq!(game.turn(), Turn::Choice(1));
+        let game = game.apply(Action::Check);
+        assert_eq!(game.turn(), Turn::Chance);
+        let game = game.force_apply(Action::Draw(Hand::try_from("4h").unwrap()));
+        assert_eq!(game.turn(), Turn::Choice(0));
+    }
+
     #[test]
     fn everyone_folds_pref() {
         let game = Game::root();
@@ -1872,15 +1934,75 @@ mod tests {
         }
     }
 
+    /// Injection variants leave a raise between two distant anchors
+    /// off-tree, handing back the verbatim action. Raise(7) = 3.5 BB is
+    /// more than 10% from both BBs(3) and BBs(4).
+    #[test]
+    fn translate_injection_off_tree_is_free() {
+        use rand::SeedableRng;
+        use rand::rngs::SmallRng;
+        let game = Game::root();
+        let ref mut rng = SmallRng::seed_from_u64(0);
+        for lang in [
+            Translation::Exact,
+            Translation::EpsilonPrune,
+            Translation::EpsilonHarmonic,
+        ] {
+            let result = game.translate(Action::Raise(7), 0, &lang, rng);
+            assert_eq!(result, Translated::Free(Action::Raise(7)), "translation {lang:?}");
+        }
+        let canonical = game.translate(Action::Raise(6), 0, &Translation::Exact, rng);
+        assert_eq!(canonical, Translated::Snap(Edge::Open(3)));
+    }
+
+    /// Spare labels come from the pot-fraction sizes the grid leaves
+    /// out.
+    #[test]
+    fn spare_picks_nearest_unused_raise() {
+        let game = Game::root();
+        let spare = game.spare(2 * game.pot(), 1).expect("3-bet grid leaves sizes unused");
+        assert_eq!(spare, Edge::Raise(Odds::new(3, 2)));
+        assert!(!Edge::raises(game.street(), 1).contains(&spare));
+    }
+
+    /// An off-grid preflop open still gets a spare: the BB-denominated
+    /// grid leaves every pot-fraction size free.
+    #[test]
+    fn spare_labels_offgrid_open() {
+        let game = Game::root();
+        let chips = 7 * B_BLIND / 2;
+        let ref mut rng = rand::rng();
+        assert_eq!(
+            game.translate(Action::Raise(chips), 0, &Translation::Exact, rng),
+            Translated::Free(Action::Raise(chips))
+        );
+        let spare = game.spare(chips, 0).expect("opening grid has no pot-fraction sizes");
+        assert!(matches!(spare, Edge::Raise(_)));
+        assert!(!Edge::raises(game.street(), 0).contains(&spare));
+        let nearest = RAISES
+            .iter()
+            .map(|&(n, d)| (Probability::from(Odds::new(n, d)) - chips as Utility / game.pot() as Utility).abs())
+            .fold(Utility::INFINITY, Utility::min);
+        let Edge::Raise(odds) = spare else { unreachable!() };
+        assert_eq!((Probability::from(odds) - chips as Utility / game.pot() as Utility).abs(), nearest);
+    }
+
     /// Non-raise actions resolve to the same canonical Edge under every
-    /// translation. Exhaustive over the three variants.
+    /// translation. Exhaustive over the six variants.
     #[test]
     fn translate_non_raise_actions_invariant_across_translations() {
         use rand::SeedableRng;
         use rand::rngs::SmallRng;
         let game = Game::root();
         let ref mut rng = SmallRng::seed_from_u64(0);
-        let translations = [Translation::Snap, Translation::Harmonic, Translation::Phargmax];
+        let translations = [
+            Translation::Snap,
+            Translation::Harmonic,
+            Translation::Phargmax,
+            Translation::Exact,
+            Translation::EpsilonPrune,
+            Translation::EpsilonHarmonic,
+        ];
         let cases = [
             (Action::Fold, Edge::Fold),
             (Action::Check, Edge::Check),
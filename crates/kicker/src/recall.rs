@@ -23,6 +23,7 @@ use pokerkit::Translated;
 /// - `subgame()` — Current street edges only
 /// - `choices()` — Available actions at current state
 /// - `aggression()` — Trailing aggressive action count
+/// - `offtree()` — Latest action, if the translation leaves it off-tree
 /// - `complete()` — Complete action sequence including blinds (for display)
 pub trait Recall {
     /// The starting game state for replaying actions (POST-blind).
@@ -72,12 +73,11 @@ pub trait Recall {
     /// Full edge history (all streets).
     ///
     /// Maps each `Action` onto an `Edge` via the global [`pokerkit::translation`].
-    /// All current [`pokerkit::Translation`] variants (`Snap`, `Harmonic`,
-    /// `Phargmax`) always resolve on-tree; the `Translated::Free` arm
-    /// is unreachable under the live enum and triggers `unreachable!()`
-    /// if hit. If a future Brown-style variant is added, this arm needs
-    /// to be revisited (likely via a custom history walker on the
-    /// player that consumes off-tree actions).
+    /// Injection variants (`Exact`, `EpsilonPrune`, `EpsilonHarmonic`)
+    /// can leave a raise off-tree; blueprint keys have no edge for it,
+    /// so the `Translated::Free` arm falls back to the nearest grid edge
+    /// via [`Game::edgify`]. Players that honor the off-tree amount
+    /// detect it with [`Self::offtree`] and re-solve instead.
     fn history(&self) -> Vec<Edge> {
         let translation = pokerkit::translation();
         let ref mut rng = rand::rng();
@@ -87,11 +87,7 @@ pub trait Recall {
             .scan(Path::default(), |past, (game, action)| {
                 let edge = match game.translate(*action, past.aggression(), &translation, rng) {
                     Translated::Snap(edge) => edge,
-                    Translated::Free(_) => unreachable!(
-                        "no current Translation variant emits Translated::Free; \
-                         add a custom history walker for any future \
-                         off-tree-emitting translation",
-                    ),
+                    Translated::Free(action) => game.edgify(action, past.aggression()),
                 };
                 *past = (*past).into_iter().chain(std::iter::once(edge)).collect();
                 Some(edge)
@@ -99,6 +95,24 @@ pub trait Recall {
             .collect()
     }
 
+    /// The latest action, if the global translation leaves it off the
+    /// abstract tree, with the state it was played from and the raise
+    /// depth there.
+    fn offtree(&self) -> Option<(Game, Action, usize)> {
+        let (action, past) = self.actions().split_last()?;
+        let game = self.states()[past.len()];
+        let depth = past
+            .iter()
+            .rev()
+            .take_while(|a| a.is_choice())
+            .filter(|a| a.is_aggro())
+            .count();
+        match game.translate(*action, depth, &pokerkit::translation(), &mut rand::rng()) {
+            Translated::Free(action) => Some((game, action, depth)),
+            Translated::Snap(_) => None,
+        }
+    }
+
     /// Current street edges only (trailing choice edges before any Draw).
     fn subgame(&self) -> Path {
         self.history()
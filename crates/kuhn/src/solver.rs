@@ -24,7 +24,13 @@ where
         &self.profile
     }
 
-    fn payoffs(&self, prefix: &Prefix<Self::T, Self::E>, game: &Self::G, _: Self::T) -> Payoffs<D> {
+    fn payoffs(
+        &self,
+        prefix: &Prefix<Self::T, Self::E>,
+        _: &[(Self::T, Self::I, Self::E)],
+        game: &Self::G,
+        _: Self::T,
+    ) -> Payoffs<D> {
         Payoffs::uniform(
             self.profile
                 .frontier_payoff(&self.resume(prefix.into_iter().edges(), game)),
@@ -135,6 +141,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use monge::Density;
 
     const N18: usize = 1 << 18;
 
@@ -287,6 +294,7 @@ mod tests {
     // hold regardless of which cards were dealt, providing strong structural
     // correctness checks beyond regret convergence alone.
 
+    const N14: usize = 1 << 14;
     const N16: usize = 1 << 16;
 
     const WORLDS: usize = 2;
@@ -515,4 +523,218 @@ mod tests {
         eprintln!("  regret < 0.01 ✓");
         eprintln!("  K|XB calls ✓");
     }
+
+    // ── gadget tests ────────────────────────────────────────────────────
+    //
+    // Every P1 response to P0's opening is re-solved from the
+    // reach-conditioned posterior over P0's hand, spliced back into the
+    // blueprint, and scored on the full game. Gadget resolves cover P1's
+    // whole range at once; the world partition resolves one dealt hand at
+    // a time, so the opponent inside its subgame faces a single known hand.
+
+    type Blueprint = Kuhn<FlooredRegret, LinearWeight, ExternalSampling>;
+
+    type Policy = std::collections::BTreeMap<KuhnEdge, pokerkit::Probability>;
+
+    const RANKS: [Rank; 3] = [Rank::J, Rank::Q, Rank::K];
+
+    const OPENINGS: [(KuhnEdge, History); 2] = [(KuhnEdge::Check, History::Check), (KuhnEdge::Bet, History::Bet)];
+
+    /// Entry recall, gift recall and reach-conditioned prior over P0's hand
+    /// after P0 plays `opening`, with P1 dealt `rank`.
+    fn resolve_spot(
+        blueprint: &Blueprint,
+        rank: Rank,
+        opening: KuhnEdge,
+    ) -> (CfrRecall<KuhnGame>, CfrRecall<KuhnGame>, Posterior<Rank>) {
+        let hero = Card::ALL.into_iter().find(|c| c.rank() == rank).expect("rank in deck");
+        let villain = Card::ALL.into_iter().find(|c| *c != hero).expect("second card");
+        let root = KuhnGame::root().with_card(1, hero).with_card(0, villain);
+        let prior = Card::ALL
+            .into_iter()
+            .filter(|&c| c != hero)
+            .map(|c| root.with_card(0, c))
+            .map(|game| (game.hole_rank(0), blueprint.external_reach(game, KuhnTurn::Player(1), [opening])))
+            .fold(Posterior::default(), |post, (s, r)| post.add(s, r));
+        let entry = CfrRecall::new(descents_from(root, [opening]), root.apply(opening));
+        let before = CfrRecall::new(vec![], root);
+        (entry, before, prior)
+    }
+
+    /// Averaged subgame policy at `info`, pooled over the first `worlds` worlds.
+    fn averaged(
+        profile: &WorldProfile<'_, subgame::DepthView<'_, KuhnProfile, 1>>,
+        worlds: usize,
+        info: KuhnInfo,
+    ) -> Policy {
+        CfrInfo::choices(&info)
+            .map(|edge| {
+                let p = (0..worlds)
+                    .map(|w| WorldInfo::new(World::from(w), subgame::DepthInfo::<_, 1>::Game(info)))
+                    .map(|wi| CfrNash::averaged_policy(profile, &wi, &subgame::DepthEdge::<_, 1>::Game(edge)))
+                    .sum::<pokerkit::Probability>();
+                (edge, p / worlds as pokerkit::Probability)
+            })
+            .collect()
+    }
+
+    /// Full-game exploitability of the blueprint with every P1 response
+    /// replaced by what `resolve` returns for each opening.
+    fn spliced<F>(blueprint: &Blueprint, resolve: F) -> pokerkit::Utility
+    where
+        F: Fn(KuhnEdge, History) -> Vec<(KuhnInfo, Policy)>,
+    {
+        let mut splice = Splice::new(blueprint.profile());
+        for (opening, node) in OPENINGS {
+            for (info, policy) in resolve(opening, node) {
+                splice.splice(info, policy);
+            }
+        }
+        splice.measure(blueprint.encoder())
+    }
+
+    fn partitioned(blueprint: &Blueprint, n: usize) -> pokerkit::Utility {
+        spliced(blueprint, |opening, node| {
+            RANKS
+                .into_iter()
+                .map(|rank| {
+                    let (entry, _, prior) = resolve_spot(blueprint, rank, opening);
+                    let solver = SubGameSolver::<_, 1, _, _, _>::new(
+                        blueprint,
+                        KuhnTurn::Player(0),
+                        prior.partition::<WORLDS>(),
+                        entry,
+                    )
+                    .solve(n);
+                    (view(rank, node), averaged(solver.profile(), WORLDS, view(rank, node)))
+                })
+                .collect()
+        })
+    }
+
+    /// Reach-weighted ranks of P0 and P1 after `opening`, marginalized over every deal.
+    fn ranges(blueprint: &Blueprint, opening: KuhnEdge) -> (Posterior<Rank>, Posterior<Rank>) {
+        KuhnGame::all_roots().fold((Posterior::default(), Posterior::default()), |(p0, p1), root| {
+            let reach = |actor: usize| blueprint.external_reach(root, KuhnTurn::Player(1 - actor), [opening]);
+            (p0.add(root.hole_rank(0), reach(0)), p1.add(root.hole_rank(1), reach(1)))
+        })
+    }
+
+    fn gadget(
+        blueprint: &Blueprint,
+        gadget: Gadget,
+        opening: KuhnEdge,
+        n: usize,
+    ) -> GadgetSolver<'_, 1, Blueprint, KuhnInfo, Rank> {
+        let (entry, before, _) = resolve_spot(blueprint, Rank::J, opening);
+        let (prior, range) = ranges(blueprint, opening);
+        GadgetSolver::new(blueprint, KuhnTurn::Player(0), gadget, prior, entry)
+            .with_range(range)
+            .with_gift(before, opening)
+            .solve(n)
+    }
+
+    fn gadgeted(blueprint: &Blueprint, gadget: Gadget, n: usize) -> pokerkit::Utility {
+        spliced(blueprint, |opening, node| {
+            let solver = self::gadget(blueprint, gadget, opening, n);
+            RANKS
+                .into_iter()
+                .map(|rank| (view(rank, node), averaged(solver.profile(), 1, view(rank, node))))
+                .collect()
+        })
+    }
+
+    /// A maxmargin resolve of P1's whole range keeps Nash's pure actions.
+    #[test]
+    #[rustfmt::skip]
+    fn gadget_keeps_pure_actions() {
+        let ref blueprint = Blueprint::default().solve(N18);
+        let check = gadget(blueprint, Gadget::Maxmargin, KuhnEdge::Check, N14);
+        let bet = gadget(blueprint, Gadget::Maxmargin, KuhnEdge::Bet, N14);
+        let policy = |solver: &GadgetSolver<'_, 1, _, _, _>, r, h, e| averaged(solver.profile(), 1, view(r, h))[&e];
+        assert!(policy(&check, Rank::K, History::Check, KuhnEdge::Bet)  > 0.90, "K|X  should bet");
+        assert!(policy(&bet,   Rank::K, History::Bet,   KuhnEdge::Call) > 0.90, "K|B  should call");
+        assert!(policy(&bet,   Rank::J, History::Bet,   KuhnEdge::Fold) > 0.90, "J|B  should fold");
+    }
+
+    /// Against a near-Nash blueprint every gadget's splice stays near Nash.
+    #[test]
+    fn gadgets_stay_safe() {
+        let ref blueprint = Blueprint::default().solve(N18);
+        let base = Solver::exploitability(blueprint);
+        for gadget in Gadget::all() {
+            let e = gadgeted(blueprint, gadget, N14);
+            assert!(e < base + 0.03, "{gadget}: exploitability {e:.4} vs blueprint {base:.4}");
+        }
+    }
+
+    #[test]
+    #[ignore = "slow: gadget vs world partition sweep"]
+    fn gadget_exploitability_table() {
+        let ref weak = Blueprint::default().solve(1 << 8);
+        let ref strong = Blueprint::default().solve(N18);
+        eprintln!("\n=== full-game exploitability after resolving P1's response ===");
+        eprintln!(
+            "{:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            "blueprint", "none", "partition", "resolve", "maxmargin", "reach"
+        );
+        eprintln!(
+            "{:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            "---------", "----", "---------", "-------", "---------", "-----"
+        );
+        for (label, blueprint) in [("2^8", weak), ("2^18", strong)] {
+            let [resolve, maxmargin, reach] = Gadget::all().map(|g| gadgeted(blueprint, g, N16));
+            eprintln!(
+                "{:>10} {:>10.4} {:>10.4} {:>10.4} {:>10.4} {:>10.4}",
+                label,
+                Solver::exploitability(blueprint),
+                partitioned(blueprint, N16),
+                resolve,
+                maxmargin,
+                reach,
+            );
+        }
+    }
+
+    // ── lock tests ──────────────────────────────────────────────────────
+    //
+    // P0 is pinned to always fold facing a bet after checking. With full
+    // fold equity P1 bluffs every J behind P0's check. Read off the
+    // harvested (regret-matched) policy that a bot would play: the averaged
+    // policy carries the blueprint's warmstart weight. One world, so no
+    // unvisited world falls back to the blueprint's bluffs.
+
+    #[test]
+    #[rustfmt::skip]
+    fn locked_folder_gets_bluffed() {
+        let ref blueprint = Blueprint::default().solve(N18);
+        let lock = Lock::new(Response::Best).pin(0, KuhnPublic::new(true, History::CheckBet), [(KuhnEdge::Call, 0.)]);
+        let (entry, _, prior) = resolve_spot(blueprint, Rank::J, KuhnEdge::Check);
+        let solver = SubGameSolver::<_, 1, _, _, _>::new(blueprint, KuhnTurn::Player(0), prior.partition::<1>(), entry)
+            .with_lock(lock)
+            .solve(N16);
+        let bluff = solver.harvest(view(Rank::J, History::Check)).refined[&KuhnEdge::Bet];
+        assert!(bluff > 0.90, "J|X  should always bluff: {bluff:.3}");
+    }
+
+    /// Halving P0's calls as a tilt rather than a policy keeps the
+    /// blueprint's hand ranking: K still calls, J still folds, and only
+    /// the mixed Q folds more.
+    #[test]
+    fn tilted_caller_keeps_hand_strength() {
+        let ref blueprint = Blueprint::default().solve(N18);
+        let lock = Lock::new(Response::Best).tilt(0, KuhnPublic::new(true, History::CheckBet), [(KuhnEdge::Call, 0.5)]);
+        let profile = WorldProfile::new(blueprint.profile()).with_lock(lock);
+        let call = |rank| {
+            let info = WorldInfo::new(World::from(0), view(rank, History::CheckBet));
+            profile.iterated_distribution(&info).density(&KuhnEdge::Call)
+        };
+        let free = blueprint
+            .profile()
+            .averaged_distribution(&view(Rank::Q, History::CheckBet))
+            .density(&KuhnEdge::Call);
+        assert!(call(Rank::K) > 0.90, "K|XB should still call: {:.3}", call(Rank::K));
+        assert!(call(Rank::J) < 0.10, "J|XB should still fold: {:.3}", call(Rank::J));
+        assert!(call(Rank::Q) < free, "Q|XB should call less: {:.3} vs {free:.3}", call(Rank::Q));
+    }
 }
@@ -16,7 +16,13 @@ where
         &self.profile
     }
 
-    fn payoffs(&self, prefix: &Prefix<Self::T, Self::E>, game: &Self::G, _: Self::T) -> Payoffs<D> {
+    fn payoffs(
+        &self,
+        prefix: &Prefix<Self::T, Self::E>,
+        _: &[(Self::T, Self::I, Self::E)],
+        game: &Self::G,
+        _: Self::T,
+    ) -> Payoffs<D> {
         Payoffs::uniform(
             self.profile
                 .frontier_payoff(&self.resume(prefix.into_iter().edges(), game)),
@@ -98,6 +104,8 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
 
     const N16: usize = 1 << 16;
     const N18: usize = 1 << 18;
@@ -301,4 +309,318 @@ mod tests {
         let n = tree.n();
         assert!(n > 10, "depth-limited tree should have front nodes: {n}");
     }
+
+    // ── gadget tests ────────────────────────────────────────────────────
+    //
+    // Same comparison as the Kuhn gadget tests: every first-round P1
+    // response to P0's opening is re-solved, spliced into the blueprint,
+    // and scored on the full game. Gadget resolves cover P1's whole range;
+    // the world partition resolves one dealt hand at a time. The board
+    // deal is a frontier, so each resolve covers the first round only.
+
+    type Blueprint = Leduc<FlooredRegret, LinearWeight, ExternalSampling>;
+
+    type Policy = std::collections::BTreeMap<LeducEdge, pokerkit::Probability>;
+
+    const N14: usize = 1 << 14;
+
+    const RANKS: [Rank; 3] = [Rank::J, Rank::Q, Rank::K];
+
+    const OPENINGS: [(LeducEdge, Spot); 2] = [(LeducEdge::Check, Spot::Checked), (LeducEdge::Raise, Spot::Raised)];
+
+    fn hero(rank: Rank, spot: Spot) -> LeducInfo {
+        leduc_info(true, rank, None, spot, None)
+    }
+
+    /// Entry recall, gift recall and reach-conditioned prior over P0's hand
+    /// after P0 plays `opening`, with P1 dealt `rank`.
+    fn resolve_spot(
+        blueprint: &Blueprint,
+        rank: Rank,
+        opening: LeducEdge,
+    ) -> (CfrRecall<LeducGame>, CfrRecall<LeducGame>, Posterior<Rank>) {
+        let hero = Card::ALL.into_iter().find(|c| c.rank() == rank).expect("rank in deck");
+        let villain = Card::ALL.into_iter().find(|c| *c != hero).expect("second card");
+        let root = LeducGame::root().with_card(1, hero).with_card(0, villain);
+        let prior = Card::ALL
+            .into_iter()
+            .filter(|&c| c != hero)
+            .map(|c| root.with_card(0, c))
+            .map(|game| (game.hole_rank(0), blueprint.external_reach(game, LeducTurn::Player(1), [opening])))
+            .fold(Posterior::default(), |post, (s, r)| post.add(s, r));
+        let entry = CfrRecall::new(descents_from(root, [opening]), root.apply(opening));
+        let before = CfrRecall::new(vec![], root);
+        (entry, before, prior)
+    }
+
+    /// Reach-weighted ranks of P0 and P1 after `opening`, marginalized over every deal.
+    fn ranges(blueprint: &Blueprint, opening: LeducEdge) -> (Posterior<Rank>, Posterior<Rank>) {
+        LeducGame::all_roots().fold((Posterior::default(), Posterior::default()), |(p0, p1), root| {
+            let reach = |actor: usize| blueprint.external_reach(root, LeducTurn::Player(1 - actor), [opening]);
+            (p0.add(root.hole_rank(0), reach(0)), p1.add(root.hole_rank(1), reach(1)))
+        })
+    }
+
+    /// Averaged subgame policy at `info`, pooled over the first `worlds` worlds.
+    fn averaged(profile: &WorldProfile<'_, DepthView<'_, LeducProfile, 1>>, worlds: usize, info: LeducInfo) -> Policy {
+        CfrInfo::choices(&info)
+            .map(|edge| {
+                let p = (0..worlds)
+                    .map(|w| WorldInfo::new(World::from(w), DepthInfo::<_, 1>::Game(info)))
+                    .map(|wi| CfrNash::averaged_policy(profile, &wi, &DepthEdge::<_, 1>::Game(edge)))
+                    .sum::<pokerkit::Probability>();
+                (edge, p / worlds as pokerkit::Probability)
+            })
+            .collect()
+    }
+
+    /// Full-game exploitability of the blueprint with every first-round
+    /// P1 response replaced by what `resolve` returns for each opening.
+    fn spliced<F>(blueprint: &Blueprint, resolve: F) -> pokerkit::Utility
+    where
+        F: Fn(LeducEdge, Spot) -> Vec<(LeducInfo, Policy)>,
+    {
+        let mut splice = Splice::new(blueprint.profile());
+        for (opening, spot) in OPENINGS {
+            for (info, policy) in resolve(opening, spot) {
+                splice.splice(info, policy);
+            }
+        }
+        splice.measure(blueprint.encoder())
+    }
+
+    fn partitioned(blueprint: &Blueprint, n: usize) -> pokerkit::Utility {
+        spliced(blueprint, |opening, spot| {
+            RANKS
+                .into_iter()
+                .map(|rank| {
+                    let (entry, _, prior) = resolve_spot(blueprint, rank, opening);
+                    let solver = SubGameSolver::<_, 1, _, _, _>::new(
+                        blueprint,
+                        LeducTurn::Player(0),
+                        prior.partition::<2>(),
+                        entry,
+                    )
+                    .solve(n);
+                    (hero(rank, spot), averaged(solver.profile(), 2, hero(rank, spot)))
+                })
+                .collect()
+        })
+    }
+
+    fn gadgeted(blueprint: &Blueprint, gadget: Gadget, n: usize) -> pokerkit::Utility {
+        spliced(blueprint, |opening, spot| {
+            let (entry, before, _) = resolve_spot(blueprint, Rank::J, opening);
+            let (prior, range) = ranges(blueprint, opening);
+            let solver = GadgetSolver::<1, _, _, _>::new(blueprint, LeducTurn::Player(0), gadget, prior, entry)
+                .with_range(range)
+                .with_gift(before, opening)
+                .solve(n);
+            RANKS
+                .into_iter()
+                .map(|rank| (hero(rank, spot), averaged(solver.profile(), 1, hero(rank, spot))))
+                .collect()
+        })
+    }
+
+    /// Against a converged blueprint every gadget's splice stays near it.
+    #[test]
+    fn gadgets_stay_safe() {
+        let ref blueprint = Blueprint::default().solve(N18);
+        let base = Solver::exploitability(blueprint);
+        for gadget in Gadget::all() {
+            let e = gadgeted(blueprint, gadget, N14);
+            assert!(e < base + 0.05, "{gadget}: exploitability {e:.4} vs blueprint {base:.4}");
+        }
+    }
+
+    #[test]
+    #[ignore = "slow: gadget vs world partition sweep"]
+    fn gadget_exploitability_table() {
+        let ref weak = Blueprint::default().solve(1 << 8);
+        let ref strong = Blueprint::default().solve(N18);
+        eprintln!("\n=== full-game exploitability after resolving P1's first-round response ===");
+        eprintln!(
+            "{:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            "blueprint", "none", "partition", "resolve", "maxmargin", "reach"
+        );
+        eprintln!(
+            "{:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            "---------", "----", "---------", "-------", "---------", "-----"
+        );
+        for (label, blueprint) in [("2^8", weak), ("2^18", strong)] {
+            let [resolve, maxmargin, reach] = Gadget::all().map(|g| gadgeted(blueprint, g, N16));
+            eprintln!(
+                "{:>10} {:>10.4} {:>10.4} {:>10.4} {:>10.4} {:>10.4}",
+                label,
+                Solver::exploitability(blueprint),
+                partitioned(blueprint, N16),
+                resolve,
+                maxmargin,
+                reach,
+            );
+        }
+    }
+
+    // ── learned frontier tests ──────────────────────────────────────────
+    //
+    // The board deal is Leduc's depth-limited frontier. Its value under the
+    // blueprint can be computed exactly, so it scores the two ways of
+    // estimating it: averaging Monte Carlo rollouts, and a value network
+    // trained offline on rollout targets.
+
+    /// First-round lines that reach the board deal.
+    const LINES: [&[LeducEdge]; 3] = [
+        &[LeducEdge::Check, LeducEdge::Check],
+        &[LeducEdge::Raise, LeducEdge::Call],
+        &[LeducEdge::Check, LeducEdge::Raise, LeducEdge::Call],
+    ];
+
+    /// Every board-deal state, one per deal and first-round line.
+    fn frontiers() -> Vec<LeducGame> {
+        LeducGame::all_roots()
+            .flat_map(|root| LINES.map(|line| line.iter().fold(root, |game, edge| game.apply(*edge))))
+            .collect()
+    }
+
+    /// Both hole ranks and the first-round spot, one-hot.
+    fn features(game: &LeducGame) -> Vec<f32> {
+        let mut x = vec![0.0; 9];
+        x[game.hole_rank(0) as usize] = 1.0;
+        x[3 + game.hole_rank(1) as usize] = 1.0;
+        x[6 + game.spots().0 as usize - 1] = 1.0;
+        x
+    }
+
+    /// P0's value of `game` under the averaged blueprint.
+    fn exact(blueprint: &Blueprint, game: LeducGame) -> pokerkit::Utility {
+        match game.turn() {
+            LeducTurn::Terminal => game.payoff(LeducTurn::Player(0)),
+            LeducTurn::Chance => {
+                let deals = game.deals().collect::<Vec<_>>();
+                deals
+                    .iter()
+                    .map(|c| exact(blueprint, game.apply(LeducEdge::Deal(*c))))
+                    .sum::<pokerkit::Utility>()
+                    / deals.len() as pokerkit::Utility
+            }
+            LeducTurn::Player(_) => blueprint
+                .profile()
+                .averaged_distribution(&blueprint.resume(std::iter::empty(), &game))
+                .into_iter()
+                .map(|(edge, p)| p * exact(blueprint, game.apply(edge)))
+                .sum(),
+        }
+    }
+
+    /// P0's payoff from one sampled playout of `game` under the averaged blueprint.
+    fn rollout(blueprint: &Blueprint, mut game: LeducGame, rng: &mut SmallRng) -> pokerkit::Utility {
+        loop {
+            game = match game.turn() {
+                LeducTurn::Terminal => return game.payoff(LeducTurn::Player(0)),
+                LeducTurn::Chance => {
+                    let deals = game.deals().collect::<Vec<_>>();
+                    game.apply(LeducEdge::Deal(deals[rng.random_range(0..deals.len())]))
+                }
+                LeducTurn::Player(_) => {
+                    let policy = blueprint
+                        .profile()
+                        .averaged_distribution(&blueprint.resume(std::iter::empty(), &game));
+                    let mut threshold = rng.random::<pokerkit::Probability>();
+                    let edge = policy
+                        .iter()
+                        .find(|(_, p)| {
+                            threshold -= p;
+                            threshold < 0.0
+                        })
+                        .or(policy.last())
+                        .expect("non-empty policy")
+                        .0;
+                    game.apply(edge)
+                }
+            }
+        }
+    }
+
+    /// P0's value of `game` averaged over `rollouts` playouts.
+    fn sampled(blueprint: &Blueprint, game: LeducGame, rollouts: usize, rng: &mut SmallRng) -> pokerkit::Utility {
+        (0..rollouts)
+            .map(|_| rollout(blueprint, game, rng))
+            .sum::<pokerkit::Utility>()
+            / rollouts as pokerkit::Utility
+    }
+
+    /// Every frontier paired with its exact value.
+    fn truths(blueprint: &Blueprint) -> Vec<(LeducGame, pokerkit::Utility)> {
+        frontiers()
+            .into_iter()
+            .map(|game| (game, exact(blueprint, game)))
+            .collect()
+    }
+
+    /// Mean squared error of `estimate` against the exact values.
+    fn error<F>(truths: &[(LeducGame, pokerkit::Utility)], mut estimate: F) -> pokerkit::Utility
+    where
+        F: FnMut(LeducGame) -> pokerkit::Utility,
+    {
+        truths
+            .iter()
+            .map(|(game, value)| (estimate(*game) - value).powi(2))
+            .sum::<pokerkit::Utility>()
+            / truths.len() as pokerkit::Utility
+    }
+
+    /// Value network fit to `samples` frontiers labeled by `rollouts`-deep averages.
+    fn learned(blueprint: &Blueprint, samples: usize, rollouts: usize, rng: &mut SmallRng) -> ValueNetwork<1> {
+        let states = frontiers();
+        let data = (0..samples)
+            .map(|i| states[i % states.len()])
+            .map(|game| (features(&game), Payoffs::uniform(sampled(blueprint, game, rollouts, rng))))
+            .collect::<Vec<_>>();
+        let mut network = ValueNetwork::new(9, &[32], 0);
+        network.fit(&data, 64, 1e-2);
+        network
+    }
+
+    fn predict(network: &ValueNetwork<1>, game: LeducGame) -> pokerkit::Utility {
+        let only = Continuation::all::<1>().next().expect("one continuation");
+        network.payoffs(&features(&game)).get(only, only)
+    }
+
+    /// Trained on noisy single rollouts, the network averages the noise away.
+    #[test]
+    fn learned_frontier_beats_rollouts() {
+        let ref blueprint = Blueprint::default().solve(N16);
+        let ref truths = truths(blueprint);
+        let ref mut rng = SmallRng::seed_from_u64(0x1eaf);
+        let network = learned(blueprint, 1 << 12, 1, rng);
+        let learned = error(truths, |game| predict(&network, game));
+        let rollouts = error(truths, |game| sampled(blueprint, game, 16, rng));
+        assert!(learned < rollouts, "learned mse {learned:.4} vs 16 rollouts {rollouts:.4}");
+    }
+
+    #[test]
+    #[ignore = "slow: frontier accuracy vs rollout sweep"]
+    fn frontier_accuracy_table() {
+        let ref blueprint = Blueprint::default().solve(N18);
+        let ref truths = truths(blueprint);
+        let ref mut rng = SmallRng::seed_from_u64(0x1eaf);
+        let n = truths.len() as f64;
+        eprintln!("\n=== board-deal frontier values: mse vs exact, time per frontier ===");
+        eprintln!("{:>14} {:>10} {:>10}", "evaluator", "mse", "µs");
+        eprintln!("{:>14} {:>10} {:>10}", "---------", "---", "--");
+        for rollouts in [1, 4, 16, 64, 256] {
+            let start = std::time::Instant::now();
+            let mse = error(truths, |game| sampled(blueprint, game, rollouts, rng));
+            let micros = start.elapsed().as_secs_f64() * 1e6 / n;
+            eprintln!("{:>14} {mse:>10.4} {micros:>10.2}", format!("rollout×{rollouts}"));
+        }
+        for rollouts in [1, 16] {
+            let network = learned(blueprint, 1 << 14, rollouts, rng);
+            let start = std::time::Instant::now();
+            let mse = error(truths, |game| predict(&network, game));
+            let micros = start.elapsed().as_secs_f64() * 1e6 / n;
+            eprintln!("{:>14} {mse:>10.4} {micros:>10.2}", format!("learned@{rollouts}"));
+        }
+    }
 }
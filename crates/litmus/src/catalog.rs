@@ -44,6 +44,11 @@ impl<'a> Catalog<'a> {
             .get(name)
             .and_then(|c| c.default_expect.as_ref())
     }
+
+    /// Street a category needs resolved on exact hole cards, if any.
+    pub fn category_exact(&self, name: &str) -> Option<Street> {
+        self.scenarios.categories.get(name).and_then(|c| c.exact)
+    }
 }
 
 /// Build a typed `Witness` from a hand-ref + history-ref pair.
@@ -54,9 +59,17 @@ impl<'a> Catalog<'a> {
 pub fn build_witness(catalog: &Catalog, hand_ref: &str, history_ref: &str) -> anyhow::Result<Witness> {
     let hand = catalog.hand(hand_ref)?;
     let history = catalog.history(history_ref)?;
+    let board = match &history.seen_template {
+        None => Hand::empty(),
+        Some(tmpl) => tmpl
+            .split_once('~')
+            .map_or(Ok(Hand::empty()), |(_, board)| Hand::try_from(board))
+            .map_err(|e| anyhow::anyhow!("board `{tmpl}`: {e}"))?,
+    };
+    let hole = resolve(&hand.cards, board).map_err(|e| anyhow::anyhow!("hand `{hand_ref}`: {e}"))?;
     let seen = match &history.seen_template {
-        None => hand.cards.clone(),
-        Some(tmpl) => tmpl.replacen('*', &hand.cards, 1),
+        None => hole.to_string(),
+        Some(tmpl) => tmpl.replacen('*', &hole.to_string(), 1),
     };
     let turn = Turn::try_from(history.turn.as_str()).map_err(|e| anyhow::anyhow!("turn `{}`: {e}", history.turn))?;
     let obs = Observation::try_from(seen.as_str()).map_err(|e| anyhow::anyhow!("observation `{seen}`: {e:?}"))?;
@@ -91,6 +104,22 @@ pub fn build_witness(catalog: &Catalog, hand_ref: &str, history_ref: &str) -> an
     Ok(witness)
 }
 
+/// Resolves a hand's `cards` — exact cards like `"AcKd"` or any
+/// [`Range`] notation like `"AKs"` — to the heaviest combo that the
+/// board leaves live, earliest in canonical order on ties.
+fn resolve(cards: &str, board: Hand) -> anyhow::Result<Hole> {
+    Range::try_from(cards)
+        .map_err(|e| anyhow::anyhow!("cards `{cards}`: {e}"))?
+        .blocked(board)
+        .iter()
+        .fold(None, |best: Option<(Hole, pokerkit::Probability)>, (hole, weight)| match best {
+            Some((_, w)) if w >= weight => best,
+            _ => Some((hole, weight)),
+        })
+        .map(|(hole, _)| hole)
+        .ok_or_else(|| anyhow::anyhow!("`{cards}` has no combo live on board {board}"))
+}
+
 pub fn parse_edge(s: &str) -> anyhow::Result<Edge> {
     Edge::try_from(s).map_err(|e| anyhow::anyhow!("edge `{s}`: {e}"))
 }
@@ -41,6 +41,17 @@ pub struct Outcome {
 }
 
 pub async fn evaluate<O: Ops>(ops: &O, catalog: &Catalog<'_>, case: &Case) -> Outcome {
+    if let Some(street) = catalog
+        .category_exact(&case.category)
+        .filter(|street| !pokerkit::endgame().exact(*street as usize))
+    {
+        return Outcome {
+            case: case.clone(),
+            status: Status::Skip,
+            detail: format!("needs an --endgame that resolves the {street} on exact cards"),
+            observed: vec![],
+        };
+    }
     match case.kind {
         TestKind::Single | TestKind::Exists => evaluate_single(ops, catalog, case).await,
         TestKind::PairDiff => evaluate_pair_diff(ops, catalog, case).await,
@@ -164,23 +175,31 @@ async fn evaluate_pair_diff<O: Ops>(ops: &O, catalog: &Catalog<'_>, case: &Case)
     let b = observed[1].1.unwrap();
     let diff = (a - b).abs();
     let expect = merged_expect(catalog, case);
-    let Some(bound) = expect.max_abs_diff else {
-        return mk(Status::Error, "pair_diff missing max_abs_diff".into(), observed);
-    };
+    let max = expect.max_abs_diff;
+    let min = expect.min_abs_diff;
+    if max.is_none() && min.is_none() {
+        return mk(Status::Error, "pair_diff missing max_abs_diff / min_abs_diff".into(), observed);
+    }
 
     let obs_str = format!("{}={}  {}={}", observed[0].0, fmt_pct(observed[0].1), observed[1].0, fmt_pct(observed[1].1));
-    if diff > bound {
-        return mk(
-            Status::Fail,
-            format!("|Δ|={} > {} ({obs_str})", fmt_pct(Some(diff)), fmt_pct(Some(bound))),
-            observed,
-        );
-    }
-    mk(
-        Status::Pass,
-        format!("|Δ|={} ≤ {} ({obs_str})", fmt_pct(Some(diff)), fmt_pct(Some(bound))),
-        observed,
-    )
+    if let Some(m) = max
+        && diff > m
+    {
+        return mk(Status::Fail, format!("|Δ|={} > {} ({obs_str})", fmt_pct(Some(diff)), fmt_pct(Some(m))), observed);
+    }
+    if let Some(m) = min
+        && diff < m
+    {
+        return mk(Status::Fail, format!("|Δ|={} < {} ({obs_str})", fmt_pct(Some(diff)), fmt_pct(Some(m))), observed);
+    }
+
+    let bound = match (max, min) {
+        (Some(mx), Some(mn)) => format!("in [{}, {}]", fmt_pct(Some(mn)), fmt_pct(Some(mx))),
+        (Some(mx), None) => format!("≤ {}", fmt_pct(Some(mx))),
+        (None, Some(mn)) => format!("≥ {}", fmt_pct(Some(mn))),
+        (None, None) => unreachable!("checked above"),
+    };
+    mk(Status::Pass, format!("|Δ|={} {bound} ({obs_str})", fmt_pct(Some(diff))), observed)
 }
 
 async fn evaluate_monotonic<O: Ops>(ops: &O, catalog: &Catalog<'_>, case: &Case) -> Outcome {
@@ -21,7 +21,7 @@ pub use catalog::{Catalog, build_witness, parse_edge};
 pub use compose::resolve;
 pub use evaluate::{Outcome, Status, evaluate};
 pub use ops::Ops;
-pub use render::render;
+pub use render::{render, statuses, versus};
 pub use schema::{
     Case, CategoryDef, Direction, Expect, Family, HandDef, Historical, HistoryDef, Scenarios, TestKind, load,
 };
@@ -91,6 +91,46 @@ pub fn render(
     out
 }
 
+/// Case name → status, the form saved as a comparison baseline.
+pub fn statuses(outcomes: &[Outcome]) -> BTreeMap<String, Status> {
+    outcomes.iter().map(|o| (o.case.name.clone(), o.status)).collect()
+}
+
+/// Markdown section comparing this run against a baseline run of the same
+/// catalog (e.g. an OCHS river version against the equity ladder). Lists
+/// every case whose status changed, then the pass counts side by side.
+pub fn versus(label: &str, baseline: &BTreeMap<String, Status>, outcomes: &[Outcome]) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("## Versus `{label}`\n\n"));
+    let changed = outcomes
+        .iter()
+        .filter_map(|o| baseline.get(&o.case.name).map(|&b| (o, b)))
+        .filter(|(o, b)| o.status != *b)
+        .collect::<Vec<_>>();
+    if changed.is_empty() {
+        out.push_str("No case changed status.\n\n");
+    } else {
+        out.push_str("| Case | Category | Baseline | Now |\n");
+        out.push_str("|---|---|---|---|\n");
+        for (o, b) in &changed {
+            out.push_str(&format!(
+                "| {} | `{}` | {} | **{}** |\n",
+                o.case.name,
+                o.case.category,
+                b.label(),
+                o.status.label()
+            ));
+        }
+        out.push('\n');
+    }
+    let now = outcomes.iter().filter(|o| o.status == Status::Pass).count();
+    let before = baseline.values().filter(|&&s| s == Status::Pass).count();
+    let fixed = changed.iter().filter(|(o, _)| o.status == Status::Pass).count();
+    let broke = changed.iter().filter(|(_, b)| *b == Status::Pass).count();
+    out.push_str(&format!("- **PASS**: {before} → {now} ({fixed} newly passing, {broke} newly failing)\n\n"));
+    out
+}
+
 fn render_category_intro(meta: &CategoryDef, out: &mut String) {
     out.push_str(&format!("_{}_\n\n", meta.description));
 }
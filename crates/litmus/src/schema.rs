@@ -34,7 +34,9 @@ pub struct Scenarios {
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct HandDef {
-    /// Hole-card encoding parsed by `Observation::try_from`. e.g. "AcKd".
+    /// Exact hole cards ("AcKd") or range notation ("AKs", "QQ+"); a range
+    /// resolves to its heaviest combo not blocked by the history's board,
+    /// earliest in canonical order on ties.
     pub cards: String,
     #[allow(dead_code)]
     pub name: String,
@@ -69,6 +71,10 @@ pub struct CategoryDef {
     pub description: String,
     /// Per-category default tolerance; overridden by case-level `expect`.
     pub default_expect: Option<Expect>,
+    /// Street the category needs resolved on exact hole cards. Its cases
+    /// SKIP unless the run's `--endgame` covers that street.
+    #[serde(default)]
+    pub exact: Option<deuce::Street>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq)]
@@ -94,6 +100,7 @@ pub struct Expect {
     pub acceptable_max: Option<f32>,
     pub acceptable_min: Option<f32>,
     pub max_abs_diff: Option<f32>,
+    pub min_abs_diff: Option<f32>,
     pub direction: Option<Direction>,
 }
 
@@ -111,6 +118,9 @@ impl Expect {
             if o.max_abs_diff.is_some() {
                 out.max_abs_diff = o.max_abs_diff;
             }
+            if o.min_abs_diff.is_some() {
+                out.min_abs_diff = o.min_abs_diff;
+            }
             if o.direction.is_some() {
                 out.direction = o.direction;
             }
@@ -242,4 +252,35 @@ mod tests {
             .count();
         assert!(n > 0, "expected at least one history with expected_spr");
     }
+
+    /// Range-notation hands resolve to the catalog's conventional combo,
+    /// so rewriting `"AcKc"` as `"AKs"` does not move any case.
+    #[test]
+    fn range_hands_resolve_to_first_live_combo() {
+        let scenarios = load(&catalog_path()).unwrap();
+        let catalog = Catalog::new(&scenarios);
+        let (street, name) = scenarios
+            .histories
+            .iter()
+            .flat_map(|(street, hs)| hs.keys().map(move |name| (street, name)))
+            .find(|(street, _)| street.as_str() == "preflop")
+            .expect("catalog has preflop histories");
+        for (hand, cards) in [("AKs", "AcKc"), ("AKo", "AcKd"), ("KQo", "KcQd")] {
+            let witness = build_witness(&catalog, hand, &format!("{street}.{name}")).unwrap();
+            assert_eq!(*witness.seen().pocket(), deuce::Hand::try_from(cards).unwrap());
+        }
+    }
+    /// Blocker cases can only pass on exact-card resolves, so the
+    /// default abstract endgame must not evaluate them.
+    #[test]
+    fn river_blockers_need_exact_river() {
+        let scenarios = load(&catalog_path()).unwrap();
+        let catalog = Catalog::new(&scenarios);
+        let street = catalog
+            .category_exact("river_blockers")
+            .expect("river_blockers declares `exact`");
+        assert_eq!(street, deuce::Street::Rive);
+        assert!(!pokerkit::Endgame::default().exact(street as usize));
+        assert!(pokerkit::Endgame::River.exact(street as usize));
+    }
 }
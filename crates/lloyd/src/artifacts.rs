@@ -8,9 +8,12 @@ use super::*;
 /// - [`Lookup`] — Isomorphism → Abstraction mapping
 /// - [`Metric`] — Pairwise EMD distances between abstractions
 /// - [`Future`] — Abstraction → Histogram centroids (transition model)
-pub struct Artifacts {
+///
+/// The assignment table is a [`Lookup`] keyed by isomorphism, or a
+/// [`RecallLookup`] keyed by perfect-recall index under `Version::V6`.
+pub struct Artifacts<L = Lookup> {
     /// The hand-to-bucket assignment table.
-    pub lookup: Lookup,
+    pub lookup: L,
     /// Pairwise distances for use in parent layer's EMD.
     pub metric: Metric,
     /// Cluster centroids for transition modeling.
@@ -27,8 +30,80 @@ impl From<Lookup> for Artifacts {
     }
 }
 
+impl From<RecallLookup> for Artifacts<RecallLookup> {
+    fn from(lookup: RecallLookup) -> Self {
+        Self {
+            lookup,
+            metric: Metric::default(),
+            future: Future::default(),
+        }
+    }
+}
+
+/// A hand-to-bucket table that [`Artifacts`] stores as `{street}.lookup`.
+pub trait Assignment: Sized {
+    /// The street whose hands the table assigns.
+    fn street(&self) -> deuce::Street;
+    /// Writes the table to `path`.
+    fn save(&self, path: &std::path::Path) -> std::io::Result<()>;
+    /// Reads a table written by [`Self::save`].
+    fn load(path: &std::path::Path) -> std::io::Result<Self>;
+}
+
+impl Assignment for Lookup {
+    fn street(&self) -> deuce::Street {
+        Lookup::street(self)
+    }
+    fn save(&self, path: &std::path::Path) -> std::io::Result<()> {
+        Lookup::save(self, path)
+    }
+    fn load(path: &std::path::Path) -> std::io::Result<Self> {
+        Lookup::load(path)
+    }
+}
+
+impl Assignment for RecallLookup {
+    fn street(&self) -> deuce::Street {
+        RecallLookup::street(self)
+    }
+    fn save(&self, path: &std::path::Path) -> std::io::Result<()> {
+        RecallLookup::save(self, path)
+    }
+    fn load(path: &std::path::Path) -> std::io::Result<Self> {
+        RecallLookup::load(path)
+    }
+}
+
+/// One directory holds every street's artifacts as
+/// `{street}.lookup`, `{street}.metric`, and `{street}.future`.
+impl<L: Assignment> Artifacts<L> {
+    fn path(dir: &std::path::Path, street: deuce::Street, kind: &str) -> std::path::PathBuf {
+        dir.join(format!("{}.{kind}", format!("{street}").to_lowercase()))
+    }
+
+    /// Writes all three artifacts into `dir`, keyed by the lookup's street.
+    pub fn save(&self, dir: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let dir = dir.as_ref();
+        let street = self.lookup.street();
+        std::fs::create_dir_all(dir)?;
+        self.lookup.save(&Self::path(dir, street, "lookup"))?;
+        self.metric.save(Self::path(dir, street, "metric"))?;
+        self.future.save(Self::path(dir, street, "future"))
+    }
+
+    /// Reads a street's artifacts written by [`Self::save`].
+    pub fn load(dir: impl AsRef<std::path::Path>, street: deuce::Street) -> std::io::Result<Self> {
+        let dir = dir.as_ref();
+        Ok(Self {
+            lookup: L::load(&Self::path(dir, street, "lookup"))?,
+            metric: Metric::load(Self::path(dir, street, "metric"))?,
+            future: Future::load(Self::path(dir, street, "future"))?,
+        })
+    }
+}
+
 #[cfg(feature = "server")]
-impl Artifacts {
+impl<L: daybook::Streamable> Artifacts<L> {
     pub async fn stream(self, client: &tokio_postgres::Client) {
         use daybook::Streamable;
         self.lookup.stream(client).await;
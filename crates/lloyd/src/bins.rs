@@ -1,3 +1,4 @@
+use super::Ochs;
 use deuce::*;
 use kicker::*;
 use pokerkit::*;
@@ -26,6 +27,8 @@ pub type BinsRive = Bins<N_RIVE>;
 /// The array size `N` is determined at compile time based on the street's
 /// abstraction count. This enables stack allocation while supporting
 /// different sizes per street (e.g., 169 preflop vs 200 flop buckets).
+/// Counts are `u32` so that wide builds (`k4096`) keep a flop or turn
+/// histogram at 16 KiB.
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
 pub struct Bins<const N: usize> {
     /// The street these bins represent.
@@ -33,7 +36,7 @@ pub struct Bins<const N: usize> {
     /// Total count across all bins (normalization denominator).
     weight: usize,
     /// Dense array of counts indexed by abstraction index.
-    counts: [usize; N],
+    counts: [u32; N],
 }
 
 impl<const N: usize> Bins<N> {
@@ -47,7 +50,7 @@ impl<const N: usize> Bins<N> {
     }
     /// Sets the count for a specific abstraction.
     pub fn set(&mut self, abs: Abstraction, count: usize) {
-        unsafe { *self.counts.get_unchecked_mut(abs.index()) = count }
+        unsafe { *self.counts.get_unchecked_mut(abs.index()) = count as u32 }
         self.weight += count;
     }
     /// Number of non-zero bins (support size).
@@ -59,8 +62,8 @@ impl<const N: usize> Bins<N> {
         unsafe { *self.counts.get_unchecked(x.index()) as f32 / self.weight as f32 }
     }
     /// Iterates over (index, count) pairs.
-    pub fn counts(&self) -> impl Iterator<Item = (usize, &usize)> + '_ {
-        self.counts.iter().enumerate()
+    pub fn counts(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.counts.iter().map(|&c| c as usize).enumerate()
     }
     /// The street these bins represent.
     pub fn street(&self) -> Street {
@@ -69,7 +72,7 @@ impl<const N: usize> Bins<N> {
     /// Increments the count for an abstraction by 1.
     pub fn increment(&mut self, abs: Abstraction) {
         self.weight.add_assign(1usize);
-        unsafe { self.counts.get_unchecked_mut(abs.index()).add_assign(1usize) }
+        unsafe { self.counts.get_unchecked_mut(abs.index()).add_assign(1u32) }
     }
     /// Merges another bin array into this one.
     pub fn merge<const M: usize>(&mut self, other: &Bins<M>) {
@@ -83,7 +86,7 @@ impl<const N: usize> Bins<N> {
     /// Iterates over abstractions with non-zero counts.
     pub fn support(&self) -> impl Iterator<Item = Abstraction> + '_ {
         self.counts()
-            .filter(|&(_, &count)| count > 0)
+            .filter(|&(_, count)| count > 0)
             .map(|(i, _)| Abstraction::from((self.street(), i)))
     }
     /// Returns first abstraction in support (for type inference).
@@ -98,17 +101,26 @@ impl<const N: usize> Bins<N> {
         self.pdf().iter().map(|(x, y)| x * y).sum()
     }
     /// Returns (equity, probability) pairs for visualization.
-    /// The equity abstraction is converted to its `[0,1]` value.
+    /// The equity abstraction is converted to its `[0,1]` value; OCHS
+    /// river buckets (`Version::V5`) use their centroid's stored mean
+    /// strength, see [`Ochs::install`].
     pub fn pdf(&self) -> Vec<(Probability, Probability)> {
         debug_assert!(matches!(self.street(), Street::Rive));
         debug_assert!(matches!(self.peek().street(), Street::Rive));
         self.counts()
-            .filter(|(_, c)| c > &&0)
-            .map(|(i, &count)| (Abstraction::from((self.street(), i)), count as f32))
+            .filter(|&(_, c)| c > 0)
+            .map(|(i, count)| (Abstraction::from((self.street(), i)), count as f32))
             .map(|(a, b)| (a, b / self.weight as f32))
-            .map(|(k, v)| (Probability::from(k), Probability::from(v)))
+            .map(|(k, v)| (Self::value(k), Probability::from(v)))
             .collect()
     }
+    fn value(abs: Abstraction) -> Probability {
+        if pokerkit::version().ochs() {
+            Ochs::equity(&abs)
+        } else {
+            Probability::from(abs)
+        }
+    }
     /// Returns (abstraction, density) pairs sorted by density descending.
     pub fn distribution(&self) -> Vec<(Abstraction, Probability)> {
         let mut distribution = self.support().map(|abs| (abs, self.density(&abs))).collect::<Vec<_>>();
@@ -0,0 +1,160 @@
+use super::*;
+use kicker::Abstraction;
+use monge::Barycenter;
+use pokerkit::*;
+use std::collections::HashMap;
+
+/// How k-means rebuilds a centroid from the histograms assigned to it.
+///
+/// Assignment measures points by EMD, but [`Absorb`] merges their counts
+/// bin by bin, so the default centroid is a Euclidean mean that need not
+/// minimize within-cluster EMD. The barycenter rule instead moves mass
+/// along the layer's ground metric.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Centroid {
+    /// Bin-wise arithmetic mean via [`Absorb`].
+    #[default]
+    Mean,
+    /// Entropic Wasserstein barycenter under the layer's ground metric.
+    Barycenter,
+}
+
+impl Centroid {
+    /// Counts assigned to a barycenter's unit mass when it is stored back
+    /// into integer [`Histogram`] bins. Bins below `1 / RESOLUTION` vanish.
+    const RESOLUTION: f32 = 65536.;
+
+    /// Metric label value.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Mean => "mean",
+            Self::Barycenter => "barycenter",
+        }
+    }
+
+    /// Aggregates cluster members into one centroid. `identity` is the
+    /// empty histogram returned for an empty cluster.
+    pub fn aggregate<'a>(
+        &self,
+        identity: Histogram,
+        metric: &Metric,
+        members: impl Iterator<Item = &'a Histogram>,
+    ) -> Histogram {
+        match self {
+            Self::Mean => members.fold(identity, Histogram::absorb),
+            Self::Barycenter => Self::barycenter(identity, metric, members),
+        }
+    }
+
+    /// Entropic barycenter of the members, weighted by multiplicity and
+    /// supported on the union of their supports.
+    ///
+    /// The union is a limitation, not the true optimum: mass can only move
+    /// to bins some member already occupies, so two members that share no
+    /// bucket cannot meet halfway. The full bucket grid would lift that,
+    /// but its Gibbs kernel is `K²` per cluster, which wide builds cannot
+    /// afford on every k-means step.
+    fn barycenter<'a>(identity: Histogram, metric: &Metric, members: impl Iterator<Item = &'a Histogram>) -> Histogram {
+        let mut unique = HashMap::<&Histogram, usize>::new();
+        members.for_each(|h| *unique.entry(h).or_default() += 1);
+        if unique.is_empty() {
+            return identity;
+        }
+        let mut support = unique
+            .keys()
+            .flat_map(|h| h.support())
+            .map(ClusterAbs::from)
+            .collect::<Vec<_>>();
+        support.sort_unstable();
+        support.dedup();
+        let params = crate::SinkhornHyperParams::DEFAULT;
+        let mut barycenter = Barycenter::new(metric, support, params.temperature())
+            .with_iterations(params.iterations())
+            .with_tolerance(params.tolerance());
+        unique.iter().for_each(|(h, n)| barycenter.add(*h, *n as Probability));
+        barycenter
+            .solve()
+            .into_iter()
+            .map(|(x, p)| (Abstraction::from(x), (p * Self::RESOLUTION).round() as usize))
+            .filter(|&(_, count)| count > 0)
+            .fold(identity, |mut h, (x, count)| {
+                h.set(x, count);
+                h
+            })
+    }
+}
+
+impl std::fmt::Display for Centroid {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deuce::Street;
+
+    fn flop_hist(entries: &[(usize, usize)]) -> Histogram {
+        let mut h = Histogram::empty(Street::Flop);
+        for &(idx, count) in entries {
+            h.set(Abstraction::from((Street::Flop, idx)), count);
+        }
+        h
+    }
+
+    /// Flop buckets on a line: distance `|i − j| / 16`.
+    fn line_metric() -> Metric {
+        let mut m = Metric::new(Street::Flop);
+        for i in 0..16 {
+            for j in (i + 1)..16 {
+                m.set(Pair::new(Street::Flop, i, j), (j - i) as f32 / 16.);
+            }
+        }
+        m
+    }
+
+    /// Within-cluster EMD, measured exactly.
+    fn spread(metric: &Metric, centroid: &Histogram, members: &[Histogram]) -> f32 {
+        use monge::Coupling;
+        members
+            .iter()
+            .map(|h| monge::NetworkSimplex::from((h, centroid, metric)).minimize().cost())
+            .sum()
+    }
+
+    #[test]
+    fn mean_matches_absorb() {
+        let metric = line_metric();
+        let members = [&[(0, 2)][..], &[(3, 1), (4, 1)]].map(flop_hist);
+        let mean = Centroid::Mean.aggregate(Histogram::empty(Street::Flop), &metric, members.iter());
+        assert_eq!(mean, members[0].absorb(&members[1]));
+    }
+    #[test]
+    fn empty_cluster_keeps_identity() {
+        let metric = line_metric();
+        let identity = Histogram::empty(Street::Flop);
+        let centroid = Centroid::Barycenter.aggregate(identity, &metric, std::iter::empty());
+        assert_eq!(centroid, identity);
+    }
+    #[test]
+    fn barycenter_lowers_within_cluster_emd() {
+        let metric = line_metric();
+        let members = [&[(1, 1)][..], &[(2, 1)], &[(9, 1)]].map(flop_hist);
+        let identity = Histogram::empty(Street::Flop);
+        let mean = Centroid::Mean.aggregate(identity, &metric, members.iter());
+        let bary = Centroid::Barycenter.aggregate(identity, &metric, members.iter());
+        let (mean, bary) = (spread(&metric, &mean, &members), spread(&metric, &bary, &members));
+        assert!(bary < mean, "barycenter {bary} vs mean {mean}");
+    }
+    #[test]
+    fn barycenter_stays_on_member_support() {
+        let metric = line_metric();
+        let members = [&[(1, 1)][..], &[(9, 1)]].map(flop_hist);
+        let identity = Histogram::empty(Street::Flop);
+        let bary = Centroid::Barycenter.aggregate(identity, &metric, members.iter());
+        let support = bary.support().map(|abs| abs.index()).collect::<Vec<usize>>();
+        assert!(!support.is_empty());
+        assert!(support.iter().all(|i| [1, 9].contains(i)), "{support:?}");
+    }
+}
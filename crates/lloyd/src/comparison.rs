@@ -0,0 +1,264 @@
+//! Cross-version abstraction comparison.
+//!
+//! Two [`Lookup`]s of the same street — two versions, or two values of K —
+//! partition the same isomorphisms. A [`Comparison`] measures how far those
+//! partitions agree before anyone spends cluster time retraining a
+//! blueprint on the new one: chance-corrected agreement (adjusted Rand
+//! index), shared information (normalized mutual information), which
+//! buckets map onto which, and which hands moved furthest in equity.
+
+use super::*;
+use deuce::*;
+use kicker::*;
+use pokerkit::*;
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+/// Agreement between two abstractions of one street.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Comparison {
+    pub street: Street,
+    /// Distinct buckets used by the left lookup.
+    pub left: usize,
+    /// Distinct buckets used by the right lookup.
+    pub right: usize,
+    /// Adjusted Rand index: 1 for identical partitions, about 0 for
+    /// independent ones.
+    pub rand: f64,
+    /// Mutual information over the mean of both entropies, in `[0, 1]`.
+    pub mutual: f64,
+    /// Nonzero cells of the bucket-to-bucket contingency table.
+    pub overlaps: Vec<Overlap>,
+    /// Hands whose bucket equity moved furthest, largest first.
+    pub shifts: Vec<Shift>,
+}
+
+/// Isomorphisms shared by a left bucket and a right bucket.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Overlap {
+    pub left: Abstraction,
+    pub right: Abstraction,
+    pub count: usize,
+    /// Share of the left bucket that landed in the right one.
+    pub share: f64,
+    /// Intersection over union of the two buckets.
+    pub jaccard: f64,
+}
+
+/// One hand's reassignment, valued by the sampled mean equity of the
+/// bucket it left and the bucket it joined.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Shift {
+    pub obs: Observation,
+    pub left: Abstraction,
+    pub right: Abstraction,
+    /// Equity of the right bucket minus equity of the left bucket.
+    pub delta: Probability,
+}
+
+impl Comparison {
+    /// Compares two lookups of the same street. Bucket equities are
+    /// means over `n` seeded sample hands, so hands in buckets the sample
+    /// missed are left out of `shifts`, which keeps the `top` largest.
+    pub fn new(left: &Lookup, right: &Lookup, n: usize, top: usize) -> Self {
+        assert_eq!(left.street(), right.street(), "lookups must share a street");
+        let street = left.street();
+        let table = Self::contingency(left, right);
+        let overlaps = Self::overlaps(&table);
+        let (rand, mutual) = (Self::rand(&table), Self::mutual(&table));
+        let (lefts, rights) = Self::marginals(&table);
+        let shifts = Self::shifts(left, right, n, top);
+        Self {
+            street,
+            left: lefts.len(),
+            right: rights.len(),
+            rand,
+            mutual,
+            overlaps,
+            shifts,
+        }
+    }
+
+    /// Every isomorphism of the street, in parallel.
+    fn isomorphisms(street: Street) -> impl ParallelIterator<Item = Isomorphism> {
+        (0..street.n_isomorphisms())
+            .into_par_iter()
+            .map(move |i| Isomorphism::from((street, i)))
+    }
+
+    /// Isomorphism counts per `(left, right)` bucket pair.
+    fn contingency(left: &Lookup, right: &Lookup) -> BTreeMap<(Abstraction, Abstraction), usize> {
+        Self::isomorphisms(left.street())
+            .map(|iso| (left.lookup(&iso), right.lookup(&iso)))
+            .fold(HashMap::new, |mut table, pair| {
+                *table.entry(pair).or_insert(0usize) += 1;
+                table
+            })
+            .reduce(HashMap::new, |mut a, b| {
+                b.into_iter().for_each(|(k, v)| *a.entry(k).or_insert(0) += v);
+                a
+            })
+            .into_iter()
+            .collect()
+    }
+
+    /// Row and column sums of a contingency table.
+    fn marginals(
+        table: &BTreeMap<(Abstraction, Abstraction), usize>,
+    ) -> (BTreeMap<Abstraction, usize>, BTreeMap<Abstraction, usize>) {
+        table
+            .iter()
+            .fold((BTreeMap::new(), BTreeMap::new()), |(mut rows, mut cols), (&(a, b), &n)| {
+                *rows.entry(a).or_default() += n;
+                *cols.entry(b).or_default() += n;
+                (rows, cols)
+            })
+    }
+
+    fn overlaps(table: &BTreeMap<(Abstraction, Abstraction), usize>) -> Vec<Overlap> {
+        let (rows, cols) = Self::marginals(table);
+        table
+            .iter()
+            .map(|(&(left, right), &count)| Overlap {
+                left,
+                right,
+                count,
+                share: count as f64 / rows[&left] as f64,
+                jaccard: count as f64 / (rows[&left] + cols[&right] - count) as f64,
+            })
+            .collect()
+    }
+
+    /// Hubert–Arabie adjusted Rand index.
+    fn rand(table: &BTreeMap<(Abstraction, Abstraction), usize>) -> f64 {
+        fn pairs(n: usize) -> f64 {
+            n as f64 * (n as f64 - 1.) / 2.
+        }
+        let (rows, cols) = Self::marginals(table);
+        let total = rows.values().sum::<usize>();
+        let index = table.values().map(|&n| pairs(n)).sum::<f64>();
+        let a = rows.values().map(|&n| pairs(n)).sum::<f64>();
+        let b = cols.values().map(|&n| pairs(n)).sum::<f64>();
+        let expected = a * b / pairs(total);
+        let maximum = f64::midpoint(a, b);
+        match maximum - expected {
+            d if d.abs() < f64::EPSILON => 1.,
+            d => (index - expected) / d,
+        }
+    }
+
+    /// Mutual information normalized by the arithmetic mean of the two
+    /// entropies. Two single-bucket partitions count as identical.
+    fn mutual(table: &BTreeMap<(Abstraction, Abstraction), usize>) -> f64 {
+        fn entropy(counts: impl Iterator<Item = usize>, total: f64) -> f64 {
+            counts.map(|n| n as f64 / total).map(|p| -p * p.ln()).sum()
+        }
+        let (rows, cols) = Self::marginals(table);
+        let total = rows.values().sum::<usize>() as f64;
+        let information = table
+            .iter()
+            .map(|(&(a, b), &n)| {
+                let joint = n as f64 / total;
+                joint * (n as f64 * total / (rows[&a] as f64 * cols[&b] as f64)).ln()
+            })
+            .sum::<f64>();
+        let mean = f64::midpoint(entropy(rows.values().copied(), total), entropy(cols.values().copied(), total));
+        match mean {
+            m if m < f64::EPSILON => 1.,
+            m => (information / m).clamp(0., 1.),
+        }
+    }
+
+    /// Mean sampled equity of each bucket under `lookup`.
+    fn equities(lookup: &Lookup, sample: &[(Isomorphism, Probability)]) -> HashMap<Abstraction, Probability> {
+        sample
+            .iter()
+            .fold(HashMap::<Abstraction, (Probability, usize)>::new(), |mut sums, (iso, x)| {
+                let entry = sums.entry(lookup.lookup(iso)).or_default();
+                entry.0 += x;
+                entry.1 += 1;
+                sums
+            })
+            .into_iter()
+            .map(|(abs, (sum, n))| (abs, sum / n as Probability))
+            .collect()
+    }
+
+    /// The `top` reassigned hands with the largest bucket equity change.
+    fn shifts(left: &Lookup, right: &Lookup, n: usize, top: usize) -> Vec<Shift> {
+        let sample = Sample::isomorphisms(left.street(), n)
+            .into_par_iter()
+            .map(|iso| (iso, Report::equity(&iso)))
+            .collect::<Vec<_>>();
+        let (lhs, rhs) = (Self::equities(left, &sample), Self::equities(right, &sample));
+        let keep = |mut shifts: Vec<Shift>| {
+            shifts.sort_by(|a, b| b.delta.abs().total_cmp(&a.delta.abs()));
+            shifts.truncate(top);
+            shifts
+        };
+        Self::isomorphisms(left.street())
+            .map(|iso| (iso, left.lookup(&iso), right.lookup(&iso)))
+            .filter_map(|(iso, a, b)| {
+                Some(Shift {
+                    obs: Observation::from(iso),
+                    left: a,
+                    right: b,
+                    delta: rhs.get(&b)? - lhs.get(&a)?,
+                })
+            })
+            .filter(|shift| shift.delta != 0.)
+            .fold(Vec::new, |mut shifts, shift| {
+                shifts.push(shift);
+                if shifts.len() > 2 * top.max(1) {
+                    shifts = keep(shifts);
+                }
+                shifts
+            })
+            .map(keep)
+            .reduce(Vec::new, |a, b| keep(a.into_iter().chain(b).collect()))
+    }
+
+    pub fn json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("comparison serialize")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(cells: &[(usize, usize, usize)]) -> BTreeMap<(Abstraction, Abstraction), usize> {
+        cells
+            .iter()
+            .map(|&(a, b, n)| ((Abstraction::from((Street::Flop, a)), Abstraction::from((Street::Flop, b))), n))
+            .collect()
+    }
+
+    #[test]
+    fn relabeled_partitions_agree() {
+        let relabeled = table(&[(0, 5, 10), (1, 3, 20), (2, 4, 30)]);
+        assert!((Comparison::rand(&relabeled) - 1.).abs() < 1e-9);
+        assert!((Comparison::mutual(&relabeled) - 1.).abs() < 1e-9);
+        let overlaps = Comparison::overlaps(&relabeled);
+        assert!(overlaps.iter().all(|o| o.share == 1. && o.jaccard == 1.));
+    }
+
+    #[test]
+    fn independent_partitions_disagree() {
+        let independent = table(&[(0, 0, 25), (0, 1, 25), (1, 0, 25), (1, 1, 25)]);
+        assert!(Comparison::rand(&independent).abs() < 0.05);
+        assert!(Comparison::mutual(&independent) < 1e-9);
+        let split = Comparison::overlaps(&independent);
+        assert!(split.iter().all(|o| o.share == 0.5));
+    }
+
+    #[test]
+    fn identical_lookups_have_no_shifts() {
+        let lookup = Lookup::grow(Street::Pref);
+        let comparison = Comparison::new(&lookup, &lookup, 64, 10);
+        assert_eq!(comparison.left, Street::Pref.n_isomorphisms());
+        assert!((comparison.rand - 1.).abs() < 1e-9);
+        assert!(comparison.shifts.is_empty());
+    }
+}
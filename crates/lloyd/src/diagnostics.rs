@@ -0,0 +1,540 @@
+//! Clustering quality diagnostics.
+//!
+//! Drift and inertia telemetry say whether k-means converged, not whether
+//! the buckets it converged to separate hands well. A [`Report`] measures
+//! that per street on a seeded sample of isomorphisms: how tight buckets
+//! are, how far apart their centroids sit, how close each hand came to
+//! being assigned elsewhere, and how equity spreads within each bucket.
+
+use super::*;
+use deuce::*;
+use kicker::*;
+use pokerkit::*;
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+
+/// Sampled hands that enter the pairwise silhouette, which is quadratic.
+const SILHOUETTE: usize = 256;
+/// Monte Carlo runouts per hand for pre-river equity.
+const TRIALS: usize = 2048;
+/// Bins in the nearest-centroid margin histogram.
+const BINS: usize = 20;
+
+/// Evidence about one street's clustering, gathered from files or the
+/// database and reduced into a [`Report`].
+pub struct Sample {
+    street: Street,
+    /// Isomorphisms per bucket over the whole street.
+    populations: BTreeMap<Abstraction, usize>,
+    /// Sampled hands with their bucket and next-street histogram.
+    /// Histograms are absent on the river.
+    points: Vec<(Isomorphism, Abstraction, Option<Histogram>)>,
+    /// Bucket centroids over next-street buckets.
+    centroids: Future,
+    /// Ground metric over next-street buckets.
+    ground: Metric,
+}
+
+impl Sample {
+    /// `n` distinct isomorphisms of the street, seeded by street so that
+    /// file and database reports sample the same hands.
+    pub(crate) fn isomorphisms(street: Street, n: usize) -> Vec<Isomorphism> {
+        use rand::SeedableRng;
+        use rand::rngs::SmallRng;
+        let ref mut rng = SmallRng::seed_from_u64(street as u64);
+        let size = street.n_isomorphisms();
+        let mut sample = rand::seq::index::sample(rng, size, n.min(size)).into_vec();
+        sample.sort_unstable();
+        sample.into_iter().map(|i| Isomorphism::from((street, i))).collect()
+    }
+
+    /// Ground metric of the street below `next`. River artifacts built by
+    /// [`Artifacts::from`] carry no metric of their own, so equity-ladder
+    /// rivers fall back to [`Metric::new`].
+    fn ground(next: &Artifacts) -> Metric {
+        if next.metric.street() == next.lookup.street() {
+            next.metric.clone()
+        } else {
+            Metric::new(next.lookup.street())
+        }
+    }
+
+    /// Samples `n` hands from a street's artifacts. `next` is the street
+    /// below, which projects hands to histograms; it is `None` on the river.
+    pub fn from_artifacts(this: &Artifacts, next: Option<&Artifacts>, n: usize) -> Self {
+        let street = this.lookup.street();
+        let populations = this.lookup.iter().fold(BTreeMap::new(), |mut counts, (_, abs)| {
+            *counts.entry(abs).or_default() += 1;
+            counts
+        });
+        let points = Self::isomorphisms(street, n)
+            .into_par_iter()
+            .map(|iso| (iso, this.lookup.lookup(&iso), next.map(|next| next.lookup.future(&iso))))
+            .collect();
+        Self {
+            street,
+            populations,
+            points,
+            centroids: this.future.clone(),
+            ground: next.map_or_else(|| Metric::new(street), Self::ground),
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl Sample {
+    /// Buckets of the given isomorphisms, queried in chunks.
+    async fn buckets(
+        client: &tokio_postgres::Client,
+        isos: &[i64],
+    ) -> Result<std::collections::HashMap<i64, Abstraction>, String> {
+        let sql = format!("SELECT obs, abs FROM {} WHERE obs = ANY($1)", daybook::isomorphism());
+        let mut buckets = std::collections::HashMap::new();
+        for chunk in isos.chunks(1 << 16) {
+            client
+                .query(&sql, &[&chunk])
+                .await
+                .map_err(|e| format!("{}: {e}", daybook::isomorphism()))?
+                .into_iter()
+                .map(|row| (row.get::<_, i64>(0), Abstraction::from(row.get::<_, i16>(1))))
+                .for_each(|(obs, abs)| {
+                    buckets.insert(obs, abs);
+                });
+        }
+        Ok(buckets)
+    }
+
+    /// Samples `n` hands of `street` from the database, reading only the
+    /// sampled hands and their children rather than whole lookups. Bucket
+    /// populations come precomputed from the abstraction table.
+    pub async fn from_db(client: &tokio_postgres::Client, street: Street, n: usize) -> Result<Self, String> {
+        let sql = format!("SELECT abs, population FROM {} WHERE street = $1", daybook::abstraction());
+        let populations = client
+            .query(&sql, &[&(street as i16)])
+            .await
+            .map_err(|e| format!("{}: {e}", daybook::abstraction()))?
+            .into_iter()
+            .map(|row| (Abstraction::from(row.get::<_, i16>(0)), row.get::<_, i32>(1) as usize))
+            .collect();
+        let isos = Self::isomorphisms(street, n);
+        let ids = isos.iter().copied().map(i64::from).collect::<Vec<_>>();
+        let parents = Self::buckets(client, &ids).await?;
+        let children = if street == Street::Rive {
+            Default::default()
+        } else {
+            let mut ids = isos
+                .iter()
+                .flat_map(|iso| {
+                    iso.0
+                        .children()
+                        .map(Isomorphism::from)
+                        .map(i64::from)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            ids.sort_unstable();
+            ids.dedup();
+            Self::buckets(client, &ids).await?
+        };
+        let bucket = |buckets: &std::collections::HashMap<i64, Abstraction>, iso: Isomorphism| {
+            buckets
+                .get(&i64::from(iso))
+                .copied()
+                .ok_or_else(|| format!("{street} isomorphism {} has no bucket", i64::from(iso)))
+        };
+        let points = isos
+            .into_iter()
+            .map(|iso| {
+                let histogram = match street {
+                    Street::Rive => None,
+                    _ => Some(
+                        iso.0
+                            .children()
+                            .map(|o| bucket(&children, Isomorphism::from(o)))
+                            .collect::<Result<Vec<Abstraction>, String>>()?
+                            .into(),
+                    ),
+                };
+                Ok((iso, bucket(&parents, iso)?, histogram))
+            })
+            .collect::<Result<_, String>>()?;
+        let (centroids, ground) = match street {
+            Street::Rive => (Future::default(), Metric::new(street)),
+            _ => (Future::from_street(client, street).await, Metric::from_street(client, street.next()).await),
+        };
+        Ok(Self {
+            street,
+            populations,
+            points,
+            centroids,
+            ground,
+        })
+    }
+}
+
+/// Quality of one street's clustering. Distances are EMD under the ground
+/// metric of the street below; they are absent on the river, which is
+/// bucketed by scalar features rather than histograms.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Report {
+    pub street: Street,
+    /// Hands sampled.
+    pub samples: usize,
+    /// Mean distance from a sampled hand to its own centroid.
+    pub within: Option<Energy>,
+    /// Mean distance between distinct centroids.
+    pub between: Option<Energy>,
+    /// Mean silhouette of the first [`SILHOUETTE`] samples, in `[-1, 1]`.
+    pub silhouette: Option<f32>,
+    /// Per-bucket population and equity spread.
+    pub buckets: Vec<Bucket>,
+    /// Histogram of `min_{c ≠ a} d(x, c) − d(x, a)` for hand `x` in bucket
+    /// `a`. Negative margins are hands nearer another centroid than their own.
+    pub margins: Vec<Bin>,
+}
+
+/// Equity spread of the sampled hands in one bucket.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Bucket {
+    pub abs: Abstraction,
+    /// Isomorphisms assigned over the whole street.
+    pub population: usize,
+    /// Sampled hands assigned.
+    pub samples: usize,
+    /// Mean equity of sampled hands, absent when none were sampled.
+    pub equity: Option<Probability>,
+    /// Population variance of sampled equity.
+    pub variance: Option<Probability>,
+}
+
+/// One bar of a histogram over `[lo, hi)`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Bin {
+    pub lo: Energy,
+    pub hi: Energy,
+    pub count: usize,
+}
+
+impl Report {
+    /// Equity against a random hand. Exact on the river with ties
+    /// excluded, as the equity ladder buckets it; Monte Carlo with ties
+    /// split on earlier streets.
+    pub(crate) fn equity(iso: &Isomorphism) -> Probability {
+        match iso.0.street() {
+            Street::Rive => iso.0.equity(),
+            _ => iso.0.simulate(TRIALS),
+        }
+    }
+
+    fn mean(xs: impl Iterator<Item = f32>) -> Option<f32> {
+        let (sum, n) = xs.fold((0., 0usize), |(sum, n), x| (sum + x, n + 1));
+        (n > 0).then(|| sum / n as f32)
+    }
+
+    /// Equal-width histogram over the range of `xs`.
+    fn bins(xs: &[Energy]) -> Vec<Bin> {
+        let lo = xs.iter().copied().fold(Energy::INFINITY, Energy::min);
+        let hi = xs.iter().copied().fold(Energy::NEG_INFINITY, Energy::max);
+        if xs.is_empty() {
+            return Vec::new();
+        }
+        let width = ((hi - lo) / BINS as Energy).max(Energy::EPSILON);
+        let mut bins = (0..BINS)
+            .map(|i| Bin {
+                lo: lo + width * i as Energy,
+                hi: lo + width * (i + 1) as Energy,
+                count: 0,
+            })
+            .collect::<Vec<_>>();
+        for x in xs {
+            bins[(((x - lo) / width) as usize).min(BINS - 1)].count += 1;
+        }
+        bins
+    }
+
+    /// Rousseeuw silhouette from a pairwise distance matrix. Hands alone
+    /// in their bucket score zero.
+    fn silhouette(labels: &[Abstraction], distances: &[Vec<Energy>]) -> Option<f32> {
+        Self::mean((0..labels.len()).map(|i| {
+            let mut groups = BTreeMap::<Abstraction, (Energy, usize)>::new();
+            for j in (0..labels.len()).filter(|&j| j != i) {
+                let entry = groups.entry(labels[j]).or_default();
+                entry.0 += distances[i][j];
+                entry.1 += 1;
+            }
+            let a = match groups.remove(&labels[i]) {
+                Some((sum, n)) => sum / n as Energy,
+                None => return 0.,
+            };
+            let b = groups
+                .values()
+                .map(|(sum, n)| sum / *n as Energy)
+                .fold(Energy::INFINITY, Energy::min);
+            match a.max(b) {
+                m if m > 0. && m.is_finite() => (b - a) / m,
+                _ => 0.,
+            }
+        }))
+    }
+}
+
+impl From<Sample> for Report {
+    fn from(sample: Sample) -> Self {
+        let Sample {
+            street,
+            populations,
+            points,
+            centroids,
+            ground,
+        } = sample;
+        let equities = points
+            .par_iter()
+            .map(|(iso, _, _)| Self::equity(iso))
+            .collect::<Vec<_>>();
+        let buckets = populations
+            .into_iter()
+            .map(|(abs, population)| {
+                let xs = points
+                    .iter()
+                    .zip(&equities)
+                    .filter(|((_, a, _), _)| *a == abs)
+                    .map(|(_, x)| *x)
+                    .collect::<Vec<_>>();
+                let equity = Self::mean(xs.iter().copied());
+                let variance = equity.and_then(|m| Self::mean(xs.iter().map(|x| (x - m).powi(2))));
+                Bucket {
+                    abs,
+                    population,
+                    samples: xs.len(),
+                    equity,
+                    variance,
+                }
+            })
+            .collect();
+        let hands = points
+            .iter()
+            .filter_map(|(_, a, h)| h.map(|h| (*a, h)))
+            .filter(|(a, _)| centroids.get(a).is_some())
+            .collect::<Vec<(Abstraction, Histogram)>>();
+        let (within, between, silhouette, margins) = if hands.is_empty() {
+            (None, None, None, Vec::new())
+        } else {
+            let centers = centroids.iter().collect::<Vec<_>>();
+            let (owns, margins) = hands
+                .par_iter()
+                .map(|(a, h)| {
+                    centers.iter().map(|(c, x)| (*c == a, ground.emd(h, x))).fold(
+                        (0., Energy::INFINITY),
+                        |(own, other), (mine, d)| {
+                            if mine { (d, other) } else { (own, other.min(d)) }
+                        },
+                    )
+                })
+                .map(|(own, other)| (own, other - own))
+                .filter(|(_, margin)| margin.is_finite())
+                .unzip::<_, _, Vec<_>, Vec<_>>();
+            let between = Self::mean(
+                (0..centers.len())
+                    .into_par_iter()
+                    .flat_map_iter(|i| (i + 1..centers.len()).map(move |j| (i, j)))
+                    .map(|(i, j)| ground.emd(centers[i].1, centers[j].1))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            );
+            let subset = &hands[..hands.len().min(SILHOUETTE)];
+            let distances = subset
+                .par_iter()
+                .map(|(_, x)| subset.iter().map(|(_, y)| ground.emd(x, y)).collect())
+                .collect::<Vec<Vec<Energy>>>();
+            let labels = subset.iter().map(|(a, _)| *a).collect::<Vec<_>>();
+            (Self::mean(owns.into_iter()), between, Self::silhouette(&labels, &distances), Self::bins(&margins))
+        };
+        Self {
+            street,
+            samples: points.len(),
+            within,
+            between,
+            silhouette,
+            buckets,
+            margins,
+        }
+    }
+}
+
+/// Reports for every street that was diagnosed, with JSON and HTML views.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+#[serde(transparent)]
+pub struct Diagnostics(Vec<Report>);
+
+impl FromIterator<Report> for Diagnostics {
+    fn from_iter<I: IntoIterator<Item = Report>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Diagnostics {
+    /// Diagnoses every street whose artifacts were saved into `dir` by
+    /// [`Artifacts::save`], sampling `n` hands per street.
+    pub fn from_dir(dir: impl AsRef<std::path::Path>, n: usize) -> std::io::Result<Self> {
+        let dir = dir.as_ref();
+        let artifacts = Street::all()
+            .iter()
+            .map(|&street| Artifacts::<Lookup>::load(dir, street).ok())
+            .collect::<Vec<_>>();
+        if artifacts.iter().all(Option::is_none) {
+            return Err(std::io::Error::new(std::io::ErrorKind::NotFound, "no artifacts"));
+        }
+        Ok(artifacts
+            .iter()
+            .enumerate()
+            .filter_map(|(i, this)| this.as_ref().map(|this| (this, artifacts.get(i + 1))))
+            .filter(|(this, next)| this.lookup.street() == Street::Rive || next.is_some_and(Option::is_some))
+            .map(|(this, next)| Sample::from_artifacts(this, next.and_then(Option::as_ref), n))
+            .map(Report::from)
+            .collect())
+    }
+
+    /// Samples `n` hands per street clustered into the database. Reducing
+    /// them into reports simulates equities, so callers on an async
+    /// runtime should collect [`Report::from`] on a blocking thread.
+    #[cfg(feature = "server")]
+    pub async fn sample(client: &tokio_postgres::Client, n: usize) -> Result<Vec<Sample>, String> {
+        let mut samples = Vec::new();
+        for street in Street::all() {
+            samples.push(Sample::from_db(client, street, n).await?);
+        }
+        Ok(samples)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Report> {
+        self.0.iter()
+    }
+
+    pub fn json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("diagnostics serialize")
+    }
+
+    /// Self-contained HTML page: a summary table, then per street a
+    /// margin histogram and a table of buckets.
+    pub fn html(&self) -> String {
+        fn cell(x: Option<f32>) -> String {
+            x.map_or_else(|| String::from("—"), |x| format!("{x:.4}"))
+        }
+        let mut html = String::from(
+            "<!doctype html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Clustering diagnostics</title>\n<style>\n\
+             body { font-family: sans-serif; background: #fafafa; }\n\
+             section { margin: 1em; }\n\
+             table { border-collapse: collapse; }\n\
+             th, td { padding: 0.2em 0.6em; border-bottom: 1px solid #ddd; font-size: 0.8em; text-align: right; }\n\
+             .bars { display: flex; align-items: flex-end; height: 8em; gap: 2px; }\n\
+             .bars div { width: 1.2em; }\n\
+             </style>\n</head>\n<body>\n<h2>Summary</h2>\n<table>\n\
+             <tr><th>street</th><th>samples</th><th>within</th><th>between</th><th>silhouette</th></tr>\n",
+        );
+        for report in self.iter() {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                report.street,
+                report.samples,
+                cell(report.within),
+                cell(report.between),
+                cell(report.silhouette),
+            ));
+        }
+        html.push_str("</table>\n");
+        for report in self.iter() {
+            html.push_str(&format!("<section>\n<h3>{}</h3>\n", report.street));
+            if let Some(tallest) = report.margins.iter().map(|b| b.count).max() {
+                html.push_str("<div class=\"bars\">");
+                for bin in &report.margins {
+                    let color = if bin.hi <= 0. { "#c0392b" } else { "#2c7fb8" };
+                    html.push_str(&format!(
+                        "<div style=\"height:{:.1}%;background:{color}\" title=\"[{:.4}, {:.4}) {}\"></div>",
+                        100. * bin.count as f32 / tallest.max(1) as f32,
+                        bin.lo,
+                        bin.hi,
+                        bin.count,
+                    ));
+                }
+                html.push_str("</div>\n");
+            }
+            html.push_str(
+                "<table>\n<tr><th>bucket</th><th>population</th><th>samples</th><th>equity</th><th>variance</th></tr>\n",
+            );
+            for bucket in &report.buckets {
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    bucket.abs,
+                    bucket.population,
+                    bucket.samples,
+                    cell(bucket.equity),
+                    cell(bucket.variance),
+                ));
+            }
+            html.push_str("</table>\n</section>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two flop buckets split by whether the pocket holds an ace, and one
+    /// centroid per bucket on a two-point flop metric.
+    fn split() -> (Abstraction, Abstraction, Metric) {
+        let (lo, hi) = (Abstraction::from((Street::Flop, 0)), Abstraction::from((Street::Flop, 1)));
+        let mut metric = Metric::new(Street::Flop);
+        metric.set(Pair::new(Street::Flop, 0, 1), 1.);
+        (lo, hi, metric)
+    }
+
+    #[test]
+    fn separated_buckets_score_well() {
+        let (lo, hi, ground) = split();
+        let mass = |abs| Histogram::empty(Street::Flop).increment(abs);
+        let points = Sample::isomorphisms(Street::Pref, 64)
+            .into_iter()
+            .map(|iso| {
+                let ace = iso.0.pocket().max_rank() == Some(Rank::Ace);
+                let abs = Abstraction::from((Street::Pref, usize::from(ace)));
+                (iso, abs, Some(mass(if ace { hi } else { lo })))
+            })
+            .collect::<Vec<_>>();
+        let populations = points.iter().fold(BTreeMap::new(), |mut counts, (_, abs, _)| {
+            *counts.entry(*abs).or_default() += 1;
+            counts
+        });
+        let centroids = (0..2)
+            .map(|k| (Abstraction::from((Street::Pref, k)), mass(if k == 1 { hi } else { lo })))
+            .collect::<BTreeMap<_, _>>()
+            .into();
+        let report = Report::from(Sample {
+            street: Street::Pref,
+            populations,
+            points,
+            centroids,
+            ground,
+        });
+        assert_eq!(report.samples, 64);
+        assert!(report.within.unwrap() < 1e-3);
+        assert!(report.between.unwrap() > 0.5);
+        assert!(report.silhouette.unwrap() > 0.9);
+        assert!(report.margins.iter().all(|b| b.hi > 0. || b.count == 0));
+        let aces = &report.buckets[1];
+        assert!(aces.equity.unwrap() > report.buckets[0].equity.unwrap());
+        let json = serde_json::from_str::<serde_json::Value>(&Diagnostics(vec![report]).json()).unwrap();
+        assert_eq!(json[0]["samples"], 64);
+    }
+
+    #[test]
+    fn margin_bins_cover_every_value() {
+        let bins = Report::bins(&[-1., 0., 0.5, 3.]);
+        assert_eq!(bins.len(), BINS);
+        assert_eq!(bins.iter().map(|b| b.count).sum::<usize>(), 4);
+        assert_eq!(bins[0].count, 1);
+        assert_eq!(bins[BINS - 1].count, 1);
+    }
+}
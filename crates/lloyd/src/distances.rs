@@ -7,6 +7,7 @@ use pokerkit::*;
 pub const TRI_PREF: usize = Street::Pref.n_abstractions() * (Street::Pref.n_abstractions() - 1) / 2;
 pub const TRI_FLOP: usize = Street::Flop.n_abstractions() * (Street::Flop.n_abstractions() - 1) / 2;
 pub const TRI_TURN: usize = Street::Turn.n_abstractions() * (Street::Turn.n_abstractions() - 1) / 2;
+pub const TRI_RIVE: usize = Street::Rive.n_abstractions() * (Street::Rive.n_abstractions() - 1) / 2;
 
 /// Dense triangular storage for pairwise distances between abstractions.
 ///
@@ -17,25 +18,27 @@ pub const TRI_TURN: usize = Street::Turn.n_abstractions() * (Street::Turn.n_abst
 /// # Const Generic
 ///
 /// `T` is the triangular number K*(K-1)/2 where K is the street's abstraction count.
-/// This enables stack allocation with compile-time size checking.
-#[derive(Clone, Copy)]
+/// The values live on the heap: at K=4096 a turn metric is ~8.4M entries
+/// (~32 MiB), far past any stack.
+#[derive(Clone)]
 pub struct Distances<const T: usize> {
     /// Street these distances are for.
     street: Street,
     /// Flat array of distance values indexed by triangular index.
-    values: [Energy; T],
+    values: Box<[Energy]>,
 }
 
 pub type DistPref = Distances<TRI_PREF>;
 pub type DistFlop = Distances<TRI_FLOP>;
 pub type DistTurn = Distances<TRI_TURN>;
+pub type DistRive = Distances<TRI_RIVE>;
 
 impl<const T: usize> Distances<T> {
     /// Creates empty distance storage for the given street.
-    pub const fn new(street: Street) -> Self {
+    pub fn new(street: Street) -> Self {
         Self {
             street,
-            values: [0.0; T],
+            values: vec![0.0; T].into_boxed_slice(),
         }
     }
     /// The street these distances are for.
@@ -74,6 +77,7 @@ impl<const T: usize> IntoIterator for Distances<T> {
         let street = self.street;
         Box::new(
             self.values
+                .into_vec()
                 .into_iter()
                 .enumerate()
                 .map(move |(t, d)| (Pair::split(t), d))
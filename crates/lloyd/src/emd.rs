@@ -11,6 +11,8 @@ use std::collections::BTreeMap;
 /// - Symmetry (d(x,y) = d(y,x))
 /// - Self-annihilation (d(x,x) = 0)
 /// - Triangle inequality (d(x,z) ≤ d(x,y) + d(y,z))
+///
+/// and that approximate couplings stay close to the exact one.
 pub struct EMD(Metric, Histogram, Histogram, Histogram);
 
 impl EMD {
@@ -26,6 +28,10 @@ impl EMD {
     pub fn heuristic(&self) -> Heuristic<'_> {
         Heuristic::from((&self.1, &self.2, &self.0)).minimize()
     }
+    /// Computes exact EMD via network simplex between first two histograms.
+    pub fn simplex(&self) -> NetworkSimplex<'_, Metric, Histogram, Histogram> {
+        NetworkSimplex::from((&self.1, &self.2, &self.0)).minimize()
+    }
     /// Destructures into components.
     pub fn inner(self) -> (Metric, Histogram, Histogram, Histogram) {
         (self.0, self.1, self.2, self.3)
@@ -34,10 +40,18 @@ impl EMD {
 
 impl Arbitrary for EMD {
     fn random() -> Self {
+        Self::seeded(&mut rand::rng())
+    }
+}
+
+impl EMD {
+    /// [`Arbitrary::random`] drawn from a caller's generator, so tests
+    /// can pin the sample.
+    pub fn seeded(rng: &mut impl rand::Rng) -> Self {
         // construct random metric satisfying symmetric semipositivity
-        let p = Histogram::random();
-        let q = Histogram::random();
-        let r = Histogram::random();
+        let p = Histogram::seeded(rng);
+        let q = Histogram::seeded(rng);
+        let r = Histogram::seeded(rng);
         let m = Metric::from(
             std::iter::empty()
                 .chain(p.support())
@@ -52,7 +66,7 @@ impl Arbitrary for EMD {
                 })
                 .filter(|(x, y)| x > y)
                 .map(|(x, y)| Pair::from((&x, &y)))
-                .map(|paired| (paired, rand::random::<f32>()))
+                .map(|paired| (paired, rng.random::<f32>()))
                 .collect::<BTreeMap<_, _>>(),
         );
         Self(m, p, q, r)
@@ -163,4 +177,55 @@ mod tests {
         assert_eq!(d11, 0.);
         assert_eq!(d22, 0.);
     }
+
+    /// exact implementation should be
+    /// 1. positive semidefinite
+    /// 2. symmetric
+    /// 3. exactly self-annihilating
+    /// 4. a lower bound on every feasible coupling
+
+    #[test]
+    fn is_simplex_emd_symmetric() {
+        let EMD(metric, h1, h2, _) = EMD::random();
+        let d12 = NetworkSimplex::from((&h1, &h2, &metric)).minimize().cost();
+        let d21 = NetworkSimplex::from((&h2, &h1, &metric)).minimize().cost();
+        assert!(d12 > 0., "{d12}");
+        assert!((d12 - d21).abs() < 1e-5, "{d12} vs {d21}");
+    }
+    #[test]
+    fn is_simplex_emd_zero() {
+        let EMD(metric, h1, h2, _) = EMD::random();
+        let d11 = NetworkSimplex::from((&h1, &h1, &metric)).minimize().cost();
+        let d22 = NetworkSimplex::from((&h2, &h2, &metric)).minimize().cost();
+        assert!(d11.abs() < 1e-6, "{d11}");
+        assert!(d22.abs() < 1e-6, "{d22}");
+    }
+    #[test]
+    fn is_simplex_emd_below_heuristic() {
+        for _ in 0..16 {
+            let emd = EMD::random();
+            let exact = emd.simplex().cost();
+            let greedy = emd.heuristic().cost();
+            assert!(exact <= greedy + 1e-5, "{exact} > {greedy}");
+        }
+    }
+
+    /// sinkhorn against the exact coupling should be
+    /// 1. no cheaper, up to marginal violation
+    /// 2. within a few percent of the transport cost
+
+    #[test]
+    fn is_sinkhorn_emd_close_to_exact() {
+        use rand::SeedableRng;
+        const SLACK: f32 = 0.001;
+        const RELATIVE: f32 = 0.10;
+        let ref mut rng = rand::rngs::SmallRng::seed_from_u64(0x5eed);
+        for _ in 0..32 {
+            let emd = EMD::seeded(rng);
+            let approx = emd.sinkhorn().cost();
+            let exact = emd.simplex().cost();
+            assert!(approx >= exact - SLACK, "sinkhorn {approx} below exact {exact}");
+            assert!(approx - exact <= RELATIVE * exact, "sinkhorn {approx} far from exact {exact}");
+        }
+    }
 }
@@ -1,5 +1,7 @@
 use crate::*;
+use deuce::*;
 use kicker::*;
+use pokerkit::*;
 use std::collections::BTreeMap;
 use std::sync::OnceLock;
 
@@ -12,7 +14,7 @@ use std::sync::OnceLock;
 /// # Database
 ///
 /// Persisted to the `transitions` table for use in real-time inference.
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct Future(BTreeMap<Abstraction, Histogram>);
 
 impl From<BTreeMap<Abstraction, Histogram>> for Future {
@@ -21,6 +23,110 @@ impl From<BTreeMap<Abstraction, Histogram>> for Future {
     }
 }
 
+/// Rebuilds centroids from `(prev, next, probability)` transition rows,
+/// as stored in the `transitions` table. Probabilities are scaled back to
+/// integer counts at [`Future::RESOLUTION`] per unit mass.
+impl FromIterator<(Abstraction, Abstraction, Probability)> for Future {
+    fn from_iter<I: IntoIterator<Item = (Abstraction, Abstraction, Probability)>>(iter: I) -> Self {
+        let mut map = BTreeMap::<Abstraction, Histogram>::new();
+        for (prev, next, dx) in iter {
+            let count = (dx * Self::RESOLUTION).round() as usize;
+            if count > 0 {
+                map.entry(prev)
+                    .or_insert_with(|| Histogram::empty(next.street()))
+                    .set(next, count);
+            }
+        }
+        Self(map)
+    }
+}
+
+impl Future {
+    /// Counts per unit mass when transition probabilities are turned
+    /// back into integer [`Histogram`] bins.
+    const RESOLUTION: Probability = 65536.;
+
+    /// Centroid histogram of an abstraction, if it was clustered.
+    pub fn get(&self, abs: &Abstraction) -> Option<&Histogram> {
+        self.0.get(abs)
+    }
+    /// Every `(abstraction, centroid)` in abstraction order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Abstraction, &Histogram)> {
+        self.0.iter()
+    }
+    /// Number of centroids.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// True when no street was clustered into this model.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Every `(prev, next, probability)` transition row.
+    pub fn rows(&self) -> impl Iterator<Item = (Abstraction, Abstraction, Probability)> + '_ {
+        self.0
+            .iter()
+            .flat_map(|(prev, hist)| hist.distribution().into_iter().map(move |(next, dx)| (*prev, next, dx)))
+    }
+}
+
+/// Flat binary file: magic, row count, then one little-endian
+/// `(i16 prev, i16 next, f32 probability)` per transition row.
+impl Future {
+    const MAGIC: &[u8; 8] = b"RBPFUTR1";
+
+    pub fn write(&self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        let rows = self.rows().collect::<Vec<_>>();
+        w.write_all(Self::MAGIC)?;
+        w.write_all(&(rows.len() as u64).to_le_bytes())?;
+        let bytes = rows
+            .into_iter()
+            .flat_map(|(prev, next, dx)| {
+                i16::from(prev)
+                    .to_le_bytes()
+                    .into_iter()
+                    .chain(i16::from(next).to_le_bytes())
+                    .chain(dx.to_le_bytes())
+            })
+            .collect::<Vec<u8>>();
+        w.write_all(&bytes)
+    }
+
+    pub fn read(r: &mut impl std::io::Read) -> std::io::Result<Self> {
+        let mut magic = [0; 8];
+        r.read_exact(&mut magic)?;
+        if &magic != Self::MAGIC {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "not a future file"));
+        }
+        let mut count = [0; 8];
+        r.read_exact(&mut count)?;
+        let mut bytes = vec![0; 8 * u64::from_le_bytes(count) as usize];
+        r.read_exact(&mut bytes)?;
+        Ok(bytes
+            .chunks_exact(8)
+            .map(|b| {
+                (
+                    Abstraction::from(i16::from_le_bytes([b[0], b[1]])),
+                    Abstraction::from(i16::from_le_bytes([b[2], b[3]])),
+                    Probability::from_le_bytes([b[4], b[5], b[6], b[7]]),
+                )
+            })
+            .collect())
+    }
+
+    /// Writes the transition model to `path`.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let ref mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.write(file)?;
+        std::io::Write::flush(file)
+    }
+
+    /// Reads a transition model written by [`Self::save`].
+    pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        Self::read(&mut std::io::BufReader::new(std::fs::File::open(path)?))
+    }
+}
+
 impl From<Future> for BTreeMap<Abstraction, Histogram> {
     fn from(future: Future) -> Self {
         future.0
@@ -109,3 +215,41 @@ impl daybook::Streamable for Future {
         })
     }
 }
+
+#[cfg(feature = "server")]
+impl Future {
+    pub async fn from_street(client: &tokio_postgres::Client, street: Street) -> Self {
+        let sql = format!("SELECT prev, next, dx FROM {}", daybook::transitions());
+        client
+            .query(&sql, &[])
+            .await
+            .expect("query")
+            .into_iter()
+            .map(|row| (row.get::<_, i16>(0), row.get::<_, i16>(1), row.get::<_, f32>(2)))
+            .map(|(prev, next, dx)| (Abstraction::from(prev), Abstraction::from(next), dx))
+            .filter(|(prev, _, _)| prev.street() == street)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_round_trip() {
+        let mut hist = Histogram::empty(Street::Rive);
+        hist.set(Abstraction::from((Street::Rive, 3)), 3);
+        hist.set(Abstraction::from((Street::Rive, 9)), 1);
+        let prev = Abstraction::from((Street::Turn, 2));
+        let mut map = BTreeMap::new();
+        map.insert(prev, hist);
+        let future = Future::from(map);
+        let mut bytes = Vec::new();
+        future.write(&mut bytes).unwrap();
+        let loaded = Future::read(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert!(future.rows().eq(loaded.rows()));
+        assert!(Future::read(&mut &bytes[1..]).is_err());
+    }
+}
@@ -225,16 +225,22 @@ impl Density for Histogram {
 
 impl Arbitrary for Histogram {
     fn random() -> Self {
+        Self::seeded(&mut rand::rng())
+    }
+}
+
+impl Histogram {
+    /// [`Arbitrary::random`] drawn from a caller's generator, so tests
+    /// can pin the sample.
+    pub fn seeded(rng: &mut impl rand::Rng) -> Self {
         const S: usize = 16;
         const M: usize = 64;
-        (0..)
-            .map(|_| Abstraction::random())
-            .filter(|a| a.street() == Street::Flop)
-            .take(S)
+        (0..S)
+            .map(|_| Abstraction::from((Street::Flop, rng.random_range(0..Street::Flop.k()))))
             .collect::<Vec<_>>()
             .into_iter()
             .cycle()
-            .filter(|_| rand::random::<bool>())
+            .filter(|_| rng.random::<bool>())
             .take(M)
             .fold(Histogram::empty(Street::Flop), Histogram::increment)
     }
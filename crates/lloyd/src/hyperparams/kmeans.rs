@@ -1,3 +1,4 @@
+use crate::Centroid;
 use deuce::Street;
 use pokerkit::*;
 
@@ -11,16 +12,33 @@ use pokerkit::*;
 pub struct KmeansHyperParams {
     flop_iterations: usize,
     turn_iterations: usize,
+    ochs_iterations: usize,
     drift_threshold: Energy,
+    flop_centroid: Centroid,
+    turn_centroid: Centroid,
+    flop_batch: Option<usize>,
+    turn_batch: Option<usize>,
+    minibatch_epochs: usize,
 }
 
 impl KmeansHyperParams {
     pub const DEFAULT: Self = Self {
         flop_iterations: 32, // was 20
         turn_iterations: 32, // was 24
+        ochs_iterations: 64,
         // 0.0 disables early termination — pick a real value once drift
         // values from a real run are visible in Grafana.
         drift_threshold: 0.0,
+        // Barycenters cost one entropic solve per cluster per iteration;
+        // enable per street once its inertia delta justifies the time.
+        flop_centroid: Centroid::Mean,
+        turn_centroid: Centroid::Mean,
+        // None keeps the in-memory Elkan layer. Some(b) streams points in
+        // batches of b through mini-batch k-means instead, for hosts that
+        // cannot hold a street's histograms and bounds at once.
+        flop_batch: None,
+        turn_batch: None,
+        minibatch_epochs: 4,
     };
 
     /// Lloyd's iterations for the given street. Preflop / river return 0
@@ -34,6 +52,37 @@ impl KmeansHyperParams {
         }
     }
 
+    /// Lloyd's iterations for the OCHS river layer (V5). The ladder
+    /// versions never run it.
+    pub fn ochs_iterations(&self) -> usize {
+        self.ochs_iterations
+    }
+
+    /// Centroid update rule for the given street. Unclustered streets
+    /// report the default, which their layers never exercise.
+    pub fn centroid(&self, street: Street) -> Centroid {
+        match street {
+            Street::Pref | Street::Rive => Centroid::default(),
+            Street::Flop => self.flop_centroid,
+            Street::Turn => self.turn_centroid,
+        }
+    }
+
+    /// Mini-batch size for the given street, or `None` to cluster it in
+    /// memory. Unclustered streets never stream.
+    pub fn batch(&self, street: Street) -> Option<usize> {
+        match street {
+            Street::Pref | Street::Rive => None,
+            Street::Flop => self.flop_batch,
+            Street::Turn => self.turn_batch,
+        }
+    }
+
+    /// Passes over the isomorphism stream made by mini-batch k-means.
+    pub fn minibatch_epochs(&self) -> usize {
+        self.minibatch_epochs
+    }
+
     /// Stop k-means iteration when largest centroid movement falls below this.
     pub fn drift_threshold(&self) -> Energy {
         self.drift_threshold
@@ -5,7 +5,8 @@ use pokerkit::*;
 /// These knobs are fixed at compile time. The abstraction artifact is
 /// generated once per (Version × Regime) and these values influence its
 /// output; changing them at runtime would silently desync the active
-/// blueprint from the persisted EMD distances.
+/// blueprint from the persisted EMD distances. [`SinkhornHyperParams::new`]
+/// exists only for calibration sweeps against the exact solver.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct SinkhornHyperParams {
     temperature: Entropy,
@@ -18,10 +19,24 @@ impl SinkhornHyperParams {
         temperature: 0.025,
         // Calibrated 2026-04-28 (V1 cluster run): ~91% early-term at iter cap
         // 256, bulk converges <128. Cap=256 was paranoia.
+        // Swept 2026-10-18 against exact EMD (`hyperparameter_sweep`):
+        // T ∈ [0.025, 0.05] at 32–128 iters matches ~65% of exact
+        // nearest-centroid assignments; 8 iters or T=0.01 drop to 45–58%.
+        // 512 iters falls to ~50%: the f32 plan drifts off its marginals
+        // and reports costs below the exact EMD.
         iterations: 128,
         tolerance: 0.0005,
     };
 
+    /// Custom parameters for calibration. Production code uses [`Self::DEFAULT`].
+    pub const fn new(temperature: Entropy, iterations: usize, tolerance: Energy) -> Self {
+        Self {
+            temperature,
+            iterations,
+            tolerance,
+        }
+    }
+
     /// Entropy regularization strength. Lower = closer to true EMD.
     pub fn temperature(&self) -> Entropy {
         self.temperature
@@ -1,11 +1,12 @@
 //! Iterator that drives Elkan k-means iterations as a stream of `Step<K>`.
 //!
 //! `Layer::kmeans()` returns a `Kmeans` — built fluently with optional
-//! `with_bound` / `with_threshold`. Each step yielded carries the
+//! `with_bound` / `with_threshold` / `with_centroid`. Each step yielded carries the
 //! drift, elapsed time, and freeze detection so the consumer doesn't
 //! have to track any of that itself.
 
 use crate::Bounds;
+use crate::Centroid;
 use crate::Elkan;
 use crate::Layer;
 use crate::Step;
@@ -23,7 +24,8 @@ use std::time::Instant;
 /// ```text
 /// let ref mut iter = layer.kmeans()
 ///     .with_bound(layer.t())          // bound the stream
-///     .with_threshold(1e-5);          // early-terminate at convergence
+///     .with_threshold(1e-5)           // early-terminate at convergence
+///     .with_centroid(Centroid::Mean); // centroid update rule
 /// for step in iter { … }
 /// ```
 pub struct Kmeans<'street, const K: usize, const N: usize> {
@@ -66,6 +68,13 @@ impl<'street, const K: usize, const N: usize> Kmeans<'street, K, N> {
         self.threshold = (threshold > 0.0).then_some(threshold);
         self
     }
+
+    /// Choose how each step rebuilds centroids from their members. The
+    /// rule sticks to the layer, so later inertia checks see it too.
+    pub fn with_centroid(self, centroid: Centroid) -> Self {
+        *self.layer.averaging_mut() = centroid;
+        self
+    }
 }
 
 impl<const K: usize, const N: usize> Drop for Kmeans<'_, K, N> {
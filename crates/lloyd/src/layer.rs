@@ -12,12 +12,14 @@ use std::collections::BTreeMap;
 /// A clustering layer that maps poker hand isomorphisms to abstract buckets.
 ///
 /// Each layer corresponds to a single betting street and maintains:
-/// - The full dataset of hand histograms (one per isomorphism)
+/// - The full dataset of hand histograms (one per isomorphism, or one per
+///   perfect-recall index)
 /// - K-means cluster centroids learned via the Elkan algorithm
 /// - Distance bounds for acceleration during clustering
 ///
 /// The layer produces three artifacts:
-/// 1. A `Lookup` table mapping isomorphisms to abstractions
+/// 1. A `Lookup` table mapping isomorphisms to abstractions (or, via
+///    [`Layer::recollect`], a `RecallLookup` table keyed by perfect-recall index)
 /// 2. A `Future` transition model mapping abstractions to next-street distributions
 /// 3. A `Metric` defining distances between learned abstractions
 pub struct Layer<const K: usize, const N: usize> {
@@ -31,6 +33,8 @@ pub struct Layer<const K: usize, const N: usize> {
     points: Box<[Histogram; N]>,
     /// Distance bounds for each point, used by Elkan acceleration (not persisted)
     bounds: Box<[Bounds<K>; N]>,
+    /// Rule for rebuilding centroids from their assigned points
+    averaging: Centroid,
 }
 
 impl<const K: usize, const N: usize> Layer<K, N> {
@@ -55,6 +59,11 @@ impl<const K: usize, const N: usize> Layer<K, N> {
     pub(crate) fn centroids_mut(&mut self) -> &mut Box<[Histogram; K]> {
         &mut self.kmeans
     }
+
+    /// Mutable handle to the centroid rule — used by `Kmeans::with_centroid`.
+    pub(crate) fn averaging_mut(&mut self) -> &mut Centroid {
+        &mut self.averaging
+    }
 }
 
 impl<const K: usize, const N: usize> Layer<K, N> {
@@ -64,40 +73,44 @@ impl<const K: usize, const N: usize> Layer<K, N> {
         Self: Elkan<K, N>,
     {
         tracing::info!(street = %self.street(), phase = crate::telemetry::phase::LOOKUP, "kmeans phase begin");
-        use rayon::iter::IntoParallelIterator;
-        use rayon::iter::ParallelIterator;
         match self.street() {
             Street::Pref | Street::Rive => Lookup::grow(self.street()),
-            Street::Flop | Street::Turn => (0..N)
-                .into_par_iter()
-                .map(|i| self.neighbor(i))
-                .collect::<Vec<(usize, f32)>>()
-                .into_iter()
-                .map(|(k, _)| self.abstraction(k))
-                .zip(IsomorphismIterator::from(self.street()))
-                .map(|(abs, iso)| (iso, abs))
-                .collect::<BTreeMap<Isomorphism, Abstraction>>()
-                .into(),
+            Street::Flop | Street::Turn => Lookup::from((self.street(), self.assignments())),
+        }
+    }
+
+    /// Builds a perfect-recall table mapping each index to its nearest cluster abstraction.
+    fn recall(&self) -> RecallLookup
+    where
+        Self: Elkan<K, N>,
+    {
+        tracing::info!(street = %self.street(), phase = crate::telemetry::phase::LOOKUP, "kmeans phase begin");
+        match self.street() {
+            Street::Pref | Street::Rive => RecallLookup::grow(self.street()),
+            Street::Flop | Street::Turn => RecallLookup::from((self.street(), self.assignments())),
         }
     }
 
+    /// Nearest cluster abstraction of every point, in point order.
+    fn assignments(&self) -> Vec<Abstraction>
+    where
+        Self: Elkan<K, N>,
+    {
+        use rayon::iter::IntoParallelIterator;
+        use rayon::iter::ParallelIterator;
+        (0..N)
+            .into_par_iter()
+            .map(|i| self.neighbor(i))
+            .collect::<Vec<(usize, f32)>>()
+            .into_iter()
+            .map(|(k, _)| self.abstraction(k))
+            .collect()
+    }
+
     /// Computes pairwise distances between all learned cluster centroids.
     fn metric(&self) -> Metric {
         tracing::info!(street = %self.street(), phase = crate::telemetry::phase::METRIC, "kmeans phase begin");
-        let mut metric = BTreeMap::new();
-        for (i, x) in self.kmeans.iter().enumerate() {
-            for (j, y) in self.kmeans.iter().enumerate() {
-                if i > j {
-                    let ref a = self.abstraction(i);
-                    let ref b = self.abstraction(j);
-                    let index = Pair::from((a, b));
-                    let distance = self.metric.emd(x, y) + self.metric.emd(y, x);
-                    let distance = distance / 2.;
-                    metric.insert(index, distance);
-                }
-            }
-        }
-        Metric::from(metric)
+        Metric::between(self.street(), &self.metric, &*self.kmeans)
     }
 
     /// Builds the transition future hand mapping abstractions to their centroid histograms.
@@ -137,48 +150,63 @@ impl<const K: usize, const N: usize> Elkan<K, N> for Layer<K, N> {
         self.metric.emd(h1, h2)
     }
 
+    fn aggregate<'a>(&'a self, j: usize, members: impl Iterator<Item = &'a Histogram>) -> Histogram {
+        self.averaging
+            .aggregate(self.centroid(j).identity(), &self.metric, members)
+    }
+
     fn init_centroids(&self) -> [Histogram; K] {
-        use rand::SeedableRng;
-        use rand::distr::Distribution;
-        use rand::distr::weighted::WeightedIndex;
-        use rand::rngs::SmallRng;
-        use rayon::iter::IntoParallelRefIterator;
-        use rayon::iter::ParallelIterator;
-        use std::hash::DefaultHasher;
-        use std::hash::Hash;
-        use std::hash::Hasher;
         // don't do any abstraction on preflop or river
         if matches!(self.street(), Street::Pref | Street::Rive) {
             debug_assert_eq!(N, K);
             return std::array::from_fn(|i| self.points()[i]);
         }
-        // deterministic pseudo-random clustering
-        let ref mut hasher = DefaultHasher::default();
-        self.street().hash(hasher);
-        let ref mut rng = SmallRng::seed_from_u64(hasher.finish());
-        // kmeans++ initialization
-        let mut potentials = vec![1.; N];
-        let mut histograms = Vec::with_capacity(K);
-        while histograms.len() < K {
-            let i = WeightedIndex::new(potentials.iter())
-                .expect("valid weights array")
-                .sample(rng);
-            let x = self.points()[i];
-            histograms.push(x);
-            potentials[i] = 0.;
-            potentials = self
-                .points()
-                .par_iter()
-                .map(|h| self.distance(&x, h))
-                .map(|p| p * p)
-                .collect::<Vec<Energy>>()
-                .iter()
-                .zip(potentials.iter())
-                .map(|(d0, d1)| Energy::min(*d0, *d1))
-                .collect::<Vec<Energy>>();
-        }
-        histograms.try_into().expect("K")
+        seed(self.street(), K, self.points(), &|x, y| self.distance(x, y))
+            .try_into()
+            .expect("K")
+    }
+}
+
+/// Deterministic k-means++ seeding of `k` centroids from `points`,
+/// with the RNG keyed by street so reruns reproduce the abstraction.
+pub(crate) fn seed<D>(street: Street, k: usize, points: &[Histogram], distance: &D) -> Vec<Histogram>
+where
+    D: Fn(&Histogram, &Histogram) -> Energy + Sync,
+{
+    use rand::SeedableRng;
+    use rand::distr::Distribution;
+    use rand::distr::weighted::WeightedIndex;
+    use rand::rngs::SmallRng;
+    use rayon::iter::IntoParallelRefIterator;
+    use rayon::iter::ParallelIterator;
+    use std::hash::DefaultHasher;
+    use std::hash::Hash;
+    use std::hash::Hasher;
+    // deterministic pseudo-random clustering
+    let ref mut hasher = DefaultHasher::default();
+    street.hash(hasher);
+    let ref mut rng = SmallRng::seed_from_u64(hasher.finish());
+    // kmeans++ initialization
+    let mut potentials = vec![1.; points.len()];
+    let mut histograms = Vec::with_capacity(k);
+    while histograms.len() < k {
+        let i = WeightedIndex::new(potentials.iter())
+            .expect("valid weights array")
+            .sample(rng);
+        let x = points[i];
+        histograms.push(x);
+        potentials[i] = 0.;
+        potentials = points
+            .par_iter()
+            .map(|h| distance(&x, h))
+            .map(|p| p * p)
+            .collect::<Vec<Energy>>()
+            .iter()
+            .zip(potentials.iter())
+            .map(|(d0, d1)| Energy::min(*d0, *d1))
+            .collect::<Vec<Energy>>();
     }
+    histograms
 }
 
 impl<const K: usize, const N: usize> Layer<K, N> {
@@ -198,26 +226,76 @@ impl<const K: usize, const N: usize> Layer<K, N> {
         let tel = crate::telemetry::ClusterTelemetry::new(street);
         tracing::info!(%street, phase = phase::HYDRATE, "kmeans phase begin");
         let t = Instant::now();
-        let mut layer = Self::build(street, client).await;
+        let points = match street {
+            Street::Rive => Vec::new(),
+            _ => Lookup::from_street(client, street.next()).await.projections(),
+        };
+        let mut layer = Self::build(street, client, points).await;
+        tel.phase(t, phase::HYDRATE);
+        layer.fit(&tel);
+        let t = Instant::now();
+        let lookup = layer.lookup();
+        tel.phase(t, phase::LOOKUP);
+        let t = Instant::now();
+        let metric = layer.metric();
+        tel.phase(t, phase::METRIC);
+        let t = Instant::now();
+        let future = layer.future();
+        tel.phase(t, phase::FUTURE);
+        Artifacts { lookup, metric, future }
+    }
+    /// Perfect-recall clustering: points are the histograms of every
+    /// [`Waugh`] index rather than every isomorphism. `N` must be the
+    /// street's `n_recalls()`.
+    pub async fn recollect(street: Street, client: &tokio_postgres::Client) -> Artifacts<RecallLookup> {
+        use crate::telemetry::phase;
+        use std::time::Instant;
+        let tel = crate::telemetry::ClusterTelemetry::new(street);
+        tracing::info!(%street, phase = phase::HYDRATE, "kmeans phase begin");
+        let t = Instant::now();
+        let points = match street {
+            Street::Rive => Vec::new(),
+            _ => RecallLookup::from_street(client, street.next()).await.projections(),
+        };
+        let mut layer = Self::build(street, client, points).await;
         tel.phase(t, phase::HYDRATE);
+        layer.fit(&tel);
+        let t = Instant::now();
+        let lookup = layer.recall();
+        tel.phase(t, phase::LOOKUP);
+        let t = Instant::now();
+        let metric = layer.metric();
+        tel.phase(t, phase::METRIC);
+        let t = Instant::now();
+        let future = layer.future();
+        tel.phase(t, phase::FUTURE);
+        Artifacts { lookup, metric, future }
+    }
+    /// Seeds centroids and bounds, then runs Elkan iterations to the
+    /// iteration bound or until drift freezes.
+    fn fit(&mut self, tel: &crate::telemetry::ClusterTelemetry) {
+        use crate::telemetry::phase;
+        use std::time::Instant;
+        let street = self.street();
         tracing::info!(%street, phase = phase::INIT, "kmeans phase begin");
         let t = Instant::now();
-        *layer.kmeans = layer.init_centroids();
+        *self.kmeans = self.init_centroids();
         tel.phase(t, phase::INIT);
         tracing::info!(%street, phase = phase::BOUND, "kmeans phase begin");
         let t = Instant::now();
-        layer.bounds = layer.init_bounds();
+        self.bounds = self.init_bounds();
         tel.phase(t, phase::BOUND);
         tracing::info!(%street, phase = phase::ITERATE, "kmeans phase begin");
         let iterate = Instant::now();
-        let total = layer.t();
-        let iter = layer
+        let total = self.t();
+        let iter = self
             .kmeans()
             .with_bound(total)
-            .with_threshold(crate::KmeansHyperParams::DEFAULT.drift_threshold());
+            .with_threshold(crate::KmeansHyperParams::DEFAULT.drift_threshold())
+            .with_centroid(crate::KmeansHyperParams::DEFAULT.centroid(street));
         // `for step in iter` consumes iter; Kmeans::Drop fires at the
         // end of the for-loop's implicit scope, releasing the &mut
-        // borrow on layer before the downstream lookup/metric/future.
+        // borrow on the layer before the downstream lookup/metric/future.
         for step in iter {
             tel.iteration(step.elapsed, &step.drift);
             tel.reassignment(step.reassignment);
@@ -235,19 +313,44 @@ impl<const K: usize, const N: usize> Layer<K, N> {
                 .inspect(|i| tracing::info!(%street, iter = i + 1, total, "kmeans freeze"));
         }
         tel.phase(iterate, phase::ITERATE);
-        let t = Instant::now();
-        let lookup = layer.lookup();
-        tel.phase(t, phase::LOOKUP);
-        let t = Instant::now();
-        let metric = layer.metric();
-        tel.phase(t, phase::METRIC);
-        let t = Instant::now();
-        let future = layer.future();
-        tel.phase(t, phase::FUTURE);
-        Artifacts { lookup, metric, future }
+        self.report(tel);
+    }
+    /// Records within-cluster EMD for the fitted centroids. Under the
+    /// barycenter rule, also records it for the arithmetic means of the
+    /// same assignments, so the change the rule buys is visible per street.
+    fn report(&self, tel: &crate::telemetry::ClusterTelemetry) {
+        let street = self.street();
+        let inertia = self.inertia(&self.kmeans);
+        tel.inertia(self.averaging, inertia);
+        if self.averaging == Centroid::Mean {
+            tracing::info!(%street, inertia, centroid = %self.averaging, "kmeans inertia");
+        } else {
+            let means: Box<[Histogram; K]> = (0..K)
+                .map(|j| {
+                    let members = (0..N).filter(|&i| self.bound(i).j() == j).map(|i| self.point(i));
+                    Centroid::Mean.aggregate(self.centroid(j).identity(), &self.metric, members)
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .expect("K");
+            let baseline = self.inertia(&means);
+            tel.inertia(Centroid::Mean, baseline);
+            tel.inertia_change(inertia - baseline);
+            tracing::info!(%street, inertia, baseline, centroid = %self.averaging, "kmeans inertia");
+        }
+    }
+    /// Mean EMD from each point to its assigned cluster's entry in `centroids`.
+    fn inertia(&self, centroids: &[Histogram; K]) -> Energy {
+        use rayon::prelude::*;
+        (0..N)
+            .into_par_iter()
+            .map(|i| self.metric.emd(self.point(i), &centroids[self.bound(i).j()]))
+            .sum::<Energy>()
+            / N as Energy
     }
-    /// Build layer dependencies from postgres (not disk).
-    async fn build(street: Street, client: &tokio_postgres::Client) -> Self {
+    /// Build layer dependencies from postgres (not disk), around
+    /// next-street projections hydrated by the caller.
+    async fn build(street: Street, client: &tokio_postgres::Client, points: Vec<Histogram>) -> Self {
         if street == Street::Rive {
             Self {
                 street,
@@ -255,6 +358,7 @@ impl<const K: usize, const N: usize> Layer<K, N> {
                 kmeans: Box::new(std::array::from_fn(|_| Histogram::empty(Street::Rive))),
                 bounds: vec![Bounds::default(); N].try_into().expect("N"),
                 points: vec![Histogram::empty(Street::Rive); N].try_into().expect("N"),
+                averaging: Centroid::default(),
             }
         } else {
             Self {
@@ -262,11 +366,8 @@ impl<const K: usize, const N: usize> Layer<K, N> {
                 metric: Box::new(Metric::from_street(client, street.next()).await),
                 kmeans: Box::new(std::array::from_fn(|_| Histogram::empty(street.next()))),
                 bounds: vec![Bounds::default(); N].try_into().expect("N"),
-                points: Lookup::from_street(client, street.next())
-                    .await
-                    .projections()
-                    .try_into()
-                    .expect("projections.len() == N"),
+                points: points.try_into().expect("projections.len() == N"),
+                averaging: Centroid::default(),
             }
         }
     }
@@ -6,7 +6,8 @@
 //!
 //! ## Pipeline
 //!
-//! 1. **River** — Cluster by raw equity (win probability against random hands)
+//! 1. **River** — Cluster by raw equity (win probability against random hands),
+//!    or by opponent-cluster hand strength ([`OchsLayer`]) under `Version::V5`
 //! 2. **Turn** — Cluster by distribution over river buckets
 //! 3. **Flop** — Cluster by distribution over turn buckets
 //! 4. **Preflop** — Cluster by distribution over flop buckets
@@ -16,21 +17,32 @@
 //! - [`Layer`] — A clustering layer mapping observations to abstract buckets
 //! - [`Histogram`] — Distribution over child buckets for a given hand
 //! - [`Lookup`] — Precomputed observation → bucket mapping
+//! - [`RecallLookup`] — Perfect-recall index → bucket mapping, used under `Version::V6`
 //! - [`Metric`] — Pairwise EMD distances between buckets
 //!
 //! ## Algorithms
 //!
 //! - [`Elkan`] — Accelerated k-means with triangle inequality bounds
+//! - [`StreamLayer`] — Mini-batch k-means over streamed isomorphisms, for low memory
 //! - [`Sinkhorn`] — Entropic optimal transport for EMD computation
 //! - [`Absorb`] — Incremental centroid updates during clustering
+//! - [`Centroid`] — Arithmetic-mean or Wasserstein-barycenter centroid rule
 //!
 //! ## Persistence
 //!
 //! - [`Artifacts`] — Serialization of clustering results to PostgreSQL
 //! - [`Distances`] — Precomputed distance matrices for online lookup
+//!
+//! ## Diagnostics
+//!
+//! - [`Diagnostics`] — Per-street cluster quality reports as JSON or HTML
+//! - [`Comparison`] — Agreement between two lookups of one street, across versions or K
 mod abstraction;
 mod artifacts;
 mod bins;
+mod centroid;
+mod comparison;
+mod diagnostics;
 mod distances;
 mod emd;
 mod equity;
@@ -42,19 +54,25 @@ mod kmeans;
 mod layer;
 mod lookup;
 mod metric;
+mod ochs;
 mod pair;
 mod phi;
 mod potential;
+mod recall_lookup;
 mod sinkhorn;
+mod stream;
 mod telemetry;
 mod tests;
 
 // Generic Elkan k-means engine (Energy excluded — pokerkit::Energy is the same f32).
-pub use elkan::{Absorb, Bounds, Drift, Elkan, Prior, Step};
+pub use elkan::{Absorb, Bounds, Drift, Elkan, MiniBatch, Prior, Step};
 
 pub use abstraction::*;
 pub use artifacts::*;
 pub use bins::*;
+pub use centroid::*;
+pub use comparison::*;
+pub use diagnostics::*;
 pub use distances::*;
 pub use emd::*;
 pub use equity::*;
@@ -66,8 +84,11 @@ pub use kmeans::*;
 pub use layer::*;
 pub use lookup::*;
 pub use metric::*;
+pub use ochs::*;
 pub use pair::*;
 pub use phi::*;
 pub use potential::*;
+pub use recall_lookup::*;
 pub use sinkhorn::*;
+pub use stream::*;
 pub use tests::*;
@@ -2,7 +2,6 @@ use crate::*;
 use deuce::*;
 use kicker::*;
 use rayon::prelude::*;
-use std::collections::BTreeMap;
 use std::sync::OnceLock;
 
 /// Mapping from hand isomorphisms to abstraction buckets.
@@ -11,6 +10,10 @@ use std::sync::OnceLock;
 /// (represented as a suit-isomorphic [`Isomorphism`]), look up which
 /// strategic [`Abstraction`] bucket it belongs to.
 ///
+/// Buckets of one street live in a flat array indexed by
+/// [`Isomorphism::index`], so a lookup is constant time and the table
+/// is two bytes per isomorphism.
+///
 /// # Construction
 ///
 /// - River: Computed directly from showdown equity
@@ -21,53 +24,159 @@ use std::sync::OnceLock;
 ///
 /// With the `database` feature, supports streaming to/from PostgreSQL
 /// for persistence between training runs.
-#[derive(Default)]
-pub struct Lookup(BTreeMap<Isomorphism, Abstraction>);
+pub struct Lookup {
+    street: Street,
+    buckets: Vec<Abstraction>,
+}
 
-impl From<Lookup> for BTreeMap<Isomorphism, Abstraction> {
-    fn from(lookup: Lookup) -> BTreeMap<Isomorphism, Abstraction> {
-        lookup.0
+/// Assignments in dense index order, as produced by a clustering layer.
+impl From<(Street, Vec<Abstraction>)> for Lookup {
+    fn from((street, buckets): (Street, Vec<Abstraction>)) -> Self {
+        debug_assert_eq!(buckets.len(), street.n_isomorphisms());
+        Self { street, buckets }
     }
 }
-impl From<BTreeMap<Isomorphism, Abstraction>> for Lookup {
-    fn from(map: BTreeMap<Isomorphism, Abstraction>) -> Self {
-        Self(map)
+
+/// Collects `(isomorphism, bucket)` pairs of a single street. Panics
+/// unless every isomorphism of the street appears; see [`Lookup::assemble`].
+impl FromIterator<(Isomorphism, Abstraction)> for Lookup {
+    fn from_iter<I: IntoIterator<Item = (Isomorphism, Abstraction)>>(iter: I) -> Self {
+        Self::assemble(iter).unwrap_or_else(|e| panic!("{e}"))
     }
 }
 
 impl Lookup {
+    /// Collects `(isomorphism, bucket)` pairs of a single street, failing
+    /// if the pairs are empty, mix streets, or leave any isomorphism of
+    /// the street without a bucket.
+    pub fn assemble<I>(iter: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = (Isomorphism, Abstraction)>,
+    {
+        let mut iter = iter.into_iter().peekable();
+        let street = iter.peek().ok_or("no isomorphisms to collect")?.0.0.street();
+        let mut buckets = vec![Abstraction::default(); street.n_isomorphisms()];
+        let mut filled = vec![false; street.n_isomorphisms()];
+        for (iso, abs) in iter {
+            if iso.0.street() != street {
+                return Err(format!("isomorphism {iso} is not on the {street}"));
+            }
+            buckets[iso.index()] = abs;
+            filled[iso.index()] = true;
+        }
+        match filled.iter().filter(|x| !**x).count() {
+            0 => Ok(Self { street, buckets }),
+            n => Err(format!("{n} of {} {street} isomorphisms have no bucket", street.n_isomorphisms())),
+        }
+    }
+
     /// Looks up the abstraction for a hand isomorphism.
     pub fn lookup(&self, iso: &Isomorphism) -> Abstraction {
-        self.0.get(iso).copied().expect("precomputed abstraction in lookup")
+        debug_assert_eq!(iso.0.street(), self.street);
+        self.buckets[iso.index()]
     }
 
-    /// Generates histograms for all isomorphisms at the previous street.
-    /// Used to build the data points for the next clustering layer.
+    /// Generates histograms for all isomorphisms at the previous street,
+    /// in dense index order. Used to build the data points for the next
+    /// clustering layer.
     pub fn projections(&self) -> Vec<Histogram> {
-        IsomorphismIterator::from(self.street().prev())
-            .collect::<Vec<Isomorphism>>()
+        (0..self.street().prev().n_isomorphisms())
             .into_par_iter()
+            .map(|i| Isomorphism::from((self.street().prev(), i)))
             .map(|i| self.future(&i))
             .collect::<Vec<Histogram>>()
     }
 
     /// Computes histogram over next-street abstractions for an isomorphism.
     /// This is the core operation that enables hierarchical clustering.
-    fn future(&self, iso: &Isomorphism) -> Histogram {
+    pub(crate) fn future(&self, iso: &Isomorphism) -> Histogram {
         debug_assert_ne!(iso.0.street(), Street::Rive);
         iso.0
             .children()
-            .collect::<Vec<_>>()
-            .into_par_iter()
-            .map(Isomorphism::from)
-            .map(|i| self.lookup(&i))
+            .map(|o| self.lookup(&Isomorphism(o)))
             .collect::<Vec<Abstraction>>()
             .into()
     }
 
     /// The street this lookup is for.
-    fn street(&self) -> Street {
-        self.0.keys().next().expect("non empty").0.street()
+    pub fn street(&self) -> Street {
+        self.street
+    }
+
+    /// Every `(isomorphism, bucket)` pair in dense index order.
+    pub fn iter(&self) -> impl Iterator<Item = (Isomorphism, Abstraction)> + '_ {
+        self.buckets
+            .iter()
+            .enumerate()
+            .map(|(i, abs)| (Isomorphism::from((self.street, i)), *abs))
+    }
+}
+
+/// Flat binary file: magic, street, count, then one little-endian
+/// `i16` bucket per isomorphism in dense index order.
+impl Lookup {
+    const MAGIC: &[u8; 8] = b"RBPLOOK1";
+
+    pub fn write(&self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        Self::header(w, self.street)?;
+        Self::chunk(w, &self.buckets)
+    }
+
+    /// Writes the file header for a full table of `street`. The caller
+    /// must follow it with every bucket in dense index order, possibly
+    /// over several [`Self::chunk`] calls.
+    pub(crate) fn header(w: &mut impl std::io::Write, street: Street) -> std::io::Result<()> {
+        w.write_all(Self::MAGIC)?;
+        w.write_all(&[street as u8])?;
+        w.write_all(&(street.n_isomorphisms() as u64).to_le_bytes())
+    }
+
+    /// Appends consecutive buckets to a file started by [`Self::header`].
+    pub(crate) fn chunk(w: &mut impl std::io::Write, buckets: &[Abstraction]) -> std::io::Result<()> {
+        let bytes = buckets
+            .iter()
+            .flat_map(|abs| i16::from(*abs).to_le_bytes())
+            .collect::<Vec<u8>>();
+        w.write_all(&bytes)
+    }
+
+    pub fn read(r: &mut impl std::io::Read) -> std::io::Result<Self> {
+        let invalid = |msg: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, msg.to_string());
+        let mut magic = [0; 8];
+        r.read_exact(&mut magic)?;
+        if &magic != Self::MAGIC {
+            return Err(invalid("not a lookup file"));
+        }
+        let mut street = [0; 1];
+        r.read_exact(&mut street)?;
+        let street = Street::all()
+            .get(street[0] as usize)
+            .copied()
+            .ok_or_else(|| invalid("bad street"))?;
+        let mut count = [0; 8];
+        r.read_exact(&mut count)?;
+        if u64::from_le_bytes(count) != street.n_isomorphisms() as u64 {
+            return Err(invalid("count does not match street"));
+        }
+        let mut bytes = vec![0; 2 * street.n_isomorphisms()];
+        r.read_exact(&mut bytes)?;
+        let buckets = bytes
+            .chunks_exact(2)
+            .map(|b| Abstraction::from(i16::from_le_bytes([b[0], b[1]])))
+            .collect();
+        Ok(Self { street, buckets })
+    }
+
+    /// Writes the lookup to `path`.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let ref mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.write(file)?;
+        std::io::Write::flush(file)
+    }
+
+    /// Reads a lookup written by [`Self::save`].
+    pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        Self::read(&mut std::io::BufReader::new(std::fs::File::open(path)?))
     }
 }
 
@@ -148,24 +257,42 @@ impl daybook::Streamable for Lookup {
     type Row = (i64, i16);
 
     fn rows(self) -> impl Iterator<Item = Self::Row> + Send {
-        self.0.into_iter().map(|(iso, abs)| (i64::from(iso), i16::from(abs)))
+        let street = self.street;
+        self.buckets
+            .into_iter()
+            .enumerate()
+            .map(move |(i, abs)| (i64::from(Isomorphism::from((street, i))), i16::from(abs)))
     }
 }
 
 #[cfg(feature = "server")]
 impl Lookup {
     pub async fn from_street(client: &tokio_postgres::Client, street: Street) -> Self {
-        let sql = format!("SELECT obs, abs FROM {}", daybook::isomorphism());
-        client
-            .query(&sql, &[])
+        Self::from_table(client, daybook::isomorphism(), street)
             .await
-            .expect("query")
-            .into_iter()
-            .map(|row| (row.get::<_, i64>(0), row.get::<_, i16>(1)))
-            .filter(|(obs, _)| Street::from(*obs) == street)
-            .map(|(obs, abs)| (Isomorphism::from(obs), Abstraction::from(abs)))
-            .collect::<BTreeMap<_, _>>()
-            .into()
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Reads a street from another version's clustering tables, for
+    /// comparing abstractions side by side.
+    pub async fn from_version(
+        client: &tokio_postgres::Client,
+        street: Street,
+        version: pokerkit::Version,
+    ) -> Result<Self, String> {
+        Self::from_table(client, &daybook::isomorphism_of(version), street).await
+    }
+
+    async fn from_table(client: &tokio_postgres::Client, table: &str, street: Street) -> Result<Self, String> {
+        let sql = format!("SELECT obs, abs FROM {table}");
+        let rows = client.query(&sql, &[]).await.map_err(|e| format!("{table}: {e}"))?;
+        Self::assemble(
+            rows.into_iter()
+                .map(|row| (row.get::<_, i64>(0), row.get::<_, i16>(1)))
+                .filter(|(obs, _)| Street::from(*obs) == street)
+                .map(|(obs, abs)| (Isomorphism::from(obs), Abstraction::from(abs))),
+        )
+        .map_err(|e| format!("{table}: {e}"))
     }
 }
 
@@ -176,18 +303,56 @@ impl Lookup {
     /// - Preflop: Each isomorphism gets its own bucket (no compression)
     pub fn grow(street: Street) -> Self {
         match street {
-            Street::Rive => IsomorphismIterator::from(Street::Rive)
-                .collect::<Vec<_>>()
-                .into_par_iter()
-                .map(|iso| (iso, Abstraction::from(iso.0.equity())))
-                .collect::<BTreeMap<_, _>>()
-                .into(),
+            Street::Rive => Self::from((
+                street,
+                (0..street.n_isomorphisms())
+                    .into_par_iter()
+                    .map(|i| Isomorphism::from((street, i)))
+                    .map(|iso| Abstraction::from(iso.0.equity()))
+                    .collect::<Vec<Abstraction>>(),
+            )),
             Street::Pref => IsomorphismIterator::from(Street::Pref)
                 .enumerate()
                 .map(|(k, iso)| (iso, Abstraction::from((Street::Pref, k))))
-                .collect::<BTreeMap<_, _>>()
-                .into(),
+                .collect(),
             Street::Flop | Street::Turn => panic!("lookup must be learned via layer for {street}"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preflop_grows_one_bucket_per_isomorphism() {
+        let lookup = Lookup::grow(Street::Pref);
+        let mut buckets = lookup.iter().map(|(_, abs)| abs).collect::<Vec<_>>();
+        buckets.sort_unstable();
+        buckets.dedup();
+        assert_eq!(buckets.len(), Street::Pref.n_isomorphisms());
+        let iso = Isomorphism::from(Observation::try_from("AsKs").unwrap());
+        assert_eq!(lookup.lookup(&iso), lookup.iter().find(|(i, _)| *i == iso).unwrap().1);
+    }
+
+    #[test]
+    fn gaps_fail_to_assemble() {
+        let mut pairs = Lookup::grow(Street::Pref).iter().collect::<Vec<_>>();
+        assert!(Lookup::assemble(pairs.clone()).is_ok());
+        pairs.pop();
+        assert!(Lookup::assemble(pairs).err().is_some_and(|e| e.contains("1 of 169")));
+        assert!(Lookup::assemble(std::iter::empty()).is_err());
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let lookup = Lookup::grow(Street::Pref);
+        let mut bytes = Vec::new();
+        lookup.write(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 8 + 1 + 8 + 2 * Street::Pref.n_isomorphisms());
+        let loaded = Lookup::read(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded.street(), Street::Pref);
+        assert!(lookup.iter().zip(loaded.iter()).all(|(a, b)| a == b));
+        assert!(Lookup::read(&mut &bytes[1..]).is_err());
+    }
+}
@@ -16,18 +16,22 @@ use std::sync::OnceLock;
 /// Uses triangular [`Distances`] arrays to store pairwise distances:
 /// - Preflop/Flop/Turn: Precomputed from clustering, loaded from database
 /// - River: Uses raw equity difference (no precomputation needed)
+/// - River under OCHS ([`Metric::Ochs`]): distances between the fitted
+///   opponent-cluster strength centroids, loaded like the other streets
 ///
 /// # EMD Computation
 ///
 /// For Flop/Turn histograms, EMD is computed via Sinkhorn algorithm using
 /// this metric as the ground distance. River histograms use total variation
-/// distance since equity abstractions have a natural ordering on `[0,1]`.
-#[derive(Clone, Copy)]
+/// distance since equity abstractions have a natural ordering on `[0,1]`;
+/// OCHS river buckets have no such ordering and fall back to Sinkhorn.
+#[derive(Clone)]
 pub enum Metric {
     Pref(DistPref),
     Flop(DistFlop),
     Turn(DistTurn),
     Rive,
+    Ochs(DistRive),
 }
 
 impl Default for Metric {
@@ -47,6 +51,7 @@ impl Metric {
                 (Street::Pref, Street::Pref) | (Street::Flop, Street::Flop) | (Street::Turn, Street::Turn) => {
                     self.lookup(x, y)
                 }
+                (Street::Rive, Street::Rive) if self.is_ochs() => self.lookup(x, y),
                 (Street::Rive, Street::Rive) => (Probability::from(*x) - Probability::from(*y)).abs(),
                 _ => unreachable!("mismatched streets"),
             }
@@ -65,7 +70,7 @@ impl Measure for Metric {
 
 impl Metric {
     /// Creates a new metric for the given street with zero distances.
-    pub const fn new(street: Street) -> Self {
+    pub fn new(street: Street) -> Self {
         match street {
             Street::Pref => Metric::Pref(Distances::new(street)),
             Street::Flop => Metric::Flop(Distances::new(street)),
@@ -73,13 +78,21 @@ impl Metric {
             Street::Rive => Metric::Rive,
         }
     }
+    /// Creates a new OCHS river metric with zero distances.
+    pub fn ochs() -> Self {
+        Metric::Ochs(Distances::new(Street::Rive))
+    }
+    /// Whether river distances come from OCHS centroids rather than equity.
+    pub fn is_ochs(&self) -> bool {
+        matches!(self, Metric::Ochs(_))
+    }
     /// The street this metric measures distances for.
     pub fn street(&self) -> Street {
         match self {
             Metric::Pref(_) => Street::Pref,
             Metric::Flop(_) => Street::Flop,
             Metric::Turn(_) => Street::Turn,
-            Metric::Rive => Street::Rive,
+            Metric::Rive | Metric::Ochs(_) => Street::Rive,
         }
     }
     /// Looks up precomputed distance between two abstractions.
@@ -89,6 +102,7 @@ impl Metric {
             Metric::Pref(d) => d.get(pair),
             Metric::Flop(d) => d.get(pair),
             Metric::Turn(d) => d.get(pair),
+            Metric::Ochs(d) => d.get(pair),
             Metric::Rive => unreachable!("no metric over Histogram<River>"),
         }
     }
@@ -98,6 +112,7 @@ impl Metric {
             Metric::Pref(d) => d.set(pair, value),
             Metric::Flop(d) => d.set(pair, value),
             Metric::Turn(d) => d.set(pair, value),
+            Metric::Ochs(d) => d.set(pair, value),
             Metric::Rive => unreachable!("no metric over Histogram<River>"),
         }
     }
@@ -105,20 +120,39 @@ impl Metric {
     ///
     /// For Flop/Turn: Uses Sinkhorn divergence (debiased entropic OT) so
     /// that `emd(μ, μ) = 0` despite finite regularization temperature.
-    /// For River: Uses total variation (integrated CDF difference).
+    /// For River: Uses total variation (integrated CDF difference), or
+    /// Sinkhorn again when the river buckets are OCHS clusters.
     pub fn emd(&self, source: &Histogram, target: &Histogram) -> Energy {
         match source.peek().street() {
             Street::Flop | Street::Turn => Sinkhorn::divergence(source, target, self),
+            Street::Rive if self.is_ochs() => Sinkhorn::divergence(source, target, self),
             Street::Rive => Equity::variation(source, target),
             Street::Pref => unreachable!("no preflop emd"),
         }
     }
+    /// Pairwise EMD between learned centroids under `ground`, the metric
+    /// of the street below. Symmetrized, then normalized via [`Metric::from`].
+    pub fn between(street: Street, ground: &Metric, centroids: &[Histogram]) -> Self {
+        let mut metric = std::collections::BTreeMap::new();
+        for (i, x) in centroids.iter().enumerate() {
+            for (j, y) in centroids.iter().enumerate() {
+                if i > j {
+                    let ref a = Abstraction::from((street, i));
+                    let ref b = Abstraction::from((street, j));
+                    let distance = ground.emd(x, y) + ground.emd(y, x);
+                    metric.insert(Pair::from((a, b)), distance);
+                }
+            }
+        }
+        Metric::from(metric)
+    }
     /// Normalize all distances by the maximum value.
     pub fn normalize(&mut self) {
         match self {
             Metric::Pref(d) => d.normalize(),
             Metric::Flop(d) => d.normalize(),
             Metric::Turn(d) => d.normalize(),
+            Metric::Ochs(d) => d.normalize(),
             Metric::Rive => {}
         }
     }
@@ -131,7 +165,10 @@ impl From<std::collections::BTreeMap<Pair, Energy>> for Metric {
             .keys()
             .next()
             .map(super::pair::Pair::street)
-            .map(Metric::new)
+            .map(|street| match street {
+                Street::Rive => Metric::ochs(),
+                street => Metric::new(street),
+            })
             .expect("map is empty");
         for (pair, distance) in map {
             metric.set(pair, distance / max);
@@ -149,11 +186,80 @@ impl IntoIterator for Metric {
             Metric::Pref(d) => d.into_iter(),
             Metric::Flop(d) => d.into_iter(),
             Metric::Turn(d) => d.into_iter(),
+            Metric::Ochs(d) => d.into_iter(),
             Metric::Rive => unreachable!(),
         }
     }
 }
 
+/// Flat binary file: magic, street, OCHS flag, count, then one
+/// little-endian `(i32 triangular index, f32 distance)` per pair.
+/// The equity-ladder river metric has no pairs.
+impl Metric {
+    const MAGIC: &[u8; 8] = b"RBPMTRC1";
+
+    /// Every stored `(triangular index, distance)` pair.
+    pub fn iter(&self) -> Box<dyn Iterator<Item = (i32, Energy)> + '_> {
+        match self {
+            Metric::Pref(d) => Box::new(d.iter()),
+            Metric::Flop(d) => Box::new(d.iter()),
+            Metric::Turn(d) => Box::new(d.iter()),
+            Metric::Ochs(d) => Box::new(d.iter()),
+            Metric::Rive => Box::new(std::iter::empty()),
+        }
+    }
+
+    pub fn write(&self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        let pairs = self.iter().collect::<Vec<_>>();
+        w.write_all(Self::MAGIC)?;
+        w.write_all(&[self.street() as u8, u8::from(self.is_ochs())])?;
+        w.write_all(&(pairs.len() as u64).to_le_bytes())?;
+        let bytes = pairs
+            .into_iter()
+            .flat_map(|(tri, dx)| tri.to_le_bytes().into_iter().chain(dx.to_le_bytes()))
+            .collect::<Vec<u8>>();
+        w.write_all(&bytes)
+    }
+
+    pub fn read(r: &mut impl std::io::Read) -> std::io::Result<Self> {
+        let invalid = |msg: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, msg.to_string());
+        let mut magic = [0; 8];
+        r.read_exact(&mut magic)?;
+        if &magic != Self::MAGIC {
+            return Err(invalid("not a metric file"));
+        }
+        let mut tag = [0; 2];
+        r.read_exact(&mut tag)?;
+        let street = Street::all()
+            .get(tag[0] as usize)
+            .copied()
+            .ok_or_else(|| invalid("bad street"))?;
+        let mut metric = if tag[1] == 1 { Metric::ochs() } else { Metric::new(street) };
+        let mut count = [0; 8];
+        r.read_exact(&mut count)?;
+        let mut bytes = vec![0; 8 * u64::from_le_bytes(count) as usize];
+        r.read_exact(&mut bytes)?;
+        for b in bytes.chunks_exact(8) {
+            let tri = i32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+            let dx = Energy::from_le_bytes([b[4], b[5], b[6], b[7]]);
+            metric.set(Pair::from(tri), dx);
+        }
+        Ok(metric)
+    }
+
+    /// Writes the metric to `path`.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let ref mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.write(file)?;
+        std::io::Write::flush(file)
+    }
+
+    /// Reads a metric written by [`Self::save`].
+    pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        Self::read(&mut std::io::BufReader::new(std::fs::File::open(path)?))
+    }
+}
+
 #[cfg(feature = "server")]
 impl daybook::Schema for Metric {
     fn name() -> &'static str {
@@ -237,7 +343,10 @@ impl Metric {
                 }
             }
         }
-        let mut metric = Metric::new(street);
+        let mut metric = match street {
+            Street::Rive if pokerkit::version().ochs() => Metric::ochs(),
+            street => Metric::new(street),
+        };
         client
             .query(&sql, &[])
             .await
@@ -276,4 +385,19 @@ mod tests {
         assert_eq!(a, ra);
         assert_eq!(b, rb);
     }
+    #[test]
+    fn file_round_trip() {
+        let mut metric = Metric::new(Street::Turn);
+        metric.set(Pair::new(Street::Turn, 1, 7), 0.25);
+        metric.set(Pair::new(Street::Turn, 3, 4), 1.);
+        let mut bytes = Vec::new();
+        metric.write(&mut bytes).unwrap();
+        let loaded = Metric::read(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded.street(), Street::Turn);
+        assert!(metric.iter().eq(loaded.iter()));
+        let mut ladder = Vec::new();
+        Metric::new(Street::Rive).write(&mut ladder).unwrap();
+        assert!(matches!(Metric::read(&mut ladder.as_slice()).unwrap(), Metric::Rive));
+        assert!(Metric::read(&mut &bytes[1..]).is_err());
+    }
 }
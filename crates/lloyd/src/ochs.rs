@@ -0,0 +1,469 @@
+//! Opponent-cluster hand strength (OCHS) river abstraction.
+//!
+//! The equity ladder scores a river hand by its win rate against a uniform
+//! opponent, which merges hands that beat different parts of the opponent's
+//! range equally often. OCHS (Johanson et al., 2013) instead splits the
+//! opponent's holdings into [`OCHS_OPPONENT_CLUSTER_COUNT`] preflop-strength
+//! groups and describes a river hand by its win rate against each group.
+//! River buckets are k-means clusters of those vectors, fitted on a seeded
+//! sample of river observations and then applied to every river isomorphism.
+use super::*;
+use deuce::*;
+use kicker::*;
+use pokerkit::*;
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+const C: usize = OCHS_OPPONENT_CLUSTER_COUNT;
+const SEED: u64 = 0x0c45;
+
+static EQUITIES: OnceLock<Vec<Probability>> = OnceLock::new();
+
+/// Partition of the 1,326 opponent holdings into preflop-strength groups.
+///
+/// The 169 preflop isomorphisms are ranked by seeded Monte Carlo equity
+/// against a random hand and cut into groups of near-equal combo count;
+/// a cell is never split across groups. Group 0 is the weakest.
+pub struct Opponents([[u8; 52]; 52]);
+
+impl Opponents {
+    /// The process-wide partition, built deterministically on first use.
+    pub fn get() -> &'static Self {
+        static OPPONENTS: OnceLock<Opponents> = OnceLock::new();
+        OPPONENTS.get_or_init(Self::build)
+    }
+    /// Group of a two-card opponent holding.
+    pub fn cluster(&self, hole: Hand) -> usize {
+        let mut cards = hole.map(u8::from).map(usize::from);
+        let a = cards.next().expect("two cards");
+        let b = cards.next().expect("two cards");
+        self.0[a][b] as usize
+    }
+
+    fn build() -> Self {
+        let holes = HandIterator::from((2, Hand::empty())).collect::<Vec<Hand>>();
+        let cells = holes
+            .iter()
+            .map(|hole| Isomorphism::from(Observation::from((*hole, Hand::empty()))))
+            .collect::<Vec<Isomorphism>>();
+        let equity = IsomorphismIterator::from(Street::Pref)
+            .collect::<Vec<Isomorphism>>()
+            .into_par_iter()
+            .map(|iso| (iso, Self::strength(iso.0)))
+            .collect::<BTreeMap<Isomorphism, Probability>>();
+        let mut order = (0..holes.len()).collect::<Vec<usize>>();
+        order.sort_by(|&a, &b| {
+            equity[&cells[a]]
+                .total_cmp(&equity[&cells[b]])
+                .then(cells[a].cmp(&cells[b]))
+        });
+        let mut table = [[0; 52]; 52];
+        let mut start = 0;
+        for (position, &i) in order.iter().enumerate() {
+            if position > 0 && cells[i] != cells[order[position - 1]] {
+                start = position;
+            }
+            let group = (start * C / holes.len()) as u8;
+            let mut cards = holes[i].map(u8::from).map(usize::from);
+            let a = cards.next().expect("two cards");
+            let b = cards.next().expect("two cards");
+            table[a][b] = group;
+            table[b][a] = group;
+        }
+        Self(table)
+    }
+
+    /// Seeded Monte Carlo equity of a preflop hand against a random one,
+    /// ties counting half.
+    fn strength(hero: Observation) -> Probability {
+        let ref mut rng = SmallRng::seed_from_u64(SEED ^ i64::from(hero) as u64);
+        let mut deck = Vec::<Card>::from(Hand::from(hero).complement());
+        let won = (0..OCHS_PREFLOP_TRIALS)
+            .map(|_| {
+                deck.partial_shuffle(rng, 7);
+                let villain = deck[..2].iter().copied().collect::<Hand>();
+                let board = deck[2..7].iter().copied().collect::<Hand>();
+                let h = Strength::from(Hand::add(*hero.pocket(), board));
+                let v = Strength::from(Hand::add(villain, board));
+                match h.cmp(&v) {
+                    Ordering::Greater => 1.,
+                    Ordering::Equal => 0.5,
+                    Ordering::Less => 0.,
+                }
+            })
+            .sum::<Probability>();
+        won / OCHS_PREFLOP_TRIALS as Probability
+    }
+}
+
+/// A river hand's win rate against each opponent group.
+///
+/// Doubles as a k-means centroid: `mass` counts absorbed hands and `sums`
+/// accumulates their win rates, so [`Ochs::strengths`] is always the mean.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Ochs {
+    mass: f32,
+    sums: [Probability; C],
+}
+
+impl Ochs {
+    /// Mean strength of a fitted river bucket: the OCHS stand-in for the
+    /// equity ladder's `index / 100`. Reads the strengths stored with the
+    /// clustering, which [`Ochs::install`] must have loaded.
+    pub fn equity(abs: &Abstraction) -> Probability {
+        EQUITIES.get().expect("OCHS river strengths installed")[abs.index()]
+    }
+    /// Makes a clustering's bucket strengths, in bucket order, available
+    /// to [`Ochs::equity`]. The first table installed stays for the process.
+    pub fn install(strengths: Vec<Probability>) {
+        let _ = EQUITIES.set(strengths);
+    }
+    /// Mean win rate against each opponent group. An empty centroid sits
+    /// at the coin-flip point.
+    pub fn strengths(&self) -> [Probability; C] {
+        if self.mass > 0. { self.sums.map(|s| s / self.mass) } else { [0.5; C] }
+    }
+    /// Mean win rate across groups, used to order buckets.
+    pub fn strength(&self) -> Probability {
+        self.strengths().iter().sum::<Probability>() / C as Probability
+    }
+    /// Euclidean distance between mean strength vectors.
+    pub fn distance(&self, other: &Self) -> Energy {
+        self.strengths()
+            .iter()
+            .zip(other.strengths())
+            .map(|(x, y)| (x - y) * (x - y))
+            .sum::<Energy>()
+            .sqrt()
+    }
+}
+
+/// Exact win rates against each group, ties excluded as in
+/// [`Observation::equity`]. Groups without a live combo count as 0.5.
+impl From<Observation> for Ochs {
+    fn from(obs: Observation) -> Self {
+        debug_assert_eq!(obs.street(), Street::Rive);
+        let opponents = Opponents::get();
+        let hero = Strength::from(Hand::from(obs));
+        let mut won = [0u32; C];
+        let mut sum = [0u32; C];
+        for villain in HandIterator::from((2, Hand::from(obs))) {
+            let c = opponents.cluster(villain);
+            match hero.cmp(&Strength::from(Hand::add(villain, *obs.public()))) {
+                Ordering::Greater => {
+                    won[c] += 1;
+                    sum[c] += 1;
+                }
+                Ordering::Equal => {}
+                Ordering::Less => sum[c] += 1,
+            }
+        }
+        Self {
+            mass: 1.,
+            sums: std::array::from_fn(|c| match sum[c] {
+                0 => 0.5,
+                n => won[c] as Probability / n as Probability,
+            }),
+        }
+    }
+}
+
+impl Absorb for Ochs {
+    fn identity(&self) -> Self {
+        Self::default()
+    }
+    fn absorb(self, other: &Self) -> Self {
+        Self {
+            mass: self.mass + other.mass,
+            sums: std::array::from_fn(|c| self.sums[c] + other.sums[c]),
+        }
+    }
+}
+
+/// The river layer a `Version::V5` clustering run fits.
+pub type OchsRiver = OchsLayer<{ Street::Rive.n_abstractions() }, OCHS_RIVER_SAMPLE_COUNT>;
+
+/// K-means over OCHS vectors, fitted on `N` sampled river observations.
+///
+/// Produces the same [`Artifacts`] as the other layers: a river [`Lookup`]
+/// over every isomorphism, and a [`Metric::Ochs`] between the fitted
+/// centroids for the turn layer's Sinkhorn EMD. Buckets are ordered by
+/// mean strength so the river index still rises with hand strength.
+pub struct OchsLayer<const K: usize, const N: usize> {
+    points: Box<[Ochs; N]>,
+    kmeans: Box<[Ochs; K]>,
+    bounds: Box<[Bounds<K>; N]>,
+}
+
+impl<const K: usize, const N: usize> OchsLayer<K, N> {
+    /// Samples `N` river observations with a fixed seed and initializes
+    /// centroids with k-means++.
+    pub fn new() -> Self {
+        let ref mut rng = SmallRng::seed_from_u64(SEED);
+        let mut deck = Vec::<Card>::from(Hand::empty().complement());
+        let observations = (0..N)
+            .map(|_| {
+                deck.partial_shuffle(rng, 7);
+                let pocket = deck[..2].iter().copied().collect::<Hand>();
+                let public = deck[2..7].iter().copied().collect::<Hand>();
+                Observation::from((pocket, public))
+            })
+            .collect::<Vec<Observation>>();
+        let mut layer = Self {
+            points: observations
+                .into_par_iter()
+                .map(Ochs::from)
+                .collect::<Vec<Ochs>>()
+                .try_into()
+                .expect("N"),
+            kmeans: Box::new([Ochs::default(); K]),
+            bounds: vec![Bounds::default(); N].try_into().expect("N"),
+        };
+        *layer.kmeans = layer.init_centroids();
+        layer.bounds = layer.init_bounds();
+        layer
+    }
+
+    /// Runs Elkan iterations until the iteration budget or the drift
+    /// threshold, then orders centroids by mean strength.
+    pub fn fit(&mut self) {
+        let threshold = KmeansHyperParams::DEFAULT.drift_threshold();
+        let placeholder = vec![Bounds::default(); N].try_into().expect("N");
+        let mut bounds = std::mem::replace(&mut self.bounds, placeholder);
+        for _ in 0..self.t() {
+            let (centroids, drift) = self.step_elkan(&mut bounds);
+            *self.kmeans = centroids;
+            if drift.frozen(threshold) {
+                break;
+            }
+        }
+        self.kmeans.sort_by(|a, b| a.strength().total_cmp(&b.strength()));
+        self.bounds = self.init_bounds();
+    }
+
+    /// Nearest fitted bucket for a river observation.
+    pub fn abstraction(&self, obs: Observation) -> Abstraction {
+        let ochs = Ochs::from(obs);
+        let (k, _) = self
+            .kmeans
+            .iter()
+            .map(|centroid| centroid.distance(&ochs))
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .expect("K > 0");
+        Abstraction::from((Street::Rive, k))
+    }
+
+    /// Assigns every river isomorphism to its nearest bucket.
+    pub fn lookup(&self) -> Lookup {
+        let buckets = (0..Street::Rive.n_isomorphisms())
+            .into_par_iter()
+            .map(|i| Isomorphism::from((Street::Rive, i)))
+            .map(|iso| self.abstraction(iso.0))
+            .collect::<Vec<Abstraction>>();
+        Lookup::from((Street::Rive, buckets))
+    }
+
+    /// Mean strength of each fitted centroid, in bucket order.
+    pub fn strengths(&self) -> Vec<Probability> {
+        self.kmeans.iter().map(Ochs::strength).collect()
+    }
+
+    /// Pairwise distances between fitted centroids.
+    pub fn metric(&self) -> Metric {
+        let mut metric = BTreeMap::new();
+        for (i, x) in self.kmeans.iter().enumerate() {
+            for (j, y) in self.kmeans.iter().enumerate().take(i) {
+                let ref a = Abstraction::from((Street::Rive, i));
+                let ref b = Abstraction::from((Street::Rive, j));
+                metric.insert(Pair::from((a, b)), x.distance(y));
+            }
+        }
+        Metric::from(metric)
+    }
+
+    /// Samples, fits, and emits the river artifacts.
+    pub fn cluster() -> Artifacts {
+        let mut layer = Self::new();
+        layer.fit();
+        layer.artifacts()
+    }
+
+    /// The river artifacts of this fit.
+    pub fn artifacts(&self) -> Artifacts {
+        Artifacts {
+            lookup: self.lookup(),
+            metric: self.metric(),
+            future: Future::default(),
+        }
+    }
+}
+
+/// Bucket strengths live in their own small table: the derived
+/// abstraction table is rebuilt on every run, and refitting to recover
+/// them would cost as much as the clustering itself.
+#[cfg(feature = "server")]
+impl<const K: usize, const N: usize> OchsLayer<K, N> {
+    /// Samples, fits, stores the bucket strengths, and emits the river
+    /// artifacts.
+    pub async fn cluster_into(client: &tokio_postgres::Client) -> Artifacts {
+        let mut layer = Self::new();
+        layer.fit();
+        layer.persist(client).await;
+        layer.artifacts()
+    }
+
+    /// Replaces the stored bucket strengths with this fit's.
+    pub async fn persist(&self, client: &tokio_postgres::Client) {
+        let t = daybook::centroid();
+        client
+            .batch_execute(&format!(
+                "CREATE TABLE IF NOT EXISTS {t} (
+                abs      SMALLINT NOT NULL,
+                strength REAL     NOT NULL
+            );
+            TRUNCATE TABLE {t};"
+            ))
+            .await
+            .expect("ensure centroid table");
+        let insert = format!("INSERT INTO {t} (abs, strength) VALUES ($1, $2)");
+        for (k, strength) in self.strengths().into_iter().enumerate() {
+            let abs = i16::from(Abstraction::from((Street::Rive, k)));
+            client
+                .execute(&insert, &[&abs, &strength])
+                .await
+                .expect("insert bucket strength");
+        }
+    }
+
+    /// Bucket strengths written by [`Self::persist`], in bucket order.
+    pub async fn stored(client: &tokio_postgres::Client) -> Vec<Probability> {
+        let sql = format!("SELECT strength FROM {} ORDER BY abs", daybook::centroid());
+        let strengths = client
+            .query(&sql, &[])
+            .await
+            .expect("query bucket strengths")
+            .into_iter()
+            .map(|row| row.get::<_, f32>(0))
+            .collect::<Vec<Probability>>();
+        assert_eq!(strengths.len(), K, "stored OCHS strengths match the river K");
+        strengths
+    }
+}
+
+impl<const K: usize, const N: usize> Default for OchsLayer<K, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const K: usize, const N: usize> Elkan<K, N> for OchsLayer<K, N> {
+    type P = Ochs;
+
+    fn t(&self) -> usize {
+        KmeansHyperParams::DEFAULT.ochs_iterations()
+    }
+
+    fn points(&self) -> &[Ochs; N] {
+        &self.points
+    }
+
+    fn centroids(&self) -> &[Ochs; K] {
+        &self.kmeans
+    }
+
+    fn boundings(&self) -> &[Bounds<K>; N] {
+        &self.bounds
+    }
+
+    fn distance(&self, x: &Ochs, y: &Ochs) -> Energy {
+        x.distance(y)
+    }
+
+    fn init_centroids(&self) -> [Ochs; K] {
+        use rand::distr::Distribution;
+        use rand::distr::weighted::WeightedIndex;
+        let ref mut rng = SmallRng::seed_from_u64(SEED);
+        let mut potentials = vec![1.; N];
+        let mut centroids = Vec::with_capacity(K);
+        while centroids.len() < K {
+            let i = WeightedIndex::new(potentials.iter())
+                .expect("valid weights array")
+                .sample(rng);
+            let x = self.points()[i];
+            centroids.push(x);
+            potentials = self
+                .points()
+                .par_iter()
+                .map(|p| self.distance(&x, p))
+                .map(|p| p * p)
+                .zip(potentials.par_iter())
+                .map(|(d0, d1)| Energy::min(d0, *d1))
+                .collect::<Vec<Energy>>();
+            potentials[i] = 0.;
+        }
+        centroids.try_into().expect("K")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opponents_partition_evenly() {
+        let opponents = Opponents::get();
+        let mut sizes = [0usize; C];
+        for hole in HandIterator::from((2, Hand::empty())) {
+            sizes[opponents.cluster(hole)] += 1;
+        }
+        let fair = 1326 / C;
+        assert!(sizes.iter().all(|&n| n + 12 >= fair && n <= fair + 12), "{sizes:?}");
+        assert_eq!(opponents.cluster(Hand::try_from("AsAh").unwrap()), C - 1);
+        assert_eq!(opponents.cluster(Hand::try_from("7s2h").unwrap()), 0);
+    }
+
+    #[test]
+    fn nuts_beat_every_group() {
+        let royal = Ochs::from(Observation::try_from("AsKs~QsJsTs2d3c").unwrap());
+        assert_eq!(royal.strengths(), [1.; C]);
+        let board = Ochs::from(Observation::try_from("2c3d~AsKsQsJsTs").unwrap());
+        assert_eq!(board.strengths(), [0.5; C]);
+    }
+
+    #[test]
+    fn centroids_are_means() {
+        let a = Ochs::from(Observation::try_from("AsKs~QsJsTs2d3c").unwrap());
+        let b = Ochs::from(Observation::try_from("2c3d~AsKsQsJsTs").unwrap());
+        let mean = a.identity().absorb(&a).absorb(&b);
+        assert_eq!(mean.strengths(), [0.75; C]);
+        assert!((a.distance(&b) - (C as Energy * 0.25).sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn fits_ordered_buckets() {
+        let mut layer = OchsLayer::<4, 128>::new();
+        layer.fit();
+        let strengths = layer.strengths();
+        assert!(strengths.is_sorted());
+        assert!(strengths.iter().all(|s| (0. ..=1.).contains(s)));
+        let nuts = layer.abstraction(Observation::try_from("AsKs~QsJsTs2d3c").unwrap());
+        assert_eq!(nuts, Abstraction::from((Street::Rive, 3)));
+        assert!(layer.metric().is_ochs());
+    }
+
+    #[test]
+    fn equity_reads_installed_strengths() {
+        let strengths = (0..Street::Rive.n_abstractions())
+            .map(|k| k as Probability / Street::Rive.n_abstractions() as Probability)
+            .collect::<Vec<Probability>>();
+        Ochs::install(strengths.clone());
+        let abs = Abstraction::from((Street::Rive, 7));
+        assert_eq!(Ochs::equity(&abs), strengths[7]);
+    }
+}
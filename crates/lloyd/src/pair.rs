@@ -179,6 +179,15 @@ mod tests {
         }
     }
     #[test]
+    fn wide_pairs_roundtrip() {
+        for (i, j) in [(0, 4095), (4094, 4095), (1023, 4000), (0, kicker::INDEX_LIMIT - 1)] {
+            let pair = Pair::new(Street::Turn, i, j);
+            assert_eq!(pair.street(), Street::Turn);
+            let (lo, hi) = pair.abstractions();
+            assert_eq!((lo.index(), hi.index()), (i, j));
+        }
+    }
+    #[test]
     fn merge_split_roundtrip() {
         for t in 0..32768 {
             let (i, j) = Pair::split(t);
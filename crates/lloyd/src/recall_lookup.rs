@@ -0,0 +1,259 @@
+use crate::*;
+use deuce::*;
+use kicker::*;
+use rayon::prelude::*;
+use std::sync::OnceLock;
+
+/// Mapping from perfect-recall hand indices to abstraction buckets.
+///
+/// The perfect-recall counterpart of [`Lookup`]: keys are dense [`Waugh`]
+/// indices of one street, so the table is a flat array rather than a map.
+/// Deals that [`Isomorphism`] merges because the same cards arrived on
+/// different streets keep separate entries here, which is what lets the
+/// flop and turn layers cluster them apart under `Version::V6`.
+pub struct RecallLookup {
+    street: Street,
+    buckets: Vec<Abstraction>,
+}
+
+impl RecallLookup {
+    /// Looks up the abstraction for a perfect-recall index.
+    pub fn lookup(&self, waugh: &Waugh) -> Abstraction {
+        debug_assert_eq!(waugh.street(), self.street);
+        self.buckets[waugh.index() as usize]
+    }
+
+    /// The street this lookup is for.
+    pub fn street(&self) -> Street {
+        self.street
+    }
+
+    /// Generates histograms for every perfect-recall index of the previous
+    /// street, in index order. Data points for the next clustering layer.
+    pub fn projections(&self) -> Vec<Histogram> {
+        (0..Waugh::size(self.street.prev()))
+            .into_par_iter()
+            .map(|i| Waugh::from((self.street.prev(), i)))
+            .map(|w| self.future(&w))
+            .collect::<Vec<Histogram>>()
+    }
+
+    /// Histogram over next-street abstractions for a perfect-recall index.
+    fn future(&self, waugh: &Waugh) -> Histogram {
+        debug_assert_ne!(waugh.street(), Street::Rive);
+        waugh
+            .children()
+            .map(|w| self.lookup(&w))
+            .collect::<Vec<Abstraction>>()
+            .into()
+    }
+
+    /// Creates lookup tables for streets that don't require clustering.
+    ///
+    /// - River: Uses equity as abstraction, as [`Lookup::grow`] does
+    /// - Preflop: Each index gets its own bucket (no compression)
+    pub fn grow(street: Street) -> Self {
+        let buckets = match street {
+            Street::Rive => (0..Waugh::size(street))
+                .into_par_iter()
+                .map(|i| ObservationSeq::from(Waugh::from((street, i))))
+                .map(|seq| Abstraction::from(seq.equity()))
+                .collect(),
+            Street::Pref => (0..Waugh::size(street) as usize)
+                .map(|k| Abstraction::from((Street::Pref, k)))
+                .collect(),
+            Street::Flop | Street::Turn => panic!("recall must be learned via layer for {street}"),
+        };
+        Self { street, buckets }
+    }
+}
+
+/// Assignments in index order, as produced by a clustering layer.
+impl From<(Street, Vec<Abstraction>)> for RecallLookup {
+    fn from((street, buckets): (Street, Vec<Abstraction>)) -> Self {
+        debug_assert_eq!(buckets.len() as u64, Waugh::size(street));
+        Self { street, buckets }
+    }
+}
+
+/// Flat binary file: magic, street, count, then one little-endian
+/// `i16` bucket per perfect-recall index in index order.
+impl RecallLookup {
+    const MAGIC: &[u8; 8] = b"RBPRCAL1";
+
+    pub fn write(&self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        w.write_all(Self::MAGIC)?;
+        w.write_all(&[self.street as u8])?;
+        w.write_all(&(self.buckets.len() as u64).to_le_bytes())?;
+        let bytes = self
+            .buckets
+            .iter()
+            .flat_map(|abs| i16::from(*abs).to_le_bytes())
+            .collect::<Vec<u8>>();
+        w.write_all(&bytes)
+    }
+
+    pub fn read(r: &mut impl std::io::Read) -> std::io::Result<Self> {
+        let invalid = |msg: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, msg.to_string());
+        let mut magic = [0; 8];
+        r.read_exact(&mut magic)?;
+        if &magic != Self::MAGIC {
+            return Err(invalid("not a recall lookup file"));
+        }
+        let mut street = [0; 1];
+        r.read_exact(&mut street)?;
+        let street = Street::all()
+            .get(street[0] as usize)
+            .copied()
+            .ok_or_else(|| invalid("bad street"))?;
+        let mut count = [0; 8];
+        r.read_exact(&mut count)?;
+        if u64::from_le_bytes(count) != Waugh::size(street) {
+            return Err(invalid("count does not match street"));
+        }
+        let mut bytes = vec![0; 2 * Waugh::size(street) as usize];
+        r.read_exact(&mut bytes)?;
+        let buckets = bytes
+            .chunks_exact(2)
+            .map(|b| Abstraction::from(i16::from_le_bytes([b[0], b[1]])))
+            .collect();
+        Ok(Self { street, buckets })
+    }
+
+    /// Writes the lookup to `path`.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let ref mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.write(file)?;
+        std::io::Write::flush(file)
+    }
+
+    /// Reads a lookup written by [`Self::save`].
+    pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        Self::read(&mut std::io::BufReader::new(std::fs::File::open(path)?))
+    }
+}
+
+#[cfg(feature = "server")]
+impl daybook::Schema for RecallLookup {
+    fn name() -> &'static str {
+        daybook::recall()
+    }
+
+    fn columns() -> &'static [tokio_postgres::types::Type] {
+        &[
+            tokio_postgres::types::Type::INT8, // obs (packed street and perfect-recall index)
+            tokio_postgres::types::Type::INT2, // abs (abstraction bucket)
+        ]
+    }
+
+    fn creates() -> &'static str {
+        static SQL: OnceLock<&str> = OnceLock::<&str>::new();
+        SQL.get_or_init(|| {
+            daybook::leaked(format!(
+                "CREATE TABLE IF NOT EXISTS {} (
+                obs      BIGINT   NOT NULL,
+                abs      SMALLINT NOT NULL
+            );",
+                daybook::recall()
+            ))
+        })
+    }
+
+    fn indices() -> &'static str {
+        static SQL: OnceLock<&str> = OnceLock::<&str>::new();
+        let t = daybook::recall();
+        SQL.get_or_init(|| {
+            daybook::leaked(format!(
+                "CREATE INDEX IF NOT EXISTS idx_{t}_obs ON {t} (obs);
+             CREATE INDEX IF NOT EXISTS idx_{t}_abs ON {t} (abs);"
+            ))
+        })
+    }
+
+    fn copy() -> &'static str {
+        static SQL: OnceLock<&str> = OnceLock::<&str>::new();
+        SQL.get_or_init(|| daybook::leaked(format!("COPY {} (obs, abs) FROM STDIN BINARY", daybook::recall())))
+    }
+
+    fn truncates() -> &'static str {
+        static SQL: OnceLock<&str> = OnceLock::<&str>::new();
+        SQL.get_or_init(|| daybook::leaked(format!("TRUNCATE TABLE {};", daybook::recall())))
+    }
+
+    fn freeze() -> &'static str {
+        static SQL: OnceLock<&str> = OnceLock::<&str>::new();
+        let t = daybook::recall();
+        SQL.get_or_init(|| {
+            daybook::leaked(format!(
+                "ALTER TABLE {t} SET (fillfactor = 100);
+             ALTER TABLE {t} SET (autovacuum_enabled = false);"
+            ))
+        })
+    }
+}
+
+#[cfg(feature = "server")]
+#[async_trait::async_trait]
+impl daybook::Streamable for RecallLookup {
+    type Row = (i64, i16);
+
+    fn rows(self) -> impl Iterator<Item = Self::Row> + Send {
+        let street = self.street;
+        self.buckets
+            .into_iter()
+            .enumerate()
+            .map(move |(i, abs)| (i64::from(Waugh::from((street, i as u64))), i16::from(abs)))
+    }
+}
+
+#[cfg(feature = "server")]
+impl RecallLookup {
+    pub async fn from_street(client: &tokio_postgres::Client, street: Street) -> Self {
+        let sql = format!("SELECT obs, abs FROM {} WHERE obs & 3 = $1", daybook::recall());
+        let mut buckets = vec![Abstraction::default(); Waugh::size(street) as usize];
+        for row in client.query(&sql, &[&(street as i64)]).await.expect("query") {
+            let waugh = Waugh::from(row.get::<_, i64>(0));
+            buckets[waugh.index() as usize] = Abstraction::from(row.get::<_, i16>(1));
+        }
+        Self { street, buckets }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preflop_grows_one_bucket_per_index() {
+        let recall = RecallLookup::grow(Street::Pref);
+        assert_eq!(recall.buckets.len(), Street::Pref.n_recalls());
+        let waugh = Waugh::from(ObservationSeq::from(Observation::try_from("AsKs").unwrap()));
+        assert_eq!(recall.lookup(&waugh), Abstraction::from((Street::Pref, waugh.index() as usize)));
+    }
+
+    #[test]
+    fn artifacts_round_trip() {
+        let dir = std::env::temp_dir().join(format!("recall-artifacts-{}", std::process::id()));
+        let built = Artifacts::from(RecallLookup::grow(Street::Pref));
+        built.save(&dir).unwrap();
+        let loaded = Artifacts::<RecallLookup>::load(&dir, Street::Pref).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(loaded.lookup.street(), Street::Pref);
+        assert_eq!(loaded.lookup.buckets, built.lookup.buckets);
+        let waugh = Waugh::from(ObservationSeq::from(Observation::try_from("7s2h").unwrap()));
+        assert_eq!(loaded.lookup.lookup(&waugh), built.lookup.lookup(&waugh));
+        let mut bytes = Vec::new();
+        built.lookup.write(&mut bytes).unwrap();
+        assert!(RecallLookup::read(&mut &bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn future_spans_child_buckets() {
+        let bucket = Abstraction::from((Street::Flop, 7));
+        let recall = RecallLookup::from((Street::Flop, vec![bucket; Street::Flop.n_recalls()]));
+        let hole = Waugh::from(ObservationSeq::from(Observation::try_from("AsKs").unwrap()));
+        let histogram = recall.future(&hole);
+        assert_eq!(histogram.support().collect::<Vec<_>>(), vec![bucket]);
+        assert_eq!(histogram.density(&bucket), 1.);
+    }
+}
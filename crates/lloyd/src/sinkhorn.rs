@@ -15,13 +15,14 @@ use std::hash::{Hash, Hasher};
 /// is correct under three invariants — all currently true in this crate:
 ///
 /// 1. `SinkhornHyperParams::DEFAULT` (temperature, iters, tolerance) is
-///    `const`, so `T` never changes within a process.
+///    `const`, so `T` never changes within a process. Couplings built with
+///    [`Sinkhorn::with`] never touch the cache.
 /// 2. Each `Metric` is constructed once and never mutated. `Metric::set`
 ///    is only called during construction (in `Metric::from`).
-/// 3. All `Metric` instances for the same `Street` within a process
-///    yield identical `raw_distance(x, y)` values — they all derive
-///    from the same DB row set or the same deterministic clustering
-///    output.
+/// 3. All `Metric` instances for the same `Street` (and, on the river,
+///    the same ladder-vs-OCHS kind) within a process yield identical
+///    `raw_distance(x, y)` values — they all derive from the same DB row
+///    set or the same deterministic clustering output.
 ///
 /// **If any of these is violated** (e.g. a runtime-tunable temperature, or
 /// a mutating `Metric`), this cache will return stale values and must be
@@ -70,9 +71,16 @@ pub struct Sinkhorn<'a> {
     lhs: Potential,
     /// RHS potential (dual variable).
     rhs: Potential,
+    /// Regularization and stopping parameters.
+    params: crate::SinkhornHyperParams,
 }
 
 impl Sinkhorn<'_> {
+    /// Overrides the default hyperparameters, for calibration sweeps.
+    pub fn with(mut self, params: crate::SinkhornHyperParams) -> Self {
+        self.params = params;
+        self
+    }
     /// Runs Sinkhorn iteration until convergence.
     fn sinkhorn(&mut self) {
         #[allow(unused)]
@@ -139,15 +147,15 @@ impl Sinkhorn<'_> {
     }
     /// Entropic regularization strength. Lower = closer to exact EMD.
     fn temperature(&self) -> Entropy {
-        crate::SinkhornHyperParams::DEFAULT.temperature()
+        self.params.temperature()
     }
     /// Maximum iteration count before forced termination.
     fn iterations(&self) -> usize {
-        crate::SinkhornHyperParams::DEFAULT.iterations()
+        self.params.iterations()
     }
     /// Convergence tolerance for early stopping.
     fn tolerance(&self) -> Energy {
-        crate::SinkhornHyperParams::DEFAULT.tolerance()
+        self.params.tolerance()
     }
     /// Sinkhorn divergence: entropic-bias-debiased EMD.
     ///
@@ -176,6 +184,7 @@ impl Sinkhorn<'_> {
         let ref mut hasher = std::collections::hash_map::DefaultHasher::new();
         h.hash(hasher);
         metric.street().hash(hasher);
+        metric.is_ochs().hash(hasher);
         let key = hasher.finish();
         SELF_COST_CACHE.with_borrow_mut(|cache| {
             if let Some(&v) = cache.get(&key) {
@@ -225,6 +234,7 @@ impl<'a> From<(&'a Histogram, &'a Histogram, &'a Metric)> for Sinkhorn<'a> {
             nu,
             lhs: Potential::uniform(mu),
             rhs: Potential::uniform(nu),
+            params: crate::SinkhornHyperParams::DEFAULT,
         }
     }
 }
@@ -339,4 +349,65 @@ mod tests {
         let cold = Sinkhorn::divergence(&mu, &nu, &metric);
         assert!((warm - cold).abs() < 1e-6, "warm={warm} cold={cold}");
     }
+
+    /// Random Flop histogram over the support of `flop_metric`.
+    fn random_hist() -> Histogram {
+        let entries = (0..8)
+            .map(|_| (rand::random_range(0..32), rand::random_range(1..8)))
+            .collect::<Vec<_>>();
+        flop_hist(&entries)
+    }
+
+    /// Calibration report: how temperature and the iteration cap move
+    /// nearest-centroid assignments away from the exact-EMD assignments.
+    /// Columns are agreement with exact assignments, mean absolute error of
+    /// the nearest-centroid distance, and wall time for the sweep cell.
+    #[test]
+    #[ignore = "slow: sinkhorn hyperparameter sweep"]
+    fn hyperparameter_sweep() {
+        const N: usize = 512;
+        const K: usize = 8;
+        let metric = flop_metric();
+        let points = (0..N).map(|_| random_hist()).collect::<Vec<_>>();
+        let (centroids, points) = points.split_at(K);
+        let nearest = |distance: &dyn Fn(&Histogram, &Histogram) -> Energy, p: &Histogram| {
+            centroids
+                .iter()
+                .map(|c| distance(p, c))
+                .enumerate()
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .expect("centroids")
+        };
+        let exact = points
+            .iter()
+            .map(|p| nearest(&|a, b| monge::NetworkSimplex::from((a, b, &metric)).minimize().cost(), p))
+            .collect::<Vec<_>>();
+        let default = crate::SinkhornHyperParams::DEFAULT;
+        eprintln!("\n=== sinkhorn vs exact emd: k-means assignments (N={}, K={K}) ===", points.len());
+        eprintln!("{:>12} {:>10} {:>10} {:>10} {:>10}", "temperature", "iterations", "agreement", "mean err", "millis");
+        eprintln!("{:>12} {:>10} {:>10} {:>10} {:>10}", "-----------", "----------", "---------", "--------", "------");
+        for temperature in [0.1, 0.05, 0.025, 0.01] {
+            for iterations in [8, 32, 128, 512] {
+                let params = crate::SinkhornHyperParams::new(temperature, iterations, default.tolerance());
+                let ot = |a: &Histogram, b: &Histogram| Sinkhorn::from((a, b, &metric)).with(params).minimize().cost();
+                let divergence = |a: &Histogram, b: &Histogram| (ot(a, b) - 0.5 * ot(a, a) - 0.5 * ot(b, b)).max(0.);
+                let start = std::time::Instant::now();
+                let approx = points.iter().map(|p| nearest(&divergence, p)).collect::<Vec<_>>();
+                let millis = start.elapsed().as_millis();
+                let agree = approx.iter().zip(&exact).filter(|(a, e)| a.0 == e.0).count();
+                let agreement = agree as f32 / points.len() as f32;
+                let error = approx
+                    .iter()
+                    .zip(&exact)
+                    .map(|(a, e)| (a.1 - e.1).abs())
+                    .sum::<Energy>()
+                    / points.len() as f32;
+                let marker = if params == default { " <- default" } else { "" };
+                eprintln!("{temperature:>12} {iterations:>10} {agreement:>10.3} {error:>10.5} {millis:>10}{marker}");
+                if params == default {
+                    assert!(agreement > 0.5, "default agreement {agreement}");
+                }
+            }
+        }
+    }
 }
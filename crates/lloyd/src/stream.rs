@@ -0,0 +1,254 @@
+//! Mini-batch k-means layer for low-memory clustering.
+//!
+//! [`Layer`] holds one histogram and `K` bounds per isomorphism, which on
+//! the turn runs to tens of gigabytes. This layer trades Elkan's exact
+//! iterations for Sculley's mini-batch updates so that only the centroids
+//! and the next street's artifacts stay resident.
+
+use super::*;
+use deuce::*;
+use kicker::*;
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+
+/// A clustering layer that never holds a street's histograms at once.
+///
+/// Histograms are computed from the next street's [`Lookup`] one batch of
+/// isomorphisms at a time, fed to [`MiniBatch`], and dropped. A final pass
+/// assigns every isomorphism in dense index order, so the resulting table
+/// can be written out chunk by chunk via [`StreamLayer::write`].
+///
+/// Produces the same three artifacts as [`Layer`].
+pub struct StreamLayer<const K: usize> {
+    /// The betting street this layer represents
+    street: Street,
+    /// Next-street assignments, used to project isomorphisms to histograms
+    lookup: Lookup,
+    /// Distance metric for computing EMD between abstractions in the next street
+    metric: Metric,
+    /// Centroids and their running counts
+    kmeans: MiniBatch<Histogram, K>,
+    /// Isomorphisms per batch
+    batch: usize,
+}
+
+impl<const K: usize> StreamLayer<K> {
+    /// Seeds centroids by k-means++ over a sample of `max(batch, K)`
+    /// isomorphisms drawn deterministically from the street.
+    pub fn new(street: Street, lookup: Lookup, metric: Metric, batch: usize) -> Self {
+        use rand::SeedableRng;
+        use rand::rngs::SmallRng;
+        debug_assert_eq!(lookup.street(), street.next());
+        let ref mut rng = SmallRng::seed_from_u64(street as u64);
+        let n = street.n_isomorphisms();
+        let mut sample = rand::seq::index::sample(rng, n, batch.max(K).min(n)).into_vec();
+        sample.sort_unstable();
+        let points = sample
+            .into_par_iter()
+            .map(|i| lookup.future(&Isomorphism::from((street, i))))
+            .collect::<Vec<Histogram>>();
+        let seeds = crate::layer::seed(street, K, &points, &|x, y| metric.emd(x, y));
+        Self {
+            street,
+            kmeans: MiniBatch::new(seeds.try_into().expect("K")),
+            lookup,
+            metric,
+            batch,
+        }
+    }
+
+    /// Streams the street's isomorphisms `epochs` times, one mini-batch
+    /// update per chunk. Chunks follow [`Self::order`], so no batch is a
+    /// run of neighbours in canonical order.
+    pub fn fit(&mut self, epochs: usize) {
+        use std::time::Instant;
+        let street = self.street;
+        let tel = crate::telemetry::ClusterTelemetry::new(street);
+        for epoch in 0..epochs {
+            let mut stream = self.order(epoch);
+            loop {
+                let t = Instant::now();
+                let chunk = stream.by_ref().take(self.batch).collect::<Vec<Isomorphism>>();
+                if chunk.is_empty() {
+                    break;
+                }
+                let points = chunk
+                    .par_iter()
+                    .map(|iso| self.lookup.future(iso))
+                    .collect::<Vec<Histogram>>();
+                let ref metric = self.metric;
+                let drift = self.kmeans.step(&points, &|x, y| metric.emd(x, y));
+                tel.iteration(t.elapsed(), &drift);
+                tracing::debug!(%street, epoch, drift_max = drift.max(), "minibatch step");
+            }
+        }
+    }
+
+    /// Every isomorphism of the street once, as `start + k·stride` modulo
+    /// the street size. The stride sits near the golden section of the
+    /// street and is coprime to its size; `start` is drawn per epoch from
+    /// a seeded generator. Canonical order sweeps one board texture at a
+    /// time, which would drag the centroids from region to region.
+    fn order(&self, epoch: usize) -> impl Iterator<Item = Isomorphism> + use<K> {
+        use rand::Rng;
+        use rand::SeedableRng;
+        use rand::rngs::SmallRng;
+        fn gcd(a: usize, b: usize) -> usize {
+            if b == 0 { a } else { gcd(b, a % b) }
+        }
+        let street = self.street;
+        let n = street.n_isomorphisms();
+        let stride = ((n as f64 * 0.618_034) as usize..n)
+            .find(|&s| gcd(s, n) == 1)
+            .expect("coprime stride");
+        let ref mut rng = SmallRng::seed_from_u64(street as u64 ^ ((epoch as u64) << 8));
+        let start = rng.random_range(0..n);
+        (0..n).map(move |k| Isomorphism::from((street, (start + k * stride) % n)))
+    }
+
+    /// Nearest cluster abstraction of every isomorphism, batch by batch
+    /// in dense index order.
+    fn assignments(&self) -> impl Iterator<Item = Vec<Abstraction>> + '_ {
+        let n = self.street.n_isomorphisms();
+        (0..n).step_by(self.batch).map(move |start| {
+            (start..n.min(start + self.batch))
+                .into_par_iter()
+                .map(|i| self.lookup.future(&Isomorphism::from((self.street, i))))
+                .map(|h| self.kmeans.nearest(&h, &|x, y| self.metric.emd(x, y)).0)
+                .map(|k| Abstraction::from((self.street, k)))
+                .collect()
+        })
+    }
+
+    /// Writes the lookup in [`Lookup::write`] format without building it
+    /// in memory.
+    pub fn write(&self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        tracing::info!(street = %self.street, phase = crate::telemetry::phase::LOOKUP, "kmeans phase begin");
+        Lookup::header(w, self.street)?;
+        self.assignments().try_for_each(|chunk| Lookup::chunk(w, &chunk))
+    }
+
+    /// Writes the lookup to `path`, readable by [`Lookup::load`].
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let ref mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.write(file)?;
+        std::io::Write::flush(file)
+    }
+
+    /// Builds the full lookup table in memory.
+    pub fn lookup(&self) -> Lookup {
+        tracing::info!(street = %self.street, phase = crate::telemetry::phase::LOOKUP, "kmeans phase begin");
+        Lookup::from((self.street, self.assignments().flatten().collect()))
+    }
+
+    /// Computes pairwise distances between all learned cluster centroids.
+    pub fn metric(&self) -> Metric {
+        tracing::info!(street = %self.street, phase = crate::telemetry::phase::METRIC, "kmeans phase begin");
+        Metric::between(self.street, &self.metric, self.kmeans.centroids())
+    }
+
+    /// Builds the transition future mapping abstractions to their centroid histograms.
+    pub fn future(&self) -> Future {
+        tracing::info!(street = %self.street, phase = crate::telemetry::phase::FUTURE, "kmeans phase begin");
+        self.kmeans
+            .centroids()
+            .iter()
+            .copied()
+            .enumerate()
+            .map(|(k, centroid)| (Abstraction::from((self.street, k)), centroid))
+            .collect::<BTreeMap<Abstraction, Histogram>>()
+            .into()
+    }
+}
+
+#[cfg(feature = "server")]
+impl<const K: usize> StreamLayer<K> {
+    /// Mini-batch counterpart of [`Layer::cluster`], for streets with a
+    /// [`KmeansHyperParams::batch`] size.
+    pub async fn cluster(street: Street, client: &tokio_postgres::Client) -> Artifacts {
+        use crate::telemetry::phase;
+        use std::time::Instant;
+        let params = crate::KmeansHyperParams::DEFAULT;
+        let batch = params.batch(street).expect("street clusters in memory");
+        let tel = crate::telemetry::ClusterTelemetry::new(street);
+        tracing::info!(%street, phase = phase::HYDRATE, "kmeans phase begin");
+        let t = Instant::now();
+        let lookup = Lookup::from_street(client, street.next()).await;
+        let metric = Metric::from_street(client, street.next()).await;
+        tel.phase(t, phase::HYDRATE);
+        tracing::info!(%street, phase = phase::INIT, "kmeans phase begin");
+        let t = Instant::now();
+        let mut layer = Self::new(street, lookup, metric, batch);
+        tel.phase(t, phase::INIT);
+        tracing::info!(%street, phase = phase::ITERATE, "kmeans phase begin");
+        let t = Instant::now();
+        layer.fit(params.minibatch_epochs());
+        tel.phase(t, phase::ITERATE);
+        let t = Instant::now();
+        let lookup = layer.lookup();
+        tel.phase(t, phase::LOOKUP);
+        let t = Instant::now();
+        let metric = layer.metric();
+        tel.phase(t, phase::METRIC);
+        let t = Instant::now();
+        let future = layer.future();
+        tel.phase(t, phase::FUTURE);
+        Artifacts { lookup, metric, future }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pokerkit::Energy;
+
+    /// Flop lookup bucketing every flop by its highest hole card, so each
+    /// preflop hand projects to a point mass on one of four buckets.
+    fn flop_lookup() -> Lookup {
+        let street = Street::Flop;
+        let buckets = (0..street.n_isomorphisms())
+            .into_par_iter()
+            .map(|i| Isomorphism::from((street, i)))
+            .map(|iso| u8::from(iso.0.pocket().max_rank().expect("two cards")) as usize / 4)
+            .map(|k| Abstraction::from((street, k)))
+            .collect();
+        Lookup::from((street, buckets))
+    }
+    fn flop_metric() -> Metric {
+        let mut metric = Metric::new(Street::Flop);
+        for i in 0..4 {
+            for j in (i + 1)..4 {
+                metric.set(Pair::new(Street::Flop, i, j), (j - i) as Energy / 4.);
+            }
+        }
+        metric
+    }
+
+    #[test]
+    fn streamed_lookup_separates_point_masses() {
+        let mut layer = StreamLayer::<4>::new(Street::Pref, flop_lookup(), flop_metric(), 32);
+        layer.fit(1);
+        let lookup = layer.lookup();
+        let projections = flop_lookup().projections();
+        for (i, (_, a)) in lookup.iter().enumerate() {
+            for (j, (_, b)) in lookup.iter().enumerate() {
+                assert_eq!(projections[i] == projections[j], a == b, "{i} vs {j}");
+            }
+        }
+        let mut bytes = Vec::new();
+        layer.write(&mut bytes).unwrap();
+        let loaded = Lookup::read(&mut bytes.as_slice()).unwrap();
+        assert!(lookup.iter().zip(loaded.iter()).all(|(a, b)| a == b));
+    }
+
+    #[test]
+    fn epochs_visit_every_isomorphism_out_of_order() {
+        let layer = StreamLayer::<4>::new(Street::Pref, flop_lookup(), flop_metric(), 32);
+        let first = layer.order(0).map(|iso| iso.index()).collect::<Vec<usize>>();
+        let mut sorted = first.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..Street::Pref.n_isomorphisms()).collect::<Vec<usize>>());
+        assert!(first.windows(2).all(|w| w[0] + 1 != w[1]));
+        assert_ne!(first, layer.order(1).map(|iso| iso.index()).collect::<Vec<usize>>());
+    }
+}
@@ -5,6 +5,7 @@
 //! `{street}` label set built once at construction.
 
 use deuce::Street;
+use pokerkit::Energy;
 use std::time::Duration;
 use std::time::Instant;
 use vitals::KeyValue;
@@ -76,6 +77,20 @@ impl ClusterTelemetry {
         self.handles.kmeans_early_terminated.add(1, &self.labels);
     }
 
+    /// Records mean within-cluster EMD at convergence, labeled by the
+    /// centroid rule whose centroids it was measured against.
+    pub(crate) fn inertia(&self, centroid: crate::Centroid, inertia: Energy) {
+        self.handles
+            .kmeans_inertia
+            .record(f64::from(inertia), &[self.labels[0].clone(), KeyValue::new("centroid", centroid.label())]);
+    }
+
+    /// Records barycenter minus arithmetic-mean within-cluster EMD for
+    /// the same final assignments. Negative means the barycenter helped.
+    pub(crate) fn inertia_change(&self, delta: Energy) {
+        self.handles.kmeans_inertia_delta.record(f64::from(delta), &self.labels);
+    }
+
     /// Records one `cluster_size` per cluster. Empty clusters are
     /// derivable from the histogram's `le=0` bucket, so no separate
     /// gauge is emitted.
@@ -159,4 +159,60 @@ mod verify {
             assert_eq!(elkan.centroids(), naive.centroids());
         }
     }
+
+    #[test]
+    #[ignore = "slow: accelerated kmeans benchmark"]
+    /// Times every bound scheme against naive k-means on Turn histograms,
+    /// checking each reproduces the naive assignments. Prints per-step
+    /// time and bound memory per point, to pick a scheme per street.
+    fn accelerated_kmeans_benchmark() {
+        use super::K;
+        use super::N;
+        use elkan::Assignment;
+        use std::time::Duration;
+        use std::time::Instant;
+        const G: usize = 2;
+        fn js<B: Assignment>(bounds: &[B]) -> Vec<usize> {
+            bounds.iter().map(Assignment::j).collect()
+        }
+        let km = TestLayer::new();
+        let groups = km.groups::<G>();
+        let (mut naive, mut elkan, mut hamerly, mut yinyang) = (km.clone(), km.clone(), km.clone(), km.clone());
+        let mut eb = elkan.init_bounds();
+        let mut hb = hamerly.init_hamerly();
+        let mut yb = yinyang.init_yinyang::<G>(&groups);
+        let mut elapsed = [Duration::ZERO; 4];
+        let mut steps = 0;
+        while steps < km.t() && naive.centroids().iter().all(|h| h.n() > 0) {
+            let expected = (0..N).map(|i| naive.neighbor(i).0).collect::<Vec<usize>>();
+            let t = Instant::now();
+            naive.kmeans = naive.step_naive();
+            elapsed[0] += t.elapsed();
+            let t = Instant::now();
+            elkan.kmeans = elkan.step_elkan(&mut eb).0;
+            elapsed[1] += t.elapsed();
+            let t = Instant::now();
+            hamerly.kmeans = hamerly.step_hamerly(&mut hb).0;
+            elapsed[2] += t.elapsed();
+            let t = Instant::now();
+            yinyang.kmeans = yinyang.step_yinyang(&mut yb, &groups).0;
+            elapsed[3] += t.elapsed();
+            assert_eq!(js(&*eb), expected, "elkan");
+            assert_eq!(js(&*hb), expected, "hamerly");
+            assert_eq!(js(&*yb), expected, "yinyang");
+            steps += 1;
+        }
+        let bytes = [
+            0,
+            std::mem::size_of::<elkan::Bounds<K>>(),
+            std::mem::size_of::<elkan::Hamerly>(),
+            std::mem::size_of::<elkan::Yinyang<G>>(),
+        ];
+        eprintln!("K = {K}, N = {N}, G = {G}, {steps} steps");
+        eprintln!("{:>8} {:>12} {:>12}", "scheme", "ms/step", "bytes/point");
+        for ((name, time), bytes) in ["naive", "elkan", "hamerly", "yinyang"].iter().zip(elapsed).zip(bytes) {
+            let ms = time.as_secs_f64() * 1e3 / steps.max(1) as f64;
+            eprintln!("{name:>8} {ms:>12.2} {bytes:>12}");
+        }
+    }
 }
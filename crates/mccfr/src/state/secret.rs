@@ -28,6 +28,11 @@ where
     Self: std::fmt::Debug,
     Self: std::hash::Hash,
 {
+    /// Betting round this secret was dealt on, for games whose public
+    /// state resets each round. Single-round games keep the default.
+    fn round(&self) -> usize {
+        0
+    }
 }
 /// Unit secret for games with no private information.
 impl CfrSecret for () {}
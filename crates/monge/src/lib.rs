@@ -8,6 +8,8 @@
 //!
 //! - `Greenkhorn` — Sinkhorn-like algorithm with greedy row/column updates
 //! - `Greedy` — Fast approximate coupling via greedy matching
+//! - [`NetworkSimplex`] — Exact coupling via the transportation simplex
+//! - [`Barycenter`] — Entropic Wasserstein barycenter of several densities
 //!
 //! ## Core Types
 //!
@@ -21,16 +23,20 @@
 //! The Sinkhorn iterations are controlled by temperature, iteration count, and
 //! convergence tolerance parameters defined in the crate root. Lower temperature
 //! yields sharper transport plans at the cost of numerical stability.
+mod barycenter;
 mod coupling;
 mod density;
 mod greedy;
 mod greenkhorn;
 mod measure;
+mod simplex;
 mod support;
 
+pub use barycenter::*;
 pub use coupling::*;
 pub use density::*;
 pub use greedy::*;
 pub use greenkhorn::*;
 pub use measure::*;
+pub use simplex::*;
 pub use support::*;
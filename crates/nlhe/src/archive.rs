@@ -0,0 +1,117 @@
+//! File-based blueprint: a flat binary snapshot of a trained solver.
+//!
+//! Lets read-only consumers (chart export, offline analysis) load a
+//! blueprint without PostgreSQL. The layout mirrors the database tables:
+//! the encoder's flat isomorphism → abstraction arrays, then one row per
+//! (info, edge) with the blueprint table's columns. Little-endian
+//! throughout.
+use super::*;
+use kicker::*;
+use mccfr::*;
+use std::collections::HashMap;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+
+const MAGIC: &[u8; 8] = b"RBPBLUE2";
+
+impl<R, W, S> Nlhe<R, W, S>
+where
+    R: RegretSchedule,
+    W: WeightSchedule,
+    S: SamplingScheme,
+{
+    /// Writes the abstraction lookup and every encounter to `path`.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let ref mut file = BufWriter::new(std::fs::File::create(path)?);
+        file.write_all(MAGIC)?;
+        file.write_all(&(self.profile.epochs as u64).to_le_bytes())?;
+        self.encoder.write(file)?;
+        let rows = self.profile.encounters.values().map(HashMap::len).sum::<usize>();
+        file.write_all(&(rows as u64).to_le_bytes())?;
+        for (info, edges) in &self.profile.encounters {
+            for (edge, encounter) in edges {
+                file.write_all(&u64::from(info.subgame()).to_le_bytes())?;
+                file.write_all(&i16::from(info.bucket()).to_le_bytes())?;
+                file.write_all(&u64::from(info.choices()).to_le_bytes())?;
+                file.write_all(&u64::from(*edge).to_le_bytes())?;
+                file.write_all(&encounter.weight.to_le_bytes())?;
+                file.write_all(&encounter.regret.to_le_bytes())?;
+                file.write_all(&encounter.payoff.to_le_bytes())?;
+                file.write_all(&encounter.visits.to_le_bytes())?;
+            }
+        }
+        file.flush()
+    }
+
+    /// Reads a blueprint written by [`Self::save`].
+    pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let ref mut file = BufReader::new(std::fs::File::open(path)?);
+        if &take::<8>(file)? != MAGIC {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "not a blueprint file"));
+        }
+        let epochs = u64::from_le_bytes(take(file)?) as usize;
+        let encoder = NlheEncoder::read(file)?;
+        let rows = u64::from_le_bytes(take(file)?);
+        let mut encounters = HashMap::<NlheInfo, HashMap<NlheEdge, Encounter>>::new();
+        for _ in 0..rows {
+            let subgame = Path::from(u64::from_le_bytes(take(file)?));
+            let present = Abstraction::from(i16::from_le_bytes(take(file)?));
+            let choices = Path::from(u64::from_le_bytes(take(file)?));
+            let edge = NlheEdge::from(u64::from_le_bytes(take(file)?));
+            let weight = f32::from_le_bytes(take(file)?);
+            let regret = f32::from_le_bytes(take(file)?);
+            let payoff = f32::from_le_bytes(take(file)?);
+            let visits = u32::from_le_bytes(take(file)?);
+            encounters
+                .entry(NlheInfo::from((subgame, present, choices)))
+                .or_default()
+                .insert(edge, Encounter::new(weight, regret, payoff, visits));
+        }
+        let profile = NlheProfile {
+            epochs,
+            encounters,
+            metrics: Metrics::with_epoch(epochs),
+        };
+        Ok(Self::new(profile, encoder))
+    }
+}
+
+fn take<const N: usize>(file: &mut impl Read) -> std::io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    file.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deuce::IsomorphismIterator;
+    use deuce::Observation;
+    use deuce::Street;
+    use pokerkit::Arbitrary;
+
+    #[test]
+    fn round_trip() {
+        let obs = Observation::try_from("AsKs").unwrap();
+        let abs = Abstraction::from(Street::Pref);
+        let info = NlheInfo::random();
+        let edge = info.choices().into_iter().map(NlheEdge::from).next().unwrap();
+        let encoder = IsomorphismIterator::from(Street::Pref).map(|iso| (iso, abs)).collect();
+        let mut blueprint = Flagship::new(NlheProfile::default(), encoder);
+        blueprint.profile.epochs = 7;
+        blueprint
+            .profile
+            .encounters
+            .insert(info, HashMap::from([(edge, Encounter::new(0.25, -1.5, 3.0, 11))]));
+        let path = std::env::temp_dir().join(format!("nlhe-archive-{}.bin", std::process::id()));
+        blueprint.save(&path).unwrap();
+        let loaded = Flagship::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.profile.epochs, 7);
+        assert_eq!(loaded.encoder.abstraction(&obs), abs);
+        let encounter = loaded.profile.encounters[&info][&edge];
+        assert_eq!((encounter.weight, encounter.regret, encounter.payoff, encounter.visits), (0.25, -1.5, 3.0, 11));
+    }
+}
@@ -0,0 +1,233 @@
+//! Preflop chart export from a trained blueprint.
+//!
+//! Walks every preflop decision in the abstract tree whose line holds at
+//! most `depth` bets and raises (0 = unopened and limped pots, 1 = facing
+//! an open, 2 = facing a 3-bet, …) and reads the averaged blueprint
+//! policy of all 169 starting-hand cells at each one.
+use super::*;
+use deuce::*;
+use kicker::*;
+use mccfr::*;
+use pokerkit::Probability;
+
+/// Blueprint policy at one preflop decision, per starting-hand cell.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Chart {
+    /// Abstract edges leading here from the post-blind root.
+    pub line: Vec<Edge>,
+    /// Seat to act.
+    pub actor: usize,
+    /// Available edges, in the order of every cell's `policy`.
+    pub edges: Vec<Edge>,
+    /// One entry per cell, in [`Range::grid`] order.
+    pub cells: Vec<Cell>,
+}
+
+/// Action frequencies of one starting-hand cell such as `AKs`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Cell {
+    pub hand: String,
+    pub policy: Vec<Probability>,
+}
+
+/// Every preflop chart up to a raise depth, in depth-first line order.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(transparent)]
+pub struct Charts(Vec<Chart>);
+
+impl<R, W, S> Nlhe<R, W, S>
+where
+    R: RegretSchedule,
+    W: WeightSchedule,
+    S: SamplingScheme,
+{
+    /// Preflop charts for every line with at most `depth` bets and raises.
+    pub fn charts(&self, depth: usize) -> Charts {
+        let mut charts = Vec::new();
+        self.chart(Witness::initial(Turn::Choice(0)), depth, &mut charts);
+        Charts(charts)
+    }
+
+    fn chart(&self, recall: Witness, depth: usize, charts: &mut Vec<Chart>) {
+        let head = recall.head();
+        let Turn::Choice(actor) = head.turn() else {
+            return;
+        };
+        let line = recall.history();
+        if head.street() != Street::Pref || line.iter().filter(|e| e.is_aggro()).count() > depth {
+            return;
+        }
+        let recall = recall.with_pov(head.turn());
+        let edges = recall.choices().into_iter().collect::<Vec<Edge>>();
+        let cells = Range::grid()
+            .map(|hand| {
+                let hole = Range::try_from(hand.as_str())
+                    .ok()
+                    .and_then(|cell| cell.iter().next())
+                    .map(|(hole, _)| hole)
+                    .expect("grid cells parse to at least one combo");
+                let seat = recall.replace(Arrangement::from(Observation::from((Hand::from(hole), Hand::empty()))));
+                let info = NlheInfo::from((&seat, self.encoder.abstraction(&seat.seen())));
+                let distribution = self.profile.averaged_distribution(&info);
+                let policy = edges
+                    .iter()
+                    .map(|edge| {
+                        distribution
+                            .iter()
+                            .find(|(e, _)| Edge::from(*e) == *edge)
+                            .map_or(0., |(_, p)| *p)
+                    })
+                    .collect();
+                Cell { hand, policy }
+            })
+            .collect();
+        charts.push(Chart {
+            line,
+            actor,
+            edges: edges.clone(),
+            cells,
+        });
+        for edge in edges {
+            if let Ok(child) = recall.try_push(head.actionize(edge)) {
+                self.chart(child, depth, charts);
+            }
+        }
+    }
+}
+
+impl Chart {
+    /// The line as a [`Path`] string, or `root` before any decision.
+    pub fn label(&self) -> String {
+        match self.line.len() {
+            0 => "root".to_string(),
+            _ => Path::from(self.line.clone()).to_string(),
+        }
+    }
+    /// Fill color for an edge: folds blue, passive actions green, raises
+    /// warming from orange to red with size, shoves purple.
+    fn color(&self, edge: &Edge) -> &'static str {
+        const RAISES: [&str; 5] = ["#f6b26b", "#ef8354", "#e0533d", "#b8322a", "#8c1d18"];
+        match edge {
+            Edge::Fold => "#6d9eeb",
+            Edge::Check | Edge::Call => "#6aa84f",
+            Edge::Shove => "#674ea7",
+            _ => {
+                let index = self.edges.iter().filter(|e| e.is_raise()).position(|e| e == edge);
+                RAISES[index.unwrap_or_default().min(RAISES.len() - 1)]
+            }
+        }
+    }
+}
+
+impl Charts {
+    pub fn iter(&self) -> impl Iterator<Item = &Chart> {
+        self.0.iter()
+    }
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Pretty-printed JSON array of charts.
+    pub fn json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("charts serialize")
+    }
+
+    /// Long-format CSV: one row per (line, hand, edge).
+    pub fn csv(&self) -> String {
+        let mut csv = String::from("line,actor,hand,edge,frequency\n");
+        for chart in self.iter() {
+            let line = chart.label();
+            for cell in &chart.cells {
+                for (edge, p) in chart.edges.iter().zip(&cell.policy) {
+                    csv.push_str(&format!("{line},{},{},{edge},{p:.4}\n", chart.actor, cell.hand));
+                }
+            }
+        }
+        csv
+    }
+
+    /// Self-contained HTML page with one 13×13 grid per chart. Each cell
+    /// is striped by its action frequencies; hover shows the numbers.
+    pub fn html(&self) -> String {
+        let mut html = String::from(
+            "<!doctype html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Preflop charts</title>\n<style>\n\
+             body { font-family: sans-serif; background: #fafafa; }\n\
+             section { display: inline-block; margin: 1em; vertical-align: top; }\n\
+             table { border-collapse: collapse; }\n\
+             td { width: 2.6em; height: 2em; border: 1px solid #fff; font-size: 0.7em; text-align: center; }\n\
+             .legend span { display: inline-block; padding: 0 0.5em; margin-right: 0.3em; color: #fff; }\n\
+             </style>\n</head>\n<body>\n",
+        );
+        for chart in self.iter() {
+            html.push_str(&format!("<section>\n<h3>{} · seat {}</h3>\n", chart.label(), chart.actor));
+            html.push_str("<div class=\"legend\">");
+            for edge in &chart.edges {
+                html.push_str(&format!("<span style=\"background:{}\">{edge}</span>", chart.color(edge)));
+            }
+            html.push_str("</div>\n<table>\n");
+            for row in chart.cells.chunks(13) {
+                html.push_str("<tr>");
+                for cell in row {
+                    let mut stops = Vec::new();
+                    let mut title = Vec::new();
+                    let mut start = 0.;
+                    for (edge, p) in chart.edges.iter().zip(&cell.policy) {
+                        let end = start + p * 100.;
+                        stops.push(format!("{} {start:.1}% {end:.1}%", chart.color(edge)));
+                        title.push(format!("{edge} {p:.2}"));
+                        start = end;
+                    }
+                    html.push_str(&format!(
+                        "<td style=\"background:linear-gradient(to right, {})\" title=\"{}\">{}</td>",
+                        stops.join(", "),
+                        title.join(" · "),
+                        cell.hand
+                    ));
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</table>\n</section>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An untrained blueprint still yields a full, normalized chart at
+    /// every preflop decision, and deeper limits only add lines.
+    #[test]
+    fn walks_preflop_lines() {
+        let lookup = Range::grid()
+            .map(|hand| Range::try_from(hand.as_str()).unwrap())
+            .flat_map(|cell| cell.iter().map(|(hole, _)| hole).collect::<Vec<_>>())
+            .map(|hole| Observation::from((Hand::from(hole), Hand::empty())))
+            .map(|obs| (Isomorphism::from(obs), Abstraction::from(Street::Pref)))
+            .collect::<NlheEncoder>();
+        let blueprint = Flagship::new(NlheProfile::default(), lookup);
+        let shallow = blueprint.charts(0);
+        let deeper = blueprint.charts(1);
+        assert!(!shallow.is_empty());
+        assert!(deeper.len() > shallow.len());
+        let root = shallow.iter().next().unwrap();
+        assert!(root.line.is_empty());
+        assert_eq!(root.cells.len(), 169);
+        for cell in &root.cells {
+            assert!((cell.policy.iter().sum::<Probability>() - 1.).abs() < 1e-4);
+        }
+        assert!(
+            deeper
+                .iter()
+                .all(|c| c.line.iter().filter(|e| e.is_aggro()).count() <= 1)
+        );
+        let csv = shallow.csv();
+        assert_eq!(csv.lines().count(), 1 + shallow.iter().map(|c| 169 * c.edges.len()).sum::<usize>());
+        assert_eq!(shallow.html().matches("<td").count(), 169 * shallow.len());
+    }
+}
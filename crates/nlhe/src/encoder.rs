@@ -3,7 +3,6 @@ use deuce::*;
 use kicker::*;
 use mccfr::*;
 use pokerkit::*;
-use std::collections::BTreeMap;
 use subgame::*;
 
 type NlheTree = Tree<NlheTurn, NlheEdge, NlheGame, NlheInfo>;
@@ -15,29 +14,173 @@ type NlheTree = Tree<NlheTurn, NlheEdge, NlheGame, NlheInfo>;
 /// This mapping is loaded from the database and represents the output of
 /// the k-means clustering pipeline.
 ///
+/// Each street's buckets sit in a flat array indexed by
+/// [`Isomorphism::index`], so an abstraction lookup is constant time and
+/// needs no canonicalization. A bitmask alongside records which slots
+/// were actually loaded, so a gap in the table is an error rather than
+/// a silent bucket 0. The arrays load from a flat file via
+/// [`Self::load`] as well as from the database.
+///
 /// # Database Loading
 ///
 /// With the `database` feature, implements `Hydrate` to load the
 /// isomorphism→abstraction mapping from PostgreSQL.
 #[derive(Default)]
-pub struct NlheEncoder(BTreeMap<Isomorphism, Abstraction>);
+pub struct NlheEncoder {
+    buckets: [Vec<Abstraction>; 4],
+    filled: [Vec<u64>; 4],
+}
+
+/// Collects `(isomorphism, bucket)` pairs. A street's array is allocated
+/// on its first pair; isomorphisms never collected stay unfilled, and
+/// looking one up panics.
+impl FromIterator<(Isomorphism, Abstraction)> for NlheEncoder {
+    fn from_iter<I: IntoIterator<Item = (Isomorphism, Abstraction)>>(iter: I) -> Self {
+        let mut encoder = Self::default();
+        encoder.extend(iter);
+        encoder
+    }
+}
+
+impl Extend<(Isomorphism, Abstraction)> for NlheEncoder {
+    fn extend<I: IntoIterator<Item = (Isomorphism, Abstraction)>>(&mut self, iter: I) {
+        for (iso, abs) in iter {
+            self.insert(iso, abs);
+        }
+    }
+}
 
 impl NlheEncoder {
+    pub(crate) const MAGIC: &[u8; 8] = b"RBPENCD1";
+
     /// Looks up the abstraction bucket for an observation.
     ///
-    /// Internally converts to canonical isomorphism for lookup.
-    /// Panics if the isomorphism is not in the lookup table.
+    /// Panics if the observation's isomorphism was never loaded.
     pub fn abstraction(&self, obs: &Observation) -> Abstraction {
-        self.0
-            .get(&Isomorphism::from(*obs))
-            .copied()
-            .expect("isomorphism not found in abstraction lookup")
+        let street = obs.street();
+        let index = Isomorphism(*obs).index();
+        assert!(self.has(street, index), "no bucket loaded for {obs}");
+        self.buckets[street as usize][index]
+    }
+    /// Number of isomorphisms with a loaded bucket.
+    pub fn len(&self) -> usize {
+        self.filled.iter().flatten().map(|w| w.count_ones() as usize).sum()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Streets that were partly loaded, with how many isomorphisms each
+    /// is missing. A complete table returns nothing.
+    pub fn missing(&self) -> Vec<(Street, usize)> {
+        Street::all()
+            .into_iter()
+            .filter(|street| !self.buckets[*street as usize].is_empty())
+            .map(|street| (street, street.n_isomorphisms() - self.loaded(street)))
+            .filter(|(_, n)| *n > 0)
+            .collect()
+    }
+    /// Every loaded `(isomorphism, bucket)` pair, street by street in
+    /// dense index order.
+    pub fn iter(&self) -> impl Iterator<Item = (Isomorphism, Abstraction)> + '_ {
+        Street::all().into_iter().flat_map(move |street| {
+            self.buckets[street as usize]
+                .iter()
+                .enumerate()
+                .filter(move |(i, _)| self.has(street, *i))
+                .map(move |(i, abs)| (Isomorphism::from((street, i)), *abs))
+        })
+    }
+    fn insert(&mut self, iso: Isomorphism, abs: Abstraction) {
+        let street = iso.0.street();
+        let s = street as usize;
+        if self.buckets[s].is_empty() {
+            self.buckets[s] = vec![Abstraction::default(); street.n_isomorphisms()];
+            self.filled[s] = vec![0; street.n_isomorphisms().div_ceil(64)];
+        }
+        let i = iso.index();
+        self.buckets[s][i] = abs;
+        self.filled[s][i / 64] |= 1 << (i % 64);
+    }
+    fn has(&self, street: Street, i: usize) -> bool {
+        self.filled[street as usize]
+            .get(i / 64)
+            .is_some_and(|w| w >> (i % 64) & 1 == 1)
+    }
+    fn loaded(&self, street: Street) -> usize {
+        self.filled[street as usize]
+            .iter()
+            .map(|w| w.count_ones() as usize)
+            .sum()
+    }
+
+    /// Writes every street's flat array: magic, then per street a count
+    /// and that many little-endian `i16` buckets. Refuses partly loaded
+    /// streets, which the format can't represent.
+    pub fn write(&self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        if let Some((street, n)) = self.missing().first() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{n} {street} isomorphisms have no bucket"),
+            ));
+        }
+        w.write_all(Self::MAGIC)?;
+        for buckets in &self.buckets {
+            w.write_all(&(buckets.len() as u64).to_le_bytes())?;
+            let bytes = buckets
+                .iter()
+                .flat_map(|abs| i16::from(*abs).to_le_bytes())
+                .collect::<Vec<u8>>();
+            w.write_all(&bytes)?;
+        }
+        Ok(())
+    }
+    /// Reads arrays written by [`Self::write`].
+    pub fn read(r: &mut impl std::io::Read) -> std::io::Result<Self> {
+        let invalid = |msg: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, msg.to_string());
+        let mut magic = [0; 8];
+        r.read_exact(&mut magic)?;
+        if &magic != Self::MAGIC {
+            return Err(invalid("not an encoder file"));
+        }
+        let mut encoder = Self::default();
+        for street in Street::all() {
+            let mut count = [0; 8];
+            r.read_exact(&mut count)?;
+            let count = u64::from_le_bytes(count) as usize;
+            if count != 0 && count != street.n_isomorphisms() {
+                return Err(invalid("count does not match street"));
+            }
+            let mut bytes = vec![0; 2 * count];
+            r.read_exact(&mut bytes)?;
+            encoder.buckets[street as usize] = bytes
+                .chunks_exact(2)
+                .map(|b| Abstraction::from(i16::from_le_bytes([b[0], b[1]])))
+                .collect();
+            encoder.filled[street as usize] = (0..count.div_ceil(64))
+                .map(|w| match count - 64 * w {
+                    n if n >= 64 => u64::MAX,
+                    n => (1 << n) - 1,
+                })
+                .collect();
+        }
+        Ok(encoder)
+    }
+    /// Writes the lookup to `path`.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let ref mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.write(file)?;
+        std::io::Write::flush(file)
+    }
+    /// Reads a lookup written by [`Self::save`], in roughly the time it
+    /// takes to read 280 MB from disk.
+    pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        Self::read(&mut std::io::BufReader::new(std::fs::File::open(path)?))
     }
     /// Creates an info set for the root game state.
     pub fn root(&self, game: &NlheGame) -> NlheInfo {
         let subgame = Path::default();
         let present = self.abstraction(&game.sweat());
-        let choices = game.as_ref().choices(0);
+        let choices = game.choices(0);
         NlheInfo::from((subgame, present, choices))
     }
 }
@@ -62,7 +205,7 @@ impl mccfr::CfrEncoder for NlheEncoder {
     {
         let subgame = past.into_iter().map(Edge::from).collect::<Path>();
         let present = self.abstraction(&game.sweat());
-        let choices = game.as_ref().choices(subgame.aggression());
+        let choices = game.choices(subgame.aggression());
         NlheInfo::from((subgame, present, choices))
     }
 }
@@ -114,6 +257,68 @@ impl NlheEncoder {
             }
         }
     }
+    /// Frontier payoff matrix averaged over `rollouts` biased rollouts
+    /// per continuation pair.
+    pub(crate) fn rollout_payoffs<P, const LEAVES: usize>(
+        &self,
+        prefix: &Prefix<NlheTurn, NlheEdge>,
+        game: &NlheGame,
+        internal: NlheTurn,
+        profile: &P,
+        rollouts: usize,
+    ) -> Payoffs<LEAVES>
+    where
+        P: RefProf<T = NlheTurn, E = NlheEdge, G = NlheGame, I = NlheInfo>,
+    {
+        Payoffs::tabulate(|k, j| {
+            (0..rollouts)
+                .map(|_| self.biased_rollout(prefix, game, internal, k, j, profile))
+                .sum::<Utility>()
+                / rollouts.max(1) as Utility
+        })
+    }
+    /// Plays `profile` from a fresh deal up to a street-boundary chance
+    /// node, the kind of state a depth-limited solve treats as frontier.
+    /// Returns the walk there, the public decisions of the street it
+    /// closes, and the state itself.
+    ///
+    /// Each boundary reached is kept with probability `stop`, otherwise
+    /// the next card is dealt and play continues. Hands that end before a
+    /// boundary is kept yield `None`.
+    pub(crate) fn frontier_state<P>(
+        &self,
+        profile: &P,
+        stop: Probability,
+    ) -> Option<(Prefix<NlheTurn, NlheEdge>, Vec<(NlheTurn, NlhePublic, NlheEdge)>, NlheGame)>
+    where
+        P: RefProf<T = NlheTurn, E = NlheEdge, G = NlheGame, I = NlheInfo>,
+    {
+        let mut game = Game::root();
+        let mut story: Story<NlheTurn, NlheEdge> = Story::default();
+        let mut line = Vec::new();
+        loop {
+            match game.turn() {
+                Turn::Terminal => return None,
+                Turn::Chance if rand::random::<Probability>() < stop => {
+                    return Some((story.into_iter().collect(), line, NlheGame::from(game)));
+                }
+                Turn::Chance => {
+                    story.push(Descent(NlheTurn::chance(), NlheEdge::from(Edge::Draw)));
+                    game = game.apply(game.reveal());
+                    line.clear();
+                }
+                Turn::Choice(i) => {
+                    let info = self.resume((&story).edges(), &NlheGame::from(game));
+                    let dist = profile.averaged_distribution(&info);
+                    let edge = Self::sample_biased(&dist, Continuation::all::<1>().next().expect("unbiased"));
+                    let action = game.actionize(Edge::from(edge));
+                    story.push(Descent(NlheTurn::from(i), edge));
+                    line.push((NlheTurn::from(i), info.public(), edge));
+                    game = game.apply(game.snap(action));
+                }
+            }
+        }
+    }
     /// Sample an edge from a biased distribution.
     ///
     /// The target action type probability is multiplied by the runtime-configured
@@ -165,8 +370,7 @@ impl<const W: usize> WorldRestrict<W> for NlheEncoder {
         let available = Hand::or(Hand::from(baseline.deck()), Hand::from(baseline.seats()[position].cards()));
         std::iter::repeat_with(|| Deck::from(available).hole())
             .take(MAX_REJECTIONS)
-            .map(|hole| baseline.deal(position, hole))
-            .map(NlheGame::from)
+            .map(|hole| observed.deal(position, hole))
             .map(|game| (game, game.sweat_at(position)))
             .map(|(game, obs)| (game, self.abstraction(&obs)))
             .map(|(game, abs)| (game, NlheSecret::from(abs)))
@@ -178,7 +382,7 @@ impl<const W: usize> WorldRestrict<W> for NlheEncoder {
                         max_rejections = MAX_REJECTIONS,
                         "rejection sampling exhausted; falling back to unconstrained hole",
                     );
-                    NlheGame::from(baseline.deal(position, Deck::from(available).hole()))
+                    observed.deal(position, Deck::from(available).hole())
                 },
                 |(game, _)| game,
             )
@@ -189,7 +393,8 @@ impl<const W: usize> WorldRestrict<W> for NlheEncoder {
 #[async_trait::async_trait]
 impl daybook::Hydrate for NlheEncoder {
     /// Streams rows from the database one at a time, avoiding the peak
-    /// memory spike of buffering all 138M rows as `Vec<Row>`.
+    /// memory spike of buffering all 138M rows as `Vec<Row>`. Each row
+    /// lands directly in its street's flat array.
     async fn hydrate(client: std::sync::Arc<tokio_postgres::Client>) -> Self {
         use futures::StreamExt;
         tracing::info!("{:<32}{:<32}", "loading isomorphism", "from database");
@@ -197,19 +402,60 @@ impl daybook::Hydrate for NlheEncoder {
         let params: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> = vec![];
         let stream = client.query_raw(&sql, params).await.expect("isomorphism query");
         futures::pin_mut!(stream);
-        let mut lookup = BTreeMap::new();
+        let mut encoder = Self::default();
         let mut count = 0usize;
         while let Some(row) = stream.next().await {
             let row = row.expect("isomorphism row");
             let obs = Isomorphism::from(row.get::<_, i64>(0));
             let abs = Abstraction::from(row.get::<_, i16>(1));
-            lookup.insert(obs, abs);
+            encoder.insert(obs, abs);
             count += 1;
             if count.is_multiple_of(10_000_000) {
                 tracing::info!("{:<32}{:<32}", format!("{count:>16} isomorphisms"), "from database");
             }
         }
         tracing::info!("{:<32}{:<32}", format!("{count:>16} isomorphisms"), "from database");
-        Self(lookup)
+        if let Some((street, n)) = encoder.missing().first() {
+            panic!("isomorphism table is missing {n} {street} rows");
+        }
+        encoder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flat_lookup_ignores_suit_labels() {
+        let bucket = Abstraction::from((Street::Flop, 3));
+        let iso = Isomorphism::from(Observation::try_from("AsKs~QhJhTd").unwrap());
+        let encoder = std::iter::once((iso, bucket)).collect::<NlheEncoder>();
+        assert_eq!(encoder.len(), 1);
+        assert_eq!(encoder.abstraction(&Observation::try_from("AhKh~QsJsTd").unwrap()), bucket);
+        assert_eq!(encoder.missing(), vec![(Street::Flop, Street::Flop.n_isomorphisms() - 1)]);
+        assert!(encoder.write(&mut Vec::new()).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "no bucket loaded")]
+    fn unloaded_isomorphism_panics() {
+        let iso = Isomorphism::from(Observation::try_from("AsKs~QhJhTd").unwrap());
+        let encoder = std::iter::once((iso, Abstraction::from((Street::Flop, 3)))).collect::<NlheEncoder>();
+        encoder.abstraction(&Observation::try_from("2c7d~QhJhTd").unwrap());
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let encoder = IsomorphismIterator::from(Street::Pref)
+            .enumerate()
+            .map(|(k, iso)| (iso, Abstraction::from((Street::Pref, k))))
+            .collect::<NlheEncoder>();
+        assert!(encoder.missing().is_empty());
+        let mut bytes = Vec::new();
+        encoder.write(&mut bytes).unwrap();
+        let loaded = NlheEncoder::read(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded.len(), Street::Pref.n_isomorphisms());
+        assert!(encoder.iter().eq(loaded.iter()));
     }
 }
@@ -0,0 +1,350 @@
+//! Card-unabstracted endgame solving.
+//!
+//! The blueprint keys every infoset on an [`NlheSecret`] bucket, so two
+//! river hands with different blockers can share one strategy. Libratus
+//! resolved river endgames with no card abstraction at all; the types
+//! here do the same for the combined [`SubGameSolver`]:
+//!
+//! - [`ExactSecret`] — the acting player's exact [`Observation`]
+//! - [`ExactInfo`] — [`NlhePublic`] paired with an [`ExactSecret`]
+//! - [`ExactProfile`] — read-through to the blueprint by projecting each
+//!   exact infoset onto its bucket, for warmstart and rollouts
+//! - [`ExactEncoder`] — encodes exact infosets, restricts the opponent to
+//!   exact-hole worlds, and delegates frontier payoffs to the blueprint
+//!
+//! The opponent belief comes from the same reach computation as
+//! [`Nlhe::opponent_observations`], partitioned before any projection.
+//! Which streets resolve this way is [`pokerkit::endgame`].
+use super::*;
+use deuce::*;
+use kicker::*;
+use mccfr::*;
+use monge::Support;
+use pokerkit::*;
+use subgame::*;
+
+type ExactTree = Tree<NlheTurn, NlheEdge, NlheGame, ExactInfo>;
+
+/// Combined safe + depth-limited solver over exact infosets. On the river
+/// no chance node remains, so it solves to terminal showdowns.
+/// Sized like any other solve; see [`Shape`].
+pub type ExactSolver<'a, const WORLDS: usize = N_WORLDS, const LEAVES: usize = FRONTIER_LEAVES> =
+    SubGameSolver<'a, WORLDS, LEAVES, ExactEncoder<'a>, ExactInfo, ExactSecret>;
+
+/// NLHE private information without abstraction: hole cards and board.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExactSecret(Observation);
+
+impl Support for ExactSecret {}
+impl CfrSecret for ExactSecret {
+    fn round(&self) -> usize {
+        self.0.street() as usize
+    }
+}
+
+impl From<Observation> for ExactSecret {
+    fn from(obs: Observation) -> Self {
+        Self(obs)
+    }
+}
+impl From<ExactSecret> for Observation {
+    fn from(secret: ExactSecret) -> Self {
+        secret.0
+    }
+}
+
+impl std::fmt::Display for ExactSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Exact-card counterpart of [`NlheInfo`]: same public state, with the
+/// observation itself in place of its bucket.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ExactInfo(Composite<NlhePublic, ExactSecret>);
+
+impl ExactInfo {
+    pub fn new(public: NlhePublic, secret: ExactSecret) -> Self {
+        Self(Composite::new(public, secret))
+    }
+    /// The exact hole cards and board.
+    pub fn observation(&self) -> Observation {
+        Observation::from(self.0.secret())
+    }
+    /// The blueprint infoset this one refines.
+    pub fn project(&self, encoder: &NlheEncoder) -> NlheInfo {
+        let bucket = encoder.abstraction(&self.observation());
+        NlheInfo::new(self.0.public(), NlheSecret::from(bucket))
+    }
+}
+
+/// The exact infoset behind a blueprint decision `info` for a player
+/// who sees `seen`.
+impl From<(NlheInfo, Observation)> for ExactInfo {
+    fn from((info, seen): (NlheInfo, Observation)) -> Self {
+        Self::new(info.public(), ExactSecret::from(seen))
+    }
+}
+
+impl CfrInfo for ExactInfo {
+    type X = NlhePublic;
+    type Y = ExactSecret;
+    type E = NlheEdge;
+    type T = NlheTurn;
+
+    fn public(&self) -> Self::X {
+        self.0.public()
+    }
+
+    fn secret(&self) -> Self::Y {
+        self.0.secret()
+    }
+}
+
+impl std::fmt::Display for ExactInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}>>{}", self.observation().street(), self.0.public().subgame(), self.0.secret())
+    }
+}
+
+/// Read-only blueprint view keyed by [`ExactInfo`].
+///
+/// Every lookup projects the exact infoset onto its abstraction bucket,
+/// so the subgame warmstarts each exact hand from its bucket's blueprint
+/// strategy and then lets them diverge.
+#[derive(Clone, Copy)]
+pub struct ExactProfile<'a> {
+    profile: &'a NlheProfile,
+    encoder: &'a NlheEncoder,
+}
+
+impl<'a> ExactProfile<'a> {
+    pub fn new(profile: &'a NlheProfile, encoder: &'a NlheEncoder) -> Self {
+        Self { profile, encoder }
+    }
+    fn project(&self, info: &ExactInfo) -> NlheInfo {
+        info.project(self.encoder)
+    }
+}
+
+impl CfrRule for ExactProfile<'_> {
+    type T = NlheTurn;
+    type E = NlheEdge;
+    type G = NlheGame;
+    type I = ExactInfo;
+}
+
+impl RefProf for ExactProfile<'_> {
+    fn t(&self) -> usize {
+        self.profile.t()
+    }
+
+    fn sum_regret(&self) -> Utility {
+        self.profile.sum_regret()
+    }
+
+    fn cum_weight(&self, info: &Self::I, edge: &Self::E) -> Probability {
+        self.profile.cum_weight(&self.project(info), edge)
+    }
+
+    fn cum_regret(&self, info: &Self::I, edge: &Self::E) -> Utility {
+        self.profile.cum_regret(&self.project(info), edge)
+    }
+
+    fn cum_payoff(&self, info: &Self::I, edge: &Self::E) -> Utility {
+        self.profile.cum_payoff(&self.project(info), edge)
+    }
+
+    fn cum_visits(&self, info: &Self::I, edge: &Self::E) -> u32 {
+        self.profile.cum_visits(&self.project(info), edge)
+    }
+
+    fn warmstart(&self, info: &Self::I, edge: &Self::E) -> Encounter {
+        self.profile.warmstart(&self.project(info), edge)
+    }
+}
+
+impl CfrSampling for ExactProfile<'_> {
+    fn increment(&mut self) {}
+    fn walker(&self) -> Self::T {
+        CfrSampling::walker(self.profile)
+    }
+
+    fn temperature(&self) -> Entropy {
+        CfrSampling::temperature(self.profile)
+    }
+
+    fn smoothing(&self) -> Energy {
+        CfrSampling::smoothing(self.profile)
+    }
+
+    fn curiosity(&self) -> Probability {
+        CfrSampling::curiosity(self.profile)
+    }
+}
+
+/// Encoder for exact resolves, borrowing the blueprint's lookup table
+/// and profile. Built by [`Nlhe::exact`].
+#[derive(Clone, Copy)]
+pub struct ExactEncoder<'a> {
+    encoder: &'a NlheEncoder,
+    profile: ExactProfile<'a>,
+}
+
+impl<'a> ExactEncoder<'a> {
+    pub fn new(encoder: &'a NlheEncoder, profile: &'a NlheProfile) -> Self {
+        Self {
+            encoder,
+            profile: ExactProfile::new(profile, encoder),
+        }
+    }
+    /// This resolve's frontier valued by `model`, for a solve from `recall`.
+    pub fn learned(&'a self, model: &'a LeafModel, recall: &Witness) -> LeafSampler<'a, Self> {
+        LeafSampler::new(self, self.encoder, self.profile.profile, model, recall)
+    }
+}
+
+impl CfrEncoder for ExactEncoder<'_> {
+    type T = NlheTurn;
+    type E = NlheEdge;
+    type G = NlheGame;
+    type I = ExactInfo;
+
+    fn seed(&self, root: &Self::G) -> Self::I {
+        self.resume(std::iter::empty(), root)
+    }
+
+    fn info(&self, tree: &ExactTree, (edge, ref game, head): Leaf<Self::E, Self::G>) -> Self::I {
+        let subgame = std::iter::once(edge)
+            .chain(tree.at(head).map(mccfr::Jump::edge))
+            .take_while(NlheEdge::is_choice)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .map(Edge::from)
+            .collect::<Path>();
+        let choices = game.choices(subgame.aggression());
+        ExactInfo::new(NlhePublic::new(subgame, choices), ExactSecret::from(game.sweat()))
+    }
+
+    fn resume<P>(&self, past: P, game: &Self::G) -> Self::I
+    where
+        P: IntoIterator<Item = Self::E>,
+    {
+        let history = past.into_iter().map(Edge::from).collect::<Path>();
+        let choices = game.choices(history.aggression());
+        ExactInfo::new(NlhePublic::trailing(history, choices), ExactSecret::from(game.sweat()))
+    }
+}
+
+impl<const W: usize> WorldRestrict<W> for ExactEncoder<'_> {
+    /// Sample an opponent hole whose exact observation the belief places
+    /// in `world`, falling back to an unconstrained hole when rejection
+    /// sampling exhausts, as [`NlheEncoder`]'s restriction does.
+    fn restrict(
+        &self,
+        external: Self::T,
+        world: World,
+        belief: &Belief<Secret<Self>, W>,
+        observed: &Self::G,
+    ) -> Self::G {
+        const MAX_REJECTIONS: usize = 10_000;
+        let position = Turn::from(external).position();
+        let baseline = Game::from(*observed);
+        let available = Hand::or(Hand::from(baseline.deck()), Hand::from(baseline.seats()[position].cards()));
+        std::iter::repeat_with(|| Deck::from(available).hole())
+            .take(MAX_REJECTIONS)
+            .map(|hole| observed.deal(position, hole))
+            .find(|game| belief.remember(&ExactSecret::from(game.sweat_at(position)), world))
+            .unwrap_or_else(|| {
+                tracing::warn!(
+                    world = world.index(),
+                    max_rejections = MAX_REJECTIONS,
+                    "rejection sampling exhausted; falling back to unconstrained hole",
+                );
+                observed.deal(position, Deck::from(available).hole())
+            })
+    }
+}
+
+impl<'a, const LEAVES: usize> DepthSampler<LEAVES> for ExactEncoder<'a> {
+    type Blueprint = ExactProfile<'a>;
+
+    fn blueprint(&self) -> &Self::Blueprint {
+        &self.profile
+    }
+
+    /// Frontier values come from the abstracted blueprint: past the
+    /// resolved street the blueprint has no exact strategy to follow.
+    fn payoffs(
+        &self,
+        prefix: &Prefix<NlheTurn, NlheEdge>,
+        _: &[(NlheTurn, ExactInfo, NlheEdge)],
+        game: &NlheGame,
+        internal: NlheTurn,
+    ) -> Payoffs<LEAVES> {
+        let rollouts = FrontierHyperParams::get().rollouts();
+        self.encoder
+            .rollout_payoffs(prefix, game, internal, self.profile.profile, rollouts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    const BOARD: &str = "Ks7s2s5d9h";
+
+    fn obs(hole: &str) -> Observation {
+        Observation::try_from(format!("{hole}~{BOARD}").as_str()).unwrap()
+    }
+
+    fn river() -> NlheGame {
+        let hole = |s: &str| Hole::from(Hand::try_from(s).unwrap());
+        NlheGame::from(Game::spot(Hand::try_from(BOARD).unwrap(), 60, [170, 170]))
+            .deal(0, hole("QcQh"))
+            .deal(1, hole("JcJh"))
+    }
+
+    #[test]
+    fn blockers_split_a_shared_bucket() {
+        let bucket = Abstraction::from((Street::Rive, 7));
+        let encoder = [obs("AsQd"), obs("AcQd")]
+            .into_iter()
+            .map(|o| (Isomorphism::from(o), bucket))
+            .collect::<NlheEncoder>();
+        let public = NlhePublic::new(Path::default(), Path::default());
+        let blocker = ExactInfo::new(public, ExactSecret::from(obs("AsQd")));
+        let clean = ExactInfo::new(public, ExactSecret::from(obs("AcQd")));
+        assert_ne!(blocker, clean);
+        assert_eq!(blocker.project(&encoder), clean.project(&encoder));
+    }
+
+    #[test]
+    fn resume_keys_on_the_actors_cards() {
+        let encoder = NlheEncoder::default();
+        let profile = NlheProfile::default();
+        let exact = ExactEncoder::new(&encoder, &profile);
+        let game = river();
+        let info = exact.resume(std::iter::empty(), &game);
+        assert_eq!(info.observation(), game.sweat());
+        assert_eq!(info.public().subgame(), Path::default());
+    }
+
+    #[test]
+    fn restriction_deals_from_the_world() {
+        let encoder = NlheEncoder::default();
+        let profile = NlheProfile::default();
+        let exact = ExactEncoder::new(&encoder, &profile);
+        let members = ["AhAd", "AhAc", "AdAc"]
+            .into_iter()
+            .map(|h| (ExactSecret::from(obs(h)), World::from(0)))
+            .collect::<BTreeMap<_, _>>();
+        let belief = Belief::<ExactSecret, 2>::new(members, [0.5, 0.5]);
+        let game = exact.restrict(NlheTurn::from(1_usize), World::from(0), &belief, &river());
+        assert!(belief.remember(&ExactSecret::from(game.sweat_at(1)), World::from(0)));
+        assert_eq!(game.sweat_at(0), river().sweat_at(0));
+    }
+}
@@ -3,15 +3,48 @@ use super::*;
 use deuce::*;
 use kicker::*;
 use mccfr::*;
+use pokerkit::Chips;
 use pokerkit::Utility;
 
 /// NLHE game state for CFR traversal.
 ///
-/// Newtype wrapper around gameplay `Game` for NLHE-specific CFR.
+/// Newtype wrapper around gameplay `Game` for NLHE-specific CFR,
+/// optionally carrying an [`Injection`] at the root of a nested subgame.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct NlheGame(Game);
+pub struct NlheGame(Game, Option<Injection>);
+
+/// An off-tree opponent raise grafted onto the tree as a spare edge.
+///
+/// Nested subgame solving roots its tree at the state the raise was
+/// played from. There the spare `edge` joins the grid choices, and
+/// applying it plays the observed `chips` verbatim instead of the
+/// edge's own pot fraction. The injection lives on that one state
+/// only; every child is a plain game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Injection {
+    edge: Edge,
+    chips: Chips,
+}
 
 impl NlheGame {
+    /// Roots a nested subgame at `game`, where `edge` stands for a
+    /// raise of `chips`.
+    pub fn inject(game: Game, edge: Edge, chips: Chips) -> Self {
+        Self(game, Some(Injection { edge, chips }))
+    }
+    /// Available edges at this state: the abstract grid plus the
+    /// injected edge, if any.
+    pub fn choices(&self, depth: usize) -> Path {
+        self.0
+            .choices(depth)
+            .into_iter()
+            .chain(self.1.map(|injection| injection.edge))
+            .collect()
+    }
+    /// Replaces `position`'s hole cards, keeping any injection.
+    pub fn deal(&self, position: usize, hole: Hole) -> Self {
+        Self(self.0.deal(position, hole), self.1)
+    }
     /// Current betting round (street).
     pub fn street(&self) -> Street {
         self.0.street()
@@ -31,7 +64,7 @@ impl CfrGame for NlheGame {
     type T = NlheTurn;
 
     fn root() -> Self {
-        Self(Game::root())
+        Self::from(Game::root())
     }
 
     fn turn(&self) -> Self::T {
@@ -47,26 +80,31 @@ impl CfrGame for NlheGame {
     /// - Choice edge at Chance: auto-deal through chance nodes first
     /// - Draw edge at Choice: skip (canonical hasn't reached that street)
     /// - Any edge at Terminal: return terminal (canonical ended early)
+    ///
+    /// The injected edge of a nested root plays its observed chips.
     fn apply(&self, edge: Self::E) -> Self {
         let edge = Edge::from(edge);
         let mut game = self.0;
+        if let Some(injection) = self.1.filter(|injection| injection.edge == edge) {
+            return Self::from(game.apply(Action::Raise(injection.chips)));
+        }
         if game.turn() == Turn::Terminal {
-            return Self(game);
+            return Self::from(game);
         }
         if edge.is_choice() {
             while game.turn() == Turn::Chance {
                 game = game.force_apply(game.reveal());
             }
             if game.turn() == Turn::Terminal {
-                return Self(game);
+                return Self::from(game);
             }
         }
         if edge.is_chance() && game.turn() != Turn::Chance {
-            return Self(game);
+            return Self::from(game);
         }
         let action = game.actionize(edge);
         let action = game.snap(action);
-        Self(game.apply(action))
+        Self::from(game.apply(action))
     }
 
     fn depth(&self) -> usize {
@@ -84,7 +122,7 @@ impl CfrGame for NlheGame {
 
 impl From<Game> for NlheGame {
     fn from(game: Game) -> Self {
-        Self(game)
+        Self(game, None)
     }
 }
 impl From<NlheGame> for Game {
@@ -97,3 +135,20 @@ impl AsRef<Game> for NlheGame {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn injected_edge_plays_observed_chips() {
+        let edge = Edge::Raise(Odds::new(5, 4));
+        let game = Game::root();
+        let root = NlheGame::inject(game, edge, 7);
+        assert!(root.choices(0).into_iter().any(|e| e == edge));
+        assert!(NlheGame::from(game).choices(0).into_iter().all(|e| e != edge));
+        let child = root.apply(NlheEdge::from(edge));
+        assert_eq!(child, NlheGame::from(game.apply(Action::Raise(7))));
+        assert_eq!(child.choices(1), Game::from(child).choices(1));
+    }
+}
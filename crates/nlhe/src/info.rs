@@ -42,7 +42,7 @@ type NlheLeaf = Leaf<NlheEdge, NlheGame>;
 pub struct NlheInfo(Composite<NlhePublic, NlheSecret>);
 
 impl NlheInfo {
-    fn new(public: NlhePublic, secret: NlheSecret) -> Self {
+    pub(crate) fn new(public: NlhePublic, secret: NlheSecret) -> Self {
         Self(Composite::new(public, secret))
     }
     /// The current street (from secret's embedded street).
@@ -124,15 +124,7 @@ where
 
 impl From<(Path, Abstraction, Path)> for NlheInfo {
     fn from((subgame, secret, choices): (Path, Abstraction, Path)) -> Self {
-        let subgame = subgame
-            .into_iter()
-            .rev()
-            .take_while(kicker::Edge::is_choice)
-            .collect::<Vec<_>>()
-            .into_iter()
-            .rev()
-            .collect::<Path>();
-        let public = NlhePublic::new(subgame, choices);
+        let public = NlhePublic::trailing(subgame, choices);
         let secret = NlheSecret::from(secret);
         Self::new(public, secret)
     }
@@ -152,7 +144,7 @@ impl From<(&NlheEncoder, &NlheTree, NlheLeaf)> for NlheInfo {
             .rev()
             .map(Edge::from)
             .collect::<Path>();
-        let choices = game.as_ref().choices(subgame.aggression());
+        let choices = game.choices(subgame.aggression());
         let secret = NlheSecret::from(encoder.abstraction(&game.sweat()));
         let public = NlhePublic::new(subgame, choices);
         Self::new(public, secret)
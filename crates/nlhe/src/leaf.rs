@@ -0,0 +1,437 @@
+//! Learned frontier values for depth-limited NLHE solves.
+//!
+//! Biased rollouts are the default frontier evaluator (see
+//! [`Nlhe::rollout_payoffs`]); they are noisy and take most of a
+//! decision's time budget. A [`LeafModel`] replaces them with one forward
+//! pass of a small [`ValueNetwork`] trained offline on rollout targets
+//! from blueprint self-play. A [`LeafSampler`] plugs the model into a
+//! solve as an alternative [`DepthSampler::payoffs`]; [`Nlhe::shaped`]
+//! builds one under [`Frontier::Learned`] from the model loaded via
+//! [`init_leaf_model`].
+use super::*;
+use deuce::*;
+use kicker::*;
+use mccfr::*;
+use pokerkit::*;
+use subgame::*;
+
+/// Hidden layer widths of a fresh model.
+const HIDDEN: [usize; 2] = [128, 64];
+
+/// Value network over NLHE frontier features.
+///
+/// Features are the street, pot and stacks relative to the starting
+/// stack, whether the internal player holds the button, and a belief over
+/// each seat's abstraction bucket. The internal player's belief is the
+/// point mass on their own bucket. The opponent's is their range: every
+/// hole the internal player cannot rule out, weighted by how likely the
+/// blueprint was to take the opponent's decisions on the closing street
+/// holding it.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LeafModel(ValueNetwork<FRONTIER_LEAVES>);
+
+impl LeafModel {
+    /// Untrained model sized for [`Self::width`] features.
+    pub fn new(seed: u64) -> Self {
+        Self(ValueNetwork::new(Self::width(), &HIDDEN, seed))
+    }
+
+    /// Bucket slots per seat: the widest street's abstraction count.
+    fn buckets() -> usize {
+        Street::all().iter().map(Street::n_abstractions).max().unwrap_or(0)
+    }
+
+    /// Feature count: street one-hot, four scalars, two bucket beliefs.
+    pub fn width() -> usize {
+        Street::all().len() + 4 + 2 * Self::buckets()
+    }
+
+    /// Encodes a frontier state from `internal`'s point of view. `line`
+    /// holds the public decisions of the street the frontier closes,
+    /// first to last; they shape the opponent's range.
+    pub fn features<P>(
+        encoder: &NlheEncoder,
+        profile: &P,
+        line: &[(NlheTurn, NlhePublic, NlheEdge)],
+        game: &NlheGame,
+        internal: NlheTurn,
+    ) -> Vec<f32>
+    where
+        P: RefProf<T = NlheTurn, E = NlheEdge, G = NlheGame, I = NlheInfo>,
+    {
+        let hero = Turn::from(internal).position();
+        let villain = (hero + 1) % N;
+        let state = Game::from(*game);
+        let stack = STACK as f32;
+        let mut features = vec![0.0; Self::width()];
+        features[game.street() as usize] = 1.0;
+        let scalars = Street::all().len();
+        features[scalars] = state.pot() as f32 / (N as f32 * stack);
+        features[scalars + 1] = state.stacks()[hero] as f32 / stack;
+        features[scalars + 2] = state.stacks()[villain] as f32 / stack;
+        features[scalars + 3] = if state.dealer().position() == hero { 1.0 } else { 0.0 };
+        let beliefs = scalars + 4;
+        let bucket = encoder.abstraction(&game.sweat_at(hero)).index();
+        features[beliefs + bucket.min(Self::buckets() - 1)] = 1.0;
+        let range = Self::range(encoder, profile, line, game, internal);
+        features[beliefs + Self::buckets()..].copy_from_slice(&range);
+        features
+    }
+
+    /// Opponent's bucket distribution given the internal player's cards
+    /// and the opponent's decisions on the closing street. Falls back to
+    /// the card-removal prior when the blueprint never takes that line.
+    fn range<P>(
+        encoder: &NlheEncoder,
+        profile: &P,
+        line: &[(NlheTurn, NlhePublic, NlheEdge)],
+        game: &NlheGame,
+        internal: NlheTurn,
+    ) -> Vec<f32>
+    where
+        P: RefProf<T = NlheTurn, E = NlheEdge, G = NlheGame, I = NlheInfo>,
+    {
+        let decisions = line
+            .iter()
+            .filter(|(turn, _, _)| *turn != internal)
+            .map(|(_, public, edge)| (*public, *edge))
+            .collect::<Vec<_>>();
+        let mut likelihoods = std::collections::HashMap::<Abstraction, Probability>::new();
+        let mut prior = vec![0.0; Self::buckets()];
+        let mut range = vec![0.0; Self::buckets()];
+        let hero = game.sweat_at(Turn::from(internal).position());
+        for hole in hero.opponents() {
+            let bucket = encoder.abstraction(&hole);
+            let likelihood = *likelihoods.entry(bucket).or_insert_with(|| {
+                decisions
+                    .iter()
+                    .map(|(public, edge)| {
+                        profile.averaged_policy(&NlheInfo::new(*public, NlheSecret::from(bucket)), edge)
+                    })
+                    .product()
+            });
+            let slot = bucket.index().min(Self::buckets() - 1);
+            prior[slot] += 1.0;
+            range[slot] += likelihood;
+        }
+        let range = if range.iter().sum::<f32>() > 0.0 { range } else { prior };
+        let total = range.iter().sum::<f32>();
+        range.into_iter().map(|mass| mass / total).collect()
+    }
+
+    /// Predicted frontier payoffs for `internal`; see [`Self::features`].
+    pub fn payoffs<P>(
+        &self,
+        encoder: &NlheEncoder,
+        profile: &P,
+        line: &[(NlheTurn, NlhePublic, NlheEdge)],
+        game: &NlheGame,
+        internal: NlheTurn,
+    ) -> Payoffs<FRONTIER_LEAVES>
+    where
+        P: RefProf<T = NlheTurn, E = NlheEdge, G = NlheGame, I = NlheInfo>,
+    {
+        self.0.payoffs(&Self::features(encoder, profile, line, game, internal))
+    }
+
+    /// Trains on `(features, rollout payoffs)` pairs; see [`ValueNetwork::fit`].
+    pub fn fit(&mut self, samples: &[(Vec<f32>, Payoffs<FRONTIER_LEAVES>)], epochs: usize, rate: f32) -> Vec<Utility> {
+        self.0.fit(samples, epochs, rate)
+    }
+
+    /// Mean squared error per payoff entry against `samples`.
+    pub fn loss(&self, samples: &[(Vec<f32>, Payoffs<FRONTIER_LEAVES>)]) -> Utility {
+        self.0.loss(samples)
+    }
+
+    /// Writes the model as JSON to `path`.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        serde_json::to_writer(file, self).map_err(std::io::Error::other)
+    }
+
+    /// Reads a model written by [`Self::save`].
+    pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let file = std::io::BufReader::new(std::fs::File::open(path)?);
+        let model = serde_json::from_reader::<_, Self>(file).map_err(std::io::Error::other)?;
+        if model.0.inputs() == Self::width() {
+            Ok(model)
+        } else {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("model expects {} features, abstraction has {}", model.0.inputs(), Self::width()),
+            ))
+        }
+    }
+}
+
+impl<R, W, S> Nlhe<R, W, S>
+where
+    R: RegretSchedule,
+    W: WeightSchedule,
+    S: SamplingScheme,
+{
+    /// Frontier states reached by the averaged blueprint in self-play,
+    /// each paired with the walk that led there and an internal seat
+    /// alternating between the two.
+    pub fn frontier_states(&self, count: usize) -> Vec<FrontierState> {
+        std::iter::repeat_with(|| self.encoder.frontier_state(&self.profile, 0.5))
+            .flatten()
+            .take(count)
+            .enumerate()
+            .map(|(i, (prefix, line, game))| FrontierState {
+                prefix,
+                line,
+                game,
+                internal: NlheTurn::from(i % N),
+            })
+            .collect()
+    }
+
+    /// Frontier payoffs averaged over `rollouts` biased rollouts per
+    /// continuation pair.
+    pub fn rollout_payoffs(
+        &self,
+        prefix: &Prefix<NlheTurn, NlheEdge>,
+        game: &NlheGame,
+        internal: NlheTurn,
+        rollouts: usize,
+    ) -> Payoffs<FRONTIER_LEAVES> {
+        self.encoder
+            .rollout_payoffs(prefix, game, internal, &self.profile, rollouts)
+    }
+
+    /// Frontier payoffs predicted by `model` under this blueprint.
+    pub fn leaf_payoffs(&self, model: &LeafModel, state: &FrontierState) -> Payoffs<FRONTIER_LEAVES> {
+        model.payoffs(&self.encoder, &self.profile, &state.line, &state.game, state.internal)
+    }
+
+    /// Training pairs from blueprint self-play: features of each frontier
+    /// state labeled with its `rollouts`-deep rollout payoffs.
+    pub fn leaf_samples(&self, count: usize, rollouts: usize) -> Vec<(Vec<f32>, Payoffs<FRONTIER_LEAVES>)> {
+        self.frontier_states(count)
+            .into_iter()
+            .map(|state| {
+                let features =
+                    LeafModel::features(&self.encoder, &self.profile, &state.line, &state.game, state.internal);
+                let payoffs = self.rollout_payoffs(&state.prefix, &state.game, state.internal, rollouts);
+                (features, payoffs)
+            })
+            .collect()
+    }
+}
+
+/// A frontier reached in blueprint self-play; see [`Nlhe::frontier_states`].
+#[derive(Debug, Clone)]
+pub struct FrontierState {
+    /// Walk from the root to the frontier.
+    pub prefix: Prefix<NlheTurn, NlheEdge>,
+    /// Public decisions on the street the frontier closes, first to last.
+    pub line: Vec<(NlheTurn, NlhePublic, NlheEdge)>,
+    /// The frontier state itself.
+    pub game: NlheGame,
+    /// Seat whose payoffs are evaluated.
+    pub internal: NlheTurn,
+}
+
+/// Frontier sampler valued by a [`LeafModel`] instead of rollouts.
+///
+/// Wraps the rollout sampler `N` of a solve — [`Nlhe`] or
+/// [`ExactEncoder`] — and defers to it for everything but
+/// [`DepthSampler::payoffs`]. The model reads the opponent's range off
+/// the whole closing street, so the sampler keeps the decisions taken on
+/// it before the solve root and prepends them to each in-solve line.
+pub struct LeafSampler<'a, N> {
+    inner: &'a N,
+    encoder: &'a NlheEncoder,
+    profile: &'a NlheProfile,
+    model: &'a LeafModel,
+    opening: Vec<(NlheTurn, NlhePublic, NlheEdge)>,
+}
+
+impl<'a, N> LeafSampler<'a, N> {
+    /// Wraps `inner` for a solve rooted at `recall`'s head.
+    pub(crate) fn new(
+        inner: &'a N,
+        encoder: &'a NlheEncoder,
+        profile: &'a NlheProfile,
+        model: &'a LeafModel,
+        recall: &Witness,
+    ) -> Self {
+        Self {
+            inner,
+            encoder,
+            profile,
+            model,
+            opening: Self::opening(recall),
+        }
+    }
+
+    /// Current-street decisions in `recall`, each with the public state
+    /// it was taken from, as [`NlheInfo::from`] keys it.
+    fn opening(recall: &Witness) -> Vec<(NlheTurn, NlhePublic, NlheEdge)> {
+        let decisions = recall
+            .states()
+            .into_iter()
+            .zip(recall.history().iter().copied())
+            .collect::<Vec<_>>();
+        let start = decisions
+            .iter()
+            .rposition(|(_, edge)| !edge.is_choice())
+            .map_or(0, |i| i + 1);
+        let mut subgame = Vec::new();
+        decisions[start..]
+            .iter()
+            .map(|(state, edge)| {
+                let path = subgame.iter().copied().collect::<Path>();
+                let choices = NlheGame::from(*state).choices(path.aggression());
+                subgame.push(*edge);
+                (NlheTurn::from(state.turn()), NlhePublic::new(path, choices), NlheEdge::from(*edge))
+            })
+            .collect()
+    }
+}
+
+impl<N> CfrEncoder for LeafSampler<'_, N>
+where
+    N: CfrEncoder,
+{
+    type T = N::T;
+    type E = N::E;
+    type G = N::G;
+    type I = N::I;
+    const CHECK_RECALL: bool = N::CHECK_RECALL;
+
+    fn seed(&self, game: &Self::G) -> Self::I {
+        self.inner.seed(game)
+    }
+
+    fn info(&self, tree: &Tree<Self::T, Self::E, Self::G, Self::I>, leaf: Leaf<Self::E, Self::G>) -> Self::I {
+        self.inner.info(tree, leaf)
+    }
+
+    fn resume<P>(&self, past: P, game: &Self::G) -> Self::I
+    where
+        P: IntoIterator<Item = Self::E>,
+    {
+        self.inner.resume(past, game)
+    }
+
+    fn branches(&self, node: &Node<Self::T, Self::E, Self::G, Self::I>) -> Vec<Leaf<Self::E, Self::G>> {
+        self.inner.branches(node)
+    }
+}
+
+impl<N, const W: usize> WorldRestrict<W> for LeafSampler<'_, N>
+where
+    N: WorldRestrict<W>,
+{
+    fn restrict(
+        &self,
+        external: Self::T,
+        world: World,
+        belief: &Belief<Secret<Self>, W>,
+        observed: &Self::G,
+    ) -> Self::G {
+        self.inner.restrict(external, world, belief, observed)
+    }
+}
+
+impl<N, const LEAVES: usize> DepthSampler<LEAVES> for LeafSampler<'_, N>
+where
+    N: DepthSampler<LEAVES, T = NlheTurn, E = NlheEdge, G = NlheGame>,
+    N::I: CfrInfo<X = NlhePublic>,
+{
+    type Blueprint = N::Blueprint;
+
+    fn blueprint(&self) -> &Self::Blueprint {
+        self.inner.blueprint()
+    }
+
+    /// One pass of the model. Continuations are nested — a solve with
+    /// fewer leaves uses the first of the [`FRONTIER_LEAVES`] the model
+    /// was trained on — so those read the leading block of its matrix.
+    /// Wider solves roll out instead, with a warning the first time.
+    fn payoffs(
+        &self,
+        prefix: &Prefix<NlheTurn, NlheEdge>,
+        line: &[(NlheTurn, N::I, NlheEdge)],
+        game: &NlheGame,
+        internal: NlheTurn,
+    ) -> Payoffs<LEAVES> {
+        static WIDER: std::sync::Once = std::sync::Once::new();
+        if LEAVES > FRONTIER_LEAVES {
+            WIDER.call_once(|| {
+                tracing::warn!(
+                    leaves = LEAVES,
+                    trained = FRONTIER_LEAVES,
+                    "leaf model is narrower than this solve; rolling out instead",
+                );
+            });
+            return self.inner.payoffs(prefix, line, game, internal);
+        }
+        let line = self
+            .opening
+            .iter()
+            .copied()
+            .chain(line.iter().map(|(turn, info, edge)| (*turn, info.public(), *edge)))
+            .collect::<Vec<_>>();
+        let learned = self.model.payoffs(self.encoder, self.profile, &line, game, internal);
+        Payoffs::tabulate(|k, j| learned.get(k, j))
+    }
+}
+
+impl<R, W, S> Nlhe<R, W, S>
+where
+    R: RegretSchedule,
+    W: WeightSchedule,
+    S: SamplingScheme,
+{
+    /// This blueprint's frontier valued by `model`, for a solve from `recall`.
+    pub fn learned<'a>(&'a self, model: &'a LeafModel, recall: &Witness) -> LeafSampler<'a, Self> {
+        LeafSampler::new(self, &self.encoder, &self.profile, model, recall)
+    }
+}
+
+static LEAF_MODEL: std::sync::OnceLock<LeafModel> = std::sync::OnceLock::<LeafModel>::new();
+
+/// Returns the loaded leaf model. Panics if [`init_leaf_model`] was never
+/// called, which only happens when [`Frontier::Learned`] is selected
+/// without a model.
+pub fn leaf_model() -> &'static LeafModel {
+    LEAF_MODEL
+        .get()
+        .expect("frontier is learned but no leaf model was loaded")
+}
+
+/// Installs the leaf model used by [`Frontier::Learned`]. Later calls
+/// are ignored.
+pub fn init_leaf_model(model: LeafModel) {
+    let _ = LEAF_MODEL.set(model);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hero in the big blind with QcQh, limped to the flop.
+    fn flop() -> Witness {
+        let cards = ["Qc", "Qh", "Ks", "7s", "2s"]
+            .into_iter()
+            .map(|c| Card::try_from(c).unwrap())
+            .collect::<Vec<_>>();
+        Witness::from((Turn::Choice(1), Arrangement::from(cards)))
+            .push(Action::Call(1))
+            .push(Action::Check)
+    }
+
+    #[test]
+    fn opening_keys_decisions_like_their_recall() {
+        let flop = flop();
+        assert!(LeafSampler::<()>::opening(&flop).is_empty(), "preflop leaked past the deal");
+        let public = NlheInfo::from((&flop, Abstraction::from(Street::Flop))).public();
+        let checked = flop.push(Action::Check);
+        let opening = LeafSampler::<()>::opening(&checked);
+        assert_eq!(opening, vec![(NlheTurn::from(flop.turn()), public, NlheEdge::from(Edge::Check))]);
+    }
+}
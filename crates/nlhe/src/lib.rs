@@ -20,9 +20,12 @@
 //! - [`NlhePublic`] — Public state: street + current-street edges
 //! - `Info` — Information set: public + private state
 //! - [`NlheEncoder`] — Maps game states to `Info` using clustering
+//! - [`ExactEncoder`] — Card-unabstracted infosets for endgame resolves
+//! - [`LeafModel`] — Learned frontier values, an alternative to rollouts
 //! - [`NlheProfile`] — Stores accumulated regrets and strategies
 //! - [`Nlhe`] — Generic solver combining encoder and profile
 //! - [`Flagship`] — Pluribus-configured solver (top-level alias)
+//! - [`Charts`] — Preflop strategy charts exported from a blueprint
 //!
 //! # Abstraction
 //!
@@ -37,11 +40,15 @@
 //! raise sizes (see `Info::raises`). This keeps the action space tractable
 //! while preserving strategically important bet sizes.
 
+mod archive;
+mod chart;
 mod edge;
 mod encoder;
+mod exact;
 mod flagship;
 mod game;
 mod info;
+mod leaf;
 #[cfg(feature = "server")]
 mod lookup;
 mod memory;
@@ -50,6 +57,7 @@ mod profile;
 mod public;
 mod record;
 mod secret;
+mod shaped;
 #[cfg(feature = "server")]
 mod sink;
 mod solver;
@@ -58,17 +66,21 @@ mod source;
 mod strategy;
 mod turn;
 
+pub use chart::*;
 pub use edge::*;
 pub use encoder::*;
+pub use exact::*;
 pub use flagship::*;
 pub use game::*;
 pub use info::*;
+pub use leaf::*;
 #[cfg(feature = "server")]
 pub use lookup::*;
 pub use memory::*;
 pub use public::*;
 pub use record::*;
 pub use secret::*;
+pub use shaped::*;
 #[cfg(feature = "server")]
 pub use sink::*;
 pub use solver::*;
@@ -27,6 +27,19 @@ impl NlhePublic {
     pub fn new(subgame: Path, choices: Path) -> Self {
         Self { subgame, choices }
     }
+    /// Public state after `history`, keeping only its trailing
+    /// current-street choice edges.
+    pub fn trailing(history: Path, choices: Path) -> Self {
+        let subgame = history
+            .into_iter()
+            .rev()
+            .take_while(Edge::is_choice)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect::<Path>();
+        Self::new(subgame, choices)
+    }
     /// Current-street historical edges as a Path.
     pub fn subgame(&self) -> Path {
         self.subgame
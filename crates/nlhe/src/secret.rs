@@ -18,7 +18,11 @@ impl NlheSecret {
 }
 
 impl Support for NlheSecret {}
-impl CfrSecret for NlheSecret {}
+impl CfrSecret for NlheSecret {
+    fn round(&self) -> usize {
+        self.street() as usize
+    }
+}
 
 impl From<Abstraction> for NlheSecret {
     fn from(abs: Abstraction) -> Self {
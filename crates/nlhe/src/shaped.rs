@@ -0,0 +1,291 @@
+//! Runtime [`Shape`] dispatch onto monomorphized subgame solvers.
+//!
+//! Solver types carry their world and leaf counts as const generics, so a
+//! solve sized at runtime can't be returned by value. Callers hand in a
+//! [`Visit`] instead; [`Nlhe::shaped`] matches the shape against
+//! [`WORLD_COUNTS`] × [`LEAF_COUNTS`], builds the solver at that size, and
+//! passes it on. Off-tree opponent raises are nested here too, exactly as
+//! the fixed-size `nested_*` constructors do. [`Nlhe::locked`] and
+//! [`Nlhe::locked_exact`] do the same with the opponent pinned at a
+//! [`NlheLock`]'s nodes. Depth-limited layers value their frontier with
+//! the sampler [`pokerkit::frontier`] selects.
+use super::*;
+use kicker::*;
+use mccfr::*;
+use pokerkit::*;
+
+/// Consumer of a solver whose size is picked at runtime.
+pub trait Visit {
+    type Output;
+    /// Runs `solver` and harvests hero's decision at `info`.
+    fn visit<S>(self, solver: S, info: S::Base) -> Self::Output
+    where
+        S: Solver + Harvest<Edge = NlheEdge>;
+}
+
+/// Opponent policies pinned at NLHE public nodes.
+pub type NlheLock = subgame::Lock<NlhePublic, NlheEdge>;
+
+/// Which subgame layers a solve stacks: depth-limited, world-partitioned,
+/// or both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layers {
+    Leaf,
+    Safe,
+    Full,
+}
+
+/// Binds `$w` to `$shape.worlds()` as a const for `$body`.
+macro_rules! worlds {
+    ($shape:expr, $w:ident => $body:expr) => {
+        match $shape.worlds() {
+            2 => {
+                const $w: usize = 2;
+                $body
+            }
+            4 => {
+                const $w: usize = 4;
+                $body
+            }
+            8 => {
+                const $w: usize = 8;
+                $body
+            }
+            n => unreachable!("shape validated against WORLD_COUNTS, got {n} worlds"),
+        }
+    };
+}
+
+/// Binds `$l` to `$shape.leaves()` as a const for `$body`.
+macro_rules! leaves {
+    ($shape:expr, $l:ident => $body:expr) => {
+        match $shape.leaves() {
+            1 => {
+                const $l: usize = 1;
+                $body
+            }
+            2 => {
+                const $l: usize = 2;
+                $body
+            }
+            4 => {
+                const $l: usize = 4;
+                $body
+            }
+            n => unreachable!("shape validated against LEAF_COUNTS, got {n} leaves"),
+        }
+    };
+}
+
+/// Binds `$n` to the frontier sampler [`pokerkit::frontier`] selects:
+/// `$rollout` itself, or `$learned` wrapping it in a [`LeafSampler`].
+macro_rules! sampler {
+    ($rollout:expr, $learned:expr, $n:ident => $body:expr) => {
+        match pokerkit::frontier() {
+            Frontier::Rollout => {
+                let $n = $rollout;
+                $body
+            }
+            Frontier::Learned => {
+                let ref learned = $learned;
+                let $n = learned;
+                $body
+            }
+        }
+    };
+}
+
+impl<R, W, S> Nlhe<R, W, S>
+where
+    R: RegretSchedule,
+    W: WeightSchedule,
+    S: SamplingScheme,
+{
+    /// Builds the `layers` solver for `recall` at `shape` and hands it to
+    /// `visit`, with `info` as the harvest point. An off-tree opponent
+    /// raise roots the solve before the raise instead (see [`Self::nest`]).
+    pub fn shaped<V>(&self, layers: Layers, recall: &Witness, info: NlheInfo, shape: Shape, visit: V) -> V::Output
+    where
+        V: Visit,
+    {
+        self.rooted(recall, info, |recall, entry, info| match layers {
+            Layers::Leaf => sampler!(self, self.learned(leaf_model(), recall), n => {
+                leaves!(shape, LEAVES => visit.visit(self.leaf::<_, LEAVES>(n, recall, entry), info))
+            }),
+            Layers::Safe => worlds!(shape, WORLDS => visit.visit(self.safe::<WORLDS>(recall, entry), info)),
+            Layers::Full => sampler!(self, self.learned(leaf_model(), recall), n => {
+                worlds!(shape, WORLDS => leaves!(shape, LEAVES => visit.visit(self.full::<_, WORLDS, LEAVES>(n, recall, entry), info)))
+            }),
+        })
+    }
+
+    /// [`Self::shaped`] with the opponent pinned at `lock`'s nodes. Locks
+    /// live on the world profile, so [`Layers::Leaf`] has nothing to pin
+    /// and solves as [`Layers::Full`].
+    pub fn locked<V>(
+        &self,
+        layers: Layers,
+        recall: &Witness,
+        info: NlheInfo,
+        shape: Shape,
+        lock: &NlheLock,
+        visit: V,
+    ) -> V::Output
+    where
+        V: Visit,
+    {
+        self.rooted(recall, info, |recall, entry, info| match layers {
+            Layers::Safe => worlds!(shape, WORLDS => visit.visit(self.safe::<WORLDS>(recall, entry).with_lock(lock.clone()), info)),
+            Layers::Leaf | Layers::Full => sampler!(self, self.learned(leaf_model(), recall), n => {
+                worlds!(shape, WORLDS => leaves!(shape, LEAVES => visit.visit(self.full::<_, WORLDS, LEAVES>(n, recall, entry).with_lock(lock.clone()), info)))
+            }),
+        })
+    }
+
+    /// Exact-card counterpart of [`Self::shaped`] for streets
+    /// [`pokerkit::endgame`] resolves without the card abstraction.
+    /// Always both layers: on the river it never reaches a frontier.
+    pub fn shaped_exact<'a, V>(
+        &self,
+        exact: &'a ExactEncoder<'a>,
+        recall: &Witness,
+        info: NlheInfo,
+        shape: Shape,
+        visit: V,
+    ) -> V::Output
+    where
+        V: Visit,
+    {
+        let seen = recall.seen();
+        self.rooted(recall, info, |recall, entry, info| {
+            let info = ExactInfo::from((info, seen));
+            sampler!(exact, exact.learned(leaf_model(), recall), n => {
+                worlds!(shape, WORLDS => leaves!(shape, LEAVES => visit.visit(self.endgame::<_, WORLDS, LEAVES>(n, recall, entry), info)))
+            })
+        })
+    }
+
+    /// [`Self::shaped_exact`] with the opponent pinned at `lock`'s nodes.
+    pub fn locked_exact<'a, V>(
+        &self,
+        exact: &'a ExactEncoder<'a>,
+        recall: &Witness,
+        info: NlheInfo,
+        shape: Shape,
+        lock: &NlheLock,
+        visit: V,
+    ) -> V::Output
+    where
+        V: Visit,
+    {
+        let seen = recall.seen();
+        self.rooted(recall, info, |recall, entry, info| {
+            let info = ExactInfo::from((info, seen));
+            sampler!(exact, exact.learned(leaf_model(), recall), n => {
+                worlds!(shape, WORLDS => leaves!(shape, LEAVES => visit.visit(self.endgame::<_, WORLDS, LEAVES>(n, recall, entry).with_lock(lock.clone()), info)))
+            })
+        })
+    }
+
+    /// Runs `solve` from the recall, entry node and harvest point of the
+    /// solve: before an off-tree opponent raise if there is one, here
+    /// otherwise.
+    fn rooted<T, F>(&self, recall: &Witness, info: NlheInfo, solve: F) -> T
+    where
+        F: FnOnce(&Witness, NlheGame, NlheInfo) -> T,
+    {
+        match self.nest(recall) {
+            Some(ref nest) => solve(nest.before(), nest.root(), nest.info()),
+            None => solve(recall, NlheGame::from(recall.head()), info),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deuce::*;
+    use monge::Density;
+    use subgame::{World, WorldInfo, WorldProfile};
+
+    const BOARD: &str = "Ks7s2s5d9h";
+
+    /// Steps a solver a fixed number of times and harvests it.
+    struct Steps(usize);
+
+    impl Visit for Steps {
+        type Output = Harvested<NlheEdge>;
+        fn visit<S>(self, mut solver: S, info: S::Base) -> Self::Output
+        where
+            S: Solver + Harvest<Edge = NlheEdge>,
+        {
+            (0..self.0).for_each(|_| solver.step());
+            solver.harvest(info)
+        }
+    }
+
+    /// Hero in the big blind with QcQh, checked down to the river and
+    /// first to act there.
+    fn river() -> Witness {
+        let cards = ["Qc", "Qh", "Ks", "7s", "2s", "5d", "9h"]
+            .into_iter()
+            .map(|c| Card::try_from(c).unwrap())
+            .collect::<Vec<_>>();
+        std::iter::repeat_n(Action::Check, 5).fold(
+            Witness::from((Turn::Choice(1), Arrangement::from(cards))).push(Action::Call(1)),
+            |recall, action| recall.push(action),
+        )
+    }
+
+    /// One bucket per street up to the turn, where the opponent's range
+    /// replays its decisions on sampled boards, then river buckets for
+    /// every hole on the real board, spread so each world gets members.
+    fn blueprint() -> Flagship {
+        let mut bytes = NlheEncoder::MAGIC.to_vec();
+        for street in Street::all() {
+            let count = if street == Street::Rive { 0 } else { street.n_isomorphisms() };
+            let bucket = i16::from(Abstraction::from((street, 0))).to_le_bytes();
+            bytes.extend((count as u64).to_le_bytes());
+            bytes.extend(bucket.repeat(count));
+        }
+        let mut encoder = NlheEncoder::read(&mut bytes.as_slice()).unwrap();
+        let board = Hand::try_from(BOARD).unwrap();
+        encoder.extend(HandIterator::from((2, board)).enumerate().map(|(k, hole)| {
+            (Isomorphism::from(Observation::from((hole, board))), Abstraction::from((Street::Rive, k % 8)))
+        }));
+        Flagship::new(NlheProfile::default(), encoder)
+    }
+
+    #[test]
+    fn shaped_solves_off_default_shapes() {
+        let blueprint = blueprint();
+        let recall = river();
+        assert_eq!(recall.street(), Street::Rive);
+        let info = NlheInfo::from((&recall, blueprint.encoder().abstraction(&recall.seen())));
+        for shape in [Shape::parse("2x1"), Shape::parse("8x2")].map(Option::unwrap) {
+            assert_ne!(shape, Shape::default());
+            for layers in [Layers::Safe, Layers::Full] {
+                let harvest = blueprint.shaped(layers, &recall, info, shape, Steps(4));
+                let total = harvest.refined.values().sum::<Probability>();
+                assert!((total - 1.).abs() < 1e-3, "{layers:?} at {shape} harvested {total}");
+            }
+        }
+    }
+
+    #[test]
+    fn flop_pins_skip_turn_nodes() {
+        let public = NlhePublic::new(Path::from(vec![Edge::Check]), Path::from(vec![Edge::Check, Edge::Shove]));
+        let info = |street| NlheInfo::new(public, NlheSecret::from(Abstraction::from((street, 0))));
+        let check = NlheEdge::from(Edge::Check);
+        let lock = NlheLock::default().pin(Street::Flop as usize, public, [(check, 0.)]);
+        let blueprint = NlheProfile::default();
+        let profile = WorldProfile::new(&blueprint).with_lock(lock);
+        let world = |street| WorldInfo::new(World::from(0), info(street));
+        let flop = profile.iterated_distribution(&world(Street::Flop));
+        let turn = profile.iterated_distribution(&world(Street::Turn));
+        let free = blueprint.iterated_distribution(&info(Street::Turn));
+        assert!(flop.density(&check) < 1e-3, "flop pin ignored: {flop:?}");
+        assert!(free.density(&check) > 0.5);
+        assert!((turn.density(&check) - free.density(&check)).abs() < 1e-6, "flop pin leaked to the turn: {turn:?}");
+    }
+}
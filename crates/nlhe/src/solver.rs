@@ -36,7 +36,31 @@ fn subgame_descents(recall: &Witness) -> Vec<Descent<NlheTurn, NlheEdge>> {
         .collect()
 }
 
-impl<R, W, S> DepthSampler<{ pokerkit::FRONTIER_LEAVES }> for Nlhe<R, W, S>
+/// An opponent raise left off the abstract tree, staged for a nested
+/// subgame solve by [`Nlhe::nest`].
+pub struct Nest {
+    /// The recall rewound to just before the raise.
+    before: Witness,
+    /// The state before the raise, with the raise injected as an edge.
+    root: NlheGame,
+    /// Hero's infoset after the injected edge, for harvesting.
+    info: NlheInfo,
+}
+
+impl Nest {
+    /// Hero's decision in the nested tree, keyed by the injected edge.
+    pub fn info(&self) -> NlheInfo {
+        self.info
+    }
+    pub(crate) fn before(&self) -> &Witness {
+        &self.before
+    }
+    pub(crate) fn root(&self) -> NlheGame {
+        self.root
+    }
+}
+
+impl<R, W, S, const LEAVES: usize> DepthSampler<LEAVES> for Nlhe<R, W, S>
 where
     R: RegretSchedule,
     W: WeightSchedule,
@@ -48,21 +72,17 @@ where
         &self.profile
     }
 
+    /// Averaged biased rollouts; [`LeafSampler`] is the learned alternative.
     fn payoffs(
         &self,
         prefix: &Prefix<NlheTurn, NlheEdge>,
+        _: &[(NlheTurn, NlheInfo, NlheEdge)],
         game: &NlheGame,
         internal: NlheTurn,
-    ) -> Payoffs<{ pokerkit::FRONTIER_LEAVES }> {
-        let ref encoder = self.encoder;
-        let ref profile = self.profile;
+    ) -> Payoffs<LEAVES> {
         let rollouts = FrontierHyperParams::get().rollouts();
-        Payoffs::tabulate(|k, j| {
-            (0..rollouts)
-                .map(|_| encoder.biased_rollout(prefix, game, internal, k, j, profile))
-                .sum::<Utility>()
-                / rollouts as Utility
-        })
+        self.encoder
+            .rollout_payoffs(prefix, game, internal, &self.profile, rollouts)
     }
 }
 
@@ -95,10 +115,7 @@ where
     /// depth-limited tree from `recall.head()` using biased continuation
     /// rollouts at the leaves.
     pub fn adapt_leaf(&self, recall: &Witness) -> DepthSolver<'_, Self, { pokerkit::FRONTIER_LEAVES }> {
-        let internal = NlheTurn::from(recall.turn());
-        let entry = NlheGame::from(recall.head());
-        let prefix = subgame_descents(recall);
-        DepthSolver::new(self, prefix, internal, entry)
+        self.leaf(self, recall, NlheGame::from(recall.head()))
     }
     /// Creates a safe subgame solver from game history (no depth limiting).
     ///
@@ -109,8 +126,7 @@ where
         &self,
         recall: &Witness,
     ) -> WorldSolver<'_, { pokerkit::N_WORLDS }, NlheProfile, NlheEncoder, NlheInfo, NlheSecret> {
-        let (external, partition, recall) = self.setup(recall);
-        WorldSolver::new(&self.encoder, &self.profile, external, partition, recall)
+        self.safe(recall, NlheGame::from(recall.head()))
     }
     /// Creates a combined safe + depth-limited subgame solver.
     ///
@@ -122,17 +138,145 @@ where
         &self,
         recall: &Witness,
     ) -> SubGameSolver<'_, { pokerkit::N_WORLDS }, { pokerkit::FRONTIER_LEAVES }, Self, NlheInfo, NlheSecret> {
-        let (external, partition, recall) = self.setup(recall);
-        SubGameSolver::new(self, external, partition, recall)
+        self.full(self, recall, NlheGame::from(recall.head()))
+    }
+    /// Nested counterpart of [`Self::adapt_leaf`], rooted before the
+    /// off-tree raise.
+    pub fn nested_leaf(&self, nest: &Nest) -> DepthSolver<'_, Self, { pokerkit::FRONTIER_LEAVES }> {
+        self.leaf(self, &nest.before, nest.root)
+    }
+    /// Nested counterpart of [`Self::adapt_safe`], rooted before the
+    /// off-tree raise.
+    pub fn nested_safe(
+        &self,
+        nest: &Nest,
+    ) -> WorldSolver<'_, { pokerkit::N_WORLDS }, NlheProfile, NlheEncoder, NlheInfo, NlheSecret> {
+        self.safe(&nest.before, nest.root)
+    }
+    /// Nested counterpart of [`Self::adapt_full`], rooted before the
+    /// off-tree raise.
+    pub fn nested_full(
+        &self,
+        nest: &Nest,
+    ) -> SubGameSolver<'_, { pokerkit::N_WORLDS }, { pokerkit::FRONTIER_LEAVES }, Self, NlheInfo, NlheSecret> {
+        self.full(self, &nest.before, nest.root)
+    }
+    /// Borrows the blueprint for card-unabstracted resolves. Keep it
+    /// alive beside the solver built by [`Self::adapt_exact`].
+    pub fn exact(&self) -> ExactEncoder<'_> {
+        ExactEncoder::new(&self.encoder, &self.profile)
+    }
+    /// Creates a combined safe + depth-limited solver over exact hole
+    /// cards, for streets [`pokerkit::endgame`] resolves without the
+    /// card abstraction.
+    ///
+    /// Same setup as [`Self::adapt_full`], except the opponent range is
+    /// partitioned over observations rather than buckets.
+    pub fn adapt_exact<'a>(&self, exact: &'a ExactEncoder<'a>, recall: &Witness) -> ExactSolver<'a> {
+        self.endgame(exact, recall, NlheGame::from(recall.head()))
+    }
+    /// Nested counterpart of [`Self::adapt_exact`], rooted before the
+    /// off-tree raise.
+    pub fn nested_exact<'a>(&self, exact: &'a ExactEncoder<'a>, nest: &Nest) -> ExactSolver<'a> {
+        self.endgame(exact, &nest.before, nest.root)
+    }
+    /// Stages a nested solve when the opponent's latest action is a
+    /// raise the global [`pokerkit::translation`] leaves off-tree.
+    ///
+    /// The nested tree roots at the state the raise was played from,
+    /// with the observed chips grafted on as a spare edge (see
+    /// [`Game::spare`]), and the opponent range comes from the blueprint
+    /// reach up to that state. `None` when the latest action snaps onto
+    /// the grid, was hero's own, or no spare edge is left to label it —
+    /// callers then solve the snapped tree as usual.
+    pub fn nest(&self, recall: &Witness) -> Option<Nest> {
+        let (game, action, depth) = recall.offtree()?;
+        let Action::Raise(chips) = action else {
+            return None;
+        };
+        if game.turn() == recall.turn() {
+            return None;
+        }
+        let edge = game.spare(chips, depth)?;
+        let before = recall.undo();
+        let subgame = before
+            .subgame()
+            .into_iter()
+            .chain(std::iter::once(edge))
+            .collect::<Path>();
+        let choices = recall.head().choices(subgame.aggression());
+        let secret = self.encoder.abstraction(&recall.seen());
+        Some(Nest {
+            info: NlheInfo::from((subgame, secret, choices)),
+            root: NlheGame::inject(game, edge, chips),
+            before,
+        })
+    }
+    /// Depth-limited solver valuing its frontier with `sampler`: this
+    /// blueprint's rollouts, or a [`LeafSampler`] wrapping them.
+    pub(crate) fn leaf<'a, N, const LEAVES: usize>(
+        &self,
+        sampler: &'a N,
+        recall: &Witness,
+        entry: NlheGame,
+    ) -> DepthSolver<'a, N, LEAVES>
+    where
+        N: DepthSampler<LEAVES, T = NlheTurn, E = NlheEdge, G = NlheGame>,
+    {
+        let internal = NlheTurn::from(recall.turn());
+        let prefix = subgame_descents(recall);
+        DepthSolver::new(sampler, prefix, internal, entry)
+    }
+    pub(crate) fn safe<const WORLDS: usize>(
+        &self,
+        recall: &Witness,
+        entry: NlheGame,
+    ) -> WorldSolver<'_, WORLDS, NlheProfile, NlheEncoder, NlheInfo, NlheSecret> {
+        let (external, partition, recall) = self.setup(recall, entry);
+        WorldSolver::new(&self.encoder, &self.profile, external, partition, recall)
+    }
+    /// Combined solver valuing its frontier with `sampler`; see [`Self::leaf`].
+    pub(crate) fn full<'a, N, const WORLDS: usize, const LEAVES: usize>(
+        &self,
+        sampler: &'a N,
+        recall: &Witness,
+        entry: NlheGame,
+    ) -> SubGameSolver<'a, WORLDS, LEAVES, N, NlheInfo, NlheSecret>
+    where
+        N: DepthSampler<LEAVES, T = NlheTurn, E = NlheEdge, G = NlheGame, Blueprint: CfrFlow<I = NlheInfo>>,
+        N: WorldRestrict<WORLDS, I = NlheInfo>,
+    {
+        let (external, partition, recall) = self.setup(recall, entry);
+        SubGameSolver::new(sampler, external, partition, recall)
+    }
+    /// Exact-card solver valuing its frontier with `sampler`: an
+    /// [`ExactEncoder`], or a [`LeafSampler`] wrapping one.
+    pub(crate) fn endgame<'a, N, const WORLDS: usize, const LEAVES: usize>(
+        &self,
+        sampler: &'a N,
+        recall: &Witness,
+        entry: NlheGame,
+    ) -> SubGameSolver<'a, WORLDS, LEAVES, N, ExactInfo, ExactSecret>
+    where
+        N: DepthSampler<LEAVES, T = NlheTurn, E = NlheEdge, G = NlheGame, Blueprint: CfrFlow<I = ExactInfo>>,
+        N: WorldRestrict<WORLDS, I = ExactInfo>,
+    {
+        let external = opposing(recall.turn());
+        let partition = self.opponent_exact_range(recall).partition();
+        let path = subgame_descents(recall);
+        SubGameSolver::new(sampler, external, partition, CfrRecall::new(path, entry))
     }
     /// Common setup for safe solvers: external identity, belief partition, recall.
-    fn setup(&self, recall: &Witness) -> (NlheTurn, Belief<NlheSecret, { pokerkit::N_WORLDS }>, CfrRecall<NlheGame>) {
+    fn setup<const WORLDS: usize>(
+        &self,
+        recall: &Witness,
+        entry: NlheGame,
+    ) -> (NlheTurn, Belief<NlheSecret, WORLDS>, CfrRecall<NlheGame>) {
         let external = opposing(recall.turn());
         let prior = self.opponent_range(recall);
         let partition = prior.partition();
         let path = subgame_descents(recall);
-        let game = NlheGame::from(recall.head());
-        (external, partition, CfrRecall::new(path, game))
+        (external, partition, CfrRecall::new(path, entry))
     }
     /// Reach for one complete-info history along `subject`'s decision
     /// nodes — the product of the blueprint's averaged policy at every
@@ -195,6 +339,15 @@ where
             .map(|(obs, reach)| (NlheSecret::from(self.encoder.abstraction(&obs)), reach))
             .collect::<Posterior<NlheSecret>>()
     }
+    /// Opponent posterior over exact observations: the same likelihoods
+    /// as [`Self::opponent_range`] with step 4 skipped, so holes sharing
+    /// a bucket stay distinct.
+    pub fn opponent_exact_range(&self, recall: &Witness) -> Posterior<ExactSecret> {
+        self.opponent_reaches(recall)
+            .into_iter()
+            .map(|(obs, reach)| (ExactSecret::from(obs), reach))
+            .collect::<Posterior<ExactSecret>>()
+    }
     /// Hole-card-level normalized opponent range.
     ///
     /// Same likelihood computation as [`Self::opponent_range`] but skips
@@ -269,6 +269,10 @@ impl Engine<Dealing> {
         tracing::debug!(seat = pos, "asking for action");
         // Tell the player actor it's their turn so internal Players can decide.
         self.core.unicast(pos, Event::Decision(self.core.recall(pos)));
+        // Let the other seats ponder on this seat's clock.
+        for j in (0..self.core.players.len()).filter(|j| *j != pos) {
+            self.core.unicast(j, Event::Waiting(self.core.recall(j)));
+        }
         // Push fresh snapshot so the wire learns who to_act is + sees legal moves.
         self.core.push_snapshots();
         let (action, prompt) = self.next_action(pos).await;
@@ -314,7 +318,7 @@ impl Engine<Dealing> {
                     self.core.disconnected.insert(p);
                     Some((self.core.live.game().passive(), Prompt::Acted))
                 }
-                Event::Decision(_) => None,
+                Event::Decision(_) | Event::Waiting(_) | Event::Concluded(_) => None,
             }),
         }
     }
@@ -377,10 +381,14 @@ impl Engine<Showdown> {
         }
     }
 
-    /// Apply settlement and push hand-end snapshot.
+    /// Apply settlement, tell every seat how the hand went and push the
+    /// hand-end snapshot.
     pub fn settle(&mut self) {
         let settlements = self.core.live.game().settlements();
         tracing::debug!(?settlements, "settle");
+        for i in 0..self.core.players.len() {
+            self.core.unicast(i, Event::Concluded(self.core.recall(i)));
+        }
         self.core.live.settle(settlements);
         self.core.record_hand_closing();
         self.core.push_snapshots();
@@ -21,6 +21,12 @@ impl Prompt {
 pub enum Event {
     /// Engine→actor: it's this player's turn; here is their authoritative recall.
     Decision(Witness),
+    /// Engine→actor: another seat is deciding; here is this player's recall,
+    /// so bots can ponder their reply.
+    Waiting(Witness),
+    /// Engine→actor: the hand is over; here is this player's final recall,
+    /// so bots can learn from how it played out.
+    Concluded(Witness),
     /// Actor→engine: the player chose this action.
     Action(Action),
     /// Actor→engine: the player has dropped (channel closed).
@@ -41,6 +47,8 @@ impl std::fmt::Display for Event {
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
+            Event::Waiting(recall) => write!(f, "Waiting on {}", recall.head().turn()),
+            Event::Concluded(recall) => write!(f, "Hand over: pot {}", recall.head().pot()),
             Event::Action(action) => write!(f, "{action}"),
             Event::Disconnect(pos) => write!(f, "P{pos}: disconnected"),
         }
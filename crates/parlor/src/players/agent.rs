@@ -9,6 +9,10 @@
 //! Dirac-as-Brain means sampling from a Dirac delta = deterministic
 //! argmax — no separate "argmax player" code path, the distribution's
 //! shape carries the semantics.
+//!
+//! While the opponent is on the clock, subgame brains [`Ponder`] their
+//! likeliest replies; `decide` picks up the matching warm solve. When a
+//! hand concludes the brain [`observe`](Brain::observe)s it.
 use std::collections::BTreeMap;
 
 use kicker::Action;
@@ -23,7 +27,9 @@ use rand::prelude::*;
 
 use super::Brain;
 use super::Mount;
+use super::Ponder;
 use super::Tag;
+use crate::Event;
 use crate::Player;
 
 pub struct Agent<B>
@@ -31,6 +37,7 @@ where
     B: Brain,
 {
     brain: B,
+    ponder: Ponder,
 }
 
 impl<B> Agent<B>
@@ -80,6 +87,7 @@ where
     fn mount(tag: Tag, model: &'static Flagship) -> Self {
         Self {
             brain: B::mount(tag, model),
+            ponder: Ponder::default(),
         }
     }
 }
@@ -106,7 +114,19 @@ where
         span.record("depth", if tag.config.depth { "on" } else { "off" });
         span.record("world", if tag.config.world { "on" } else { "off" });
         span.record("dirac", if tag.config.dirac { "on" } else { "off" });
-        let dist = self.brain.distrib(recall);
+        let warm = self.ponder.take(recall).await;
+        let dist = self.brain.distrib(recall, warm);
         self.sample(&recall.head(), &dist)
     }
+
+    async fn notify(&mut self, event: &Event) {
+        let config = self.brain.tag().config;
+        match event {
+            Event::Waiting(recall) if (config.depth || config.world) && !self.brain.adaptive(recall) => {
+                self.ponder.start(&self.brain, recall);
+            }
+            Event::Concluded(recall) => self.brain.observe(recall),
+            _ => {}
+        }
+    }
 }
@@ -0,0 +1,38 @@
+use crate::*;
+use kicker::*;
+
+/// Deliberately exploitable CPU players: each one overdoes a single
+/// tendency every hand, so an opponent-modeling bot has a known leak to
+/// find. Like [`Fish`], none of them looks at its cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Biased {
+    /// Never folds and never raises: checks or calls every bet.
+    Station,
+    /// Sees every flop, then checks and folds to any bet.
+    Nit,
+    /// Min-raises whenever it can, short of shoving; calls when it can't.
+    Maniac,
+}
+
+#[async_trait::async_trait]
+impl Player for Biased {
+    fn shows(&self) -> bool {
+        true
+    }
+
+    async fn decide(&mut self, recall: &Witness) -> Action {
+        let game = recall.head();
+        let legal = game.legal();
+        let call = legal
+            .iter()
+            .copied()
+            .find(|a| matches!(a, Action::Check | Action::Call(_)));
+        match self {
+            Self::Station => call,
+            Self::Nit if game.street() == deuce::Street::Pref => call,
+            Self::Nit => None,
+            Self::Maniac => legal.iter().copied().find(Action::is_raise).or(call),
+        }
+        .unwrap_or_else(|| game.passive())
+    }
+}
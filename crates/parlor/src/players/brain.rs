@@ -8,7 +8,11 @@
 //! Two trivial accessors (`tag`, `model`), one overrideable hook
 //! ([`solve`](Brain::solve)), one default body ([`distrib`](Brain::distrib)).
 //! Subgame layers override only `solve`; preflop blueprint, postflop
-//! solve+blend, and the no-solve fallback all live in the default.
+//! solve+blend, and the no-solve fallback all live in the default. A
+//! solve pondered ahead of time (see [`Ponder`](super::Ponder)) is handed
+//! to `distrib` in place of a fresh one. Brains that learn from finished
+//! hands ([`Exploit`](super::Exploit)) also override
+//! [`observe`](Brain::observe) and [`adaptive`](Brain::adaptive).
 use std::collections::BTreeMap;
 use std::time::Duration;
 
@@ -21,7 +25,10 @@ use pokerkit::Probability;
 use pokerkit::Utility;
 use subgame::SubgameHyperParams;
 
+use super::Budget;
+use super::SolutionCache;
 use super::Solved;
+use super::Spot;
 use super::Tag;
 
 pub trait Brain: Send {
@@ -34,10 +41,21 @@ pub trait Brain: Send {
     /// solve). Subgame layers ([`Depth`](super::Depth),
     /// [`World`](super::World), `World<Depth<…>>`) override to run their
     /// `flagship.adapt_*` and harvest refined+visits.
-    fn solve(&self, _recall: &Witness, _info: NlheInfo, _deadline: Duration) -> Option<Solved> {
+    fn solve(&self, _recall: &Witness, _info: NlheInfo, _budget: &Budget) -> Option<Solved> {
         None
     }
 
+    /// Whether `solve` at `recall` depends on what this brain has
+    /// observed, not only on the spot. Such solves skip the
+    /// [`SolutionCache`] and pondered warm solves, which were keyed
+    /// without it. Default = never.
+    fn adaptive(&self, _recall: &Witness) -> bool {
+        false
+    }
+
+    /// A finished hand, from this bot's seat. Default = forget it.
+    fn observe(&mut self, _recall: &Witness) {}
+
     /// In-memory blueprint policy at the current decision. Both branches
     /// of the postflop pipeline (preflop pass-through and "no-solve"
     /// fallback) read this; subgame impls also use it as the prior for
@@ -58,11 +76,14 @@ pub trait Brain: Send {
     ///
     /// - Preflop → blueprint lookup, regardless of subgame layers.
     /// - Postflop with no solver (blueprint base) → blueprint lookup.
-    /// - Postflop with a solver → run `solve`, blend refined with
-    ///   blueprint by visit counts via [`Solved::extract`] (the only
-    ///   extraction strategy — pure-blueprint = use
-    ///   [`Blueprint`](super::Blueprint) directly).
-    fn distrib(&self, recall: &Witness) -> BTreeMap<Edge, Probability> {
+    /// - Postflop with a solver → run `solve` through the
+    ///   [`SolutionCache`] (or take the `warm` solve pondered for this
+    ///   exact recall; an [`adaptive`](Brain::adaptive) brain solves
+    ///   fresh instead), blend refined with blueprint by
+    ///   visit counts via [`Solved::extract`] (the only extraction
+    ///   strategy — pure-blueprint = use [`Blueprint`](super::Blueprint)
+    ///   directly).
+    fn distrib(&self, recall: &Witness, warm: Option<Solved>) -> BTreeMap<Edge, Probability> {
         let model = self.model();
         let game = recall.head();
         let info = NlheInfo::from((recall, model.encoder().abstraction(&recall.seen())));
@@ -80,7 +101,19 @@ pub trait Brain: Send {
             iterations = tracing::field::Empty,
             regret_norm = tracing::field::Empty,
         );
-        let Some(solved) = span.in_scope(|| self.solve(recall, info, timeout)) else {
+        let spot = Spot::new(tag, recall, &info);
+        let adaptive = self.adaptive(recall);
+        let solved = match warm.filter(|_| !adaptive) {
+            Some(warm) => {
+                SolutionCache::remember(spot, &warm);
+                Some(warm)
+            }
+            None if (tag.config.depth || tag.config.world) && !adaptive => span.in_scope(|| {
+                SolutionCache::solve(spot, tag, game.street(), timeout, |budget| self.solve(recall, info, budget))
+            }),
+            None => span.in_scope(|| self.solve(recall, info, &Budget::new(timeout))),
+        };
+        let Some(solved) = solved else {
             return self.policy(recall);
         };
         let visits = solved
@@ -0,0 +1,60 @@
+//! [`Budget`] — how long one subgame solve may run.
+//!
+//! A decision-time solve spends its whole deadline. A pondered solve
+//! (see [`Ponder`](super::Ponder)) gets the same deadline but can be
+//! stopped early, when the opponent plays a line it wasn't solving for.
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Wall-clock deadline plus a stop flag shared by every clone.
+#[derive(Clone, Debug)]
+pub struct Budget {
+    deadline: Duration,
+    stopped: Arc<AtomicBool>,
+}
+
+impl Budget {
+    pub fn new(deadline: Duration) -> Self {
+        Self {
+            deadline,
+            stopped: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn deadline(&self) -> Duration {
+        self.deadline
+    }
+
+    /// Ends the solve at its next iteration boundary.
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether a solve started at `start` may run another iteration.
+    pub fn open(&self, start: Instant) -> bool {
+        start.elapsed() < self.deadline && !self.stopped.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stopping_a_clone_closes_the_original() {
+        let budget = Budget::new(Duration::from_secs(60));
+        let start = Instant::now();
+        assert!(budget.open(start));
+        budget.clone().stop();
+        assert!(!budget.open(start));
+    }
+
+    #[test]
+    fn budget_closes_at_deadline() {
+        let budget = Budget::new(Duration::ZERO);
+        assert!(!budget.open(Instant::now()));
+    }
+}
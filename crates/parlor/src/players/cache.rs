@@ -0,0 +1,484 @@
+//! [`SolutionCache`] — refined policies reused across recurring spots.
+//!
+//! Subgame brains re-solve every postflop decision from scratch, yet the
+//! same line on the same texture at the same pot geometry comes up again
+//! and again. A [`Spot`] names such a recurrence; the cache maps it to
+//! the pooled [`Solved`] of every earlier solve there. Per
+//! [`CacheHyperParams`], a well-visited entry replaces the solve outright
+//! and a thin one gets a shorter fresh solve pooled into it.
+//!
+//! One process-wide cache, bounded with least-recently-used eviction.
+//! It is opt-in: until one is [installed](SolutionCache::install) every
+//! decision solves fresh. It can be backed by a JSON file
+//! ([`SolutionCache::load`]) stamped with the [`Provenance`] of its solves,
+//! rewritten in the background every [`SAVE_EVERY`] insertions and on
+//! [`SolutionCache::persist`].
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use deuce::Isomorphism;
+use deuce::Street;
+use kicker::*;
+use mccfr::CfrPublic;
+use mccfr::RefProf;
+use mccfr::Solver;
+use nlhe::Flagship;
+use nlhe::NlheInfo;
+use nlhe::NlhePublic;
+use pokerkit::Shape;
+use subgame::CacheHyperParams;
+use subgame::SubgameHyperParams;
+use vitals::KeyValue;
+
+use super::Budget;
+use super::Solved;
+use super::Tag;
+use super::solved::with_street;
+
+/// Insertions between rewrites of a file-backed cache.
+const SAVE_EVERY: usize = 64;
+
+static CACHE: OnceLock<Mutex<SolutionCache>> = OnceLock::new();
+
+/// What makes two decisions the same solve.
+///
+/// - `kind`: which solver ran — the depth and world axes, plus whether
+///   the street was resolved on exact cards.
+/// - `shape`: the solve's world and leaf counts.
+/// - `public`: current-street abstract history and available choices.
+/// - `board`: hero's cards and the board, up to suit isomorphism.
+/// - `belief`: fingerprint of what shapes both ranges beyond the current
+///   street — earlier streets' abstract actions, stack-to-pot geometry,
+///   and whether hero has the button.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Spot {
+    kind: u8,
+    shape: Shape,
+    public: NlhePublic,
+    board: Isomorphism,
+    belief: u64,
+}
+
+impl Spot {
+    pub fn new(tag: Tag, recall: &Witness, info: &NlheInfo) -> Self {
+        let street = recall.street();
+        let exact = pokerkit::endgame().exact(street as usize);
+        let game = recall.head();
+        let button = recall.turn().position() == recall.dealer();
+        let history = recall
+            .yorrify()
+            .into_iter()
+            .filter(|(_, _, s)| *s < street)
+            .map(|(_, edge, _)| u64::from(edge));
+        let belief = fingerprint(
+            std::iter::once(u64::from(game.geometry().tag()))
+                .chain(std::iter::once(u64::from(button)))
+                .chain(history),
+        );
+        Self {
+            kind: u8::from(tag.config.depth) | u8::from(tag.config.world) << 1 | u8::from(exact) << 2,
+            shape: tag.shape,
+            public: NlhePublic::new(info.subgame(), info.choices()),
+            board: Isomorphism::from(recall.seen()),
+            belief,
+        }
+    }
+}
+
+/// 64-bit FNV-1a: a fingerprint that is stable across builds, so
+/// persisted keys stay valid.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a over `words`.
+pub(super) fn fingerprint(words: impl IntoIterator<Item = u64>) -> u64 {
+    words
+        .into_iter()
+        .fold(FNV_OFFSET, |h, x| (h ^ x).wrapping_mul(FNV_PRIME))
+}
+
+/// The abstraction and blueprint a file-backed cache's solves came from.
+/// Solves under any other pair answer different questions, so
+/// [`SolutionCache::load`] refuses them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Provenance {
+    version: String,
+    blueprint: String,
+}
+
+impl Provenance {
+    /// The active abstraction version, and `model` named by its regime
+    /// and training epochs.
+    pub fn of(model: &Flagship) -> Self {
+        Self {
+            version: pokerkit::version().to_string(),
+            blueprint: format!("{}@{}", pokerkit::regime(), model.profile().t()),
+        }
+    }
+}
+
+/// Bounded map from [`Spot`] to pooled solution, evicting the least
+/// recently used entry.
+#[derive(Debug, Default)]
+pub struct SolutionCache {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<Spot, (u64, Solved)>,
+    recency: BTreeMap<u64, Spot>,
+    path: Option<PathBuf>,
+    provenance: Provenance,
+    unsaved: usize,
+}
+
+impl SolutionCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Self::default()
+        }
+    }
+
+    /// The process-wide cache, if one was [installed](Self::install).
+    pub fn global() -> Option<&'static Mutex<Self>> {
+        CACHE.get()
+    }
+
+    /// Makes `self` the process-wide cache. Later calls are ignored.
+    pub fn install(self) {
+        let _ = CACHE.set(Mutex::new(self));
+    }
+
+    fn params() -> &'static CacheHyperParams {
+        SubgameHyperParams::get().cache()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The pooled solution at `spot`, marking it recently used.
+    pub fn get(&mut self, spot: &Spot) -> Option<Solved> {
+        self.tick += 1;
+        let (tick, solved) = self.entries.get_mut(spot)?;
+        self.recency.remove(tick);
+        self.recency.insert(self.tick, *spot);
+        *tick = self.tick;
+        Some(solved.clone())
+    }
+
+    /// Stores `solved` at `spot`, evicting the least recently used entry
+    /// when full.
+    pub fn insert(&mut self, spot: Spot, solved: Solved) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        if let Some((tick, _)) = self.entries.insert(spot, (self.tick, solved)) {
+            self.recency.remove(&tick);
+        }
+        self.recency.insert(self.tick, spot);
+        while self.entries.len() > self.capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+        self.unsaved += 1;
+    }
+
+    /// Inserts into the process-wide cache. Every [`SAVE_EVERY`]
+    /// insertions a file-backed cache is snapshotted under the lock and
+    /// written on a background thread.
+    fn store(cache: &Mutex<Self>, spot: Spot, solved: Solved) {
+        let due = {
+            let mut cache = cache.lock().expect("poison");
+            cache.insert(spot, solved);
+            (cache.unsaved >= SAVE_EVERY).then(|| cache.snapshot()).flatten()
+        };
+        if let Some(snapshot) = due {
+            std::thread::spawn(move || {
+                snapshot
+                    .write()
+                    .unwrap_or_else(|e| tracing::warn!(error = %e, "solution cache save failed"));
+            });
+        }
+    }
+
+    /// Solves `spot` through the process-wide cache, recording a hit or
+    /// miss. `solve` runs under whatever budget the cached entry leaves:
+    /// none for a replacement, the pool share for a thin entry (merged
+    /// into it afterwards), or all of `timeout` on a miss.
+    pub fn solve<F>(spot: Spot, tag: Tag, street: Street, timeout: Duration, solve: F) -> Option<Solved>
+    where
+        F: FnOnce(&Budget) -> Option<Solved>,
+    {
+        let params = Self::params();
+        let Some(cache) = Self::global().filter(|cache| cache.lock().expect("poison").capacity > 0) else {
+            return solve(&Budget::new(timeout));
+        };
+        let metrics = vitals::metrics::get();
+        let labels = with_street(tag, street);
+        let cached = cache.lock().expect("poison").get(&spot);
+        let solved = match cached {
+            Some(cached) if cached.total() >= params.replace() => {
+                metrics.subgame_cache_hits.add(1, &Self::outcome(labels, "replace"));
+                return Some(cached.replayed());
+            }
+            Some(cached) => {
+                metrics.subgame_cache_hits.add(1, &Self::outcome(labels, "pool"));
+                solve(&Budget::new(timeout.mul_f32(params.pool()))).map(|solved| solved.merge(&cached))
+            }
+            None => {
+                metrics.subgame_cache_misses.add(1, &labels);
+                solve(&Budget::new(timeout))
+            }
+        }?;
+        Self::store(cache, spot, solved.clone());
+        Some(solved)
+    }
+
+    /// Pools a solve that ran outside [`Self::solve`] (e.g. a pondered
+    /// one) into the process-wide cache, if there is one.
+    pub fn remember(spot: Spot, solved: &Solved) {
+        let Some(cache) = Self::global() else {
+            return;
+        };
+        let pooled = match cache.lock().expect("poison").entries.get(&spot) {
+            Some((_, earlier)) => solved.clone().merge(earlier),
+            None => solved.clone(),
+        };
+        Self::store(cache, spot, pooled);
+    }
+
+    fn outcome([variant, shape, street]: [KeyValue; 3], outcome: &'static str) -> [KeyValue; 4] {
+        [variant, shape, street, KeyValue::new("outcome", outcome)]
+    }
+
+    /// Reads a cache written by [`Self::save`], or starts an empty one if
+    /// `path` doesn't exist yet. Either way, `path` backs the result.
+    /// A file whose solves came from another [`Provenance`] is an error.
+    pub fn load(path: impl Into<PathBuf>, capacity: usize, provenance: Provenance) -> std::io::Result<Self> {
+        let path = path.into();
+        let mut cache = Self::new(capacity);
+        if path.exists() {
+            let file = std::io::BufReader::new(std::fs::File::open(&path)?);
+            let archive = serde_json::from_reader::<_, Archive>(file).map_err(std::io::Error::other)?;
+            if archive.provenance != provenance {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!(
+                        "{} holds solves for {:?}, not {:?}; remove it or pick another path",
+                        path.display(),
+                        archive.provenance,
+                        provenance,
+                    ),
+                ));
+            }
+            for record in archive.records {
+                let (spot, solved) = record.try_into()?;
+                cache.insert(spot, solved);
+            }
+        }
+        cache.path = Some(path);
+        cache.provenance = provenance;
+        cache.unsaved = 0;
+        Ok(cache)
+    }
+
+    /// Rewrites the backing file. No-op for an in-memory cache.
+    pub fn save(&mut self) -> std::io::Result<()> {
+        self.snapshot().map_or(Ok(()), Snapshot::write)
+    }
+
+    /// Saves the process-wide cache, writing outside its lock.
+    pub fn persist() -> std::io::Result<()> {
+        let Some(cache) = Self::global() else {
+            return Ok(());
+        };
+        let snapshot = cache.lock().expect("poison").snapshot();
+        snapshot.map_or(Ok(()), Snapshot::write)
+    }
+
+    /// Every entry, oldest first so a reload keeps the recency order,
+    /// ready to write to the backing file. `None` for an in-memory cache.
+    fn snapshot(&mut self) -> Option<Snapshot> {
+        let path = self.path.clone()?;
+        let records = self
+            .recency
+            .values()
+            .filter_map(|spot| self.entries.get(spot).map(|(_, solved)| Record::from((spot, solved))))
+            .collect();
+        self.unsaved = 0;
+        Some(Snapshot {
+            path,
+            archive: Archive {
+                provenance: self.provenance.clone(),
+                records,
+            },
+        })
+    }
+}
+
+/// Serializes writers so an older snapshot never lands after a newer one.
+static WRITER: Mutex<()> = Mutex::new(());
+
+/// A cache's contents on their way to its backing file.
+struct Snapshot {
+    path: PathBuf,
+    archive: Archive,
+}
+
+impl Snapshot {
+    /// Writes beside the backing file and renames over it, so a reader
+    /// never sees a partial file.
+    fn write(self) -> std::io::Result<()> {
+        let _guard = WRITER.lock().expect("poison");
+        let partial = self.path.with_extension("partial");
+        let file = std::io::BufWriter::new(std::fs::File::create(&partial)?);
+        serde_json::to_writer(file, &self.archive).map_err(std::io::Error::other)?;
+        std::fs::rename(partial, &self.path)
+    }
+}
+
+/// On-disk form of a cache: where its solves came from, then every entry.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Archive {
+    #[serde(flatten)]
+    provenance: Provenance,
+    records: Vec<Record>,
+}
+
+/// On-disk form of one entry: every key part and edge as its integer
+/// encoding.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Record {
+    kind: u8,
+    worlds: usize,
+    leaves: usize,
+    subgame: u64,
+    choices: u64,
+    board: i64,
+    belief: u64,
+    policy: Vec<(u64, f32)>,
+    visits: Vec<(u64, u32)>,
+}
+
+impl From<(&Spot, &Solved)> for Record {
+    fn from((spot, solved): (&Spot, &Solved)) -> Self {
+        Self {
+            kind: spot.kind,
+            worlds: spot.shape.worlds(),
+            leaves: spot.shape.leaves(),
+            subgame: u64::from(spot.public.subgame()),
+            choices: u64::from(CfrPublic::choices(&spot.public).map(Edge::from).collect::<Path>()),
+            board: i64::from(spot.board),
+            belief: spot.belief,
+            policy: solved.policy().iter().map(|(e, p)| (u64::from(*e), *p)).collect(),
+            visits: solved.visits().iter().map(|(e, v)| (u64::from(*e), *v)).collect(),
+        }
+    }
+}
+
+impl TryFrom<Record> for (Spot, Solved) {
+    type Error = std::io::Error;
+    fn try_from(record: Record) -> Result<Self, Self::Error> {
+        let shape = Shape::new(record.worlds, record.leaves).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("unsupported shape {}x{}", record.worlds, record.leaves),
+            )
+        })?;
+        let spot = Spot {
+            kind: record.kind,
+            shape,
+            public: NlhePublic::new(Path::from(record.subgame), Path::from(record.choices)),
+            board: Isomorphism::from(record.board),
+            belief: record.belief,
+        };
+        let policy = record.policy.into_iter().map(|(e, p)| (Edge::from(e), p)).collect();
+        let visits = record.visits.into_iter().map(|(e, v)| (Edge::from(e), v)).collect();
+        Ok((spot, Solved::restored(policy, visits)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spot(belief: u64) -> Spot {
+        Spot {
+            kind: 0,
+            shape: Shape::default(),
+            public: NlhePublic::default(),
+            board: Isomorphism::from(deuce::Observation::try_from("AsKs").expect("valid observation")),
+            belief,
+        }
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = SolutionCache::new(2);
+        cache.insert(spot(1), Solved::default());
+        cache.insert(spot(2), Solved::default());
+        assert!(cache.get(&spot(1)).is_some());
+        cache.insert(spot(3), Solved::default());
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&spot(2)).is_none());
+        assert!(cache.get(&spot(1)).is_some());
+        assert!(cache.get(&spot(3)).is_some());
+    }
+
+    #[test]
+    fn zero_capacity_stores_nothing() {
+        let mut cache = SolutionCache::new(0);
+        cache.insert(spot(1), Solved::default());
+        assert!(cache.is_empty());
+    }
+
+    fn provenance(blueprint: &str) -> Provenance {
+        Provenance {
+            version: "v3".into(),
+            blueprint: blueprint.into(),
+        }
+    }
+
+    #[test]
+    fn file_round_trip_keeps_provenance() {
+        let path = std::env::temp_dir().join(format!("parlor-cache-{}.json", std::process::id()));
+        let mut cache = SolutionCache::load(&path, 4, provenance("pluribus@1")).expect("fresh cache");
+        cache.insert(
+            spot(1),
+            Solved::restored(BTreeMap::from([(Edge::Check, 1.0)]), BTreeMap::from([(Edge::Check, 2)])),
+        );
+        cache.save().expect("save");
+        let mut reloaded = SolutionCache::load(&path, 4, provenance("pluribus@1")).expect("reload");
+        assert_eq!(reloaded.get(&spot(1)).map(|solved| solved.total()), Some(2));
+        let stale = SolutionCache::load(&path, 4, provenance("pluribus@2"));
+        std::fs::remove_file(&path).ok();
+        assert!(stale.is_err_and(|e| e.kind() == std::io::ErrorKind::InvalidData));
+    }
+
+    #[test]
+    fn merge_weights_policies_by_visits() {
+        let earlier = Solved::restored(
+            BTreeMap::from([(Edge::Check, 1.0), (Edge::Call, 0.0)]),
+            BTreeMap::from([(Edge::Check, 3), (Edge::Call, 0)]),
+        );
+        let fresh = Solved::restored(
+            BTreeMap::from([(Edge::Check, 0.0), (Edge::Call, 1.0)]),
+            BTreeMap::from([(Edge::Check, 0), (Edge::Call, 1)]),
+        );
+        let merged = fresh.merge(&earlier);
+        assert_eq!(merged.total(), 4);
+        assert!((merged.policy()[&Edge::Check] - 0.5).abs() < 1e-6);
+        assert!((merged.policy()[&Edge::Call] - 0.5).abs() < 1e-6);
+    }
+}
@@ -1,15 +1,18 @@
-//! [`Depth<B>`] — depth-limited subgame solve via `flagship.adapt_leaf`.
+//! [`Depth<B>`] — depth-limited subgame solve via `flagship.shaped`.
 //!
-//! Wraps an inner [`Brain`]. Only `Depth<Blueprint>` has a [`Brain`] impl;
+//! Wraps an inner [`Brain`]. The solve runs at the tag's [`Shape`](pokerkit::Shape).
+//! An opponent raise the translation leaves off-tree is re-solved rooted
+//! before the raise, with its exact chips as an edge.
+//!
+//! Only `Depth<Blueprint>` has a [`Brain`] impl;
 //! non-canonical orderings (`Depth<Depth<…>>`, `Depth<World<…>>`)
 //! intentionally don't compile.
-use std::time::Duration;
-
 use kicker::*;
 use nlhe::*;
 
 use super::Blueprint;
 use super::Brain;
+use super::Budget;
 use super::Mount;
 use super::Solved;
 use super::Tag;
@@ -41,7 +44,10 @@ impl Brain for Depth<Blueprint> {
         self.inner.model()
     }
 
-    fn solve(&self, recall: &Witness, info: NlheInfo, deadline: Duration) -> Option<Solved> {
-        Some(Solved::run(self.model().adapt_leaf(recall), info, deadline))
+    fn solve(&self, recall: &Witness, info: NlheInfo, budget: &Budget) -> Option<Solved> {
+        Some(
+            self.model()
+                .shaped(Layers::Leaf, recall, info, self.tag().shape, budget),
+        )
     }
 }
@@ -8,14 +8,13 @@
 //! then commits to its mode. Sampling from a Dirac always returns the
 //! same action, so `Agent::decide` doesn't need a separate "argmax"
 //! code path — the structure of the distribution carries the semantics.
-use std::collections::BTreeMap;
-use std::time::Duration;
-
 use kicker::*;
 use nlhe::*;
 use pokerkit::Probability;
+use std::collections::BTreeMap;
 
 use super::Brain;
+use super::Budget;
 use super::Mount;
 use super::Solved;
 use super::Tag;
@@ -52,9 +51,17 @@ where
 
     /// Dirac doesn't run its own subgame solver — it transforms what
     /// the inner brain produces. Delegate `solve` so any subgame layers
-    /// underneath still take the deadline.
-    fn solve(&self, recall: &Witness, info: NlheInfo, deadline: Duration) -> Option<Solved> {
-        self.inner.solve(recall, info, deadline)
+    /// underneath still take the budget.
+    fn solve(&self, recall: &Witness, info: NlheInfo, budget: &Budget) -> Option<Solved> {
+        self.inner.solve(recall, info, budget)
+    }
+
+    fn adaptive(&self, recall: &Witness) -> bool {
+        self.inner.adaptive(recall)
+    }
+
+    fn observe(&mut self, recall: &Witness) {
+        self.inner.observe(recall);
     }
 
     /// Override the default postflop pipeline: take the inner's full
@@ -63,7 +70,7 @@ where
     /// the mode action. Shares [`nlhe::argmax`] with
     /// [`nlhe::Strategy::argmax`] so the analysis-side dirac
     /// post-process and this gameplay brain agree.
-    fn distrib(&self, recall: &Witness) -> BTreeMap<Edge, Probability> {
-        nlhe::argmax(&self.inner.distrib(recall))
+    fn distrib(&self, recall: &Witness, warm: Option<Solved>) -> BTreeMap<Edge, Probability> {
+        nlhe::argmax(&self.inner.distrib(recall, warm))
     }
 }
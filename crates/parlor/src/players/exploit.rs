@@ -0,0 +1,361 @@
+//! [`Exploit<B>`] — deviates from the blueprint toward what the opponent
+//! has actually been doing.
+//!
+//! Wraps an inner [`Brain`]. Every finished hand the engine reports
+//! through [`Event::Concluded`](crate::Event::Concluded) is folded into a
+//! [`Tally`] of the opponent's actions per public node. Each node's
+//! counts update a Dirichlet prior centred on the blueprint's frequency
+//! there over the opponent's posterior range, so a node seen a handful
+//! of times barely moves off the blueprint. Postflop, nodes on the
+//! current street with enough observations are tilted in an
+//! [`NlheLock`](nlhe::NlheLock): each bucket keeps its blueprint policy,
+//! reweighted edge by edge by posterior over prior, so the opponent still
+//! plays strong and weak hands apart. Hero re-solves against it under
+//! [`Response::Restricted`]: the opponent's unpinned nodes keep
+//! re-solving, so hero only exploits what the data supports — a
+//! data-biased restricted Nash response. With no pins the inner brain
+//! plays unchanged.
+//!
+//! Compose outside the subgame layers and inside [`Dirac`](super::Dirac):
+//! `Dirac<Exploit<World<Depth<Blueprint>>>>`. Exploit isn't a cube axis,
+//! so it has no [`zoo`](super::zoo) cell; [`Variant::Exploit`](pokerkit::Variant::Exploit)
+//! plays the full solver through [`exploiter`](super::exploiter).
+use std::collections::BTreeMap;
+
+use deuce::Street;
+use kicker::*;
+use nlhe::*;
+use pokerkit::Probability;
+use subgame::ExploitHyperParams;
+use subgame::Response;
+use subgame::SubgameHyperParams;
+
+use super::Brain;
+use super::Budget;
+use super::Mount;
+use super::Ponder;
+use super::Solved;
+use super::Tag;
+use super::fingerprint;
+
+pub struct Exploit<B>
+where
+    B: Brain,
+{
+    inner: B,
+    tally: Tally,
+}
+
+impl<B> Exploit<B>
+where
+    B: Brain,
+{
+    /// Opponent nodes to pin for hero's decision at `recall`.
+    fn lock(&self, recall: &Witness) -> NlheLock {
+        self.tally.lock(recall, SubgameHyperParams::get().exploit())
+    }
+
+    /// Solver layers matching the inner brain's cube cell. A bare
+    /// blueprint has no solver of its own, so it borrows the safe one.
+    fn layers(&self) -> Layers {
+        match (self.tag().config.depth, self.tag().config.world) {
+            (false, _) => Layers::Safe,
+            (true, false) => Layers::Leaf,
+            (true, true) => Layers::Full,
+        }
+    }
+}
+
+impl<B> Mount for Exploit<B>
+where
+    B: Brain + Mount,
+{
+    fn mount(tag: Tag, model: &'static Flagship) -> Self {
+        Self {
+            inner: B::mount(tag, model),
+            tally: Tally::default(),
+        }
+    }
+}
+
+impl<B> Brain for Exploit<B>
+where
+    B: Brain,
+{
+    fn tag(&self) -> Tag {
+        self.inner.tag()
+    }
+
+    fn model(&self) -> &'static Flagship {
+        self.inner.model()
+    }
+
+    /// Node-locked solve against the opponent model, or the inner
+    /// brain's own solve while nothing on this street is pinned. Where a
+    /// world brain would resolve the street on exact cards, so does the
+    /// locked solve.
+    fn solve(&self, recall: &Witness, info: NlheInfo, budget: &Budget) -> Option<Solved> {
+        let lock = self.lock(recall);
+        if lock.is_empty() {
+            return self.inner.solve(recall, info, budget);
+        }
+        let tag = self.tag();
+        let model = self.model();
+        if tag.config.world && pokerkit::endgame().exact(recall.street() as usize) {
+            return Some(model.locked_exact(&model.exact(), recall, info, tag.shape, &lock, budget));
+        }
+        Some(model.locked(self.layers(), recall, info, tag.shape, &lock, budget))
+    }
+
+    fn adaptive(&self, recall: &Witness) -> bool {
+        !self.lock(recall).is_empty()
+    }
+
+    fn observe(&mut self, recall: &Witness) {
+        let model = self.model();
+        self.tally.record(recall, |before| Ponder::range(model, before));
+    }
+}
+
+/// The opponent's observed actions at one public node, with the
+/// blueprint frequencies they are shrunk toward.
+#[derive(Debug, Clone, Default)]
+struct Counts {
+    prior: BTreeMap<Edge, Probability>,
+    seen: BTreeMap<Edge, u32>,
+}
+
+impl Counts {
+    fn n(&self) -> u32 {
+        self.seen.values().sum()
+    }
+
+    /// Posterior mean under a Dirichlet prior of total mass `strength`:
+    /// `(strength · prior(e) + seen(e)) / (strength + n)`.
+    fn posterior(&self, strength: Probability) -> BTreeMap<Edge, Probability> {
+        let n = self.n() as Probability;
+        self.prior
+            .iter()
+            .map(|(edge, p)| {
+                let seen = self.seen.get(edge).copied().unwrap_or_default() as Probability;
+                (*edge, (strength * p + seen) / (strength + n))
+            })
+            .collect()
+    }
+
+    /// How far the posterior moved each edge off the prior: the weights
+    /// that tilt the blueprint toward what was observed.
+    fn tilt(&self, strength: Probability) -> BTreeMap<Edge, Probability> {
+        self.posterior(strength)
+            .into_iter()
+            .map(|(edge, p)| (edge, p / self.prior[&edge].max(pokerkit::EPSILON)))
+            .collect()
+    }
+}
+
+/// One opponent's action statistics, keyed by street, [`line`](Tally::line)
+/// and public node: [`NlhePublic`] only holds the current street's
+/// action, so hands that reached it differently are kept apart.
+///
+/// Fed one finished hand at a time from hero's side of the table. Only
+/// the opponent's postflop decisions are counted: preflop never solves.
+#[derive(Debug, Clone, Default)]
+pub struct Tally {
+    nodes: BTreeMap<(Street, u64, NlhePublic), Counts>,
+    hands: usize,
+}
+
+impl Tally {
+    /// Hands recorded so far.
+    pub fn hands(&self) -> usize {
+        self.hands
+    }
+
+    /// Opponent decisions recorded at `public` on `street` after `line`.
+    pub fn seen(&self, street: Street, line: u64, public: NlhePublic) -> u32 {
+        self.nodes.get(&(street, line, public)).map_or(0, Counts::n)
+    }
+
+    /// Fingerprint of how the hand reached the street of the decision
+    /// after `i` actions: the stack-to-pot geometry the street opened
+    /// at, whether hero has the button, and every earlier street's
+    /// abstract action — the same line a [`Spot`](super::Spot)'s belief
+    /// tells apart.
+    pub fn line(recall: &Witness, i: usize) -> u64 {
+        let states = recall.states();
+        let history = recall.history();
+        let start = i - history[..i].iter().rev().take_while(|e| e.is_choice()).count();
+        let button = recall.turn().position() == recall.dealer();
+        fingerprint(
+            std::iter::once(u64::from(states[start].geometry().tag()))
+                .chain(std::iter::once(u64::from(button)))
+                .chain(history[..start].iter().filter(|e| e.is_choice()).map(|e| u64::from(*e))),
+        )
+    }
+
+    /// Hero's recall at the decision after `i` actions, with only the
+    /// cards dealt by then.
+    fn rewind(recall: &Witness, i: usize) -> Witness {
+        let street = recall.states()[i].street();
+        let reveals = recall.arr().justify(street);
+        recall.actions()[..i]
+            .iter()
+            .filter(|action| action.is_choice())
+            .fold(Witness::initial_with(recall.turn(), reveals, recall.stacks(), recall.dealer()), |before, action| {
+                before.push(*action)
+            })
+    }
+
+    /// Counts every opponent decision in a finished hand. `prior` gives
+    /// the blueprint's action frequencies at a node the first time it is
+    /// seen, from hero's recall at that decision.
+    pub fn record<F>(&mut self, recall: &Witness, prior: F)
+    where
+        F: Fn(&Witness) -> BTreeMap<Edge, Probability>,
+    {
+        let Turn::Choice(hero) = recall.turn() else {
+            return;
+        };
+        let history = recall.history();
+        for (i, (game, action)) in recall.states().iter().zip(recall.actions()).enumerate() {
+            match game.turn() {
+                Turn::Choice(villain) if villain != hero && action.is_choice() => {}
+                _ => continue,
+            }
+            if game.street() == Street::Pref {
+                continue;
+            }
+            let subgame = history[..i]
+                .iter()
+                .rev()
+                .take_while(|e| e.is_choice())
+                .copied()
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .collect::<Path>();
+            let public = NlhePublic::trailing(subgame, game.choices(subgame.aggression()));
+            let line = Self::line(recall, i);
+            let counts = self
+                .nodes
+                .entry((game.street(), line, public))
+                .or_insert_with(|| Counts {
+                    prior: prior(&Self::rewind(recall, i)),
+                    seen: BTreeMap::new(),
+                });
+            if counts.prior.contains_key(&history[i]) {
+                *counts.seen.entry(history[i]).or_default() += 1;
+            }
+        }
+        self.hands += 1;
+    }
+
+    /// Tilts toward the posterior at the nodes on hero's street and line
+    /// at `recall` with at least [`support`](ExploitHyperParams::support)
+    /// observations.
+    pub fn lock(&self, recall: &Witness, params: &ExploitHyperParams) -> NlheLock {
+        let street = recall.street();
+        let line = Self::line(recall, recall.actions().len());
+        self.nodes
+            .iter()
+            .filter(|((s, l, _), _)| *s == street && *l == line)
+            .filter(|(_, counts)| counts.n() >= params.support())
+            .fold(NlheLock::new(Response::Restricted), |lock, ((_, _, public), counts)| {
+                lock.tilt(
+                    street as usize,
+                    *public,
+                    counts
+                        .tilt(params.strength())
+                        .into_iter()
+                        .map(|(edge, w)| (NlheEdge::from(edge), w)),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uniform(recall: &Witness) -> BTreeMap<Edge, Probability> {
+        let choices = recall.head().choices(recall.subgame().aggression());
+        let p = 1. / choices.length() as Probability;
+        choices.into_iter().map(|e| (e, p)).collect()
+    }
+
+    /// Hero P0 limps, P1 checks, then P1 leads the flop with a check.
+    fn hand() -> Witness {
+        flop().push(Action::Check)
+    }
+
+    /// Hero's flop decision in [`hand`], facing the check.
+    fn flop() -> Witness {
+        Witness::from((Turn::Choice(0), Arrangement::from(Street::Flop)))
+            .push(Action::Call(1))
+            .push(Action::Check)
+            .push(Action::Check)
+    }
+
+    #[test]
+    fn counts_opponent_postflop_decisions() {
+        let mut tally = Tally::default();
+        tally.record(&hand(), uniform);
+        let states = hand().states();
+        let i = states
+            .iter()
+            .position(|game| game.street() == Street::Flop)
+            .expect("flop reached");
+        let public = NlhePublic::trailing(Path::default(), states[i].choices(0));
+        assert_eq!(tally.hands(), 1);
+        assert_eq!(tally.seen(Street::Flop, Tally::line(&hand(), i), public), 1);
+        assert_eq!(tally.nodes.len(), 1);
+    }
+
+    #[test]
+    fn rewind_replays_each_decision() {
+        let hand = hand();
+        for (i, game) in hand.states().iter().enumerate() {
+            if !matches!(game.turn(), Turn::Choice(_)) {
+                continue;
+            }
+            let before = Tally::rewind(&hand, i);
+            assert_eq!(before.actions(), &hand.actions()[..i]);
+            assert_eq!(before.seen().street(), game.street());
+        }
+    }
+
+    #[test]
+    fn pins_supported_nodes_toward_observed() {
+        let ref params = ExploitHyperParams::default();
+        let mut tally = Tally::default();
+        for _ in 1..params.support() {
+            tally.record(&hand(), uniform);
+        }
+        assert!(tally.lock(&flop(), params).is_empty());
+        tally.record(&hand(), uniform);
+        let lock = tally.lock(&flop(), params);
+        assert_eq!(lock.len(), 1);
+        let (_, _, pin) = lock.pins().next().expect("one pin");
+        let subgame::Pin::Tilt(weights) = pin else {
+            panic!("expected a tilt, got {pin:?}");
+        };
+        let check = NlheEdge::from(Edge::Check);
+        assert!(weights[&check] > 1.);
+        assert!(weights.iter().filter(|(e, _)| **e != check).all(|(_, w)| *w < 1.));
+    }
+
+    #[test]
+    fn pins_stay_on_their_line() {
+        let ref params = ExploitHyperParams::default();
+        let mut tally = Tally::default();
+        for _ in 0..params.support() {
+            tally.record(&hand(), uniform);
+        }
+        let raised = Witness::from((Turn::Choice(0), Arrangement::from(Street::Flop))).push(Action::Raise(5));
+        let raised = raised.push(raised.head().calls()).push(Action::Check);
+        assert_eq!(raised.street(), Street::Flop);
+        assert_eq!(raised.turn(), Turn::Choice(0));
+        assert!(!tally.lock(&flop(), params).is_empty());
+        assert!(tally.lock(&raised, params).is_empty());
+    }
+}
@@ -20,6 +20,7 @@ impl Player for Human {
     async fn notify(&mut self, event: &Event) {
         match event {
             Event::Decision(recall) => println!("{recall}"),
+            Event::Waiting(_) | Event::Concluded(_) => {}
             Event::Action(action) => println!("{action}"),
             Event::Disconnect(pos) => println!("P{pos}: disconnected"),
         }
@@ -7,6 +7,9 @@
 //! them in canonical order (`Dirac<World<Depth<Blueprint>>>`) and
 //! wrap with [`Agent<B>`] to get a `Player`. [`zoo`] is the single
 //! runtime → comptime binding both slumbot and the hosting server use.
+//! [`Exploit<B>`] models the opponent and re-solves against it; it sits
+//! off the cube, between `Dirac` and the subgame layers. [`Fish`] and
+//! the [`Biased`] bots are card-blind sparring partners.
 //!
 //! Every helper that operates on a typed value lives as a method on that
 //! type — picker bodies inside their own structs, telemetry on
@@ -35,20 +38,29 @@ pub async fn hydrate_blueprint(client: std::sync::Arc<tokio_postgres::Client>) -
 
 #[cfg(feature = "server")]
 mod agent;
+mod biased;
 #[cfg(feature = "server")]
 mod blueprint;
 #[cfg(feature = "server")]
 mod brain;
 #[cfg(feature = "server")]
+mod budget;
+#[cfg(feature = "server")]
+mod cache;
+#[cfg(feature = "server")]
 mod depth;
 #[cfg(feature = "server")]
 mod dirac;
+#[cfg(feature = "server")]
+mod exploit;
 mod fish;
 #[cfg(feature = "cli")]
 mod human;
 #[cfg(feature = "server")]
 mod mount;
 #[cfg(feature = "server")]
+mod ponder;
+#[cfg(feature = "server")]
 mod solved;
 #[cfg(feature = "server")]
 mod variant;
@@ -59,20 +71,29 @@ mod zoo;
 
 #[cfg(feature = "server")]
 pub use agent::*;
+pub use biased::*;
 #[cfg(feature = "server")]
 pub use blueprint::*;
 #[cfg(feature = "server")]
 pub use brain::*;
 #[cfg(feature = "server")]
+pub use budget::*;
+#[cfg(feature = "server")]
+pub use cache::*;
+#[cfg(feature = "server")]
 pub use depth::*;
 #[cfg(feature = "server")]
 pub use dirac::*;
+#[cfg(feature = "server")]
+pub use exploit::*;
 pub use fish::*;
 #[cfg(feature = "cli")]
 pub use human::*;
 #[cfg(feature = "server")]
 pub use mount::*;
 #[cfg(feature = "server")]
+pub use ponder::*;
+#[cfg(feature = "server")]
 pub use solved::*;
 #[cfg(feature = "server")]
 pub use variant::*;
@@ -0,0 +1,189 @@
+//! [`Ponder`] — speculative subgame solves while the opponent thinks.
+//!
+//! When [`Player::notify`](crate::Player::notify) reports that the
+//! opponent is on the clock, [`Agent`](super::Agent) starts hero's next
+//! solve for each of the opponent's likeliest actions. Likelihood is the
+//! opponent's blueprint frequency, averaged over their posterior range
+//! ([`Ponder::range`]).
+//! Each line runs on the blocking pool under a stoppable [`Budget`]. When
+//! the real action arrives, the line for that exact recall is awaited and
+//! handed to [`Brain::distrib`] as a warm solve, and the others stop.
+use std::collections::BTreeMap;
+use std::time::Duration;
+use std::time::Instant;
+
+use deuce::*;
+use kicker::*;
+use mccfr::RefProf;
+use mccfr::Solver;
+use nlhe::*;
+use pokerkit::Probability;
+use subgame::SubgameHyperParams;
+use tokio::task::JoinHandle;
+
+use super::Brain;
+use super::Budget;
+use super::Mount;
+use super::Solved;
+
+/// One speculative solve, rooted at hero's recall after a guessed action.
+struct Line {
+    recall: Witness,
+    budget: Budget,
+    start: Instant,
+    handle: JoinHandle<Option<Solved>>,
+}
+
+#[derive(Default)]
+pub struct Ponder {
+    lines: Vec<Line>,
+}
+
+impl Ponder {
+    /// Stops any lines still running and starts one per likely opponent
+    /// action at `recall`, up to [`SubgameHyperParams::ponder`].
+    pub fn start<B>(&mut self, brain: &B, recall: &Witness)
+    where
+        B: Brain + Mount + 'static,
+    {
+        self.clear();
+        let (tag, model) = (brain.tag(), brain.model());
+        let timeout = Duration::from_millis(SubgameHyperParams::get().timeout_ms());
+        for next in Self::lines(model, recall) {
+            tracing::debug!(variant = tag.label, line = ?next.head().turn(), "pondering");
+            self.spawn(next, timeout, move |next, budget| {
+                let info = NlheInfo::from((next, model.encoder().abstraction(&next.seen())));
+                B::mount(tag, model).solve(next, info, budget)
+            });
+        }
+    }
+
+    /// Runs `solve` for `recall` on the blocking pool under a fresh budget.
+    fn spawn<F>(&mut self, recall: Witness, timeout: Duration, solve: F)
+    where
+        F: FnOnce(&Witness, &Budget) -> Option<Solved> + Send + 'static,
+    {
+        let budget = Budget::new(timeout);
+        let handle = tokio::task::spawn_blocking({
+            let budget = budget.clone();
+            let recall = recall.clone();
+            move || solve(&recall, &budget)
+        });
+        self.lines.push(Line {
+            recall,
+            budget,
+            start: Instant::now(),
+            handle,
+        });
+    }
+
+    /// The warm solve for exactly `recall`, if one was pondered. Waits out
+    /// the rest of its budget and records how much of it ran early. Every
+    /// other line is stopped.
+    pub async fn take(&mut self, recall: &Witness) -> Option<Solved> {
+        let line = self
+            .lines
+            .iter()
+            .position(|line| line.recall == *recall)
+            .map(|i| self.lines.swap_remove(i));
+        self.clear();
+        let line = line?;
+        let saved = line.start.elapsed();
+        line.handle.await.ok().flatten().map(|solved| solved.with_saved(saved))
+    }
+
+    fn clear(&mut self) {
+        for line in self.lines.drain(..) {
+            line.budget.stop();
+        }
+    }
+
+    /// Hero's recalls after each of the opponent's likeliest actions.
+    /// Actions that close the street are skipped (the next card is
+    /// unknown), as are preflop spots, which never solve.
+    fn lines(model: &Flagship, recall: &Witness) -> Vec<Witness> {
+        let game = recall.head();
+        let mut ranked = Self::range(model, recall)
+            .into_iter()
+            .filter(|(_, p)| *p > 0.0)
+            .collect::<Vec<_>>();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranked
+            .into_iter()
+            .filter_map(|(edge, _)| recall.try_push(game.snap(game.actionize(edge))).ok())
+            .filter(|next| next.head().turn() == recall.turn())
+            .filter(|next| next.street() != Street::Pref)
+            .take(SubgameHyperParams::get().ponder())
+            .collect()
+    }
+
+    /// Blueprint frequency of each action for the opponent to act at
+    /// `recall`'s head, averaged over their posterior range: every hole
+    /// hero's cards leave them, weighted by how likely the blueprint was
+    /// to play their line so far holding it. [`Exploit`](super::Exploit)
+    /// takes it as the prior at each opponent node.
+    pub fn range(model: &Flagship, recall: &Witness) -> BTreeMap<Edge, Probability> {
+        let game = recall.head();
+        if !matches!(game.turn(), Turn::Choice(_)) || game.turn() == recall.turn() {
+            return BTreeMap::new();
+        }
+        let subgame = recall.subgame();
+        let choices = game.choices(subgame.aggression());
+        let mut frequency = BTreeMap::<Edge, Probability>::new();
+        for (bucket, weight) in model.opponent_range(recall).normalize() {
+            let info = NlheInfo::from((subgame, Abstraction::from(bucket), choices));
+            for (edge, p) in model.profile().averaged_distribution(&info) {
+                if edge.is_choice() {
+                    *frequency.entry(Edge::from(edge)).or_default() += weight * p;
+                }
+            }
+        }
+        frequency
+    }
+}
+
+impl Drop for Ponder {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hero P0 limps and P1 checks; hero is first to act on the flop.
+    fn flop() -> Witness {
+        Witness::from((Turn::Choice(0), Arrangement::from(Street::Flop)))
+            .push(Action::Call(1))
+            .push(Action::Check)
+    }
+
+    #[tokio::test]
+    async fn matching_line_is_reused() {
+        let mut ponder = Ponder::default();
+        let recall = flop();
+        ponder.spawn(recall.clone(), Duration::from_secs(60), |_, _| Some(Solved::default()));
+        assert!(ponder.take(&recall).await.is_some());
+        assert!(ponder.lines.is_empty());
+    }
+
+    #[tokio::test]
+    async fn other_lines_are_stopped() {
+        let mut ponder = Ponder::default();
+        let recall = flop();
+        let (done, stopped) = std::sync::mpsc::channel();
+        ponder.spawn(recall.clone(), Duration::from_secs(60), move |_, budget| {
+            let start = Instant::now();
+            while budget.open(start) {
+                std::thread::yield_now();
+            }
+            done.send(()).ok();
+            Some(Solved::default())
+        });
+        let elsewhere = recall.push(Action::Check);
+        assert!(ponder.take(&elsewhere).await.is_none());
+        assert!(ponder.lines.is_empty());
+        assert!(stopped.recv_timeout(Duration::from_secs(10)).is_ok());
+    }
+}
@@ -1,26 +1,34 @@
 //! One subgame solve's output — refined policy and per-edge visit counts,
-//! plus the (iterations / elapsed / regret) triple used for telemetry.
+//! plus the (iterations / elapsed / regret) triple used for telemetry and
+//! how much of the budget pondering covered before the decision.
+//! Solves of a recurring spot [`merge`](Solved::merge) into one another
+//! through the [`SolutionCache`](super::SolutionCache).
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::time::Duration;
+use std::time::Instant;
 
 use deuce::Street;
 use kicker::Edge;
 use mccfr::Harvest;
 use mccfr::Solver;
 use nlhe::NlheEdge;
-use nlhe::NlheInfo;
 use pokerkit::Chips;
 use pokerkit::Probability;
 use pokerkit::Utility;
 use subgame::SubgameHyperParams;
 use vitals::KeyValue;
 
+use super::Budget;
 use super::Tag;
 
+#[derive(Clone, Debug, Default)]
 pub struct Solved {
     iterations: usize,
     elapsed: Duration,
+    /// Share of `elapsed` spent pondering before the decision arrived.
+    /// Zero for solves started at decision time.
+    saved: Duration,
     /// `Σ_a max(0, R(info, a))` at the decision infoset (partition-summed
     /// for `world` / full variants). Chips. Divide by total visits at info
     /// for per-iteration regret — `Brain::distrib` does that and further
@@ -32,15 +40,22 @@ pub struct Solved {
 }
 
 impl Solved {
-    /// Run the full solve pipeline — `spend` the deadline, `harvest` the
-    /// result at `info`, choice-filter, and assemble. The 3 subgame
-    /// `Brain::solve` overrides differ only in which `adapt_*` they pass
-    /// in; everything else collapses to one call here.
-    pub fn run<S>(mut solver: S, info: NlheInfo, deadline: Duration) -> Self
+    /// Run the full solve pipeline — step until the `budget` closes,
+    /// `harvest` the result at `info`, choice-filter, and assemble. The 3
+    /// subgame `Brain::solve` overrides differ only in which `adapt_*` they
+    /// pass in; everything else collapses to one call here. `info` is keyed
+    /// however the solver keys infosets: [`nlhe::NlheInfo`] for bucketed
+    /// solves, [`nlhe::ExactInfo`] for exact endgames.
+    pub fn run<S>(mut solver: S, info: S::Base, budget: &Budget) -> Self
     where
-        S: Solver + Harvest<Base = NlheInfo, Edge = NlheEdge>,
+        S: Solver + Harvest<Edge = NlheEdge>,
     {
-        let (iterations, elapsed) = solver.spend(deadline);
+        let start = Instant::now();
+        let iterations = std::iter::repeat_with(|| ())
+            .take_while(|()| budget.open(start))
+            .map(|()| solver.step())
+            .count();
+        let elapsed = start.elapsed();
         let harvest = solver.harvest(info);
         let policy = harvest
             .refined
@@ -57,6 +72,7 @@ impl Solved {
         Self {
             iterations,
             elapsed,
+            saved: Duration::ZERO,
             regret: harvest.regret,
             policy,
             visits,
@@ -71,10 +87,82 @@ impl Solved {
         self.elapsed
     }
 
+    pub fn saved(&self) -> Duration {
+        self.saved
+    }
+
+    /// Marks a pondered solve: `saved` of its budget ran before the
+    /// decision, capped at the time it actually spent.
+    pub fn with_saved(mut self, saved: Duration) -> Self {
+        self.saved = saved.min(self.elapsed);
+        self
+    }
+
     pub fn regret(&self) -> Utility {
         self.regret
     }
 
+    /// A solve restored from storage: policy and visits only, no run.
+    pub fn restored(policy: BTreeMap<Edge, Probability>, visits: BTreeMap<Edge, u32>) -> Self {
+        Self {
+            policy,
+            visits,
+            ..Self::default()
+        }
+    }
+
+    /// The same solution served without running: zero iterations and
+    /// time, so decision telemetry reflects this decision's cost.
+    pub fn replayed(&self) -> Self {
+        Self {
+            iterations: 0,
+            elapsed: Duration::ZERO,
+            saved: Duration::ZERO,
+            ..self.clone()
+        }
+    }
+
+    /// Visits summed over every root edge.
+    pub fn total(&self) -> u32 {
+        self.visits.values().fold(0, |sum, v| sum.saturating_add(*v))
+    }
+
+    /// Pools this solve with an earlier one of the same spot. Visits add
+    /// up; each edge's probability is the visit-weighted mean of the two,
+    /// then renormalised. Telemetry stays this solve's own.
+    pub fn merge(mut self, earlier: &Self) -> Self {
+        let edges = self
+            .policy
+            .keys()
+            .chain(earlier.policy.keys())
+            .copied()
+            .collect::<BTreeSet<_>>();
+        let raw = edges
+            .into_iter()
+            .map(|e| {
+                let v1 = self.visits.get(&e).copied().unwrap_or(0);
+                let v0 = earlier.visits.get(&e).copied().unwrap_or(0);
+                let p1 = self.policy.get(&e).copied().unwrap_or(0.0);
+                let p0 = earlier.policy.get(&e).copied().unwrap_or(0.0);
+                let n = u64::from(v0) + u64::from(v1);
+                let p = if n > 0 {
+                    (v1 as Probability * p1 + v0 as Probability * p0) / n as Probability
+                } else {
+                    p0.midpoint(p1)
+                };
+                (e, p, v0.saturating_add(v1))
+            })
+            .collect::<Vec<_>>();
+        let total = raw
+            .iter()
+            .map(|(_, p, _)| p)
+            .sum::<Probability>()
+            .max(pokerkit::EPSILON);
+        self.visits = raw.iter().map(|(e, _, v)| (*e, *v)).collect();
+        self.policy = raw.into_iter().map(|(e, p, _)| (e, p / total)).collect();
+        self
+    }
+
     pub fn policy(&self) -> &BTreeMap<Edge, Probability> {
         &self.policy
     }
@@ -94,11 +182,17 @@ impl Solved {
             .subgame_decision_ms
             .record(self.elapsed.as_secs_f64() * 1000.0, &labels);
         metrics.subgame_relative_regret.record(relative as f64, &labels);
+        if !self.saved.is_zero() {
+            metrics
+                .subgame_ponder_saved_ms
+                .record(self.saved.as_secs_f64() * 1000.0, &labels);
+        }
         tracing::debug!(
             variant = tag.label,
             street = %street,
             iterations = self.iterations,
             elapsed_ms = self.elapsed.as_millis() as u64,
+            saved_ms = self.saved.as_millis() as u64,
             regret = self.regret as f64,
             pot = pot as i64,
             relative = relative as f64,
@@ -194,13 +288,26 @@ impl Solved {
     }
 }
 
-/// Subgame metric label set: `variant` + `street`. The cube axes
-/// (`depth`/`world`/`dirac`) are intentionally omitted — they are
+/// Subgame metric label set: `variant` + `shape` + `street`. The cube
+/// axes (`depth`/`world`/`dirac`) are intentionally omitted — they are
 /// determined by `variant`, so emitting both triples Prometheus
-/// series count for no PromQL recoverability gain.
-fn with_street(tag: Tag, street: Street) -> [KeyValue; 2] {
+/// series count for no PromQL recoverability gain. `shape` is not: the
+/// same variant may run at several shapes in one process.
+pub(super) fn with_street(tag: Tag, street: Street) -> [KeyValue; 3] {
     [
         KeyValue::new("variant", tag.label),
+        KeyValue::new("shape", tag.shape.to_string()),
         KeyValue::new("street", street.to_string()),
     ]
 }
+
+/// A solve sized at runtime runs under this budget like any other.
+impl nlhe::Visit for &Budget {
+    type Output = Solved;
+    fn visit<S>(self, solver: S, info: S::Base) -> Solved
+    where
+        S: Solver + Harvest<Edge = NlheEdge>,
+    {
+        Solved::run(solver, info, self)
+    }
+}
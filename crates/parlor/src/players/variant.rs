@@ -2,21 +2,24 @@
 //!
 //! The pure data shape of `Variant` lives in `pokerkit` so the WASM
 //! client can use it. This module adds the methods that need
-//! gameroom-only types: constructing a [`Player`] via [`zoo`], building
-//! a [`Member`] / [`User`] for the room sit-down, and emitting OTLP
-//! [`KeyValue`]s for telemetry.
+//! gameroom-only types: constructing a [`Player`] via [`zoo`] (or
+//! [`exploiter`]), building a [`Member`] / [`User`] for the room
+//! sit-down, and emitting OTLP [`KeyValue`]s for telemetry.
 use crate::Player;
 
 use super::Fish;
 use super::Tag;
+use super::exploiter;
 use super::zoo;
 use bouncer::Member;
 use bouncer::User;
 use pokerkit::Config;
 use pokerkit::ID;
+use pokerkit::Shape;
 use pokerkit::Variant;
 use pokerkit::slumbot_opponent_username;
 use pokerkit::slumbot_opponent_uuid;
+use subgame::SubgameHyperParams;
 use vitals::KeyValue;
 
 /// Gameroom-only methods on [`Variant`]. Implemented as an extension
@@ -25,21 +28,30 @@ use vitals::KeyValue;
 /// `vitals` that the client must not pull in.
 pub trait VariantExt {
     fn tag(self) -> Option<Tag>;
+    fn shaped(self, shape: Shape) -> Option<Tag>;
     fn email(self) -> String;
     fn id(self) -> ID<Member>;
     fn member(self) -> Member;
+    fn shaped_member(self, shape: Shape) -> Member;
     fn user(self) -> User;
     fn keys(self) -> [KeyValue; 4];
     fn into_player(self, flagship: Option<&'static nlhe::Flagship>) -> Box<dyn Player>;
+    fn into_shaped(self, shape: Shape, flagship: Option<&'static nlhe::Flagship>) -> Box<dyn Player>;
 }
 
 impl VariantExt for Variant {
     /// Telemetry tag carrying both the canonical label and the cube
-    /// triple. `None` for fish.
+    /// triple, sized by the process-wide [`SubgameHyperParams::shape`].
+    /// `None` for fish.
     fn tag(self) -> Option<Tag> {
+        self.shaped(SubgameHyperParams::get().shape())
+    }
+    /// [`Self::tag`] with an explicit solve shape.
+    fn shaped(self, shape: Shape) -> Option<Tag> {
         self.config().map(|config: Config| Tag {
             label: self.label(),
             config,
+            shape,
         })
     }
     fn email(self) -> String {
@@ -51,6 +63,16 @@ impl VariantExt for Variant {
     fn member(self) -> Member {
         Member::new(self.id(), self.username(), self.email())
     }
+    /// [`Self::member`] solving at `shape`, so benchmark runs of one
+    /// variant at several shapes record under distinct players.
+    fn shaped_member(self, shape: Shape) -> Member {
+        let email = if shape == Shape::default() {
+            self.email()
+        } else {
+            format!("{}.{shape}@robopoker.io", self.label())
+        };
+        Member::new(ID::from(self.shaped_uuid(shape)), self.shaped_username(shape), email)
+    }
     fn user(self) -> User {
         User::Auth(self.member())
     }
@@ -71,9 +93,14 @@ impl VariantExt for Variant {
     /// Build the concrete `Player` for this variant. Consumes `self` —
     /// each Variant materializes exactly one player.
     fn into_player(self, flagship: Option<&'static nlhe::Flagship>) -> Box<dyn Player> {
-        match self.tag() {
-            None => Box::new(Fish),
-            Some(tag) => zoo(tag, flagship.expect("bot variant requires flagship")),
+        self.into_shaped(SubgameHyperParams::get().shape(), flagship)
+    }
+    /// [`Self::into_player`] with an explicit solve shape.
+    fn into_shaped(self, shape: Shape, flagship: Option<&'static nlhe::Flagship>) -> Box<dyn Player> {
+        match (self, self.shaped(shape)) {
+            (_, None) => Box::new(Fish),
+            (Variant::Exploit, Some(tag)) => exploiter(tag, flagship.expect("exploit variant requires flagship")),
+            (_, Some(tag)) => zoo(tag, flagship.expect("bot variant requires flagship")),
         }
     }
 }
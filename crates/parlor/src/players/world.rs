@@ -1,16 +1,19 @@
 //! [`World<B>`] — world-partitioned subgame solve.
 //!
-//! Wraps an inner [`Brain`]. `World<Blueprint>` runs `flagship.adapt_safe`
-//! (no depth limit); `World<Depth<Blueprint>>` runs `flagship.adapt_full`
-//! (world-partitioned + depth-limited). Other orderings have no [`Brain`]
-//! impl.
-use std::time::Duration;
-
+//! Wraps an inner [`Brain`]. `World<Blueprint>` solves [`Layers::Safe`]
+//! (no depth limit); `World<Depth<Blueprint>>` solves [`Layers::Full`]
+//! (world-partitioned + depth-limited), both at the tag's
+//! [`Shape`](pokerkit::Shape). An opponent raise the translation leaves
+//! off-tree is nested the same way either solve. On streets the global
+//! [`pokerkit::endgame`] resolves exactly, both swap in
+//! `flagship.shaped_exact`, keyed on hero's hole cards instead of their
+//! bucket. Other orderings have no [`Brain`] impl.
 use kicker::*;
 use nlhe::*;
 
 use super::Blueprint;
 use super::Brain;
+use super::Budget;
 use super::Depth;
 use super::Mount;
 use super::Solved;
@@ -43,8 +46,14 @@ impl Brain for World<Blueprint> {
         self.inner.model()
     }
 
-    fn solve(&self, recall: &Witness, info: NlheInfo, deadline: Duration) -> Option<Solved> {
-        Some(Solved::run(self.model().adapt_safe(recall), info, deadline))
+    fn solve(&self, recall: &Witness, info: NlheInfo, budget: &Budget) -> Option<Solved> {
+        if exact(recall) {
+            return Some(endgame(self.tag(), self.model(), recall, info, budget));
+        }
+        Some(
+            self.model()
+                .shaped(Layers::Safe, recall, info, self.tag().shape, budget),
+        )
     }
 }
 
@@ -57,7 +66,25 @@ impl Brain for World<Depth<Blueprint>> {
         self.inner.model()
     }
 
-    fn solve(&self, recall: &Witness, info: NlheInfo, deadline: Duration) -> Option<Solved> {
-        Some(Solved::run(self.model().adapt_full(recall), info, deadline))
+    fn solve(&self, recall: &Witness, info: NlheInfo, budget: &Budget) -> Option<Solved> {
+        if exact(recall) {
+            return Some(endgame(self.tag(), self.model(), recall, info, budget));
+        }
+        Some(
+            self.model()
+                .shaped(Layers::Full, recall, info, self.tag().shape, budget),
+        )
     }
 }
+
+/// Whether the configured endgame drops the card abstraction on this street.
+fn exact(recall: &Witness) -> bool {
+    pokerkit::endgame().exact(recall.street() as usize)
+}
+
+/// Exact-card solve shared by both orderings: [`nlhe::ExactSolver`] is
+/// already world-partitioned and depth-limited, and on the river it never
+/// reaches a frontier.
+fn endgame(tag: Tag, model: &'static Flagship, recall: &Witness, info: NlheInfo, budget: &Budget) -> Solved {
+    model.shaped_exact(&model.exact(), recall, info, tag.shape, budget)
+}
@@ -11,12 +11,14 @@
 //! position in the bottom row's `Dirac<World<Depth<Blueprint>>>` —
 //! reading column-by-column tells you which axis is active.
 use pokerkit::Config;
+use pokerkit::Shape;
 use vitals::KeyValue;
 
 use super::Agent;
 use super::Blueprint;
 use super::Depth;
 use super::Dirac;
+use super::Exploit;
 use super::World;
 use crate::Player;
 use nlhe::Flagship;
@@ -27,11 +29,14 @@ use nlhe::Flagship;
 /// `dirac`) onto Prometheus series alongside the composite `variant`
 /// label. Lets Grafana group/filter by axis and compute corner-pair
 /// diffs (e.g. marginal value of depth-limiting averaged over the
-/// `world × dirac` plane) directly in PromQL.
+/// `world × dirac` plane) directly in PromQL. `shape` sizes the
+/// subgame solves and is reported on subgame metrics as its own label,
+/// so one variant can be A/B'd across shapes in the same run.
 #[derive(Copy, Clone, Debug)]
 pub struct Tag {
     pub label: &'static str,
     pub config: Config,
+    pub shape: Shape,
 }
 
 impl Tag {
@@ -66,3 +71,9 @@ pub fn zoo(tag: Tag, model: &'static Flagship) -> Box<dyn Player> {
         (true,  true,  true ) => Agent::<Dirac<World<Depth<Blueprint>>>>::boxed(tag, model),
     }
 }
+
+/// The opponent-modeling bot, off the cube: [`Exploit`] over the full
+/// subgame solver its [`Tag`] names.
+pub fn exploiter(tag: Tag, model: &'static Flagship) -> Box<dyn Player> {
+    Agent::<Exploit<World<Depth<Blueprint>>>>::boxed(tag, model)
+}
@@ -0,0 +1,78 @@
+//! Runtime-dispatched endgame card resolution.
+//!
+//! Mirrors the [`crate::Translation`] pattern: a process-global `OnceLock`
+//! set once at startup via [`init_endgame`] and queried via [`endgame`].
+//! Binaries take `--endgame <name>` on the CLI via clap.
+//!
+//! ## Why not a cube axis?
+//!
+//! [`crate::Variant`] is the bot's identity: its label names the DB user
+//! and its UUID. Whether late-street resolves see exact hole cards is a
+//! refinement of the `world` cells rather than a different bot, so it
+//! lives beside [`crate::Translation`] as static inference configuration.
+//! Training never consults it.
+
+/// How much of the endgame the world-partitioned subgame solvers resolve
+/// without the card abstraction. Streets before the named one keep
+/// solving over abstraction buckets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Endgame {
+    /// Every street resolves over abstraction buckets.
+    #[default]
+    Abstract,
+    /// River resolves key infosets on exact hole cards.
+    River,
+    /// Turn and river resolves key infosets on exact hole cards.
+    Turn,
+}
+
+impl Endgame {
+    /// Whether a resolve on `street` (0 = preflop … 3 = river) drops
+    /// the card abstraction.
+    pub fn exact(self, street: usize) -> bool {
+        match self {
+            Self::Abstract => false,
+            Self::River => street >= 3,
+            Self::Turn => street >= 2,
+        }
+    }
+}
+
+impl std::fmt::Display for Endgame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Abstract => write!(f, "abstract"),
+            Self::River => write!(f, "river"),
+            Self::Turn => write!(f, "turn"),
+        }
+    }
+}
+
+static ENDGAME: std::sync::OnceLock<Endgame> = std::sync::OnceLock::<Endgame>::new();
+
+/// Returns the active endgame resolution. Defaults to `Abstract` if
+/// [`init_endgame`] was never called.
+pub fn endgame() -> Endgame {
+    *ENDGAME.get_or_init(|| Endgame::Abstract)
+}
+
+/// Sets the active endgame resolution. Must be called before any
+/// subgame solve queries it. Panics if called twice with different values.
+pub fn init_endgame(e: Endgame) {
+    if let Err(existing) = ENDGAME.set(e) {
+        assert_eq!(existing, e, "endgame already set to {existing:?}, cannot change to {e:?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_streets_are_a_suffix() {
+        assert!((0..4).all(|s| !Endgame::Abstract.exact(s)));
+        assert_eq!((0..4).filter(|s| Endgame::River.exact(*s)).collect::<Vec<_>>(), vec![3]);
+        assert_eq!((0..4).filter(|s| Endgame::Turn.exact(*s)).collect::<Vec<_>>(), vec![2, 3]);
+    }
+}
@@ -0,0 +1,42 @@
+//! Runtime-dispatched frontier evaluation for depth-limited solves.
+//!
+//! Mirrors [`crate::Endgame`]: a process-global `OnceLock` set once at
+//! startup via [`init_frontier`] and queried via [`frontier`]. Binaries
+//! take `--frontier <name>` on the CLI via clap. Like the endgame switch,
+//! this is inference configuration, not part of the bot's identity.
+
+/// How depth-limited subgame solvers value their frontier nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Frontier {
+    /// Monte Carlo rollouts of the biased blueprint continuations.
+    #[default]
+    Rollout,
+    /// A value network trained offline on rollout targets.
+    Learned,
+}
+
+impl std::fmt::Display for Frontier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rollout => write!(f, "rollout"),
+            Self::Learned => write!(f, "learned"),
+        }
+    }
+}
+
+static FRONTIER: std::sync::OnceLock<Frontier> = std::sync::OnceLock::<Frontier>::new();
+
+/// Returns the active frontier evaluation. Defaults to `Rollout` if
+/// [`init_frontier`] was never called.
+pub fn frontier() -> Frontier {
+    *FRONTIER.get_or_init(|| Frontier::Rollout)
+}
+
+/// Sets the active frontier evaluation. Must be called before any
+/// subgame solve queries it. Panics if called twice with different values.
+pub fn init_frontier(f: Frontier) {
+    if let Err(existing) = FRONTIER.set(f) {
+        assert_eq!(existing, f, "frontier already set to {existing:?}, cannot change to {f:?}");
+    }
+}
@@ -4,18 +4,24 @@
 //! used throughout the robopoker workspace.
 #![allow(dead_code)]
 
+mod endgame;
+mod frontier;
 mod id;
 mod macros;
 mod metrics;
 mod regime;
+mod shape;
 mod translate;
 mod translation;
 mod variant;
 mod version;
 
+pub use endgame::*;
+pub use frontier::*;
 pub use id::*;
 pub use metrics::*;
 pub use regime::*;
+pub use shape::*;
 pub use translate::*;
 pub use translation::*;
 pub use variant::*;
@@ -173,6 +179,8 @@ pub const MAX_IDLE_HANDS: usize = 3;
 // ============================================================================
 // K-MEANS CLUSTERING — STRUCTURAL CONSTANTS
 // Cluster counts are const-generic / array-size; can't be runtime config.
+// Wider runs are selected at build time with the `k1024` / `k4096`
+// features and written under `Version::V4`.
 // Tuning knobs (iterations, RMS interval, drift threshold) live in
 // `KmeansHyperParams` (lloyd); Sinkhorn knobs in
 // `SinkhornHyperParams`.
@@ -180,15 +188,32 @@ pub const MAX_IDLE_HANDS: usize = 3;
 const _: () = assert!(KMEANS_FLOP_CLUSTER_COUNT <= KMEANS_MAX_CLUSTER_COUNT);
 const _: () = assert!(KMEANS_TURN_CLUSTER_COUNT <= KMEANS_MAX_CLUSTER_COUNT);
 const _: () = assert!(KMEANS_EQTY_CLUSTER_COUNT <= KMEANS_MAX_CLUSTER_COUNT);
-/// Maximum clusters per street. Bound by Abstraction's 8-bit index field
-/// (0..=255 = 256 distinct values).
-pub const KMEANS_MAX_CLUSTER_COUNT: usize = 256;
+/// Maximum clusters per street. Bound by Abstraction's 14-bit index field
+/// (0..=16383 = 16384 distinct values).
+pub const KMEANS_MAX_CLUSTER_COUNT: usize = 1 << 14;
 /// Number of flop buckets (distributions over turn clusters).
+#[cfg(not(any(feature = "k1024", feature = "k4096")))]
 pub const KMEANS_FLOP_CLUSTER_COUNT: usize = 256;
+#[cfg(all(feature = "k1024", not(feature = "k4096")))]
+pub const KMEANS_FLOP_CLUSTER_COUNT: usize = 1024;
+#[cfg(feature = "k4096")]
+pub const KMEANS_FLOP_CLUSTER_COUNT: usize = 4096;
 /// Number of turn buckets (distributions over river equity).
+#[cfg(not(any(feature = "k1024", feature = "k4096")))]
 pub const KMEANS_TURN_CLUSTER_COUNT: usize = 256;
+#[cfg(all(feature = "k1024", not(feature = "k4096")))]
+pub const KMEANS_TURN_CLUSTER_COUNT: usize = 1024;
+#[cfg(feature = "k4096")]
+pub const KMEANS_TURN_CLUSTER_COUNT: usize = 4096;
 /// Equity histogram resolution (0%, 1%, ..., 100%).
 pub const KMEANS_EQTY_CLUSTER_COUNT: usize = 101;
+/// Opponent clusters in an OCHS river feature (preflop-strength groups).
+pub const OCHS_OPPONENT_CLUSTER_COUNT: usize = 8;
+/// River observations sampled to fit the OCHS k-means; every river
+/// isomorphism is then assigned to its nearest fitted centroid.
+pub const OCHS_RIVER_SAMPLE_COUNT: usize = 1 << 16;
+/// Seeded Monte Carlo runouts per preflop hand when ranking opponents.
+pub const OCHS_PREFLOP_TRIALS: usize = 2048;
 
 // ============================================================================
 // MCCFR SOLVER CONFIGURATIONS
@@ -206,7 +231,8 @@ pub const EPSILON: Probability = Probability::MIN_POSITIVE;
 // ============================================================================
 // SUBGAME SOLVING — STRUCTURAL CONSTANTS
 // `N_WORLDS` and `FRONTIER_LEAVES` are const-generic depths in the world /
-// depth solvers; they can't be runtime config. Tuning knobs live in
+// depth solvers and the default `Shape`; other sizes are dispatched at
+// runtime from `WORLD_COUNTS` × `LEAF_COUNTS`. Tuning knobs live in
 // `SubgameHyperParams` (subgame) and `FrontierHyperParams` (horizon).
 // ============================================================================
 /// Alternative opponent hand partitions in the subgame (safe subgame solving).
@@ -0,0 +1,79 @@
+//! Runtime-selected world and frontier-leaf counts for subgame solves.
+//!
+//! The solvers take both counts as const generics. [`N_WORLDS`] and
+//! [`FRONTIER_LEAVES`] stay the defaults; a [`Shape`] picks another
+//! pair from the small set every solver is monomorphized for
+//! ([`WORLD_COUNTS`] × [`LEAF_COUNTS`]), so variants with different
+//! shapes can run side by side in one process.
+use crate::FRONTIER_LEAVES;
+use crate::N_WORLDS;
+
+/// World counts a subgame solve can be dispatched to.
+pub const WORLD_COUNTS: [usize; 3] = [2, 4, 8];
+/// Frontier leaf counts a subgame solve can be dispatched to. Leaves are
+/// the first of: blueprint, fold-biased, call-biased, raise-biased.
+pub const LEAF_COUNTS: [usize; 3] = [1, 2, 4];
+
+/// Opponent-range worlds × frontier continuations for one solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shape {
+    worlds: usize,
+    leaves: usize,
+}
+
+impl Shape {
+    /// `None` unless both counts are among the monomorphized sizes.
+    pub fn new(worlds: usize, leaves: usize) -> Option<Self> {
+        (WORLD_COUNTS.contains(&worlds) && LEAF_COUNTS.contains(&leaves)).then_some(Self { worlds, leaves })
+    }
+
+    pub fn worlds(&self) -> usize {
+        self.worlds
+    }
+
+    pub fn leaves(&self) -> usize {
+        self.leaves
+    }
+
+    /// Parses the `WxL` form [`Display`](std::fmt::Display) writes.
+    pub fn parse(s: &str) -> Option<Self> {
+        let (worlds, leaves) = s.split_once('x')?;
+        Self::new(worlds.parse().ok()?, leaves.parse().ok()?)
+    }
+}
+
+impl Default for Shape {
+    fn default() -> Self {
+        Self {
+            worlds: N_WORLDS,
+            leaves: FRONTIER_LEAVES,
+        }
+    }
+}
+
+/// `WxL`, e.g. `4x4`.
+impl std::fmt::Display for Shape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}", self.worlds, self.leaves)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_shape_is_supported() {
+        let shape = Shape::default();
+        assert_eq!(Shape::new(shape.worlds(), shape.leaves()), Some(shape));
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        let shape = Shape::parse("8x2").unwrap();
+        assert_eq!((shape.worlds(), shape.leaves()), (8, 2));
+        assert_eq!(shape.to_string(), "8x2");
+        assert_eq!(Shape::parse("3x4"), None);
+        assert_eq!(Shape::parse("4"), None);
+    }
+}
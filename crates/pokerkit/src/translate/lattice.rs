@@ -131,8 +131,8 @@ where
 // --- Algorithms (dispatched by [`crate::Translation::resolve`]) ---
 //
 // All snap-family algorithms return [`Anchor`] directly. Brown-style
-// injection variants (future PR B) will return `Option<Anchor>` and let
-// the dispatcher lift to [`Translated::Free`] when off-grid.
+// injection algorithms return `Option<Anchor>` and let the dispatcher
+// lift `None` to [`Translated::Free`] when the observation stays off-grid.
 
 impl<A, P> Lattice<A, P>
 where
@@ -187,6 +187,35 @@ where
             bracket.hi()
         }
     }
+
+    /// Nearest anchor if it lies within relative distance `epsilon` of
+    /// `observed`, otherwise `None`. Distance is measured against the
+    /// larger of the two values so the rule is symmetric in over- and
+    /// under-sized observations.
+    pub fn prune(&self, observed: Scalar<A>, epsilon: f64) -> Option<Anchor> {
+        let anchor = self.snap(observed);
+        let x = observed.value();
+        let a = self.pairs[anchor.idx()].0;
+        ((x - a).abs() <= epsilon * x.abs().max(a.abs())).then_some(anchor)
+    }
+
+    /// Pseudo-harmonic argmax when the mapping is confident — the lower
+    /// anchor's probability sits outside `(epsilon, 1 - epsilon)` — and
+    /// `None` when the observation falls in the ambiguous middle of its
+    /// bracket. Clamped observations have no bracket to be confident
+    /// about and defer to [`Self::prune`].
+    pub fn confident(&self, observed: Scalar<A>, epsilon: f64) -> Option<Anchor> {
+        let bracket = self.bracket(observed);
+        if bracket.is_clamped() {
+            self.prune(observed, epsilon)
+        } else {
+            match self.pharmonic(bracket, observed) {
+                p if p >= 1. - epsilon => Some(bracket.lo()),
+                p if p <= epsilon => Some(bracket.hi()),
+                _ => None,
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -270,6 +299,24 @@ mod tests {
         assert_eq!(b.hi(), Anchor::new(1));
     }
 
+    #[test]
+    fn prune_keeps_only_near_anchors() {
+        let l = lat([0.5, 1.0, 2.0]);
+        assert_eq!(l.prune(obs(1.05), 0.1), Some(Anchor::new(1)));
+        assert_eq!(l.prune(obs(0.75), 0.1), None);
+        assert_eq!(l.prune(obs(2.1), 0.1), Some(Anchor::new(2)));
+        assert_eq!(l.prune(obs(3.0), 0.1), None);
+    }
+
+    #[test]
+    fn confident_injects_mid_bracket() {
+        let l = lat([0.5, 1.0]);
+        assert_eq!(l.confident(obs(0.51), 0.1), Some(Anchor::new(0)));
+        assert_eq!(l.confident(obs(0.99), 0.1), Some(Anchor::new(1)));
+        assert_eq!(l.confident(obs(0.75), 0.1), None);
+        assert_eq!(l.confident(obs(4.0), 0.1), None);
+    }
+
     #[test]
     fn pharmonic_formula_exact() {
         let l = lat([0.5, 1.0]);
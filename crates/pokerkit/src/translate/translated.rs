@@ -4,11 +4,10 @@
 /// [`crate::Lattice`] and a [`crate::Scalar`].
 ///
 /// - [`Self::Snap`] — resolved to a canonical lattice payload (the
-///   common case under all current policies).
+///   common case; always the outcome of the snap family).
 /// - [`Self::Free`] — left off-grid; carries the verbatim observation
 ///   value supplied by the caller. Only emitted by Brown-style
-///   injection policies (`Exact`, `EpsilonPrune`, `EpsilonHarmonic`),
-///   which are not yet wired up.
+///   injection policies (`Exact`, `EpsilonPrune`, `EpsilonHarmonic`).
 ///
 /// Type parameters:
 /// - `P` is the lattice payload type (e.g. `Size`, `Edge`, `()`)
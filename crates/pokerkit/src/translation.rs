@@ -25,12 +25,13 @@ use rand::Rng;
 /// resolve runs that algorithm against a [`Lattice`] and a [`Scalar`]
 /// to produce a [`Translated<P, F>`].
 ///
-/// All current variants always return [`Translated::Snap`] (never
-/// [`Translated::Free`]) — they snap onto the abstract grid one way or
-/// another. Brown-style abstraction-free variants (`Exact`,
-/// `EpsilonPrune`, `EpsilonHarmonic`) were elided until a player exists
-/// that can consume off-tree resolutions; pure-blueprint players panic
-/// on that case.
+/// The snap family (`Snap`, `Harmonic`, `Phargmax`) always returns
+/// [`Translated::Snap`]. The Brown-style abstraction-free family
+/// (`Exact`, `EpsilonPrune`, `EpsilonHarmonic`) returns
+/// [`Translated::Free`] whenever the observation is too far from the
+/// grid to snap with confidence. Subgame-solving players consume that
+/// case by re-solving with the observed amount as a real edge;
+/// blueprint-only lookups fall back to the nearest grid edge.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
 pub enum Translation {
@@ -41,14 +42,28 @@ pub enum Translation {
     Harmonic,
     /// Deterministic argmax variant of [`Self::Harmonic`] for replay.
     Phargmax,
+    /// Snap only onto an anchor within rounding distance; every other
+    /// amount is injected verbatim.
+    Exact,
+    /// Snap onto the nearest anchor within [`Self::EPSILON`] relative
+    /// distance, inject otherwise.
+    EpsilonPrune,
+    /// Pseudo-harmonic argmax when the lower-anchor probability is
+    /// within [`Self::EPSILON`] of certainty, inject otherwise.
+    EpsilonHarmonic,
 }
 
 impl Translation {
+    /// Relative distance below which [`Self::Exact`] treats an
+    /// observation as the anchor it rounds to.
+    pub const ROUNDING: f64 = 0.01;
+    /// Tolerance of the epsilon variants.
+    pub const EPSILON: f64 = 0.1;
+
     /// Resolve `observed` against `lattice` end-to-end. The `free` value
-    /// is the off-grid representation the caller will receive back via
+    /// is the off-grid representation the caller receives back via
     /// [`Translated::Free`] when a Brown-style variant elects not to
-    /// snap. Today's variants never read it; it is moved unconditionally
-    /// to keep the call site shape stable across future variants.
+    /// snap.
     pub fn resolve<A, P, F, R>(
         &self,
         observed: Scalar<A>,
@@ -61,13 +76,18 @@ impl Translation {
         P: Copy,
         R: Rng + ?Sized,
     {
-        let _ = free;
         let anchor = match self {
-            Self::Snap => lattice.snap(observed),
-            Self::Phargmax => lattice.phargmax(observed),
-            Self::Harmonic => lattice.harmonic(observed, rng),
+            Self::Snap => Some(lattice.snap(observed)),
+            Self::Phargmax => Some(lattice.phargmax(observed)),
+            Self::Harmonic => Some(lattice.harmonic(observed, rng)),
+            Self::Exact => lattice.prune(observed, Self::ROUNDING),
+            Self::EpsilonPrune => lattice.prune(observed, Self::EPSILON),
+            Self::EpsilonHarmonic => lattice.confident(observed, Self::EPSILON),
         };
-        Translated::Snap(*lattice.payload(anchor))
+        match anchor {
+            Some(anchor) => Translated::Snap(*lattice.payload(anchor)),
+            None => Translated::Free(free),
+        }
     }
 }
 
@@ -77,6 +97,9 @@ impl std::fmt::Display for Translation {
             Self::Snap => write!(f, "snap"),
             Self::Harmonic => write!(f, "harmonic"),
             Self::Phargmax => write!(f, "phargmax"),
+            Self::Exact => write!(f, "exact"),
+            Self::EpsilonPrune => write!(f, "epsilon-prune"),
+            Self::EpsilonHarmonic => write!(f, "epsilon-harmonic"),
         }
     }
 }
@@ -175,4 +198,16 @@ mod tests {
         assert_eq!(Translation::Snap.resolve(obs(0.4), &l, 0u32, rng), Translated::Snap("lo"),);
         assert_eq!(Translation::Phargmax.resolve(obs(1.9), &l, 0u32, rng), Translated::Snap("hi"),);
     }
+
+    #[test]
+    fn injection_variants_free_off_grid() {
+        let l: Lattice<T, &'static str> = [(0.5, "lo"), (1.0, "hi")].into_iter().collect();
+        let ref mut rng = seeded();
+        assert_eq!(Translation::Exact.resolve(obs(1.005), &l, 7u32, rng), Translated::Snap("hi"));
+        assert_eq!(Translation::Exact.resolve(obs(1.05), &l, 7u32, rng), Translated::Free(7));
+        assert_eq!(Translation::EpsilonPrune.resolve(obs(1.05), &l, 7u32, rng), Translated::Snap("hi"));
+        assert_eq!(Translation::EpsilonPrune.resolve(obs(0.75), &l, 7u32, rng), Translated::Free(7));
+        assert_eq!(Translation::EpsilonHarmonic.resolve(obs(0.52), &l, 7u32, rng), Translated::Snap("lo"));
+        assert_eq!(Translation::EpsilonHarmonic.resolve(obs(0.75), &l, 7u32, rng), Translated::Free(7));
+    }
 }
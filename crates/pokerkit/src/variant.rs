@@ -10,19 +10,22 @@
 //! `parlor::players::variant` and read this type.
 //!
 //! ```text
-//! Variant := Fish | Bot { depth: bool, world: bool, dirac: bool }
+//! Variant := Fish | Exploit | Bot { depth: bool, world: bool, dirac: bool }
 //! ```
 //!
 //! # Wire format (serde)
 //!
 //! ```json
 //! { "kind": "fish" }
+//! { "kind": "exploit" }
 //! { "kind": "bot", "depth": false, "world": true, "dirac": false }
 //! ```
 //!
 //! # CLI grammar
 //!
 //! - `fish` — random opponent (no model, not in the cube)
+//! - `exploit` — models its opponent and re-solves against the model
+//!   (the full `depth+world` solver, off the cube)
 //! - `base` — the empty flag-set cube cell (raw blueprint sample)
 //! - `+`-joined flags from `{depth, world, dirac}` in canonical order:
 //!   `depth`, `world`, `dirac`, `depth+world`, `depth+dirac`,
@@ -57,6 +60,7 @@ pub struct Config {
 pub enum Variant {
     #[default]
     Fish,
+    Exploit,
     Bot {
         depth: bool,
         world: bool,
@@ -71,6 +75,7 @@ impl Variant {
     pub fn label(self) -> &'static str {
         match self {
             Self::Fish                                                 => "fish",
+            Self::Exploit                                              => "exploit",
             Self::Bot { depth: false, world: false, dirac: false } => "base",
             Self::Bot { depth: true,  world: false, dirac: false } => "depth",
             Self::Bot { depth: false, world: true,  dirac: false } => "world",
@@ -81,10 +86,16 @@ impl Variant {
             Self::Bot { depth: true,  world: true,  dirac: true  } => "depth+world+dirac",
         }
     }
-    /// `Some(Config)` for cube cells, `None` for fish.
+    /// `Some(Config)` for cube cells, `None` for fish. The exploiter
+    /// reports the `depth+world` cell its solves run on.
     pub fn config(self) -> Option<Config> {
         match self {
             Self::Fish => None,
+            Self::Exploit => Some(Config {
+                depth: true,
+                world: true,
+                dirac: false,
+            }),
             Self::Bot { depth, world, dirac } => Some(Config { depth, world, dirac }),
         }
     }
@@ -93,6 +104,7 @@ impl Variant {
     pub fn description(self) -> &'static str {
         match self {
             Self::Fish                                                 => "Random actions",
+            Self::Exploit                                              => "Opponent-modeling subgame solver",
             Self::Bot { depth: false, world: false, dirac: false } => "Sampled blueprint",
             Self::Bot { depth: true,  world: false, dirac: false } => "Depth-limited subgame",
             Self::Bot { depth: false, world: true,  dirac: false } => "World-partitioned subgame",
@@ -111,9 +123,23 @@ impl Variant {
     pub fn uuid(self) -> uuid::Uuid {
         uuid::Uuid::new_v5(&VARIANT_NAMESPACE, self.username().as_bytes())
     }
+    /// [`Self::username`] of this variant solving at `shape`,
+    /// `bot:<label>@<shape>`. The default shape keeps the bare username,
+    /// so its recorded history carries over.
+    pub fn shaped_username(self, shape: crate::Shape) -> String {
+        if shape == crate::Shape::default() {
+            self.username()
+        } else {
+            format!("{}@{shape}", self.username())
+        }
+    }
+    /// [`Self::uuid`] of this variant solving at `shape`.
+    pub fn shaped_uuid(self, shape: crate::Shape) -> uuid::Uuid {
+        uuid::Uuid::new_v5(&VARIANT_NAMESPACE, self.shaped_username(shape).as_bytes())
+    }
     /// True if this variant needs an in-memory blueprint to play.
     pub fn requires_blueprint(self) -> bool {
-        matches!(self, Self::Bot { .. })
+        matches!(self, Self::Bot { .. } | Self::Exploit)
     }
     /// True if this variant can be chosen as the hero's opponent in a
     /// live game. Currently every variant is spawnable locally except
@@ -122,7 +148,7 @@ impl Variant {
         true
     }
     /// All variants in canonical UI display order: 8 cube cells first
-    /// (by axis triple), then fish.
+    /// (by axis triple), then the exploiter, then fish.
     #[rustfmt::skip]
     pub const fn all() -> &'static [Self] {
         &[
@@ -134,6 +160,7 @@ impl Variant {
             Self::Bot { depth: true,  world: false, dirac: true  },
             Self::Bot { depth: false, world: true,  dirac: true  },
             Self::Bot { depth: true,  world: true,  dirac: true  },
+            Self::Exploit,
             Self::Fish,
         ]
     }
@@ -142,6 +169,7 @@ impl Variant {
     pub fn parse(token: &str) -> Option<Self> {
         match token.trim() {
             "fish" => Some(Self::Fish),
+            "exploit" => Some(Self::Exploit),
             "base" => Some(Self::Bot {
                 depth: false,
                 world: false,
@@ -195,3 +223,22 @@ pub fn slumbot_opponent_username() -> &'static str {
 pub fn slumbot_opponent_uuid() -> uuid::Uuid {
     uuid::Uuid::new_v5(&VARIANT_NAMESPACE, slumbot_opponent_username().as_bytes())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Shape;
+
+    #[test]
+    fn shapes_get_distinct_identities() {
+        let depth = Variant::Bot {
+            depth: true,
+            world: false,
+            dirac: false,
+        };
+        let wide = Shape::parse("8x2").unwrap();
+        assert_eq!(depth.shaped_uuid(Shape::default()), depth.uuid());
+        assert_eq!(depth.shaped_username(wide), "bot:depth@8x2");
+        assert_ne!(depth.shaped_uuid(wide), depth.uuid());
+    }
+}
@@ -9,9 +9,13 @@
 //! V3 is the live writable version. V0/V1/V2 remain in the enum so tooling
 //! can address their DB tables, but the live training/serving codepath
 //! (post-SPR-cutover) only writes V3 — V0/V1/V2 tables are cold storage.
+//! V4 (wide K), V5 (OCHS river), and V6 (perfect recall) are opt-in
+//! clustering experiments, selected with `init_version` before any table
+//! access. V4 and only V4 pairs with a `k1024`/`k4096` build, since the
+//! cluster counts are compile-time.
 
 /// Abstraction version controlling clustering parameters and table names.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
 pub enum Version {
     /// The initial abstraction family. Bare table names (no suffix) for
@@ -29,26 +33,59 @@ pub enum Version {
     /// Pluribus-faithful grid with no SPR axis on the InfoSet key.
     /// Blueprint tables suffixed `_v3`; clustering tables continue to be
     /// read from the `_v1` suffix via [`Self::clustering_suffix`].
-    #[default]
     V3,
+    /// Wide-index clustering: flop/turn K beyond 256 (built with the
+    /// `k1024` or `k4096` feature) using Abstraction's 14-bit index.
+    /// Both clustering and blueprint tables are suffixed `_v4`, so
+    /// narrow V1 clusters are never mixed with wide ones.
+    V4,
+    /// Potential-aware river: buckets come from k-means over opponent
+    /// cluster hand strength (OCHS) instead of the 101-step equity ladder.
+    /// Clustering and blueprint tables are suffixed `_v5`.
+    V5,
+    /// Perfect recall: flop and turn cluster perfect-recall hand indices,
+    /// which remember the round each board card arrived on, instead of
+    /// suit isomorphisms of the merged board. Assignments land in the
+    /// `recall` table; clustering tables are suffixed `_v6`.
+    V6,
 }
 
 static VERSION: std::sync::OnceLock<Version> = std::sync::OnceLock::<Version>::new();
 
-/// Returns the active version. Defaults to V3.
+/// Whether this build clusters flop and turn beyond 256 buckets.
+const WIDE: bool = cfg!(any(feature = "k1024", feature = "k4096"));
+
+/// Returns the active version. Defaults to V3, or V4 in a wide build.
 pub fn version() -> Version {
-    *VERSION.get_or_init(|| Version::V3)
+    *VERSION.get_or_init(Version::native)
 }
 
 /// Sets the active version. Must be called before any table access.
-/// Panics if called twice with different values.
+/// Panics if called twice with different values, or if the version's
+/// cluster counts don't match this build's `k1024`/`k4096` features.
 pub fn init_version(v: Version) {
+    assert_eq!(
+        v.wide(),
+        WIDE,
+        "version {v} needs a build {} the k1024/k4096 feature",
+        if v.wide() { "with" } else { "without" }
+    );
     if let Err(existing) = VERSION.set(v) {
         assert_eq!(existing, v, "version already set to {existing:?}, cannot change to {v:?}");
     }
 }
 
 impl Version {
+    /// The version this build's cluster counts were made for.
+    pub fn native() -> Self {
+        if WIDE { Self::V4 } else { Self::V3 }
+    }
+
+    /// Whether this version's flop and turn clusters need a wide build.
+    pub fn wide(self) -> bool {
+        matches!(self, Self::V4)
+    }
+
     /// Database table suffix for this version.
     /// V0 uses no suffix for backwards compatibility with existing tables.
     pub fn suffix(self) -> &'static str {
@@ -57,6 +94,9 @@ impl Version {
             Self::V1 => "_v1",
             Self::V2 => "_v2",
             Self::V3 => "_v3",
+            Self::V4 => "_v4",
+            Self::V5 => "_v5",
+            Self::V6 => "_v6",
         }
     }
 
@@ -71,8 +111,31 @@ impl Version {
         match self {
             Self::V0 => "",
             Self::V1 | Self::V2 | Self::V3 => "_v1",
+            Self::V4 => "_v4",
+            Self::V5 => "_v5",
+            Self::V6 => "_v6",
         }
     }
+
+    /// Whether river buckets come from OCHS clustering rather than the
+    /// equity ladder. Under OCHS a river bucket's index orders buckets by
+    /// mean strength but is no longer a literal equity percentage.
+    pub fn ochs(self) -> bool {
+        matches!(self, Self::V5)
+    }
+
+    /// Whether clustering keys hands by perfect-recall index rather than
+    /// by suit isomorphism.
+    pub fn recall(self) -> bool {
+        matches!(self, Self::V6)
+    }
+}
+
+/// [`Version::native`], so CLI defaults follow the build's features.
+impl Default for Version {
+    fn default() -> Self {
+        Self::native()
+    }
 }
 
 impl std::fmt::Display for Version {
@@ -82,6 +145,21 @@ impl std::fmt::Display for Version {
             Self::V1 => write!(f, "v1"),
             Self::V2 => write!(f, "v2"),
             Self::V3 => write!(f, "v3"),
+            Self::V4 => write!(f, "v4"),
+            Self::V5 => write!(f, "v5"),
+            Self::V6 => write!(f, "v6"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn native_version_matches_build() {
+        assert_eq!(Version::native().wide(), WIDE);
+        assert!(Version::V4.wide());
+        assert!(!Version::V3.wide() && !Version::V5.wide() && !Version::V6.wide());
+    }
+}
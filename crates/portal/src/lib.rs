@@ -6,6 +6,7 @@
 //! - [`strategy`]  — Strategy lookups
 //! - [`gameplay`]  — Hand history evaluation and AIVAT analysis
 //! - [`hosting`]   — WebSocket game hosting infrastructure
+//! - [`study`]     — Range-vs-range solving of user-specified spots
 //! - [`training`]  — MCCFR training observability
 
 pub mod gameplay;
@@ -13,6 +14,7 @@ pub mod hosting;
 pub mod litmus;
 mod metrics;
 pub mod strategy;
+pub mod study;
 pub mod topology;
 pub mod training;
 
@@ -90,7 +92,7 @@ pub async fn run() -> Result<(), std::io::Error> {
     let crypto = web::Data::new(bouncer::Crypto::from_env());
     let casino = web::Data::new(hosting::Casino::new(client.clone()).with_blueprint(blueprint));
     let litmus_backend = litmus::Backend::new(
-        strategy::StrategyAPI::new(client.clone()),
+        strategy::StrategyAPI::new(client.clone()).with_blueprint(blueprint),
         training::TrainingAPI::new(client.clone()),
     );
     let litmus = web::Data::new(Arc::new(::litmus::Litmus::new(litmus_backend)));
@@ -142,7 +144,9 @@ pub async fn run() -> Result<(), std::io::Error> {
                     .route("/exp-wrt-obs", web::post().to(topology::handlers::exp_wrt_obs))
                     .route("/hst-wrt-abs", web::post().to(topology::handlers::hst_wrt_abs))
                     .route("/hst-wrt-obs", web::post().to(topology::handlers::hst_wrt_obs))
-                    .route("/distance", web::post().to(topology::handlers::distance)),
+                    .route("/distance", web::post().to(topology::handlers::distance))
+                    .route("/matchup", web::post().to(topology::handlers::matchup))
+                    .route("/diagnostics", web::get().to(topology::handlers::diagnostics)),
             )
             .service(
                 web::scope("/strategy")
@@ -150,9 +154,12 @@ pub async fn run() -> Result<(), std::io::Error> {
                     .route("/depth", web::post().to(strategy::handlers::solve_depth))
                     .route("/world", web::post().to(strategy::handlers::solve_world))
                     .route("/full", web::post().to(strategy::handlers::solve_full))
+                    .route("/exact", web::post().to(strategy::handlers::solve_exact))
+                    .route("/lock", web::post().to(strategy::handlers::lock))
                     .route("/range", web::post().to(strategy::handlers::range))
                     .route("/signalled", web::post().to(strategy::handlers::signalled))
-                    .route("/grid-usage", web::get().to(strategy::handlers::grid_usage)),
+                    .route("/grid-usage", web::get().to(strategy::handlers::grid_usage))
+                    .route("/chart", web::get().to(strategy::handlers::chart)),
             )
             .service(
                 web::scope("/gameplay")
@@ -176,6 +183,10 @@ pub async fn run() -> Result<(), std::io::Error> {
                     .route("/run", web::post().to(litmus::handlers::run))
                     .route("/run/markdown", web::post().to(litmus::handlers::report)),
             )
+            .service(
+                web::scope("/study")
+                    .route("/solve", web::post().to(study::handlers::solve)),
+            )
     })
     .workers(6)
     .bind(std::env::var("BIND_ADDR").expect("BIND_ADDR must be set"))?
@@ -17,7 +17,14 @@ impl Backend {
 
 #[async_trait::async_trait]
 impl litmus::Ops for Backend {
+    /// Blueprint lookup, except on streets the global
+    /// [`pokerkit::endgame`] resolves exactly, where the exact-card
+    /// subgame solve answers instead so blocker scenarios can tell hands
+    /// in one bucket apart.
     async fn policy(&self, recall: Witness) -> anyhow::Result<Option<ApiStrategy>> {
+        if pokerkit::endgame().exact(recall.street() as usize) {
+            return Ok(Some(self.strategy.solve_exact(recall).await?.policy));
+        }
         self.strategy.policy(recall).await
     }
 
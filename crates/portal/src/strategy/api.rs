@@ -2,13 +2,16 @@ use daybook::*;
 use kicker::*;
 use mccfr::Solver;
 use nlhe::*;
+use parlor::Budget;
 use parlor::Solved;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::future::Future;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::OnceLock;
 use std::time::Duration;
+use subgame::Response;
 use tokio_postgres::Client;
 
 /// Default per-decision deadline for runtime subgame solvers in
@@ -75,13 +78,42 @@ fn street_name(s: i16) -> &'static str {
 /// across an `await`.
 fn run_solve(blueprint: &'static Flagship, recall: &Witness, kind: Kind) -> ApiSolved {
     let info = NlheInfo::from((recall, blueprint.encoder().abstraction(&recall.seen())));
-    let deadline = Duration::from_millis(DEFAULT_SOLVE_DEADLINE_MS);
+    let ref budget = Budget::new(Duration::from_millis(DEFAULT_SOLVE_DEADLINE_MS));
     let solved = match kind {
-        Kind::Depth => Solved::run(blueprint.adapt_leaf(recall), info, deadline),
-        Kind::World => Solved::run(blueprint.adapt_safe(recall), info, deadline),
-        Kind::Full => Solved::run(blueprint.adapt_full(recall), info, deadline),
+        Kind::Depth => Solved::run(blueprint.adapt_leaf(recall), info, budget),
+        Kind::World => Solved::run(blueprint.adapt_safe(recall), info, budget),
+        Kind::Full => Solved::run(blueprint.adapt_full(recall), info, budget),
+        Kind::Exact => {
+            let exact = blueprint.exact();
+            let info = ExactInfo::from((info, recall.seen()));
+            Solved::run(blueprint.adapt_exact(&exact, recall), info, budget)
+        }
         Kind::Blueprint => unreachable!("Kind::Blueprint goes through the lookup path, not solve"),
     };
+    envelope(recall, info, kind, &solved)
+}
+
+/// Node-locked counterpart of [`run_solve`]: `kind` picks the world
+/// layers, and the opponent reads `lock` at its pinned nodes. Streets
+/// [`pokerkit::endgame`] resolves exactly lock the exact solve instead.
+fn run_locked(blueprint: &'static Flagship, recall: &Witness, kind: Kind, lock: &NlheLock) -> ApiSolved {
+    let info = NlheInfo::from((recall, blueprint.encoder().abstraction(&recall.seen())));
+    let ref budget = Budget::new(Duration::from_millis(DEFAULT_SOLVE_DEADLINE_MS));
+    let shape = pokerkit::Shape::default();
+    let layers = match kind {
+        Kind::World => Layers::Safe,
+        _ => Layers::Full,
+    };
+    let solved = if pokerkit::endgame().exact(recall.street() as usize) {
+        blueprint.locked_exact(&blueprint.exact(), recall, info, shape, lock, budget)
+    } else {
+        blueprint.locked(layers, recall, info, shape, lock, budget)
+    };
+    envelope(recall, info, kind, &solved)
+}
+
+/// Wraps a runtime solve's policy at `info` in the API envelope.
+fn envelope(recall: &Witness, info: NlheInfo, kind: Kind, solved: &Solved) -> ApiSolved {
     let policy = ApiStrategy {
         history: info.subgame(),
         present: Abstraction::from(info.bucket()),
@@ -142,7 +174,13 @@ impl StrategyAPI {
         self.solve(recall, Kind::Full).await
     }
 
-    /// Common dispatch for the three subgame solvers. Validates the
+    /// Runs a combined safe + depth-limited subgame solve keyed on
+    /// exact hole cards rather than abstraction buckets.
+    pub async fn solve_exact(&self, recall: Witness) -> anyhow::Result<ApiSolved> {
+        self.solve(recall, Kind::Exact).await
+    }
+
+    /// Common dispatch for the subgame solvers. Validates the
     /// witness, requires an in-memory blueprint, runs the solve on a
     /// blocking thread, and wraps the result in an `ApiSolved`. The
     /// `Kind` selects which `flagship.adapt_*` is invoked.
@@ -161,23 +199,88 @@ impl StrategyAPI {
         .await)
     }
 
+    /// Re-solves hero's decision with the opponent pinned at `pins`.
+    /// Each pin is the opponent's decision, reached by continuing
+    /// `recall` on its street, and the frequencies to fix there. `kind` must be `World`
+    /// or `Full`, the solves that carry a lockable world profile. Never
+    /// memoized: locks are what the user is experimenting with.
+    pub async fn lock(
+        &self,
+        recall: Witness,
+        kind: Kind,
+        exploit: bool,
+        pins: Vec<(Witness, BTreeMap<Edge, f32>)>,
+    ) -> anyhow::Result<ApiSolved> {
+        let recall = recall.validate()?;
+        let blueprint = self
+            .blueprint
+            .ok_or_else(|| anyhow::anyhow!("node locking requires in-memory blueprint"))?;
+        if !matches!(kind, Kind::World | Kind::Full) {
+            anyhow::bail!("node locking needs a world or full solve, not {}", kind.label());
+        }
+        let response = if exploit { Response::Best } else { Response::Restricted };
+        let lock = pins
+            .into_iter()
+            .try_fold(NlheLock::new(response), |lock, (pin, policy)| {
+                let pin = pin.validate_observation()?;
+                let (round, public) = Self::pinned(blueprint, &recall, &pin, &policy)?;
+                anyhow::Ok(lock.pin(round, public, policy.into_iter().map(|(e, p)| (NlheEdge::from(e), p))))
+            })?;
+        Ok(tokio::task::spawn_blocking(move || run_locked(blueprint, &recall, kind, &lock)).await?)
+    }
+
+    /// Street and public node of the opponent decision at `pin`, after
+    /// checking that it continues `recall` on the same street, is the
+    /// opponent's to act, and that `policy` only names edges available
+    /// there. A public node only names current-street history, so a pin
+    /// on a later street would also fix every other line into it.
+    fn pinned(
+        blueprint: &'static Flagship,
+        recall: &Witness,
+        pin: &Witness,
+        policy: &BTreeMap<Edge, f32>,
+    ) -> anyhow::Result<(usize, NlhePublic)> {
+        if !pin.actions().starts_with(recall.actions()) {
+            anyhow::bail!("pin {pin} does not continue {recall}");
+        }
+        if pin.street() != recall.street() {
+            anyhow::bail!("pin {pin} is past the {} decision being solved", recall.street());
+        }
+        if !matches!(pin.head().turn(), Turn::Choice(_)) || pin.head().turn() == recall.turn() {
+            anyhow::bail!("pin {pin} is not an opponent decision");
+        }
+        let info = NlheInfo::from((pin, blueprint.encoder().abstraction(&pin.seen())));
+        let choices = info.choices().into_iter().collect::<Vec<Edge>>();
+        if let Some(edge) = policy.keys().find(|e| !choices.contains(e)) {
+            anyhow::bail!("edge {edge} is not available at pin {pin}");
+        }
+        Ok((info.street() as usize, mccfr::CfrInfo::public(&info)))
+    }
+
     /// Opponent's hole-card-level posterior range from hero's POV.
-    pub fn range(&self, recall: Witness) -> anyhow::Result<ApiOpponentRange> {
-        self.posterior(recall, nlhe::Flagship::opponent_observations)
+    pub fn range(&self, recall: Witness, within: Option<deuce::Range>) -> anyhow::Result<ApiOpponentRange> {
+        self.posterior(recall, within, nlhe::Flagship::opponent_observations)
     }
 
     /// Hero's hole-card-level **signalled** range — the posterior an
     /// opponent could form over hero's hand from hero's observed action
     /// history. Same response shape as [`Self::range`] with hero/opponent
     /// roles swapped in the underlying reach computation.
-    pub fn signalled(&self, recall: Witness) -> anyhow::Result<ApiOpponentRange> {
-        self.posterior(recall, nlhe::Flagship::signalled_observations)
+    pub fn signalled(&self, recall: Witness, within: Option<deuce::Range>) -> anyhow::Result<ApiOpponentRange> {
+        self.posterior(recall, within, nlhe::Flagship::signalled_observations)
     }
 
     /// Common shape for `/strategy/range` and `/strategy/signalled`:
     /// validate the witness, require an in-memory blueprint, and project
     /// the `(observation, probability)` stream into the API response.
-    fn posterior<F>(&self, recall: Witness, compute: F) -> anyhow::Result<ApiOpponentRange>
+    /// With `within`, only its combos are kept and their weights are
+    /// renormalized to sum to one; an empty intersection is an error.
+    fn posterior<F>(
+        &self,
+        recall: Witness,
+        within: Option<deuce::Range>,
+        compute: F,
+    ) -> anyhow::Result<ApiOpponentRange>
     where
         F: FnOnce(&'static nlhe::Flagship, &Witness) -> Vec<(deuce::Observation, pokerkit::Probability)>,
     {
@@ -185,14 +288,42 @@ impl StrategyAPI {
         let blueprint = self
             .blueprint
             .ok_or_else(|| anyhow::anyhow!("posterior endpoint requires in-memory blueprint"))?;
+        let entries = compute(blueprint, &recall)
+            .into_iter()
+            .filter(|(obs, _)| within.as_ref().is_none_or(|r| r.weight(&deuce::Hole::from(*obs)) > 0.))
+            .collect::<Vec<_>>();
+        let mass = match within {
+            None => 1.,
+            Some(_) => entries.iter().map(|(_, w)| w).sum::<pokerkit::Probability>(),
+        };
+        if mass <= 0. {
+            anyhow::bail!("no combos of the posterior fall within the requested range");
+        }
+        let range = entries
+            .iter()
+            .map(|(obs, w)| (deuce::Hole::from(*obs), *w))
+            .collect::<deuce::Range>();
         Ok(ApiOpponentRange {
-            entries: compute(blueprint, &recall)
+            notation: range.scaled().quantized(20).notation(*recall.seen().public()),
+            entries: entries
                 .into_iter()
-                .map(|(obs, weight)| ApiRangeEntry { obs, weight })
+                .map(|(obs, weight)| ApiRangeEntry {
+                    obs,
+                    weight: weight / mass,
+                })
                 .collect(),
         })
     }
 
+    /// Preflop charts for every line with at most `depth` bets and raises,
+    /// read from the in-memory blueprint off the async runtime.
+    pub async fn charts(&self, depth: usize) -> anyhow::Result<nlhe::Charts> {
+        let blueprint = self
+            .blueprint
+            .ok_or_else(|| anyhow::anyhow!("chart export requires in-memory blueprint"))?;
+        Ok(tokio::task::spawn_blocking(move || blueprint.charts(depth)).await?)
+    }
+
     /// Aggregate per-(street, edge) strategy frequency across the entire
     /// blueprint. Expensive — full table scan with a window function.
     /// Use sparingly; intended for diagnostics, not real-time UI.
@@ -214,7 +345,7 @@ impl StrategyAPI {
                           SUM(weight) OVER (PARTITION BY past, present, choices) AS dec_total \
                    FROM   {} \
                  ) \
-                 SELECT (present::int >> 8)::SMALLINT AS street, \
+                 SELECT ((present::int >> 8) & 3)::SMALLINT AS street, \
                         edge, \
                         AVG(weight / NULLIF(dec_total, 0))::REAL AS avg_freq, \
                         (SUM(weight) / NULLIF(SUM(dec_total), 0))::REAL AS weighted_freq, \
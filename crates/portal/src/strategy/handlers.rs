@@ -2,8 +2,14 @@ use super::StrategyAPI;
 use actix_web::HttpResponse;
 use actix_web::Responder;
 use actix_web::web;
+use deuce::Range;
 use kicker::*;
 
+/// Longest preflop line of bets and raises: a sized raise at every
+/// depth through [`pokerkit::MAX_RAISE_REPEATS`], then the shove. Deeper
+/// chart requests walk nothing more.
+const MAX_CHART_DEPTH: usize = pokerkit::MAX_RAISE_REPEATS + 2;
+
 pub async fn policy(api: web::Data<StrategyAPI>, req: web::Json<GetPolicy>) -> impl Responder {
     let started = std::time::Instant::now();
     match Witness::try_build(req.turn, req.seen, req.past.clone()) {
@@ -31,8 +37,12 @@ pub async fn solve_full(api: web::Data<StrategyAPI>, req: web::Json<GetPolicy>)
     solve(req, |recall| api.solve_full(recall)).await
 }
 
+pub async fn solve_exact(api: web::Data<StrategyAPI>, req: web::Json<GetPolicy>) -> impl Responder {
+    solve(req, |recall| api.solve_exact(recall)).await
+}
+
 /// Validates the witness and dispatches to the kind-specific solver.
-/// Wrapper exists because the three solve handlers share everything
+/// Wrapper exists because the solve handlers share everything
 /// except *which* `StrategyAPI::solve_*` they call.
 async fn solve<F, Fut>(req: web::Json<GetPolicy>, dispatch: F) -> HttpResponse
 where
@@ -48,27 +58,57 @@ where
     }
 }
 
-pub async fn range(api: web::Data<StrategyAPI>, req: web::Json<GetPolicy>) -> impl Responder {
-    posterior(req, |r| api.range(r))
+pub async fn lock(api: web::Data<StrategyAPI>, req: web::Json<GetLock>) -> impl Responder {
+    let req = req.into_inner();
+    let pins = req
+        .pins
+        .into_iter()
+        .map(|pin| Witness::try_build(req.turn, req.seen, pin.past).map(|w| (w, pin.policy)))
+        .collect::<Result<Vec<_>, _>>();
+    match Witness::try_build(req.turn, req.seen, req.past).and_then(|recall| pins.map(|pins| (recall, pins))) {
+        Err(e) => HttpResponse::BadRequest().body(format!("invalid action sequence: {e}")),
+        Ok((recall, pins)) => api.lock(recall, req.kind, req.exploit, pins).await.map_or_else(
+            |e| HttpResponse::InternalServerError().body(e.to_string()),
+            |solved| HttpResponse::Ok().json(solved),
+        ),
+    }
 }
 
-pub async fn signalled(api: web::Data<StrategyAPI>, req: web::Json<GetPolicy>) -> impl Responder {
-    posterior(req, |r| api.signalled(r))
+pub async fn range(api: web::Data<StrategyAPI>, req: web::Json<GetPosterior>) -> impl Responder {
+    posterior(req, |r, within| api.range(r, within))
 }
 
-fn posterior<F>(req: web::Json<GetPolicy>, compute: F) -> HttpResponse
+pub async fn signalled(api: web::Data<StrategyAPI>, req: web::Json<GetPosterior>) -> impl Responder {
+    posterior(req, |r, within| api.signalled(r, within))
+}
+
+fn posterior<F>(req: web::Json<GetPosterior>, compute: F) -> HttpResponse
 where
-    F: FnOnce(Witness) -> anyhow::Result<ApiOpponentRange>,
+    F: FnOnce(Witness, Option<Range>) -> anyhow::Result<ApiOpponentRange>,
 {
-    match Witness::try_build(req.turn, req.seen, req.past.clone()) {
+    let req = req.into_inner();
+    match Witness::try_build(req.turn, req.seen, req.past) {
         Err(e) => HttpResponse::BadRequest().body(format!("invalid action sequence: {e}")),
-        Ok(recall) => compute(recall).map_or_else(
+        Ok(recall) => compute(recall, req.within).map_or_else(
             |e| HttpResponse::InternalServerError().body(e.to_string()),
             |range| HttpResponse::Ok().json(range),
         ),
     }
 }
 
+pub async fn chart(api: web::Data<StrategyAPI>, req: web::Query<GetChart>) -> impl Responder {
+    match api.charts(req.depth.min(MAX_CHART_DEPTH)).await {
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+        Ok(charts) => match req.format {
+            ChartFormat::Json => HttpResponse::Ok().content_type("application/json").body(charts.json()),
+            ChartFormat::Csv => HttpResponse::Ok().content_type("text/csv").body(charts.csv()),
+            ChartFormat::Html => HttpResponse::Ok()
+                .content_type("text/html; charset=utf-8")
+                .body(charts.html()),
+        },
+    }
+}
+
 pub async fn grid_usage(api: web::Data<StrategyAPI>) -> impl Responder {
     match api.grid_usage().await {
         Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
@@ -0,0 +1,58 @@
+//! HTTP handlers for the `/study/*` route family.
+//!
+//!   /study/solve  → POST spot.json plus `iterations`, returns `study::Solution`
+
+use actix_web::{HttpResponse, Responder, web};
+use study::Spot;
+use study::Study;
+use tokio::sync::Semaphore;
+
+/// Most iterations a single request may ask for.
+const MAX_ITERATIONS: usize = 10_000;
+/// Most strategy-cell updates (tree cells × iterations) a single request
+/// may ask for, so a large tree cannot also take the full iteration cap.
+const MAX_WORK: usize = 1 << 33;
+/// Solves allowed to run at once; others are turned away, not queued.
+const MAX_SOLVES: usize = 2;
+
+static SOLVES: Semaphore = Semaphore::const_new(MAX_SOLVES);
+
+#[derive(Debug, serde::Deserialize)]
+pub struct SolveSpot {
+    #[serde(flatten)]
+    spot: Spot,
+    #[serde(default = "SolveSpot::iterations")]
+    iterations: usize,
+}
+
+impl SolveSpot {
+    fn iterations() -> usize {
+        1_000
+    }
+}
+
+pub async fn solve(req: web::Json<SolveSpot>) -> impl Responder {
+    let SolveSpot { spot, iterations } = req.into_inner();
+    if iterations > MAX_ITERATIONS {
+        return HttpResponse::BadRequest().body(format!("iterations capped at {MAX_ITERATIONS}"));
+    }
+    let Ok(permit) = SOLVES.try_acquire() else {
+        return HttpResponse::ServiceUnavailable().body(format!("{MAX_SOLVES} solves already running; retry shortly"));
+    };
+    match tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        let mut study = Study::try_from(spot)?;
+        anyhow::ensure!(
+            study.cells().saturating_mul(iterations) <= MAX_WORK,
+            "{} strategy cells × {iterations} iterations exceeds {MAX_WORK}; ask for fewer iterations",
+            study.cells()
+        );
+        Ok(study.solve(iterations))
+    })
+    .await
+    {
+        Ok(Ok(solution)) => HttpResponse::Ok().json(solution),
+        Ok(Err(e)) => HttpResponse::BadRequest().body(format!("invalid spot: {e}")),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
@@ -0,0 +1,6 @@
+//! Range-vs-range spot solving.
+//!
+//! Stateless: every request carries its own spot and is solved from
+//! scratch on a blocking thread.
+
+pub mod handlers;
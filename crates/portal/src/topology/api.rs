@@ -115,6 +115,32 @@ impl TopologyAPI {
     }
 }
 
+// range equity calculations
+impl TopologyAPI {
+    /// Range-vs-range or multiway equity, computed off the async runtime.
+    pub async fn matchup(&self, board: Hand, ranges: Vec<Range>, trials: usize) -> anyhow::Result<Estimate> {
+        let matchup = Matchup::new(board, ranges).map_err(|e| anyhow::anyhow!("invalid matchup: {e}"))?;
+        tokio::task::spawn_blocking(move || matchup.equity(trials))
+            .await
+            .map_err(|e| anyhow::anyhow!("matchup task: {e}"))?
+            .map_err(|e| anyhow::anyhow!("matchup equity: {e}"))
+    }
+}
+
+// clustering diagnostics
+impl TopologyAPI {
+    /// Cluster quality of every street, sampling `n` hands per street.
+    /// Equities are simulated off the async runtime.
+    pub async fn diagnostics(&self, n: usize) -> anyhow::Result<Diagnostics> {
+        let samples = Diagnostics::sample(&self.0, n)
+            .await
+            .map_err(|e| anyhow::anyhow!("sample diagnostics: {e}"))?;
+        tokio::task::spawn_blocking(move || samples.into_iter().map(Report::from).collect())
+            .await
+            .map_err(|e| anyhow::anyhow!("diagnostics task: {e}"))
+    }
+}
+
 // distance calculations
 impl TopologyAPI {
     pub async fn abs_distance(&self, abs1: Abstraction, abs2: Abstraction) -> anyhow::Result<Energy> {
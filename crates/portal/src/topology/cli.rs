@@ -35,8 +35,147 @@ impl CLI {
         }
     }
 
+    /// One-shot mode for `convert <command> ...` from the shell. Commands
+    /// that need no database (range notation, charts from a blueprint
+    /// file, diagnostics from artifact files) run without connecting to one.
+    pub async fn once(args: impl IntoIterator<Item = String>) {
+        let result = match Query::try_parse_from(args) {
+            Err(e) => e.exit(),
+            Ok(query) if query.offline() => Self::local(query),
+            Ok(query) => Self(TopologyAPI::from(daybook::db().await)).query(query).await,
+        };
+        if let Err(e) = result {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    }
+
     async fn handle(&self, input: &str) -> Result<(), Box<dyn std::error::Error>> {
-        match Query::try_parse_from(std::iter::once("> ").chain(input.split_whitespace()))? {
+        self.query(Query::try_parse_from(std::iter::once("> ").chain(input.split_whitespace()))?)
+            .await
+    }
+
+    /// Queries answered without touching the database.
+    fn local(query: Query) -> Result<(), Box<dyn std::error::Error>> {
+        match query {
+            Query::Range { range, board } => {
+                let board = Hand::try_from(board.as_str())?;
+                let range = Range::try_from(range.join(",").as_str())?.blocked(board);
+                println!("{}", range.notation(board));
+                println!("{} combos, {:.2} weighted", range.len(), range.mass());
+                Ok(())
+            }
+            Query::Chart {
+                depth,
+                out,
+                blueprint: Some(path),
+            } => Self::chart(&nlhe::Flagship::load(path)?, depth, &out),
+            Query::Diagnose {
+                samples,
+                out,
+                artifacts: Some(dir),
+            } => Self::diagnose(&lloyd::Diagnostics::from_dir(dir, samples)?, &out),
+            Query::Compare {
+                left,
+                right,
+                samples,
+                top,
+                out,
+                ..
+            } if Query::version(&left).is_none() && Query::version(&right).is_none() => {
+                Self::compare(&lloyd::Lookup::load(left)?, &lloyd::Lookup::load(right)?, samples, top, &out)
+            }
+            _ => Err("query requires a database connection".into()),
+        }
+    }
+
+    /// Writes `preflop.{json,csv,html}` for every line up to `depth` into `out`.
+    fn chart(blueprint: &nlhe::Flagship, depth: usize, out: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let charts = blueprint.charts(depth);
+        let dir = std::path::Path::new(out);
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join("preflop.json"), charts.json())?;
+        std::fs::write(dir.join("preflop.csv"), charts.csv())?;
+        std::fs::write(dir.join("preflop.html"), charts.html())?;
+        println!("{} charts → {}/preflop.{{json,csv,html}}", charts.len(), dir.display());
+        Ok(())
+    }
+
+    /// Writes `diagnostics.{json,html}` into `out`.
+    fn diagnose(report: &lloyd::Diagnostics, out: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::path::Path::new(out);
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join("diagnostics.json"), report.json())?;
+        std::fs::write(dir.join("diagnostics.html"), report.html())?;
+        println!("{} streets → {}/diagnostics.{{json,html}}", report.iter().count(), dir.display());
+        Ok(())
+    }
+
+    /// Writes the comparison of two lookups to `out` as JSON.
+    fn compare(
+        left: &lloyd::Lookup,
+        right: &lloyd::Lookup,
+        samples: usize,
+        top: usize,
+        out: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if left.street() != right.street() {
+            return Err(format!("cannot compare {} with {}", left.street(), right.street()).into());
+        }
+        let comparison = lloyd::Comparison::new(left, right, samples, top);
+        std::fs::write(out, comparison.json())?;
+        println!(
+            "{} buckets → {} buckets, ARI {:.4}, NMI {:.4} → {out}",
+            comparison.left, comparison.right, comparison.rand, comparison.mutual
+        );
+        Ok(())
+    }
+
+    /// One side of a comparison: a version's table, or a lookup file.
+    async fn side(&self, target: &str, street: Street) -> Result<lloyd::Lookup, Box<dyn std::error::Error>> {
+        match Query::version(target) {
+            Some(version) => Ok(lloyd::Lookup::from_version(self.0.client(), street, version).await?),
+            None => Ok(lloyd::Lookup::load(target)?),
+        }
+    }
+
+    async fn query(&self, query: Query) -> Result<(), Box<dyn std::error::Error>> {
+        match query {
+            query @ (Query::Range { .. }
+            | Query::Chart { blueprint: Some(_), .. }
+            | Query::Diagnose { artifacts: Some(_), .. }) => Self::local(query),
+            Query::Diagnose {
+                samples,
+                out,
+                artifacts: None,
+            } => Self::diagnose(&self.0.diagnostics(samples).await?, &out),
+            Query::Chart {
+                depth,
+                out,
+                blueprint: None,
+            } => {
+                let blueprint = <nlhe::Flagship as daybook::Hydrate>::hydrate(self.0.client().clone()).await;
+                Self::chart(&blueprint, depth, &out)
+            }
+            query @ Query::Compare { .. } if query.offline() => Self::local(query),
+            Query::Compare {
+                left,
+                right,
+                street,
+                samples,
+                top,
+                out,
+            } => {
+                let street = Street::try_from(street.as_str())?;
+                let (left, right) = (self.side(&left, street).await?, self.side(&right, street).await?);
+                Self::compare(&left, &right, samples, top, &out)
+            }
+            Query::Save { path } => {
+                let blueprint = <nlhe::Flagship as daybook::Hydrate>::hydrate(self.0.client().clone()).await;
+                blueprint.save(&path)?;
+                println!("blueprint → {path}");
+                Ok(())
+            }
             Query::Abstraction { target } => {
                 if let Ok(obs) = Observation::try_from(target.as_str()) {
                     println!("{}", self.0.obs_to_abs(obs).await?);
@@ -70,6 +209,22 @@ impl CLI {
                 }
                 Err("invalid equity target".into())
             }
+            Query::Matchup { ranges, board, trials } => {
+                let board = Hand::try_from(board.as_str())?;
+                let ranges = Self::ranges(&ranges)
+                    .iter()
+                    .map(|r| Range::try_from(r.as_str()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let estimate = self.0.matchup(board, ranges.clone(), trials).await?;
+                let method = if estimate.exact { "exact" } else { "monte carlo" };
+                println!("{method} over {} showdowns", estimate.samples);
+                for (i, range) in ranges.iter().enumerate() {
+                    let equity = estimate.equity[i];
+                    let error = estimate.error[i];
+                    println!("{:>2}. {:>7.4} ± {:.4}  ({} combos)", i + 1, equity, error, range.len());
+                }
+                Ok(())
+            }
             Query::Population { target } => {
                 if let Ok(obs) = Observation::try_from(target.as_str()) {
                     println!("{}", self.0.obs_population(obs).await?);
@@ -227,4 +382,16 @@ impl CLI {
             }
         }
     }
+
+    /// Re-joins whitespace-split range arguments, so that canonical
+    /// notation like `QQ+, AK` (tokens ending in a comma) stays one range.
+    fn ranges(args: &[String]) -> Vec<String> {
+        args.iter().fold(Vec::<String>::new(), |mut ranges, arg| {
+            match ranges.last_mut() {
+                Some(last) if last.ends_with(',') => *last = format!("{last} {arg}"),
+                _ => ranges.push(arg.clone()),
+            }
+            ranges
+        })
+    }
 }
@@ -5,6 +5,13 @@ use actix_web::web;
 use deuce::*;
 use kicker::*;
 
+/// Hands sampled per street by one diagnostics request; each pre-river
+/// sample simulates its equity.
+const MAX_DIAGNOSTIC_SAMPLES: usize = 4096;
+
+/// Monte Carlo trials one matchup request may ask for: 16× the default.
+const MAX_MATCHUP_TRIALS: usize = Matchup::TRIALS << 4;
+
 pub async fn replace_obs(api: web::Data<TopologyAPI>, req: web::Json<ReplaceObs>) -> impl Responder {
     match api.replace_obs(req.obs).await {
         Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
@@ -103,3 +110,37 @@ pub async fn distance(api: web::Data<TopologyAPI>, req: web::Json<GetDistance>)
         Ok(d) => HttpResponse::Ok().json(d),
     }
 }
+
+pub async fn matchup(api: web::Data<TopologyAPI>, req: web::Json<GetMatchup>) -> impl Responder {
+    let board = match Hand::try_from(req.board.as_str()) {
+        Ok(board) => board,
+        Err(e) => return HttpResponse::BadRequest().body(format!("invalid board: {e}")),
+    };
+    let ranges = match req.ranges.iter().map(|r| Range::try_from(r.as_str())).collect() {
+        Ok(ranges) => ranges,
+        Err(e) => return HttpResponse::BadRequest().body(format!("invalid range: {e}")),
+    };
+    let trials = match req.trials.unwrap_or(Matchup::TRIALS) {
+        0 => return HttpResponse::BadRequest().body("trials must be at least 1"),
+        n if n > MAX_MATCHUP_TRIALS => {
+            return HttpResponse::BadRequest().body(format!("trials must be at most {MAX_MATCHUP_TRIALS}"));
+        }
+        n => n,
+    };
+    match api.matchup(board, ranges, trials).await {
+        Err(e) => HttpResponse::BadRequest().body(e.to_string()),
+        Ok(estimate) => HttpResponse::Ok().json(estimate),
+    }
+}
+
+pub async fn diagnostics(api: web::Data<TopologyAPI>, req: web::Query<GetDiagnostics>) -> impl Responder {
+    match api.diagnostics(req.samples.min(MAX_DIAGNOSTIC_SAMPLES)).await {
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+        Ok(report) => match req.format {
+            ReportFormat::Json => HttpResponse::Ok().content_type("application/json").body(report.json()),
+            ReportFormat::Html => HttpResponse::Ok()
+                .content_type("text/html; charset=utf-8")
+                .body(report.html()),
+        },
+    }
+}
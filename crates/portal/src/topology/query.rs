@@ -1,4 +1,5 @@
 use clap::Parser;
+use deuce::Matchup;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -36,6 +37,78 @@ pub enum Query {
         #[arg(required = true)]
         target: String,
     },
+    #[command(
+        about = "Find the showdown equity of two or more ranges, e.g. `AKs, TT+ A5s-A2s --board AhKd2c`",
+        alias = "vs"
+    )]
+    Matchup {
+        #[arg(required = true, num_args = 2..)]
+        ranges: Vec<String>,
+        #[arg(long, short, default_value = "")]
+        board: String,
+        #[arg(long, short, default_value_t = Matchup::TRIALS)]
+        trials: usize,
+    },
+    #[command(
+        about = "Normalize a range to canonical notation, e.g. `AKs AKo:0.5 QQ KK AA --board Ah7d2c`",
+        alias = "rng"
+    )]
+    Range {
+        #[arg(required = true, num_args = 1..)]
+        range: Vec<String>,
+        #[arg(long, short, default_value = "")]
+        board: String,
+    },
+    #[command(
+        about = "Export preflop charts (JSON, CSV, HTML) for lines with up to `depth` bets and raises",
+        alias = "chr"
+    )]
+    Chart {
+        #[arg(long, short, default_value_t = 2)]
+        depth: usize,
+        #[arg(long, short, default_value = "charts")]
+        out: String,
+        /// Read the blueprint from a file written by `save` instead of the database.
+        #[arg(long, short)]
+        blueprint: Option<String>,
+    },
+    #[command(
+        about = "Report clustering quality (JSON, HTML) per street over `samples` sampled hands",
+        alias = "dgn"
+    )]
+    Diagnose {
+        #[arg(long, short, default_value_t = 1024)]
+        samples: usize,
+        #[arg(long, short, default_value = "diagnostics")]
+        out: String,
+        /// Read artifacts from a directory written by `trainer --cluster --artifacts` instead of the database.
+        #[arg(long, short)]
+        artifacts: Option<String>,
+    },
+    #[command(
+        about = "Compare two abstractions of a street, each a version (e.g. `v1`) or a lookup file",
+        alias = "cmp"
+    )]
+    Compare {
+        #[arg(required = true)]
+        left: String,
+        #[arg(required = true)]
+        right: String,
+        /// Street to read when a side names a version.
+        #[arg(long, default_value = "flop")]
+        street: String,
+        #[arg(long, short, default_value_t = 4096)]
+        samples: usize,
+        #[arg(long, short, default_value_t = 20)]
+        top: usize,
+        #[arg(long, short, default_value = "comparison.json")]
+        out: String,
+    },
+    #[command(about = "Save the database blueprint to a file for offline use", alias = "sav")]
+    Save {
+        #[arg(required = true)]
+        path: String,
+    },
     #[command(about = "Find the population of any given observation or abstraction", alias = "pop")]
     Population {
         #[arg(required = true)]
@@ -72,3 +145,21 @@ pub enum Query {
         value: i64,
     },
 }
+
+impl Query {
+    /// True for queries that never need the database.
+    pub fn offline(&self) -> bool {
+        match self {
+            Self::Compare { left, right, .. } => Self::version(left).is_none() && Self::version(right).is_none(),
+            _ => matches!(
+                self,
+                Self::Range { .. } | Self::Chart { blueprint: Some(_), .. } | Self::Diagnose { artifacts: Some(_), .. }
+            ),
+        }
+    }
+
+    /// Parses a comparison side that names a version rather than a file.
+    pub fn version(target: &str) -> Option<pokerkit::Version> {
+        <pokerkit::Version as clap::ValueEnum>::from_str(target, true).ok()
+    }
+}
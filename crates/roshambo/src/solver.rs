@@ -24,7 +24,13 @@ where
         &self.profile
     }
 
-    fn payoffs(&self, prefix: &Prefix<Self::T, Self::E>, game: &Self::G, _: Self::T) -> Payoffs<L> {
+    fn payoffs(
+        &self,
+        prefix: &Prefix<Self::T, Self::E>,
+        _: &[(Self::T, Self::I, Self::E)],
+        game: &Self::G,
+        _: Self::T,
+    ) -> Payoffs<L> {
         Payoffs::uniform(
             self.profile
                 .frontier_payoff(&self.resume(prefix.into_iter().edges(), game)),
@@ -4,6 +4,7 @@ use crate::result::*;
 use crate::session::*;
 use kicker::Turn;
 use parlor::VariantExt;
+use pokerkit::Shape;
 use pokerkit::Variant;
 use tracing::Instrument;
 use vitals::KeyValue;
@@ -19,6 +20,7 @@ impl Benchmark {
     /// the aggregate in-flight cap is enforced globally, not per-variant.
     pub async fn run(
         variant: Variant,
+        shape: Shape,
         player: &mut dyn parlor::Player,
         hands: usize,
         recorder: &mut Recorder,
@@ -41,7 +43,7 @@ impl Benchmark {
             let outcome = Session::play(&mut client, player, recorder).instrument(span).await;
             match outcome {
                 Ok(result) => {
-                    record_hand(variant, &result);
+                    record_hand(variant, shape, &result);
                     bench.results.push(result);
                     if (i + 1) % 100 == 0 || i + 1 == hands {
                         tracing::info!(
@@ -55,7 +57,7 @@ impl Benchmark {
                     }
                 }
                 Err(e) => {
-                    record_error(variant);
+                    record_error(variant, shape);
                     tracing::warn!(variant = label, hand = i + 1, error = %e, "slumbot hand failed");
                     client = Client::new().with_throttle(throttle.clone());
                 }
@@ -66,6 +68,7 @@ impl Benchmark {
     /// Play hands continuously until interrupted, with rate limiting and backoff.
     pub async fn continuous(
         variant: Variant,
+        shape: Shape,
         player: &mut dyn parlor::Player,
         recorder: &mut Recorder,
         throttle: Throttle,
@@ -87,7 +90,7 @@ impl Benchmark {
             let outcome = Session::play(&mut client, player, recorder).instrument(span).await;
             match outcome {
                 Ok(result) => {
-                    record_hand(variant, &result);
+                    record_hand(variant, shape, &result);
                     bench.results.push(result);
                     errors = 0;
                     if bench.results.len().is_multiple_of(100) {
@@ -102,7 +105,7 @@ impl Benchmark {
                     tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
                 }
                 Err(e) => {
-                    record_error(variant);
+                    record_error(variant, shape);
                     errors += 1;
                     tracing::warn!(
                         variant = label,
@@ -173,14 +176,22 @@ impl Benchmark {
     }
 }
 
-/// Standard slumbot metric label set: cube coordinate (4 keys) + regime.
-fn labels(variant: Variant) -> [KeyValue; 5] {
+/// Standard slumbot metric label set: cube coordinate (4 keys) + solve
+/// shape + regime.
+fn labels(variant: Variant, shape: Shape) -> [KeyValue; 6] {
     let [v, p, d, w] = variant.keys();
-    [v, p, d, w, KeyValue::new("regime", pokerkit::regime().to_string())]
+    [
+        v,
+        p,
+        d,
+        w,
+        KeyValue::new("shape", shape.to_string()),
+        KeyValue::new("regime", pokerkit::regime().to_string()),
+    ]
 }
 
-fn record_hand(variant: Variant, result: &HandResult) {
-    let lab = labels(variant);
+fn record_hand(variant: Variant, shape: Shape, result: &HandResult) {
+    let lab = labels(variant, shape);
     let m = vitals::metrics::get();
     m.slumbot_hands.add(1, &lab);
     m.slumbot_hand_bb.record(result.winnings_bb, &lab);
@@ -191,7 +202,7 @@ fn record_hand(variant: Variant, result: &HandResult) {
     }
 }
 
-fn record_error(variant: Variant) {
-    let lab = labels(variant);
+fn record_error(variant: Variant, shape: Shape) {
+    let lab = labels(variant, shape);
     vitals::metrics::get().slumbot_errors.add(1, &lab);
 }
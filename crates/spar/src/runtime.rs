@@ -7,7 +7,10 @@
 //! `variant` grammar) and falls back to the global
 //! `--sessions N` flag (default `1`) when absent — so fast DB-lookup
 //! variants like `blueprint` can stay at 1 while CFR-thinking variants
-//! saturate vCPUs. All sessions across all variants share:
+//! saturate vCPUs. An `@WxL` before the count sizes that variant's
+//! subgame solves (see [`Shape`]), so one run can sweep shapes:
+//! `depth+world@4x4*2,depth+world@8x2*2`. All sessions across all
+//! variants share:
 //!
 //! - `Arc<tokio_postgres::Client>` for hand/action inserts
 //! - `&'static Flagship` for any subgame variant (any cell with `depth` or `world`)
@@ -15,8 +18,10 @@
 //! - SIGTERM / `TRAIN_DURATION` interrupt signal via [`pokerkit::brb`]
 //!
 //! Each session owns its own `Player`, `Recorder` (own Room row), and
-//! `slumbot::Client` (own auth token). Sessions of the same variant emit
-//! metrics with identical labels (`variant`, `regime`, `aws_ecs_task_id`)
+//! `slumbot::Client` (own auth token). Hands are recorded under the
+//! variant's shaped member (`bot:<label>@WxL` off the default shape), so
+//! shapes of one variant never share a player in the hand history. Sessions of the same variant emit
+//! metrics with identical labels (`variant`, `shape`, `regime`, `aws_ecs_task_id`)
 //! so OTLP aggregates them — the variant's bb/100 panel stays correct
 //! across session counts. The point of multi-session is filling
 //! otherwise-idle CPU cores during a single Player's CFR-solve wait:
@@ -26,7 +31,10 @@ use crate::benchmark::*;
 use crate::client::*;
 use crate::mode::*;
 use crate::recorder::*;
+use bouncer::AuthRepository;
 use parlor::VariantExt;
+use pokerkit::Shape;
+use pokerkit::Unique;
 use pokerkit::Variant;
 use tracing::Instrument;
 
@@ -34,9 +42,10 @@ use tracing::Instrument;
 /// reading CLI flags (so the Dockerfile CMD can pipe env vars through
 /// shell substitution without touching Rust).
 pub struct Runtime {
-    variants: Vec<(Variant, usize)>,
+    variants: Vec<(Variant, Shape, usize)>,
     mode: Mode,
     max_inflight: usize,
+    cache: Option<std::path::PathBuf>,
 }
 
 impl Runtime {
@@ -44,13 +53,16 @@ impl Runtime {
         let parsed = parse_list(variants);
         if parsed.is_empty() {
             eprintln!("usage: slumbot --variants=a,b,c [--hands N] [--continuous] [--throttle N] [--sessions N]");
-            eprintln!("       grammar: 8 hypercube cells + `fish`");
-            eprintln!("         fish | base | depth | world | dirac |");
+            eprintln!("       grammar: 8 hypercube cells + `fish` + `exploit`");
+            eprintln!("         fish | exploit | base | depth | world | dirac |");
             eprintln!("         depth+world | depth+dirac | world+dirac | depth+world+dirac");
             eprintln!("       (`+`-joined flags from {{depth, world, dirac}} in canonical order;");
             eprintln!("        `base` is the sentinel name for the empty flag-set cell)");
             eprintln!("       per-variant session override: trailing `*N` on a token");
             eprintln!("         example: base*1,dirac*1,depth+dirac*4,depth+world*4");
+            eprintln!("       per-variant solve shape: `@WxL` before any `*N` (worlds x frontier leaves)");
+            eprintln!("         worlds in {:?}, leaves in {:?}", pokerkit::WORLD_COUNTS, pokerkit::LEAF_COUNTS);
+            eprintln!("         example: depth+world@4x4*2,depth+world@8x2*2");
             eprintln!("       --sessions: default session count when no `*N` suffix is given (default 1).");
             eprintln!("                   set to task vCPU count to saturate CPU during CFR think.");
             std::process::exit(1);
@@ -58,44 +70,56 @@ impl Runtime {
         let default = default_sessions.max(1);
         let variants = parsed
             .into_iter()
-            .map(|(v, n)| (v, n.unwrap_or(default).max(1)))
+            .map(|(v, shape, n)| (v, shape, n.unwrap_or(default).max(1)))
             .collect();
         Self {
             variants,
             mode: if continuous { Mode::Continuous } else { Mode::Fixed(hands) },
             max_inflight,
+            cache: None,
         }
     }
 
+    /// Backs the subgame solution cache with the file at `path`, loaded
+    /// once the blueprint is and saved when the run ends. Without it,
+    /// every decision solves fresh.
+    pub fn cache(mut self, path: Option<std::path::PathBuf>) -> Self {
+        self.cache = path;
+        self
+    }
+
     pub async fn run(self) {
         tracing::info!(
-            variants = %self.variants.iter().map(|(v, n)| format!("{}*{}", v.label(), n)).collect::<Vec<_>>().join(","),
+            variants = %self.variants.iter().map(|(v, shape, n)| format!("{}@{}*{}", v.label(), shape, n)).collect::<Vec<_>>().join(","),
             mode = ?self.mode,
             max_inflight = self.max_inflight,
             "slumbot runtime starting",
         );
         let db = connect().await;
-        let flagship = if self.variants.iter().any(|(v, _)| v.requires_blueprint()) {
+        let flagship = if self.variants.iter().any(|(v, _, _)| v.requires_blueprint()) {
             Some(parlor::hydrate_blueprint(db.clone()).await)
         } else {
             None
         };
+        if let (Some(path), Some(flagship)) = (&self.cache, flagship) {
+            let capacity = subgame::SubgameHyperParams::get().cache().capacity();
+            parlor::SolutionCache::load(path, capacity, parlor::Provenance::of(flagship))
+                .expect("load solution cache")
+                .install();
+        }
         let throttle = Throttle::new(self.max_inflight);
         let handles: Vec<_> = self
             .variants
             .iter()
             .copied()
-            .flat_map(|(v, n)| (0..n).map(move |i| (v, i)))
-            .map(|(v, session)| {
+            .flat_map(|(v, shape, n)| (0..n).map(move |i| (v, shape, i)))
+            .map(|(v, shape, session)| {
                 let db = db.clone();
                 let throttle = throttle.clone();
                 let mode = self.mode;
                 tokio::spawn(
-                    async move { execute(v, db, flagship, throttle, mode).await }.instrument(tracing::info_span!(
-                        "variant",
-                        name = v.label(),
-                        session
-                    )),
+                    async move { execute(v, shape, db, flagship, throttle, mode).await }
+                        .instrument(tracing::info_span!("variant", name = v.label(), shape = %shape, session)),
                 )
             })
             .collect();
@@ -104,6 +128,9 @@ impl Runtime {
                 .inspect_err(|e| tracing::error!(error = %e, "variant task panicked"))
                 .ok();
         }
+        parlor::SolutionCache::persist()
+            .inspect_err(|e| tracing::error!(error = %e, "solution cache save failed"))
+            .ok();
     }
 }
 
@@ -123,32 +150,39 @@ async fn connect() -> std::sync::Arc<tokio_postgres::Client> {
 
 async fn execute(
     variant: Variant,
+    shape: Shape,
     db: std::sync::Arc<tokio_postgres::Client>,
     flagship: Option<&'static nlhe::Flagship>,
     throttle: Throttle,
     mode: Mode,
 ) {
-    let mut player = variant.into_player(flagship);
-    let mut recorder = Recorder::new(db, variant.id()).await;
-    run_benchmark(variant, player.as_mut(), &mut recorder, throttle, mode).await;
+    let mut player = variant.into_shaped(shape, flagship);
+    let member = variant.shaped_member(shape);
+    db.seed(&member)
+        .await
+        .inspect_err(|e| tracing::warn!(error = %e, username = member.username(), "failed to seed bot user"))
+        .ok();
+    let mut recorder = Recorder::new(db, member.id()).await;
+    run_benchmark(variant, shape, player.as_mut(), &mut recorder, throttle, mode).await;
 }
 
 async fn run_benchmark(
     variant: Variant,
+    shape: Shape,
     player: &mut dyn parlor::Player,
     recorder: &mut Recorder,
     throttle: Throttle,
     mode: Mode,
 ) {
     let label = variant.label();
-    tracing::info!(variant = label, ?mode, "benchmark starting");
+    tracing::info!(variant = label, %shape, ?mode, "benchmark starting");
     match mode {
-        Mode::Fixed(hands) => match Benchmark::run(variant, player, hands, recorder, throttle).await {
+        Mode::Fixed(hands) => match Benchmark::run(variant, shape, player, hands, recorder, throttle).await {
             Ok(bench) => bench.report(),
             Err(e) => tracing::error!(variant = label, error = %e, "benchmark failed"),
         },
         Mode::Continuous => {
-            Benchmark::continuous(variant, player, recorder, throttle)
+            Benchmark::continuous(variant, shape, player, recorder, throttle)
                 .await
                 .report();
         }
@@ -156,16 +190,24 @@ async fn run_benchmark(
 }
 
 /// Parse a comma-separated list (the `--variants` argument value),
-/// deduping by label. `Option<usize>` is the per-variant session-count
-/// override extracted from a trailing `*N`. Prints to stderr and exits
-/// on any unknown token or malformed `*N` suffix — runs at binary
-/// startup, fail-fast.
-fn parse_list(raw: &str) -> Vec<(Variant, Option<usize>)> {
-    let mut out: Vec<(Variant, Option<usize>)> = Vec::new();
+/// deduping by label and shape. The [`Shape`] comes from an `@WxL`
+/// suffix, defaulting to [`subgame::SubgameHyperParams::shape`].
+/// `Option<usize>` is the per-variant session-count override extracted
+/// from a trailing `*N`. Prints to stderr and exits on any unknown token
+/// or malformed suffix — runs at binary startup, fail-fast.
+fn parse_list(raw: &str) -> Vec<(Variant, Shape, Option<usize>)> {
+    let mut out: Vec<(Variant, Shape, Option<usize>)> = Vec::new();
     for token in raw.split(',').filter(|t| !t.is_empty()) {
         let (token, sessions) = parse_session_suffix(token.trim());
+        let (token, shape) = parse_shape_suffix(token);
         match Variant::parse(token) {
-            Some(v) if !out.iter().any(|(existing, _)| existing.label() == v.label()) => out.push((v, sessions)),
+            Some(v)
+                if !out
+                    .iter()
+                    .any(|(existing, s, _)| existing.label() == v.label() && *s == shape) =>
+            {
+                out.push((v, shape, sessions));
+            }
             Some(_) => {}
             None => {
                 eprintln!("unknown variant: {token}");
@@ -188,3 +230,16 @@ fn parse_session_suffix(token: &str) -> (&str, Option<usize>) {
         None => (token, None),
     }
 }
+
+fn parse_shape_suffix(token: &str) -> (&str, Shape) {
+    match token.rsplit_once('@') {
+        Some((variant, shape)) => {
+            let Some(shape) = Shape::parse(shape) else {
+                eprintln!("invalid shape in token (expected `@WxL` from the supported sizes): {token}");
+                std::process::exit(1);
+            };
+            (variant, shape)
+        }
+        None => (token, subgame::SubgameHyperParams::get().shape()),
+    }
+}
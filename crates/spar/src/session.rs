@@ -65,6 +65,7 @@ impl<'sesh> Session<'sesh> {
         }
         if let Some(w) = resp.winnings {
             tracing::info!(bb = to_bb(w), "immediate result");
+            player.notify(&parlor::Event::Concluded(session.witness.clone())).await;
             session.recorder.set_pnl(seat, to_chips(w));
             session.recorder.set_pnl(villain(hero), -to_chips(w));
             session
@@ -107,6 +108,9 @@ impl<'sesh> Session<'sesh> {
                 let incr = encode_action(action, &game);
                 self.witness = self.witness.try_push(snapped)?;
                 self.history.push_str(&incr);
+                if matches!(self.witness.head().turn(), Turn::Choice(v) if Turn::Choice(v) != self.hero) {
+                    player.notify(&parlor::Event::Waiting(self.witness.clone())).await;
+                }
                 let resp = self.client.act(&incr).await?;
                 tracing::trace!(
                     action = %resp.action,
@@ -136,6 +140,7 @@ impl<'sesh> Session<'sesh> {
                         self.recorder.record(villain(self.hero), Action::Fold, None);
                         self.witness = self.witness.try_push(Action::Fold)?;
                     }
+                    player.notify(&parlor::Event::Concluded(self.witness.clone())).await;
                     self.recorder.set_pnl(p, to_chips(w));
                     self.recorder.set_pnl(villain(self.hero), -to_chips(w));
                     self.recorder
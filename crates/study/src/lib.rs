@@ -0,0 +1,44 @@
+//! Range-vs-range postflop solving for user-specified spots.
+//!
+//! Where the blueprint refines one decision of one hand, a study poses a
+//! standalone problem: a board, two weighted ranges, the pot, the stacks
+//! behind and a bet-size tree. There is no card abstraction — every
+//! information set is an exact hole-card combo, and chance nodes enumerate
+//! every turn and river card.
+//!
+//! The solver is vector-form CFR: each traversal carries a reach vector
+//! over the opponent's combos, terminal nodes are evaluated against the
+//! whole range at once (sorted-strength sweeps with card-removal
+//! corrections), and regrets and average strategies are updated through
+//! [`mccfr::RegretSchedule`] and [`mccfr::WeightSchedule`].
+//!
+//! # Contents
+//!
+//! - [`Spot`] — The problem: board, pot, stacks, ranges and [`Sizes`]
+//! - [`Study`] — The solver state, iterated with [`Study::solve`]
+//! - [`Solution`] — Per-combo strategies, EVs and exploitability
+//!
+//! # Seats
+//!
+//! Seats are ordered by postflop action: [`OOP`] acts first on every
+//! street and [`IP`] holds the button.
+
+mod sizes;
+mod solution;
+mod spot;
+mod study;
+mod table;
+mod tree;
+
+pub use sizes::*;
+pub use solution::*;
+pub use spot::*;
+pub use study::*;
+
+use table::*;
+use tree::*;
+
+/// Seat of the player out of position.
+pub const OOP: usize = 0;
+/// Seat of the player in position.
+pub const IP: usize = 1;
@@ -0,0 +1,110 @@
+use kicker::*;
+use pokerkit::*;
+
+/// The bet-size tree of a [`Spot`](crate::Spot).
+///
+/// Sizes are pot fractions. An opening bet of `f` puts `f × pot` in the
+/// middle; a raise of `f` first matches the bet faced and then adds `f`
+/// times the pot that call would leave. Sizes below the minimum raise are
+/// lifted to it and sizes at or above the stack become all-ins, so a
+/// coarse tree stays legal at every stack depth.
+///
+/// The same tree applies on every street.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Sizes {
+    /// Opening bet sizes, as pot fractions.
+    pub bets: Vec<f32>,
+    /// Raise sizes, as pot fractions after calling.
+    pub raises: Vec<f32>,
+    /// Maximum bets and raises per street; past it only calls and folds remain.
+    pub cap: usize,
+    /// Whether an all-in is offered alongside the sized options.
+    pub allin: bool,
+}
+
+impl Default for Sizes {
+    fn default() -> Self {
+        Self {
+            bets: vec![0.33, 0.75],
+            raises: vec![1.0],
+            cap: 3,
+            allin: true,
+        }
+    }
+}
+
+impl Sizes {
+    /// The actions offered at `game` after `aggression` bets and raises
+    /// this street, passive options first and deduplicated.
+    pub fn actions(&self, game: &Game, aggression: usize) -> Vec<Action> {
+        let mut actions = Vec::new();
+        if game.may_fold() {
+            actions.push(game.folds());
+        }
+        if game.may_check() {
+            actions.push(game.check());
+        }
+        if game.may_call() {
+            actions.push(game.calls());
+        } else if game.may_fold() {
+            actions.push(game.shove());
+        }
+        if aggression < self.cap && game.may_shove() {
+            let fractions = match game.to_call() {
+                0 => &self.bets,
+                _ => &self.raises,
+            };
+            fractions
+                .iter()
+                .map(|f| self.sized(game, *f))
+                .chain(self.allin.then(|| game.shove()))
+                .for_each(|a| {
+                    if !actions.contains(&a) {
+                        actions.push(a);
+                    }
+                });
+        }
+        actions
+    }
+    /// The legal bet or raise nearest to a pot fraction.
+    fn sized(&self, game: &Game, fraction: f32) -> Action {
+        let call = game.to_call();
+        let chips = call + (fraction * Utility::from(game.pot() + call)).round() as Chips;
+        if chips >= game.to_shove() || game.to_raise() >= game.to_shove() {
+            game.shove()
+        } else {
+            Action::Raise(chips.max(game.to_raise()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use deuce::*;
+
+    fn root() -> Game {
+        Game::spot(Hand::try_from("Ks7d2c").unwrap(), 60, [170, 170])
+    }
+
+    #[test]
+    fn opening_sizes_are_pot_fractions() {
+        let actions = Sizes::default().actions(&root(), 0);
+        assert_eq!(actions, vec![Action::Check, Action::Raise(20), Action::Raise(45), Action::Shove(170)]);
+    }
+
+    #[test]
+    fn raises_are_sized_after_calling() {
+        let game = root().apply(Action::Raise(30));
+        let actions = Sizes::default().actions(&game, 1);
+        assert_eq!(actions, vec![Action::Fold, Action::Call(30), Action::Raise(150), Action::Shove(170)]);
+    }
+
+    #[test]
+    fn cap_leaves_only_calls_and_folds() {
+        let game = root().apply(Action::Raise(30));
+        let actions = Sizes::default().actions(&game, 3);
+        assert_eq!(actions, vec![Action::Fold, Action::Call(30)]);
+    }
+}
@@ -0,0 +1,86 @@
+use deuce::*;
+use pokerkit::*;
+
+/// The reported result of a [`Study`](crate::Study).
+///
+/// EVs are in chips won from the spot onward, counting the starting pot
+/// as dead money: the two seats' EVs sum to the pot, and a seat that
+/// checks down an even split banks half of it. Exploitability is the
+/// chips per hand a best responder gains over the equilibrium value,
+/// averaged over both seats.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Solution {
+    pub iterations: usize,
+    pub pot: Chips,
+    pub exploitability: Utility,
+    /// Exploitability as a percentage of the pot.
+    pub percent: Utility,
+    pub oop: Side,
+    pub ip: Side,
+    /// Decisions of the spot's first street, in depth-first order.
+    pub decisions: Vec<Decision>,
+}
+
+/// One seat's range-wide EV and per-combo EVs.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Side {
+    pub ev: Utility,
+    pub combos: Vec<Combo>,
+}
+
+/// A combo's range weight and EV.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Combo {
+    pub hole: Hole,
+    pub weight: Probability,
+    pub ev: Utility,
+}
+
+/// The average strategy at one decision, per combo of the acting seat.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Decision {
+    /// Actions leading here from the root.
+    pub line: Vec<String>,
+    /// `"oop"` or `"ip"`.
+    pub player: String,
+    pub actions: Vec<String>,
+    pub strategy: Vec<Mix>,
+}
+
+/// One combo's action probabilities, aligned with [`Decision::actions`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Mix {
+    pub hole: Hole,
+    /// Range weight times the combo's own probability of reaching here.
+    pub reach: Probability,
+    pub policy: Vec<Probability>,
+}
+
+impl std::fmt::Display for Solution {
+    /// A summary: EVs, exploitability and each decision's reach-weighted
+    /// action frequencies.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(f, "iterations      {}", self.iterations)?;
+        writeln!(f, "oop ev          {:>8.2}", self.oop.ev)?;
+        writeln!(f, "ip ev           {:>8.2}", self.ip.ev)?;
+        writeln!(f, "exploitability  {:>8.3} ({:.2}% pot)", self.exploitability, self.percent)?;
+        for decision in &self.decisions {
+            let mass = decision.strategy.iter().map(|m| m.reach).sum::<Probability>();
+            let line = if decision.line.is_empty() {
+                "root".to_string()
+            } else {
+                decision.line.join(" > ")
+            };
+            writeln!(f, "\n{} [{}]", line, decision.player)?;
+            for (a, action) in decision.actions.iter().enumerate() {
+                let freq = decision
+                    .strategy
+                    .iter()
+                    .map(|mix| mix.policy[a] * mix.reach)
+                    .sum::<Probability>();
+                writeln!(f, "  {:<12}{:>6.1}%", action, 100. * freq / mass)?;
+            }
+        }
+        Ok(())
+    }
+}
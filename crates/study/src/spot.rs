@@ -0,0 +1,60 @@
+use super::*;
+use deuce::*;
+use kicker::*;
+use pokerkit::*;
+
+/// A postflop problem posed by hand.
+///
+/// Deserializes from the study JSON format:
+///
+/// ```json
+/// {
+///   "board": "Ks7d2c",
+///   "pot": 60,
+///   "stacks": [170, 170],
+///   "oop": "22+,A2s+,KTs+,QTs+,JTs,ATo+,KJo+",
+///   "ip": "TT+,AQs+,AKo",
+///   "sizes": { "bets": [0.33, 0.75], "raises": [1.0], "cap": 3, "allin": true }
+/// }
+/// ```
+///
+/// Ranges use [`Range`] notation and are blocked by the board before
+/// solving. `stacks` are the chips behind, out of position first. `sizes`
+/// may be omitted for the default tree.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Spot {
+    pub board: Hand,
+    pub pot: Chips,
+    pub stacks: [Chips; 2],
+    pub oop: Range,
+    pub ip: Range,
+    #[serde(default)]
+    pub sizes: Sizes,
+}
+
+impl Spot {
+    /// Rejects spots the solver cannot pose.
+    pub fn check(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            (3..=5).contains(&self.board.size()),
+            "board must hold 3 to 5 cards, got {}",
+            self.board.size()
+        );
+        anyhow::ensure!(self.pot > 0, "pot must be positive");
+        anyhow::ensure!(self.stacks.iter().all(|s| *s >= 0), "stacks must be non-negative");
+        anyhow::ensure!(!self.range(OOP).is_empty(), "oop range is empty on this board");
+        anyhow::ensure!(!self.range(IP).is_empty(), "ip range is empty on this board");
+        Ok(())
+    }
+    /// A seat's range with board-blocked combos removed.
+    pub fn range(&self, seat: usize) -> Range {
+        match seat {
+            OOP => self.oop.blocked(self.board),
+            _ => self.ip.blocked(self.board),
+        }
+    }
+    /// The game state at the first decision of the spot.
+    pub fn root(&self) -> Game {
+        Game::spot(self.board, self.pot, self.stacks)
+    }
+}
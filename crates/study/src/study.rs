@@ -0,0 +1,260 @@
+use super::*;
+use pokerkit::*;
+
+/// A range-vs-range solve in progress.
+///
+/// Owns the expanded tree and the combo [`Table`]; each call to
+/// [`step`](Self::step) runs one alternating-update iteration of
+/// vector-form CFR, traversing once per seat.
+#[derive(Debug, Clone)]
+pub struct Study {
+    spot: Spot,
+    table: Table,
+    tree: Node,
+    cells: usize,
+    epoch: usize,
+}
+
+impl TryFrom<Spot> for Study {
+    type Error = anyhow::Error;
+    fn try_from(spot: Spot) -> anyhow::Result<Self> {
+        spot.check()?;
+        let mut table = Table::from(&spot);
+        let cells =
+            Node::cells(spot.root(), &spot, [OOP, IP].map(|seat| table.holes(seat).len())).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "tree exceeds {MAX_CELLS} strategy cells; narrow the ranges or sizes, or start on a later street"
+                )
+            })?;
+        let tree = Node::grow(spot.root(), &spot, &mut table, 0);
+        Ok(Self {
+            spot,
+            table,
+            tree,
+            cells,
+            epoch: 0,
+        })
+    }
+}
+
+impl Study {
+    /// The spot being solved.
+    pub fn spot(&self) -> &Spot {
+        &self.spot
+    }
+    /// Strategy cells in the tree, the work one iteration does per seat.
+    pub fn cells(&self) -> usize {
+        self.cells
+    }
+    /// Iterations run so far.
+    pub fn epoch(&self) -> usize {
+        self.epoch
+    }
+    /// Runs one iteration, updating each seat in turn.
+    pub fn step(&mut self) {
+        self.epoch += 1;
+        for hero in [OOP, IP] {
+            let reach = [OOP, IP].map(|seat| self.table.weights(seat).to_vec());
+            self.tree.train(&self.table, hero, &reach, self.spot.pot, self.epoch);
+        }
+    }
+    /// Runs `iterations` more iterations and reports the result.
+    pub fn solve(&mut self, iterations: usize) -> Solution {
+        (0..iterations).for_each(|_| self.step());
+        self.solution()
+    }
+
+    /// Expected chips per combo of `hero` when both seats play their
+    /// average strategies, or when `hero` best-responds.
+    fn evs(&self, hero: usize, mode: Mode) -> Vec<Utility> {
+        let reach = self.table.weights(1 - hero);
+        let masses = self.table.compatible(hero, reach);
+        self.tree
+            .value(&self.table, hero, reach, self.spot.pot, mode)
+            .into_iter()
+            .zip(masses)
+            .map(|(value, mass)| if mass > 0. { value / mass } else { 0. })
+            .collect()
+    }
+    /// Range-weighted mean of per-combo values over dealt matchups.
+    fn mean(&self, hero: usize, evs: &[Utility]) -> Utility {
+        let masses = self.table.compatible(hero, self.table.weights(1 - hero));
+        let weights = self.table.weights(hero);
+        let total = weights.iter().zip(&masses).map(|(w, m)| w * m).sum::<Probability>();
+        let value = weights
+            .iter()
+            .zip(&masses)
+            .zip(evs)
+            .map(|((w, m), v)| w * m * v)
+            .sum::<Utility>();
+        value / total
+    }
+    /// Chips per hand either seat gains by best-responding, averaged over
+    /// the two seats. Zero exactly at equilibrium.
+    pub fn exploitability(&self) -> Utility {
+        let best = [OOP, IP].map(|seat| self.mean(seat, &self.evs(seat, Mode::Best)));
+        (best[OOP] + best[IP] - Utility::from(self.spot.pot)) / 2.
+    }
+    /// Reports strategies, EVs and exploitability at the current epoch.
+    pub fn solution(&self) -> Solution {
+        let evs = [OOP, IP].map(|seat| self.evs(seat, Mode::Average));
+        let exploitability = self.exploitability();
+        Solution {
+            iterations: self.epoch,
+            pot: self.spot.pot,
+            exploitability,
+            percent: 100. * exploitability / Utility::from(self.spot.pot),
+            oop: self.side(OOP, &evs[OOP]),
+            ip: self.side(IP, &evs[IP]),
+            decisions: self.decisions(),
+        }
+    }
+    /// A seat's range-wide and per-combo EVs.
+    fn side(&self, seat: usize, evs: &[Utility]) -> Side {
+        Side {
+            ev: self.mean(seat, evs),
+            combos: self
+                .table
+                .holes(seat)
+                .iter()
+                .zip(self.table.weights(seat))
+                .zip(evs)
+                .map(|((hole, weight), ev)| Combo {
+                    hole: *hole,
+                    weight: *weight,
+                    ev: *ev,
+                })
+                .collect(),
+        }
+    }
+    /// Average strategies at every decision before the next card.
+    fn decisions(&self) -> Vec<Decision> {
+        let mut decisions = Vec::new();
+        let reach = [OOP, IP].map(|seat| self.table.weights(seat).to_vec());
+        let mut stack = vec![(Vec::new(), reach, &self.tree)];
+        while let Some((line, reach, node)) = stack.pop() {
+            let Node::Choice(choice) = node else { continue };
+            let n = self.table.holes(choice.seat).len();
+            let average = choice.average(n);
+            decisions.push(Decision {
+                line: line.clone(),
+                player: ["oop", "ip"][choice.seat].to_string(),
+                actions: choice.actions.iter().map(ToString::to_string).collect(),
+                strategy: (0..n)
+                    .map(|h| Mix {
+                        hole: self.table.holes(choice.seat)[h],
+                        reach: reach[choice.seat][h],
+                        policy: (0..choice.actions.len()).map(|a| average[a * n + h]).collect(),
+                    })
+                    .collect(),
+            });
+            for (a, (action, child)) in choice.actions.iter().zip(&choice.children).enumerate().rev() {
+                let mut line = line.clone();
+                let mut reach = reach.clone();
+                line.push(action.to_string());
+                reach[choice.seat]
+                    .iter_mut()
+                    .zip(&average[a * n..(a + 1) * n])
+                    .for_each(|(r, p)| *r *= p);
+                stack.push((line, reach, child));
+            }
+        }
+        decisions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spot(json: &str) -> Spot {
+        serde_json::from_str(json).expect("valid spot")
+    }
+
+    #[test]
+    fn checked_down_pot_goes_to_best_hand() {
+        let spot = spot(
+            r#"{ "board": "2c3d7h8s9c", "pot": 40, "stacks": [100, 100], "oop": "AA", "ip": "KK",
+            "sizes": { "bets": [], "raises": [], "cap": 0, "allin": false } }"#,
+        );
+        let solution = Study::try_from(spot).unwrap().solve(1);
+        assert!((solution.oop.ev - 40.).abs() < 1e-3);
+        assert!(solution.ip.ev.abs() < 1e-3);
+        assert!(solution.exploitability.abs() < 1e-3);
+    }
+
+    #[test]
+    fn evs_split_the_pot() {
+        let spot = spot(
+            r#"{ "board": "Ks7d2c4h9s", "pot": 60, "stacks": [170, 170],
+            "oop": "KQ,QQ,77,A7s,86s,55", "ip": "KJ,99,AA,T8s,65s" }"#,
+        );
+        let solution = Study::try_from(spot).unwrap().solve(50);
+        assert!((solution.oop.ev + solution.ip.ev - 60.).abs() < 0.1);
+        assert!(solution.oop.combos.iter().all(|c| c.ev.is_finite()));
+    }
+
+    #[test]
+    fn river_converges() {
+        let spot = spot(
+            r#"{ "board": "Ks7d2c4h9s", "pot": 60, "stacks": [170, 170],
+            "oop": "KQ,QQ,77,A7s,86s,55", "ip": "KJ,99,AA,T8s,65s" }"#,
+        );
+        let mut study = Study::try_from(spot).unwrap();
+        let early = study.solve(10).exploitability;
+        let later = study.solve(290);
+        assert!(later.exploitability < early);
+        assert!(later.percent < 1.0, "exploitability {}% of pot", later.percent);
+    }
+
+    #[test]
+    fn turn_enumerates_rivers() {
+        let spot = spot(
+            r#"{ "board": "Ks7d2c4h", "pot": 60, "stacks": [60, 60], "oop": "QQ", "ip": "AK",
+            "sizes": { "bets": [1.0], "raises": [], "cap": 1, "allin": false } }"#,
+        );
+        let solution = Study::try_from(spot).unwrap().solve(100);
+        assert!((solution.oop.ev + solution.ip.ev - 60.).abs() < 0.1);
+        assert!(solution.percent < 2.0, "exploitability {}% of pot", solution.percent);
+    }
+
+    #[test]
+    fn decisions_cover_first_street() {
+        let spot = spot(r#"{ "board": "Ks7d2c4h9s", "pot": 60, "stacks": [170, 170], "oop": "QQ", "ip": "AK,JJ" }"#);
+        let solution = Study::try_from(spot).unwrap().solve(1);
+        let root = &solution.decisions[0];
+        assert!(root.line.is_empty());
+        assert_eq!(root.player, "oop");
+        assert!(solution.decisions.iter().any(|d| d.player == "ip"));
+        assert!(solution.decisions.iter().all(|d| {
+            d.strategy
+                .iter()
+                .all(|m| (m.policy.iter().sum::<Probability>() - 1.).abs() < 1e-4)
+        }));
+    }
+
+    #[test]
+    fn cells_grow_with_ranges() {
+        let narrow = spot(r#"{ "board": "Ks7d2c4h9s", "pot": 60, "stacks": [170, 170], "oop": "QQ", "ip": "AK" }"#);
+        let wide = spot(r#"{ "board": "Ks7d2c4h9s", "pot": 60, "stacks": [170, 170], "oop": "QQ,JJ", "ip": "AK" }"#);
+        let narrow = Study::try_from(narrow).unwrap().cells();
+        let wide = Study::try_from(wide).unwrap().cells();
+        assert!(0 < narrow && narrow < wide, "{narrow} vs {wide}");
+    }
+
+    #[test]
+    fn oversized_flop_is_rejected() {
+        let spot = spot(
+            r#"{ "board": "Ks7d2c", "pot": 60, "stacks": [170, 170],
+            "oop": "22+,A2s+,K2s+,Q2s+,A2o+,K2o+", "ip": "22+,A2s+,K2s+,Q2s+,A2o+,K2o+" }"#,
+        );
+        let error = Study::try_from(spot).unwrap_err();
+        assert!(error.to_string().contains("strategy cells"), "{error}");
+    }
+
+    #[test]
+    fn empty_range_is_rejected() {
+        let spot = spot(r#"{ "board": "AsAhAd", "pot": 10, "stacks": [10, 10], "oop": "AsAh", "ip": "KK" }"#);
+        assert!(Study::try_from(spot).is_err());
+    }
+}
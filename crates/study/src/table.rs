@@ -0,0 +1,217 @@
+use super::*;
+use deuce::*;
+use pokerkit::*;
+use std::collections::HashMap;
+
+/// Combo bookkeeping shared by every node of a study tree.
+///
+/// Holds each seat's combos with their range weights and card indices,
+/// and one [`Ranks`] per distinct showdown board. Terminal values are
+/// computed here against a whole opponent reach vector at once, with
+/// card removal handled by inclusion–exclusion over per-card reach sums:
+/// the mass compatible with `h = {a, b}` is the total, minus the mass
+/// holding `a`, minus the mass holding `b`, plus the mass of `h` itself.
+#[derive(Debug, Clone)]
+pub struct Table {
+    holes: [Vec<Hole>; 2],
+    cards: [Vec<[usize; 2]>; 2],
+    weights: [Vec<Probability>; 2],
+    twins: [Vec<Option<usize>>; 2],
+    ranks: Vec<Ranks>,
+    index: HashMap<u64, usize>,
+}
+
+/// Showdown strengths of both seats' combos on one complete board.
+#[derive(Debug, Clone)]
+struct Ranks {
+    strengths: [Vec<Strength>; 2],
+    ascending: [Vec<usize>; 2],
+}
+
+impl From<&Spot> for Table {
+    fn from(spot: &Spot) -> Self {
+        let ranges = [spot.range(OOP), spot.range(IP)];
+        let holes = ranges
+            .each_ref()
+            .map(|range| range.iter().map(|(hole, _)| hole).collect::<Vec<_>>());
+        let weights = ranges
+            .each_ref()
+            .map(|range| range.iter().map(|(_, weight)| weight).collect::<Vec<_>>());
+        let cards = holes.each_ref().map(|holes| {
+            holes
+                .iter()
+                .map(|hole| {
+                    let mut cards = hole.into_iter().map(|c| usize::from(u8::from(c)));
+                    [cards.next().expect("two cards"), cards.next().expect("two cards")]
+                })
+                .collect::<Vec<_>>()
+        });
+        let twins = [OOP, IP].map(|seat| {
+            holes[seat]
+                .iter()
+                .map(|hole| holes[1 - seat].binary_search(hole).ok())
+                .collect::<Vec<_>>()
+        });
+        Self {
+            holes,
+            cards,
+            weights,
+            twins,
+            ranks: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+}
+
+impl Table {
+    /// A seat's combos, in range order.
+    pub fn holes(&self, seat: usize) -> &[Hole] {
+        &self.holes[seat]
+    }
+    /// A seat's range weights, aligned with [`holes`](Self::holes).
+    pub fn weights(&self, seat: usize) -> &[Probability] {
+        &self.weights[seat]
+    }
+    /// Whether a seat's combo holds the given card.
+    pub fn holds(&self, seat: usize, combo: usize, card: usize) -> bool {
+        self.cards[seat][combo].contains(&card)
+    }
+    /// Registers a complete board for showdowns, returning its index.
+    pub fn rank(&mut self, board: Hand) -> usize {
+        if let Some(index) = self.index.get(&u64::from(board)) {
+            return *index;
+        }
+        // combos holding a runout card carry no reach on this board;
+        // `or` just keeps their unused strengths well-defined
+        let strengths = self.holes.each_ref().map(|holes| {
+            holes
+                .iter()
+                .map(|h| Strength::from(Hand::or(Hand::from(*h), board)))
+                .collect::<Vec<_>>()
+        });
+        let ascending = strengths.each_ref().map(|strengths| {
+            let mut order = (0..strengths.len()).collect::<Vec<_>>();
+            order.sort_by_key(|i| strengths[*i]);
+            order
+        });
+        self.ranks.push(Ranks { strengths, ascending });
+        self.index.insert(u64::from(board), self.ranks.len() - 1);
+        self.ranks.len() - 1
+    }
+
+    /// Opponent reach compatible with each of `hero`'s combos.
+    pub fn compatible(&self, hero: usize, reach: &[Probability]) -> Vec<Probability> {
+        let villain = 1 - hero;
+        let (total, blocks) = self.blocks(villain, reach);
+        self.cards[hero]
+            .iter()
+            .zip(self.twins[hero].iter())
+            .map(|([a, b], twin)| total - blocks[*a] - blocks[*b] + twin.map_or(0., |t| reach[t]))
+            .collect()
+    }
+    /// Values at a fold: every compatible opponent combo pays `payoff`.
+    pub fn fold(&self, hero: usize, reach: &[Probability], payoff: Utility) -> Vec<Utility> {
+        self.compatible(hero, reach)
+            .into_iter()
+            .map(|mass| mass * payoff)
+            .collect()
+    }
+    /// Values at a showdown on a registered board, given the payoffs for
+    /// winning, tying and losing.
+    pub fn showdown(&self, board: usize, hero: usize, reach: &[Probability], payoffs: [Utility; 3]) -> Vec<Utility> {
+        let [win, tie, lose] = payoffs;
+        let weaker = self.sweep(board, hero, reach, false);
+        let stronger = self.sweep(board, hero, reach, true);
+        self.compatible(hero, reach)
+            .into_iter()
+            .zip(weaker)
+            .zip(stronger)
+            .map(|((mass, w), s)| w * win + s * lose + (mass - w - s) * tie)
+            .collect()
+    }
+
+    /// Compatible opponent mass strictly weaker (or, reversed, strictly
+    /// stronger) than each hero combo, by a merged walk over both seats'
+    /// combos in strength order.
+    fn sweep(&self, board: usize, hero: usize, reach: &[Probability], reversed: bool) -> Vec<Probability> {
+        let villain = 1 - hero;
+        let ranks = &self.ranks[board];
+        let mine = &ranks.strengths[hero];
+        let them = &ranks.strengths[villain];
+        let order = |seat: usize| -> Box<dyn Iterator<Item = &usize> + '_> {
+            if reversed {
+                Box::new(ranks.ascending[seat].iter().rev())
+            } else {
+                Box::new(ranks.ascending[seat].iter())
+            }
+        };
+        let beats = |a: Strength, b: Strength| if reversed { a > b } else { a < b };
+        let mut masses = vec![0.; mine.len()];
+        let mut total = 0.;
+        let mut blocks = [0.; 52];
+        let mut others = order(villain).peekable();
+        for h in order(hero) {
+            while let Some(o) = others.next_if(|o| beats(them[**o], mine[*h])) {
+                let [a, b] = self.cards[villain][*o];
+                total += reach[*o];
+                blocks[a] += reach[*o];
+                blocks[b] += reach[*o];
+            }
+            let [a, b] = self.cards[hero][*h];
+            masses[*h] = total - blocks[a] - blocks[b];
+        }
+        masses
+    }
+    /// Total reach of a seat and its split by card.
+    fn blocks(&self, seat: usize, reach: &[Probability]) -> (Probability, [Probability; 52]) {
+        let mut total = 0.;
+        let mut blocks = [0.; 52];
+        for i in 0..reach.len() {
+            let [a, b] = self.cards[seat][i];
+            total += reach[i];
+            blocks[a] += reach[i];
+            blocks[b] += reach[i];
+        }
+        (total, blocks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn showdown_matches_pairwise() {
+        let spot: Spot = serde_json::from_str(
+            r#"{ "board": "Ks7d2c4h9s", "pot": 10, "stacks": [10, 10], "oop": "AK,KQ,77,98s,QQ", "ip": "AK:0.5,KJ,99,AA,A2" }"#,
+        )
+        .unwrap();
+        let mut table = Table::from(&spot);
+        let board = table.rank(spot.board);
+        let reach = table
+            .weights(IP)
+            .iter()
+            .enumerate()
+            .map(|(i, w)| w * (1. + i as f32 / 7.))
+            .collect::<Vec<_>>();
+        let values = table.showdown(board, OOP, &reach, [3., 1., -2.]);
+        for (h, hole) in table.holes(OOP).iter().enumerate() {
+            let mine = Strength::from(Hand::add(Hand::from(*hole), spot.board));
+            let expected = table
+                .holes(IP)
+                .iter()
+                .zip(&reach)
+                .filter(|(other, _)| !Hand::overlaps(&Hand::from(**other), &Hand::from(*hole)))
+                .map(|(other, r)| {
+                    let theirs = Strength::from(Hand::add(Hand::from(*other), spot.board));
+                    r * match mine.cmp(&theirs) {
+                        std::cmp::Ordering::Greater => 3.,
+                        std::cmp::Ordering::Equal => 1.,
+                        std::cmp::Ordering::Less => -2.,
+                    }
+                })
+                .sum::<f32>();
+            assert!((values[h] - expected).abs() < 1e-3, "{hole}: {} vs {expected}", values[h]);
+        }
+    }
+}
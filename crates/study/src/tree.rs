@@ -0,0 +1,349 @@
+use super::*;
+use deuce::*;
+use kicker::*;
+use mccfr::*;
+use pokerkit::*;
+use rayon::prelude::*;
+
+/// Most strategy cells (actions × acting combos, summed over decision
+/// nodes) a study tree may hold. Each cell stores a regret and an average
+/// strategy weight.
+pub const MAX_CELLS: usize = 1 << 25;
+
+/// The regret schedule of study iterations (DCFR).
+type Regret = DiscountedRegret;
+/// The average-strategy weighting of study iterations.
+type Weight = LinearWeight;
+
+/// A node of the fully expanded study tree.
+///
+/// Built once from a [`Spot`] by following the [`Sizes`] tree through
+/// [`kicker::Game`], which owns the betting rules. Decision nodes store
+/// their regrets and average strategy inline, laid out action-major over
+/// the acting seat's combos. Chance nodes hold one child per runout card.
+#[derive(Debug, Clone)]
+pub enum Node {
+    Choice(Choice),
+    Chance(Chance),
+    /// Everyone else folded to `winner`; `spent` is what each seat put in
+    /// since the spot began.
+    Fold {
+        winner: usize,
+        spent: [Chips; 2],
+    },
+    /// Cards are shown on a board registered in the [`Table`].
+    Showdown {
+        board: usize,
+        spent: [Chips; 2],
+    },
+}
+
+/// A decision of one seat across all of its combos.
+#[derive(Debug, Clone)]
+pub struct Choice {
+    pub seat: usize,
+    pub actions: Vec<Action>,
+    pub children: Vec<Node>,
+    regret: Vec<Utility>,
+    policy: Vec<Probability>,
+}
+
+/// A street's worth of runout cards.
+#[derive(Debug, Clone)]
+pub struct Chance {
+    children: Vec<(usize, Node)>,
+    /// Runouts open to any pair of compatible combos.
+    outs: usize,
+}
+
+/// How a seat plays its own decisions when its values are computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// The average strategy, for equilibrium values.
+    Average,
+    /// The pointwise best action, for best-response values.
+    Best,
+}
+
+impl Node {
+    /// Strategy cells in the tree below `game`, given each seat's combo
+    /// count, or `None` past [`MAX_CELLS`]. Walks the tree without
+    /// building it and stops as soon as the budget runs out, so oversized
+    /// spots are rejected in bounded time.
+    pub fn cells(game: Game, spot: &Spot, combos: [usize; 2]) -> Option<usize> {
+        let mut budget = MAX_CELLS;
+        Self::measure(game, spot, combos, 0, &mut budget)?;
+        Some(MAX_CELLS - budget)
+    }
+    fn measure(game: Game, spot: &Spot, combos: [usize; 2], aggression: usize, budget: &mut usize) -> Option<()> {
+        *budget = budget.checked_sub(1)?;
+        match game.turn() {
+            Turn::Terminal => Some(()),
+            Turn::Chance => Hand::from(game.board()).complement().try_for_each(|card| {
+                let next = game.force_apply(Action::Draw(Hand::from(card)));
+                Self::measure(next, spot, combos, 0, budget)
+            }),
+            Turn::Choice(seat) => {
+                let actions = spot.sizes.actions(&game, aggression);
+                *budget = budget.checked_sub(actions.len() * combos[seat])?;
+                actions.iter().try_for_each(|a| {
+                    let raised = match a {
+                        Action::Raise(_) => true,
+                        Action::Shove(chips) => *chips > game.to_call(),
+                        _ => false,
+                    };
+                    Self::measure(game.apply(*a), spot, combos, aggression + usize::from(raised), budget)
+                })
+            }
+        }
+    }
+
+    /// Expands the tree below `game`, `aggression` bets and raises into
+    /// the current street.
+    pub fn grow(game: Game, spot: &Spot, table: &mut Table, aggression: usize) -> Self {
+        let spent = [OOP, IP].map(|seat| spot.stacks[seat] - game.seats()[seat].stack());
+        match game.turn() {
+            Turn::Terminal if game.is_showdown() => Self::Showdown {
+                board: table.rank(Hand::from(game.board())),
+                spent,
+            },
+            Turn::Terminal => Self::Fold {
+                winner: [OOP, IP]
+                    .into_iter()
+                    .find(|seat| game.seats()[*seat].state() != State::Folding)
+                    .expect("one seat remains"),
+                spent,
+            },
+            Turn::Chance => {
+                let dealt = Hand::from(game.board());
+                let children = dealt
+                    .complement()
+                    .map(|card| {
+                        let next = game.force_apply(Action::Draw(Hand::from(card)));
+                        (usize::from(u8::from(card)), Self::grow(next, spot, table, 0))
+                    })
+                    .collect::<Vec<_>>();
+                let outs = children.len() - 4;
+                Self::Chance(Chance { children, outs })
+            }
+            Turn::Choice(seat) => {
+                let actions = spot.sizes.actions(&game, aggression);
+                let children = actions
+                    .iter()
+                    .map(|a| {
+                        let raised = match a {
+                            Action::Raise(_) => true,
+                            Action::Shove(chips) => *chips > game.to_call(),
+                            _ => false,
+                        };
+                        Self::grow(game.apply(*a), spot, table, aggression + usize::from(raised))
+                    })
+                    .collect::<Vec<_>>();
+                let size = actions.len() * table.holes(seat).len();
+                Self::Choice(Choice {
+                    seat,
+                    actions,
+                    children,
+                    regret: vec![0.; size],
+                    policy: vec![0.; size],
+                })
+            }
+        }
+    }
+
+    /// One CFR traversal for `hero`, returning counterfactual values per
+    /// hero combo and updating hero's regrets and average strategy.
+    pub fn train(
+        &mut self,
+        table: &Table,
+        hero: usize,
+        reach: &[Vec<Probability>; 2],
+        pot: Chips,
+        epoch: usize,
+    ) -> Vec<Utility> {
+        match self {
+            Self::Choice(choice) => {
+                let n = table.holes(choice.seat).len();
+                let current = choice.current(n);
+                let subs = choice
+                    .children
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(a, child)| {
+                        let mut next = reach.clone();
+                        next[choice.seat]
+                            .iter_mut()
+                            .zip(&current[a * n..(a + 1) * n])
+                            .for_each(|(r, p)| *r *= p);
+                        child.train(table, hero, &next, pot, epoch)
+                    })
+                    .collect::<Vec<_>>();
+                if choice.seat != hero {
+                    return Self::sum(subs);
+                }
+                let mut values = vec![0.; n];
+                for (a, sub) in subs.iter().enumerate() {
+                    for h in 0..n {
+                        values[h] += current[a * n + h] * sub[h];
+                    }
+                }
+                for (a, sub) in subs.iter().enumerate() {
+                    for h in 0..n {
+                        let i = a * n + h;
+                        choice.regret[i] = Regret::gain(choice.regret[i], sub[h] - values[h], epoch);
+                        choice.policy[i] = Weight::learn(choice.policy[i], reach[hero][h] * current[i], epoch);
+                    }
+                }
+                values
+            }
+            Self::Chance(chance) => {
+                let outs = chance.outs;
+                let values = chance
+                    .children
+                    .par_iter_mut()
+                    .map(|(card, child)| {
+                        let next = Self::deal(table, reach, *card);
+                        let mut values = child.train(table, hero, &next, pot, epoch);
+                        Self::mask(table, hero, &mut values, *card);
+                        values
+                    })
+                    .reduce_with(Self::add)
+                    .unwrap_or_default();
+                values.into_iter().map(|v| v / outs as Utility).collect()
+            }
+            _ => self.terminal(table, hero, &reach[1 - hero], pot),
+        }
+    }
+
+    /// Values per hero combo with the opponent on its average strategy
+    /// and hero playing by `mode`.
+    pub fn value(&self, table: &Table, hero: usize, reach: &[Probability], pot: Chips, mode: Mode) -> Vec<Utility> {
+        match self {
+            Self::Choice(choice) if choice.seat == hero => {
+                let n = table.holes(hero).len();
+                let average = choice.average(n);
+                let subs = choice
+                    .children
+                    .iter()
+                    .map(|child| child.value(table, hero, reach, pot, mode))
+                    .collect::<Vec<_>>();
+                (0..n)
+                    .map(|h| match mode {
+                        Mode::Best => subs.iter().map(|sub| sub[h]).fold(Utility::NEG_INFINITY, Utility::max),
+                        Mode::Average => subs
+                            .iter()
+                            .enumerate()
+                            .map(|(a, sub)| average[a * n + h] * sub[h])
+                            .sum(),
+                    })
+                    .collect()
+            }
+            Self::Choice(choice) => {
+                let n = table.holes(choice.seat).len();
+                let average = choice.average(n);
+                let subs = choice
+                    .children
+                    .iter()
+                    .enumerate()
+                    .map(|(a, child)| {
+                        let next = reach
+                            .iter()
+                            .zip(&average[a * n..(a + 1) * n])
+                            .map(|(r, p)| r * p)
+                            .collect::<Vec<_>>();
+                        child.value(table, hero, &next, pot, mode)
+                    })
+                    .collect::<Vec<_>>();
+                Self::sum(subs)
+            }
+            Self::Chance(chance) => {
+                let values = chance
+                    .children
+                    .par_iter()
+                    .map(|(card, child)| {
+                        let next = reach
+                            .iter()
+                            .enumerate()
+                            .map(|(o, r)| if table.holds(1 - hero, o, *card) { 0. } else { *r })
+                            .collect::<Vec<_>>();
+                        let mut values = child.value(table, hero, &next, pot, mode);
+                        Self::mask(table, hero, &mut values, *card);
+                        values
+                    })
+                    .reduce_with(Self::add)
+                    .unwrap_or_default();
+                values.into_iter().map(|v| v / chance.outs as Utility).collect()
+            }
+            _ => self.terminal(table, hero, reach, pot),
+        }
+    }
+
+    /// Terminal values per hero combo against the opponent's reach. Payoffs
+    /// are net of what hero put in since the spot, with the starting pot
+    /// as dead money, so the two seats' payoffs always sum to the pot.
+    fn terminal(&self, table: &Table, hero: usize, reach: &[Probability], pot: Chips) -> Vec<Utility> {
+        match *self {
+            Self::Fold { winner, spent } if winner == hero => {
+                table.fold(hero, reach, Utility::from(pot + spent[1 - hero]))
+            }
+            Self::Fold { spent, .. } => table.fold(hero, reach, -Utility::from(spent[hero])),
+            Self::Showdown { board, spent } => {
+                let matched = Utility::from(spent[OOP].min(spent[IP]));
+                let pot = Utility::from(pot);
+                table.showdown(board, hero, reach, [pot + matched, pot / 2., -matched])
+            }
+            _ => unreachable!("terminal values of a non-terminal node"),
+        }
+    }
+
+    /// Both reach vectors with combos holding `card` removed.
+    fn deal(table: &Table, reach: &[Vec<Probability>; 2], card: usize) -> [Vec<Probability>; 2] {
+        [OOP, IP].map(|seat| {
+            reach[seat]
+                .iter()
+                .enumerate()
+                .map(|(i, r)| if table.holds(seat, i, card) { 0. } else { *r })
+                .collect()
+        })
+    }
+    /// Zeroes the values of hero combos holding `card`.
+    fn mask(table: &Table, hero: usize, values: &mut [Utility], card: usize) {
+        values
+            .iter_mut()
+            .enumerate()
+            .filter(|(h, _)| table.holds(hero, *h, card))
+            .for_each(|(_, v)| *v = 0.);
+    }
+    /// Elementwise sum of equal-length value vectors.
+    fn sum(vectors: Vec<Vec<Utility>>) -> Vec<Utility> {
+        vectors.into_iter().reduce(Self::add).unwrap_or_default()
+    }
+    /// Elementwise sum of two equal-length value vectors.
+    fn add(mut a: Vec<Utility>, b: Vec<Utility>) -> Vec<Utility> {
+        a.iter_mut().zip(b).for_each(|(x, y)| *x += y);
+        a
+    }
+}
+
+impl Choice {
+    /// Regret-matched strategy, action-major over `n` combos.
+    pub fn current(&self, n: usize) -> Vec<Probability> {
+        Self::normalize(&self.regret.iter().map(|r| r.max(0.)).collect::<Vec<_>>(), n)
+    }
+    /// Average strategy, action-major over `n` combos.
+    pub fn average(&self, n: usize) -> Vec<Probability> {
+        Self::normalize(&self.policy, n)
+    }
+    /// Normalizes each combo's column, falling back to uniform.
+    fn normalize(weights: &[Probability], n: usize) -> Vec<Probability> {
+        let k = weights.len() / n.max(1);
+        let mut probs = weights.to_vec();
+        for h in 0..n {
+            let total = (0..k).map(|a| weights[a * n + h]).sum::<Probability>();
+            for a in 0..k {
+                probs[a * n + h] = if total > 0. { weights[a * n + h] / total } else { 1. / k as Probability };
+            }
+        }
+        probs
+    }
+}
@@ -46,6 +46,19 @@ where
         })
     }
 
+    /// Decisions from the solve root down to `node`, unwrapped.
+    fn line(node: &Node<N::T, DepthEdge<N::E, D>, DepthGame<N::G, D>, DepthInfo<N::I, D>>) -> Vec<(N::T, N::I, N::E)> {
+        let mut line = node
+            .decisions()
+            .filter_map(|(turn, info, edge)| match edge {
+                DepthEdge::Game(edge) => Some((turn, info.inner(), edge)),
+                DepthEdge::Pick(_) => None,
+            })
+            .collect::<Vec<_>>();
+        line.reverse();
+        line
+    }
+
     fn wrap<I>(&self, inner_path: I, game: &DepthGame<N::G, D>) -> DepthInfo<N::I, D>
     where
         I: IntoIterator<Item = N::E>,
@@ -94,7 +107,9 @@ where
         let parent = node.index();
         let game = *node.game();
         let game = if game.at_frontier() {
-            let payoffs = self.inner.payoffs(&self.prefix, game.inner(), game.internal());
+            let payoffs = self
+                .inner
+                .payoffs(&self.prefix, &Self::line(node), game.inner(), game.internal());
             game.to_frontier(payoffs)
         } else {
             game
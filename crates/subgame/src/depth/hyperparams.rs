@@ -5,8 +5,8 @@ use pokerkit::Probability;
 /// Depth-limited frontier evaluation parameters.
 ///
 /// At depth-limited frontiers, each player picks from a fixed number
-/// of biased continuation strategies (the count is the const generic
-/// `FRONTIER_LEAVES`, not part of these tunables). The solver learns
+/// of biased continuation strategies (the count is the leaves of the
+/// solve's `Shape`, not part of these tunables). The solver learns
 /// the minimax mix over the resulting payoff matrix.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct FrontierHyperParams {
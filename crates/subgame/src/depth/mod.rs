@@ -28,6 +28,7 @@
 //! - `continuation` — `Continuation`
 //! - `edge` — `DepthEdge`
 //! - `payoffs` — `Payoffs` (D×D matrix)
+//! - `network` — `ValueNetwork` (learned alternative to rollouts)
 //! - `phase` — `DepthPhase` (Delegate / Frontier / Internal / External)
 //! - `game` — `DepthGame`
 //! - `info` — `DepthInfo`
@@ -44,6 +45,7 @@ mod encoder;
 mod game;
 mod hyperparams;
 mod info;
+mod network;
 mod payoffs;
 mod phase;
 mod profile;
@@ -59,6 +61,7 @@ pub use encoder::*;
 pub use game::*;
 pub use hyperparams::*;
 pub use info::*;
+pub use network::*;
 pub use payoffs::*;
 pub use phase::*;
 pub use profile::*;
@@ -0,0 +1,194 @@
+//! Learned frontier values: a small MLP standing in for rollouts.
+use super::*;
+use pokerkit::Utility;
+use rand::Rng;
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+
+/// Samples per gradient step in [`ValueNetwork::fit`].
+const BATCH: usize = 32;
+/// Adam moment decay rates.
+const BETA1: f32 = 0.9;
+const BETA2: f32 = 0.999;
+const EPSILON: f32 = 1e-8;
+
+/// Fully connected layer with Adam moments for its parameters.
+///
+/// Weights are stored row-major, one row of `inputs` per output unit.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct Dense {
+    inputs: usize,
+    weights: Vec<f32>,
+    biases: Vec<f32>,
+    #[serde(skip)]
+    moments: Vec<(f32, f32)>,
+}
+
+impl Dense {
+    /// He-uniform initialization, suited to the ReLU that follows.
+    fn new(inputs: usize, outputs: usize, rng: &mut impl Rng) -> Self {
+        let limit = (6.0 / inputs.max(1) as f32).sqrt();
+        Self {
+            inputs,
+            weights: (0..inputs * outputs).map(|_| rng.random_range(-limit..limit)).collect(),
+            biases: vec![0.0; outputs],
+            moments: Vec::new(),
+        }
+    }
+
+    fn forward(&self, x: &[f32], relu: bool) -> Vec<f32> {
+        self.weights
+            .chunks_exact(self.inputs)
+            .zip(&self.biases)
+            .map(|(row, b)| row.iter().zip(x).map(|(w, x)| w * x).sum::<f32>() + b)
+            .map(|y| if relu { y.max(0.0) } else { y })
+            .collect()
+    }
+
+    /// One Adam step over a flat gradient laid out as weights then biases.
+    fn step(&mut self, grads: &[f32], rate: f32, t: i32) {
+        if self.moments.len() != grads.len() {
+            self.moments = vec![(0.0, 0.0); grads.len()];
+        }
+        let correct1 = 1.0 - BETA1.powi(t);
+        let correct2 = 1.0 - BETA2.powi(t);
+        let params = self.weights.iter_mut().chain(self.biases.iter_mut());
+        for ((p, g), (m, v)) in params.zip(grads).zip(&mut self.moments) {
+            *m = BETA1 * *m + (1.0 - BETA1) * g;
+            *v = BETA2 * *v + (1.0 - BETA2) * g * g;
+            *p -= rate * (*m / correct1) / ((*v / correct2).sqrt() + EPSILON);
+        }
+    }
+}
+
+/// Multilayer perceptron mapping frontier features to a D×D payoff matrix.
+///
+/// An alternative to Monte Carlo rollouts in [`DepthSampler::payoffs`]:
+/// train offline on `(features, Payoffs)` pairs produced by rollouts,
+/// then evaluate frontiers with a single forward pass. What the features
+/// encode (public state, beliefs over private states) is up to the game;
+/// the network only fixes their count.
+///
+/// Targets are scaled by their root mean square before training so that
+/// chip-denominated payoffs and unit-scale toy games train alike.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ValueNetwork<const D: usize> {
+    layers: Vec<Dense>,
+    scale: Utility,
+    steps: i32,
+}
+
+impl<const D: usize> ValueNetwork<D> {
+    /// ReLU hidden layers of the given widths, then a linear D×D output.
+    pub fn new(inputs: usize, hidden: &[usize], seed: u64) -> Self {
+        let ref mut rng = SmallRng::seed_from_u64(seed);
+        let widths = std::iter::once(inputs)
+            .chain(hidden.iter().copied())
+            .chain(std::iter::once(D * D))
+            .collect::<Vec<_>>();
+        Self {
+            layers: widths.windows(2).map(|w| Dense::new(w[0], w[1], rng)).collect(),
+            scale: 1.0,
+            steps: 0,
+        }
+    }
+
+    /// Feature count the network expects.
+    pub fn inputs(&self) -> usize {
+        self.layers.first().map_or(0, |l| l.inputs)
+    }
+
+    /// Predicted frontier payoffs for one feature vector.
+    pub fn payoffs(&self, features: &[f32]) -> Payoffs<D> {
+        assert_eq!(features.len(), self.inputs(), "feature count mismatch");
+        let output = self.activations(features).pop().expect("output layer");
+        Payoffs::tabulate(|k, j| output[k.index() * D + j.index()] * self.scale)
+    }
+
+    /// Mean squared error per matrix entry, in unscaled payoff units.
+    pub fn loss(&self, samples: &[(Vec<f32>, Payoffs<D>)]) -> Utility {
+        samples.iter().map(|(x, y)| self.payoffs(x).error(y)).sum::<Utility>() / samples.len().max(1) as Utility
+    }
+
+    /// Trains with minibatch Adam, returning the loss after each epoch.
+    ///
+    /// The target scale is fixed on the first call, so later calls
+    /// fine-tune rather than reset the network.
+    pub fn fit(&mut self, samples: &[(Vec<f32>, Payoffs<D>)], epochs: usize, rate: f32) -> Vec<Utility> {
+        if self.steps == 0 {
+            let squares = samples.iter().flat_map(|(_, y)| y.values()).map(|v| v * v);
+            let rms = (squares.sum::<Utility>() / (samples.len() * D * D).max(1) as Utility).sqrt();
+            self.scale = if rms > 0.0 { rms } else { 1.0 };
+        }
+        let ref mut rng = rand::rng();
+        let mut order = (0..samples.len()).collect::<Vec<_>>();
+        (0..epochs)
+            .map(|_| {
+                order.shuffle(rng);
+                for batch in order.chunks(BATCH) {
+                    self.descend(batch.iter().map(|&i| &samples[i]), rate);
+                }
+                self.loss(samples)
+            })
+            .collect()
+    }
+
+    /// The input followed by every layer's post-activation output.
+    fn activations(&self, features: &[f32]) -> Vec<Vec<f32>> {
+        let last = self.layers.len() - 1;
+        let mut acts = vec![features.to_vec()];
+        for (i, layer) in self.layers.iter().enumerate() {
+            let next = layer.forward(acts.last().expect("input"), i < last);
+            acts.push(next);
+        }
+        acts
+    }
+
+    /// Backpropagates squared error over one minibatch and steps every layer.
+    fn descend<'a>(&mut self, batch: impl Iterator<Item = &'a (Vec<f32>, Payoffs<D>)>, rate: f32) {
+        let mut grads = self
+            .layers
+            .iter()
+            .map(|l| vec![0.0; l.weights.len() + l.biases.len()])
+            .collect::<Vec<_>>();
+        let mut count = 0;
+        for (x, y) in batch {
+            count += 1;
+            let acts = self.activations(x);
+            let output = acts.last().expect("output");
+            let mut delta = output
+                .iter()
+                .zip(y.values())
+                .map(|(o, t)| 2.0 * (o - t / self.scale) / (D * D) as f32)
+                .collect::<Vec<_>>();
+            for (i, layer) in self.layers.iter().enumerate().rev() {
+                let input = &acts[i];
+                let (gw, gb) = grads[i].split_at_mut(layer.weights.len());
+                for (o, d) in delta.iter().enumerate() {
+                    gb[o] += d;
+                    for (g, a) in gw[o * layer.inputs..(o + 1) * layer.inputs].iter_mut().zip(input) {
+                        *g += d * a;
+                    }
+                }
+                if i > 0 {
+                    delta = (0..layer.inputs)
+                        .map(|k| {
+                            let back = delta
+                                .iter()
+                                .enumerate()
+                                .map(|(o, d)| d * layer.weights[o * layer.inputs + k])
+                                .sum::<f32>();
+                            if input[k] > 0.0 { back } else { 0.0 }
+                        })
+                        .collect();
+                }
+            }
+        }
+        self.steps += 1;
+        for (layer, grads) in self.layers.iter_mut().zip(&mut grads) {
+            grads.iter_mut().for_each(|g| *g /= count.max(1) as f32);
+            layer.step(grads, rate, self.steps);
+        }
+    }
+}
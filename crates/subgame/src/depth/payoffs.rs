@@ -20,4 +20,18 @@ impl<const D: usize> Payoffs<D> {
     pub fn get(&self, row: Continuation, col: Continuation) -> Utility {
         self.0[row.index()][col.index()]
     }
+
+    /// Entries in row-major order.
+    pub fn values(&self) -> impl Iterator<Item = Utility> + '_ {
+        self.0.iter().flatten().copied()
+    }
+
+    /// Mean squared difference per entry against another matrix.
+    pub fn error(&self, other: &Self) -> Utility {
+        self.values()
+            .zip(other.values())
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum::<Utility>()
+            / (D * D).max(1) as Utility
+    }
 }
@@ -20,6 +20,14 @@ pub trait DepthSampler<const D: usize>: CfrEncoder {
     /// `prefix` carries `(turn, edge)` pairs captured by whoever walked
     /// the tree originally — the turns are ground truth, not
     /// reconstructed from edges (which is unsafe for games with
-    /// chip-snapping or randomized chance).
-    fn payoffs(&self, prefix: &Prefix<Self::T, Self::E>, game: &Self::G, internal: Self::T) -> Payoffs<D>;
+    /// chip-snapping or randomized chance). `line` holds the decisions
+    /// taken inside the solve on the way to this frontier, root first,
+    /// for samplers that value a range rather than the dealt cards.
+    fn payoffs(
+        &self,
+        prefix: &Prefix<Self::T, Self::E>,
+        line: &[(Self::T, Self::I, Self::E)],
+        game: &Self::G,
+        internal: Self::T,
+    ) -> Payoffs<D>;
 }
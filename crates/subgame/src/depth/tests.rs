@@ -225,7 +225,13 @@ mod tests {
             &self.1
         }
 
-        fn payoffs(&self, prefix: &Prefix<Self::T, Self::E>, game: &Self::G, _: Self::T) -> Payoffs<N> {
+        fn payoffs(
+            &self,
+            prefix: &Prefix<Self::T, Self::E>,
+            _: &[(Self::T, Self::I, Self::E)],
+            game: &Self::G,
+            _: Self::T,
+        ) -> Payoffs<N> {
             Payoffs::uniform(self.1.frontier_payoff(&self.resume(prefix.into_iter().edges(), game)))
         }
     }
@@ -319,4 +325,31 @@ mod tests {
         let root_node = tree.at(petgraph::graph::NodeIndex::new(0));
         assert_eq!(root_node.width(), 2, "root should have 2 game branches");
     }
+
+    #[test]
+    fn value_network_fits_bilinear_payoffs() {
+        let target =
+            |x: &[f32]| Payoffs::<D>::tabulate(|k, j| 10.0 * x[0] * k.index() as f32 - 5.0 * x[1] * j.index() as f32);
+        let samples = (0..256)
+            .map(|i| vec![(i % 16) as f32 / 16.0, (i / 16) as f32 / 16.0])
+            .map(|x| {
+                let y = target(&x);
+                (x, y)
+            })
+            .collect::<Vec<_>>();
+        let mut network = ValueNetwork::<D>::new(2, &[32, 32], 7);
+        let before = network.loss(&samples);
+        let losses = network.fit(&samples, 200, 1e-2);
+        let after = *losses.last().unwrap();
+        assert!(after < before / 50.0, "loss {before} -> {after}");
+    }
+
+    #[test]
+    fn value_network_roundtrips_through_json() {
+        let network = ValueNetwork::<D>::new(3, &[8], 1);
+        let json = serde_json::to_string(&network).unwrap();
+        let loaded = serde_json::from_str::<ValueNetwork<D>>(&json).unwrap();
+        let x = [0.2, -0.4, 0.9];
+        assert_eq!(network.payoffs(&x).error(&loaded.payoffs(&x)), 0.0);
+    }
 }
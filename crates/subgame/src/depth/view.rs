@@ -1,5 +1,6 @@
 //! Read-only view adapter for leaf-augmented games.
 use super::*;
+use crate::world::Lock;
 use mccfr::*;
 use pokerkit::*;
 
@@ -35,6 +36,18 @@ where
     pub fn new(inner: &'blueprint P) -> Self {
         Self { inner }
     }
+
+    /// Lifts a lock on the base game onto the view's game nodes. Frontier
+    /// picks are never pinned.
+    pub fn lift<X>(lock: Lock<X, P::E>) -> Lock<DepthPublic<X, D>, DepthEdge<P::E, D>>
+    where
+        X: CfrPublic<E = P::E>,
+    {
+        lock.pins()
+            .fold(Lock::new(lock.response()), |lifted, (round, public, pin)| {
+                lifted.place(round, DepthPublic::Game(*public), pin.map(DepthEdge::Game))
+            })
+    }
 }
 
 impl<P, const D: usize> CfrRule for DepthView<'_, P, D>
@@ -35,7 +35,7 @@ where
         }
     }
 
-    pub fn inner(&self) -> &N {
+    pub fn inner(&self) -> &'blueprint N {
         self.inner
     }
 
@@ -43,6 +43,22 @@ where
         self.world = world;
     }
 
+    /// Decisions from the solve root down to `node`, unwrapped.
+    #[allow(clippy::type_complexity)]
+    fn line(
+        node: &Node<N::T, DepthEdge<N::E, L>, DepthGame<N::G, L>, WorldInfo<DepthInfo<N::I, L>>>,
+    ) -> Vec<(N::T, N::I, N::E)> {
+        let mut line = node
+            .decisions()
+            .filter_map(|(turn, info, edge)| match edge {
+                DepthEdge::Game(edge) => Some((turn, info.inner().inner(), edge)),
+                DepthEdge::Pick(_) => None,
+            })
+            .collect::<Vec<_>>();
+        line.reverse();
+        line
+    }
+
     fn make_info<I>(&self, past: I, game: &DepthGame<N::G, L>) -> WorldInfo<DepthInfo<N::I, L>>
     where
         I: IntoIterator<Item = N::E>,
@@ -100,7 +116,9 @@ where
         let parent = node.index();
         let game = *node.game();
         let game = if game.at_frontier() {
-            let payoffs = self.inner.payoffs(&self.prefix, game.inner(), game.internal());
+            let payoffs = self
+                .inner
+                .payoffs(&self.prefix, &Self::line(node), game.inner(), game.internal());
             game.to_frontier(payoffs)
         } else {
             game
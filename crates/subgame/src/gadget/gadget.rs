@@ -0,0 +1,34 @@
+/// Which opponent gadget a [`super::GadgetSolver`] prepends to the subgame.
+///
+/// All three share the same subgame tree and the same per-secret
+/// alternatives; they differ only in how the opponent's gadget decision
+/// reweights secrets between iterations (see [`super::Margins`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Gadget {
+    /// Burch et al. 2014: each secret independently follows into the
+    /// subgame or terminates at its alternative value.
+    Resolve,
+    /// Moravčík et al. 2016: the opponent picks the secret with the
+    /// smallest margin, so the resolver maximizes the worst-case margin.
+    #[default]
+    Maxmargin,
+    /// Brown & Sandholm 2017: maxmargin with each alternative raised by
+    /// the value the opponent already gave up on the way to the subgame.
+    Reach,
+}
+
+impl Gadget {
+    pub const fn all() -> [Self; 3] {
+        [Self::Resolve, Self::Maxmargin, Self::Reach]
+    }
+}
+
+impl std::fmt::Display for Gadget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Resolve => write!(f, "resolve"),
+            Self::Maxmargin => write!(f, "maxmargin"),
+            Self::Reach => write!(f, "reach"),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Per-secret state of the opponent's gadget decision.
+//!
+//! The subgame itself is solved by ordinary CFR over a single tree; the
+//! gadget is a tiny one-shot game layered on top. It is small enough
+//! (one decision per opponent secret) that its regrets are kept here in
+//! closed form instead of as extra nodes in the subgame tree.
+use super::Gadget;
+use mccfr::CfrSecret;
+use mccfr::Posterior;
+use pokerkit::Probability;
+use pokerkit::Utility;
+use std::collections::BTreeMap;
+
+/// Share of the sampling distribution spread over secrets by reach,
+/// so that secrets the gadget currently ignores keep fresh values.
+const EXPLORATION: Probability = 0.1;
+
+/// Number of recent observations a secret's subgame value averages over.
+const WINDOW: u32 = 32;
+
+/// One opponent secret's entry in the gadget.
+#[derive(Debug, Clone, Copy, Default)]
+struct Margin {
+    /// Normalized reach of the secret under the blueprint.
+    reach: Probability,
+    /// Opponent's counterfactual value under the blueprint.
+    alternative: Utility,
+    /// Value the opponent gave up before the subgame (reach gadget only).
+    gift: Utility,
+    /// Opponent's recent average value inside the resolved subgame.
+    value: Utility,
+    /// Observations folded into `value`, saturating at [`WINDOW`].
+    seen: u32,
+    /// RM+ regret for entering the subgame with this secret.
+    enter: Utility,
+    /// RM+ regret for terminating at the alternative (resolve gadget only).
+    terminate: Utility,
+}
+
+impl Margin {
+    fn follow(&self) -> Probability {
+        let total = self.enter + self.terminate;
+        if total > 0.0 { self.enter / total } else { 0.5 }
+    }
+}
+
+/// Opponent gadget over secrets, trained by regret matching.
+///
+/// Each solver step observes the subgame value of one sampled secret,
+/// updates the gadget's regrets, and hands back the distribution from
+/// which the next secret is dealt.
+#[derive(Debug, Clone)]
+pub struct Margins<Y>
+where
+    Y: CfrSecret,
+{
+    gadget: Gadget,
+    margins: BTreeMap<Y, Margin>,
+}
+
+impl<Y> Margins<Y>
+where
+    Y: CfrSecret,
+{
+    /// Secrets with zero reach are dropped; the rest are renormalized.
+    pub fn new(gadget: Gadget, prior: Posterior<Y>) -> Self {
+        let total = prior.total();
+        let margins = prior
+            .into_iter()
+            .filter(|(_, p)| *p > 0.0)
+            .map(|(y, p)| {
+                let margin = Margin {
+                    reach: p / total,
+                    ..Margin::default()
+                };
+                (y, margin)
+            })
+            .collect();
+        Self { gadget, margins }
+    }
+
+    pub fn gadget(&self) -> Gadget {
+        self.gadget
+    }
+
+    pub fn secrets(&self) -> impl Iterator<Item = Y> + '_ {
+        self.margins.keys().copied()
+    }
+
+    /// Records the blueprint alternative of a secret, which also seeds its
+    /// subgame value so the gadget starts indifferent.
+    pub fn price(&mut self, secret: Y, alternative: Utility) {
+        if let Some(margin) = self.margins.get_mut(&secret) {
+            margin.alternative = alternative;
+            margin.value = alternative;
+        }
+    }
+
+    /// Adds the value a secret gave up at one earlier opponent decision.
+    /// Negative gifts are floored, so gifts only ever loosen the threshold.
+    pub fn gift(&mut self, secret: Y, gift: Utility) {
+        if let Some(margin) = self.margins.get_mut(&secret) {
+            margin.gift += gift.max(0.0);
+        }
+    }
+
+    /// Value the resolver must hold the opponent to for this secret.
+    pub fn threshold(&self, secret: &Y) -> Utility {
+        self.margins.get(secret).map_or(0.0, |m| self.bound(m))
+    }
+
+    /// Resolver's margin for a secret: how far below the threshold the
+    /// opponent's subgame value currently sits.
+    pub fn margin(&self, secret: &Y) -> Utility {
+        self.margins.get(secret).map_or(0.0, |m| self.bound(m) - m.value)
+    }
+
+    /// Smallest margin across secrets; negative means the resolve is
+    /// currently giving some secret more than the blueprint did.
+    pub fn worst(&self) -> Utility {
+        self.secrets()
+            .map(|y| self.margin(&y))
+            .fold(Utility::INFINITY, Utility::min)
+    }
+
+    /// The gadget's current distribution over secrets entering the subgame.
+    pub fn policy(&self) -> BTreeMap<Y, Probability> {
+        let weights = self
+            .margins
+            .iter()
+            .map(|(y, m)| match self.gadget {
+                Gadget::Resolve => (*y, m.reach * m.follow()),
+                Gadget::Maxmargin | Gadget::Reach => (*y, m.enter),
+            })
+            .collect::<BTreeMap<_, _>>();
+        let total = weights.values().sum::<Probability>();
+        if total > 0.0 {
+            weights.into_iter().map(|(y, w)| (y, w / total)).collect()
+        } else {
+            match self.gadget {
+                Gadget::Resolve => self.margins.iter().map(|(y, m)| (*y, m.reach)).collect(),
+                Gadget::Maxmargin | Gadget::Reach => {
+                    let n = self.margins.len() as Probability;
+                    self.secrets().map(|y| (y, 1.0 / n)).collect()
+                }
+            }
+        }
+    }
+
+    /// Sampling weights: the gadget policy mixed with reach so every
+    /// secret keeps being dealt and revalued.
+    pub fn weights(&self) -> Vec<(Y, Probability)> {
+        self.policy()
+            .into_iter()
+            .map(|(y, p)| (y, (1.0 - EXPLORATION) * p + EXPLORATION * self.margins[&y].reach))
+            .collect()
+    }
+
+    /// Folds a sampled subgame value for a secret into its moving average
+    /// and takes one regret-matching step on the gadget decision.
+    pub fn observe(&mut self, secret: Y, value: Utility) {
+        let Some(margin) = self.margins.get_mut(&secret) else {
+            return;
+        };
+        margin.seen = (margin.seen + 1).min(WINDOW);
+        margin.value += (value - margin.value) / margin.seen as Utility;
+        match self.gadget {
+            Gadget::Resolve => {
+                let follow = margin.follow();
+                let expected = follow * margin.value + (1.0 - follow) * margin.alternative;
+                margin.enter = (margin.enter + margin.value - expected).max(0.0);
+                margin.terminate = (margin.terminate + margin.alternative - expected).max(0.0);
+            }
+            Gadget::Maxmargin | Gadget::Reach => {
+                let policy = self.policy();
+                let surplus = self
+                    .margins
+                    .iter()
+                    .map(|(y, m)| (*y, m.value - self.bound(m)))
+                    .collect::<BTreeMap<_, _>>();
+                let expected = surplus.iter().map(|(y, s)| policy[y] * s).sum::<Utility>();
+                for (y, margin) in &mut self.margins {
+                    margin.enter = (margin.enter + surplus[y] - expected).max(0.0);
+                }
+            }
+        }
+    }
+
+    fn bound(&self, margin: &Margin) -> Utility {
+        match self.gadget {
+            Gadget::Reach => margin.alternative + margin.gift,
+            Gadget::Resolve | Gadget::Maxmargin => margin.alternative,
+        }
+    }
+}
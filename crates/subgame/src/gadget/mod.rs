@@ -0,0 +1,41 @@
+//! Safe subgame solving via explicit opponent gadgets.
+//!
+//! An alternative to the world partition in [`crate::world`]: instead of
+//! bucketing the opponent's range into K worlds and tagging every info set
+//! with a world index, the subgame keeps a single copy of the tree and
+//! prepends a gadget decision for the opponent. At the gadget the opponent,
+//! holding secret `y`, compares the value `v(y)` of entering the resolved
+//! subgame against an alternative `alt(y)` (their counterfactual value under
+//! the blueprint). The resolver is pushed to keep `v(y) ≤ alt(y)`, which is
+//! what makes the resolve safe.
+//!
+//! | Gadget | Opponent chooses | Objective |
+//! |--------|------------------|-----------|
+//! | `Resolve` | per secret: follow or terminate at `alt(y)` | unsafe-but-cheap re-solve |
+//! | `Maxmargin` | which secret to enter with | maximize `min_y alt(y) − v(y)` |
+//! | `Reach` | as `Maxmargin`, with `alt(y)` raised by gifts | larger margin where the opponent already erred |
+//!
+//! # File layout (one type per file)
+//!
+//! - `gadget` — `Gadget`
+//! - `margins` — `Margins` (per-secret gadget regrets)
+//! - `solver` — `GadgetSolver`
+//!
+//! # References
+//!
+//! Burch, N., Johanson, M., & Bowling, M. (2014). Solving imperfect
+//! information games using decomposition. AAAI.
+//!
+//! Moravčík, M., Schmid, M., Ha, K., Hladík, M., & Gaukrodger, S. J. (2016).
+//! Refining subgames in large imperfect information games. AAAI.
+//!
+//! Brown, N., & Sandholm, T. (2017). Safe and nested subgame solving for
+//! imperfect-information games. NeurIPS.
+
+mod gadget;
+mod margins;
+mod solver;
+
+pub use gadget::*;
+pub use margins::*;
+pub use solver::*;
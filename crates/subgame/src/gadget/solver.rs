@@ -0,0 +1,349 @@
+//! Gadget solver: safe subgame solving with an explicit opponent gadget.
+//!
+//! The solver overrides `step()` to:
+//! 1. Sample an opponent secret from the gadget's distribution, and a
+//!    resolver hand from its range when one is given
+//! 2. Deal both into the observed game state via [`WorldRestrict`]
+//! 3. Run standard CFR on the resolve-phase tree
+//! 4. Evaluate the opponent's subgame value for that secret and feed it
+//!    back to the gadget's regrets
+//!
+//! Unlike [`crate::SubGameSolver`], every secret shares one copy of the
+//! tree (world 0), so the resolver's info sets never depend on which
+//! bucket the opponent's hand fell into.
+use std::collections::BTreeMap;
+
+use super::*;
+use crate::SubGameEncoder;
+use crate::depth::*;
+use crate::world::*;
+use mccfr::*;
+use petgraph::graph::NodeIndex;
+use pokerkit::Probability;
+use pokerkit::Utility;
+
+/// Solver for safe subgame solving through a Resolve, Maxmargin or Reach gadget.
+///
+/// Drop-in alternative to [`crate::SubGameSolver`] over the same
+/// [`DepthSampler`] and [`WorldRestrict`] hooks; the source only needs to
+/// restrict into a single world.
+pub struct GadgetSolver<'blueprint, const L: usize, N, I, Y>
+where
+    N: DepthSampler<L, Blueprint: CfrFlow<I = I>>,
+    N: WorldRestrict<1, I = I>,
+    I: CfrInfo<E = N::E, T = N::T, Y = Y>,
+    Y: CfrSecret,
+{
+    encoder: SubGameEncoder<'blueprint, N, L>,
+    profile: WorldProfile<'blueprint, DepthView<'blueprint, N::Blueprint, L>>,
+    blueprint: &'blueprint DepthView<'blueprint, N::Blueprint, L>,
+    margins: Margins<Y>,
+    range: Vec<(Y, Probability)>,
+    gifts: Vec<(CfrRecall<N::G>, N::E)>,
+    priced: bool,
+    internal: N::T,
+    external: N::T,
+    recall: CfrRecall<N::G>,
+    entry: DepthGame<N::G, L>,
+    origin: Option<usize>,
+}
+
+impl<'blueprint, const L: usize, N, I, Y> GadgetSolver<'blueprint, L, N, I, Y>
+where
+    N: WorldRestrict<1, I = I> + DepthSampler<L, Blueprint: CfrFlow<I = I>>,
+    I: CfrInfo<E = N::E, T = N::T, Y = Y>,
+    Y: CfrSecret,
+{
+    pub fn new(
+        source: &'blueprint N,
+        external: N::T,
+        gadget: Gadget,
+        prior: Posterior<Y>,
+        recall: CfrRecall<N::G>,
+    ) -> Self {
+        Self::build(source, external, gadget, prior, recall, None)
+    }
+
+    pub fn with_origin(
+        source: &'blueprint N,
+        external: N::T,
+        gadget: Gadget,
+        prior: Posterior<Y>,
+        recall: CfrRecall<N::G>,
+        origin: usize,
+    ) -> Self {
+        Self::build(source, external, gadget, prior, recall, Some(origin))
+    }
+
+    fn build(
+        source: &'blueprint N,
+        external: N::T,
+        gadget: Gadget,
+        prior: Posterior<Y>,
+        recall: CfrRecall<N::G>,
+        origin: Option<usize>,
+    ) -> Self {
+        let internal = (0..N::T::players())
+            .map(N::T::from)
+            .find(|t| t != &external)
+            .expect("two player game");
+        let prefix = recall.descents().to_vec();
+        let leaf_view: &'blueprint DepthView<'blueprint, N::Blueprint, L> =
+            Box::leak(Box::new(DepthView::new(source.blueprint())));
+        let entry = DepthGame::<_, L>::new(recall.game(), internal, origin);
+        Self {
+            encoder: SubGameEncoder::new(source, prefix, World::from(0)),
+            profile: WorldProfile::new(leaf_view),
+            blueprint: leaf_view,
+            margins: Margins::new(gadget, prior),
+            range: Vec::new(),
+            gifts: Vec::new(),
+            priced: false,
+            internal,
+            external,
+            recall,
+            entry,
+            origin,
+        }
+    }
+
+    /// Resolves for the resolver's whole range instead of only the hand
+    /// dealt in the observed state. Each step deals a hand from `range`,
+    /// and alternatives and gifts become range-weighted averages.
+    pub fn with_range(mut self, range: Posterior<Y>) -> Self {
+        let total = range.total();
+        self.range = range
+            .into_iter()
+            .filter(|(_, p)| *p > 0.0)
+            .map(|(y, p)| (y, p / total))
+            .collect();
+        self
+    }
+
+    /// Credits each secret with the value it gave up at an earlier opponent
+    /// decision: the blueprint's best choice at `before` minus `chosen`.
+    /// Only the [`Gadget::Reach`] gadget reads gifts; call once per
+    /// opponent decision on the path to accumulate them.
+    pub fn with_gift(mut self, before: CfrRecall<N::G>, chosen: N::E) -> Self {
+        self.gifts.push((before, chosen));
+        self
+    }
+
+    pub fn margins(&self) -> &Margins<Y> {
+        &self.margins
+    }
+
+    pub fn into_profile(self) -> WorldProfile<'blueprint, DepthView<'blueprint, N::Blueprint, L>> {
+        self.profile
+    }
+
+    /// Prices every secret's alternative and gifts against the blueprint.
+    /// Deferred to the first step so the builders can be chained freely.
+    fn price(&mut self) {
+        let ref blueprint = WorldProfile::new(self.blueprint);
+        let secrets = self.margins.secrets().collect::<Vec<_>>();
+        for secret in secrets.iter().copied() {
+            let alternative = self
+                .hands()
+                .into_iter()
+                .map(|(hand, p)| {
+                    p * self.evaluate(&self.encoder, blueprint, self.deal(secret, hand, &self.recall.game()))
+                })
+                .sum();
+            self.margins.price(secret, alternative);
+        }
+        for (before, chosen) in std::mem::take(&mut self.gifts) {
+            let encoder = SubGameEncoder::new(self.encoder.inner(), before.descents().to_vec(), World::from(0));
+            for secret in secrets.iter().copied() {
+                let gift = self
+                    .hands()
+                    .into_iter()
+                    .map(|(hand, p)| {
+                        p * self.forgone(&encoder, blueprint, self.deal(secret, hand, &before.game()), chosen)
+                    })
+                    .sum();
+                self.margins.gift(secret, gift);
+            }
+        }
+        self.priced = true;
+    }
+
+    /// Resolver hands to average over: the range, or the dealt hand alone.
+    fn hands(&self) -> Vec<(Option<Y>, Probability)> {
+        if self.range.is_empty() {
+            vec![(None, 1.0)]
+        } else {
+            self.range.iter().map(|(y, p)| (Some(*y), *p)).collect()
+        }
+    }
+
+    fn sample(&self) -> (Y, Option<Y>) {
+        use rand::distr::weighted::WeightedIndex;
+        use rand::prelude::*;
+        let ref mut rng = rand::rng();
+        let weights = self.margins.weights();
+        let secret = WeightedIndex::new(weights.iter().map(|(_, p)| *p))
+            .map(|d| weights[d.sample(rng)].0)
+            .expect("nonempty weights");
+        let hands = self.hands();
+        let hand = WeightedIndex::new(hands.iter().map(|(_, p)| *p))
+            .map(|d| hands[d.sample(rng)].0)
+            .expect("nonempty range");
+        (secret, hand)
+    }
+
+    /// Deals `secret` to the opponent and, if given, `hand` to the resolver
+    /// in `observed`, keeping every other card.
+    fn deal(&self, secret: Y, hand: Option<Y>, observed: &N::G) -> DepthGame<N::G, L> {
+        let only = |y: Y| Belief::new(BTreeMap::from([(y, World::from(0))]), [1.0]);
+        let source = self.encoder.inner();
+        let observed =
+            hand.map_or(*observed, |hand| source.restrict(self.internal, World::from(0), &only(hand), observed));
+        let inner = source.restrict(self.external, World::from(0), &only(secret), &observed);
+        DepthGame::<_, L>::new(inner, self.internal, self.origin)
+    }
+
+    /// Opponent's expected value from `entry` with both players on the
+    /// averaged strategy of `profile`.
+    fn evaluate(
+        &self,
+        encoder: &SubGameEncoder<'blueprint, N, L>,
+        profile: &WorldProfile<'blueprint, DepthView<'blueprint, N::Blueprint, L>>,
+        entry: DepthGame<N::G, L>,
+    ) -> Utility {
+        let tree = TreeBuilder::<_, _, _, _, _, _, VanillaSampling>::new(encoder, profile, entry, 0).build();
+        profile.external_payoff(&tree.at(NodeIndex::new(0)), self.external)
+    }
+
+    /// Value the opponent forgoes at `before` by playing `chosen` instead
+    /// of the blueprint's best choice.
+    fn forgone(
+        &self,
+        encoder: &SubGameEncoder<'blueprint, N, L>,
+        profile: &WorldProfile<'blueprint, DepthView<'blueprint, N::Blueprint, L>>,
+        before: DepthGame<N::G, L>,
+        chosen: N::E,
+    ) -> Utility {
+        let tree = TreeBuilder::<_, _, _, _, _, _, VanillaSampling>::new(encoder, profile, before, 0).build();
+        let values = tree
+            .at(NodeIndex::new(0))
+            .children()
+            .iter()
+            .filter_map(|child| Some((*child.incoming()?, profile.external_payoff(child, self.external))))
+            .collect::<Vec<_>>();
+        let best = values.iter().map(|(_, v)| *v).fold(Utility::NEG_INFINITY, Utility::max);
+        values
+            .iter()
+            .find(|(e, _)| *e == DepthEdge::Game(chosen))
+            .map_or(0.0, |(_, v)| best - v)
+    }
+}
+
+impl<'blueprint, const L: usize, N, I, Y> Solver for GadgetSolver<'blueprint, L, N, I, Y>
+where
+    N: WorldRestrict<1, I = I> + DepthSampler<L, Blueprint: CfrFlow<I = I>> + Sync,
+    N::Blueprint: Sync,
+    I: CfrInfo<E = N::E, T = N::T, Y = Y>,
+    Y: CfrSecret,
+{
+    type T = N::T;
+    type E = DepthEdge<N::E, L>;
+    type G = DepthGame<N::G, L>;
+    type I = WorldInfo<DepthInfo<I, L>>;
+    type X = DepthPublic<I::X, L>;
+    type Y = I::Y;
+    type P = WorldProfile<'blueprint, DepthView<'blueprint, N::Blueprint, L>>;
+    type N = SubGameEncoder<'blueprint, N, L>;
+    type S = ExternalSampling;
+    type R = SummedRegret;
+    type W = LinearWeight;
+
+    fn batch_size() -> usize {
+        1
+    }
+
+    fn advance(&mut self) {
+        self.profile.increment();
+    }
+
+    fn encoder(&self) -> &Self::N {
+        &self.encoder
+    }
+
+    fn profile(&self) -> &Self::P {
+        &self.profile
+    }
+
+    fn storage(&mut self) -> &mut Self::P {
+        &mut self.profile
+    }
+
+    fn root(&self) -> Self::G {
+        self.entry
+    }
+
+    fn step(&mut self) {
+        if !self.priced {
+            self.price();
+        }
+        let (secret, hand) = self.sample();
+        self.entry = self.deal(secret, hand, &self.recall.game());
+        let updates = self.batch();
+        let n = updates.len();
+        for ref update in updates {
+            self.update_regret(update);
+            self.update_weight(update);
+            self.update_payoff(update);
+            self.update_visits(update);
+        }
+        let value = self.evaluate(&self.encoder, &self.profile, self.entry);
+        self.margins.observe(secret, value);
+        tracing::trace!(
+            "[{}] t={:<6} infos={:<4} regret={:.4} margin={:+.4}",
+            self.margins.gadget(),
+            self.profile.t(),
+            n,
+            self.profile.sum_regret(),
+            self.margins.worst(),
+        );
+        self.profile().metrics().inspect(|m: &&mccfr::Metrics| m.inc_epoch());
+        self.advance();
+    }
+}
+
+impl<const L: usize, N, I, Y> Harvest for GadgetSolver<'_, L, N, I, Y>
+where
+    N: WorldRestrict<1, I = I> + DepthSampler<L, Blueprint: CfrFlow<I = I>> + Sync,
+    N::Blueprint: Sync,
+    I: CfrInfo<E = N::E, T = N::T, Y = Y>,
+    Y: CfrSecret,
+{
+    type Base = I;
+    type Edge = N::E;
+
+    fn harvest(&self, base: I) -> Harvested<N::E> {
+        let ref info = WorldInfo::new(World::from(0), DepthInfo::<_, L>::Game(base));
+        let refined: BTreeMap<N::E, Probability> = self
+            .profile()
+            .iterated_distribution(info)
+            .into_iter()
+            .filter_map(|(e, p)| match e {
+                DepthEdge::Game(e) => Some((e, p)),
+                _ => None,
+            })
+            .collect();
+        let visits: BTreeMap<N::E, u32> = refined
+            .keys()
+            .map(|e| (*e, self.profile().cum_visits(info, &DepthEdge::Game(*e))))
+            .collect();
+        let regret = refined
+            .keys()
+            .map(|e| self.profile().cum_regret(info, &DepthEdge::Game(*e)).max(0.0))
+            .sum();
+        Harvested {
+            refined,
+            visits,
+            regret,
+        }
+    }
+}
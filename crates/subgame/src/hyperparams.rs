@@ -1,24 +1,43 @@
 //! Hyperparameters for safe + depth-limited subgame solving.
 
 use crate::depth::FrontierHyperParams;
+use pokerkit::Shape;
 
 /// Subgame solving parameters.
 ///
 /// Controls per-decision real-time refinement of the blueprint strategy.
-/// Composes [`FrontierHyperParams`] for the depth-limited leaf evaluation.
+/// Composes [`FrontierHyperParams`] for the depth-limited leaf evaluation
+/// and [`CacheHyperParams`] for reusing solutions across decisions, and
+/// [`ExploitHyperParams`] for bots that re-solve against an opponent model.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct SubgameHyperParams {
     timeout_ms: u64,
     visit_threshold: u32,
+    ponder: usize,
+    shape: Shape,
     frontier: FrontierHyperParams,
+    cache: CacheHyperParams,
+    exploit: ExploitHyperParams,
 }
 
 impl SubgameHyperParams {
-    pub fn new(timeout_ms: u64, visit_threshold: u32, frontier: FrontierHyperParams) -> Self {
+    pub fn new(
+        timeout_ms: u64,
+        visit_threshold: u32,
+        ponder: usize,
+        shape: Shape,
+        frontier: FrontierHyperParams,
+        cache: CacheHyperParams,
+        exploit: ExploitHyperParams,
+    ) -> Self {
         Self {
             timeout_ms,
             visit_threshold,
+            ponder,
+            shape,
             frontier,
+            cache,
+            exploit,
         }
     }
 
@@ -32,10 +51,31 @@ impl SubgameHyperParams {
         self.visit_threshold
     }
 
+    /// Opponent actions solved speculatively while the opponent is on the
+    /// clock, most likely first. Zero disables pondering.
+    pub fn ponder(&self) -> usize {
+        self.ponder
+    }
+
+    /// World and frontier-leaf counts for variants that don't set their own.
+    pub fn shape(&self) -> Shape {
+        self.shape
+    }
+
     /// Depth-limited frontier evaluation parameters.
     pub fn frontier(&self) -> &FrontierHyperParams {
         &self.frontier
     }
+
+    /// Solution cache bounds and reuse thresholds.
+    pub fn cache(&self) -> &CacheHyperParams {
+        &self.cache
+    }
+
+    /// Opponent-model shrinkage and pinning thresholds.
+    pub fn exploit(&self) -> &ExploitHyperParams {
+        &self.exploit
+    }
 }
 
 impl Default for SubgameHyperParams {
@@ -48,9 +88,120 @@ impl Default for SubgameHyperParams {
             // `w(a) = visits(a) / (visits(a) + V)`. Sits near observed
             // p50 visits → ~55-65% subgame / ~35-45% blueprint blend.
             visit_threshold: 1 << 18,
+            // Each pondered action runs a full-budget solve on its own
+            // thread. Two covers the usual check/bet or fold/call split
+            // without starving the other seats' solves of cores.
+            ponder: 2,
+            // The compiled-in N_WORLDS × FRONTIER_LEAVES.
+            shape: Shape::default(),
             frontier: FrontierHyperParams::default(),
+            cache: CacheHyperParams::default(),
+            exploit: ExploitHyperParams::default(),
         }
     }
 }
 
 pokerkit::hyperparams!(SubgameHyperParams);
+
+/// Subgame solution cache parameters.
+///
+/// A cached solution is the refined policy and visit counts from an
+/// earlier solve of the same spot. Its total visits decide how it is
+/// used: at or above `replace` it stands in for the solve outright;
+/// below, a shorter solve (`pool` of the budget) runs from scratch and
+/// its visits are pooled with the entry's, so a recurring spot
+/// accumulates visits until it crosses `replace`. The cached policy does
+/// not seed that solve; it only enters the pooled average.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CacheHyperParams {
+    capacity: usize,
+    replace: u32,
+    pool: f32,
+}
+
+impl CacheHyperParams {
+    pub fn new(capacity: usize, replace: u32, pool: f32) -> Self {
+        Self {
+            capacity,
+            replace,
+            pool,
+        }
+    }
+
+    /// Most solutions an installed cache holds before the least recently
+    /// used is evicted. Zero stores nothing.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Total root visits at which a cached solution replaces a fresh solve.
+    pub fn replace(&self) -> u32 {
+        self.replace
+    }
+
+    /// Share of the time budget a solve pooled into a thin entry gets,
+    /// in `[0, 1]`.
+    pub fn pool(&self) -> f32 {
+        self.pool.clamp(0.0, 1.0)
+    }
+}
+
+impl Default for CacheHyperParams {
+    fn default() -> Self {
+        Self {
+            // A few thousand spots covers the recurring flop textures and
+            // lines of a long session at a few KB each.
+            capacity: 1 << 12,
+            // Four times `visit_threshold`: by then the blend already
+            // trusts the subgame ~80% on well-visited edges.
+            replace: 1 << 20,
+            // Half a budget on top of the cached visits still beats a
+            // cold solve's total.
+            pool: 0.5,
+        }
+    }
+}
+
+/// Opponent-model parameters.
+///
+/// An exploiting bot counts the opponent's actions at each public node and
+/// shrinks them toward the blueprint's frequencies there with a Dirichlet
+/// prior of total mass `strength`: the modeled policy is
+/// `(strength · blueprint + counts) / (strength + n)`. Nodes seen fewer
+/// than `support` times are left to the solver. A larger `strength` keeps
+/// the model, and so hero's deviation from equilibrium, closer to the
+/// blueprint for longer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExploitHyperParams {
+    strength: f32,
+    support: u32,
+}
+
+impl ExploitHyperParams {
+    pub fn new(strength: f32, support: u32) -> Self {
+        Self { strength, support }
+    }
+
+    /// Pseudo-observations the blueprint prior is worth at every node.
+    pub fn strength(&self) -> f32 {
+        self.strength.max(0.0)
+    }
+
+    /// Observations a node needs before the opponent is pinned there.
+    pub fn support(&self) -> u32 {
+        self.support.max(1)
+    }
+}
+
+impl Default for ExploitHyperParams {
+    fn default() -> Self {
+        Self {
+            // Eight observations to move a node halfway off the blueprint:
+            // a few orbits of a session, not a single hand.
+            strength: 8.0,
+            // Below four the posterior is still mostly prior, and pinning
+            // it only costs the solver the node's own re-solve.
+            support: 4,
+        }
+    }
+}
@@ -16,6 +16,8 @@
 //!
 //! - [`SubGameEncoder`] — Tags info sets with world AND detects frontier chance nodes
 //! - [`SubGameSolver`] — Combined solver using both safety and depth-limiting
+//! - [`GadgetSolver`] — Resolve / Maxmargin / Reach gadgets in place of the world partition
+//! - [`Splice`] — Blueprint overlaid with resolved policies, for full-game exploitability
 //!
 //! Types from `worldview` and `horizon` are re-exported for convenience so
 //! that downstream callers only need `use subgame::*`.
@@ -26,14 +28,18 @@
 //! Science, 365(6456), 885-890.
 
 mod depth;
+mod gadget;
 mod world;
 pub use depth::*;
+pub use gadget::*;
 pub use world::*;
 
 mod encoder;
 mod hyperparams;
 mod solver;
+mod splice;
 
 pub use encoder::*;
 pub use hyperparams::*;
 pub use solver::*;
+pub use splice::*;
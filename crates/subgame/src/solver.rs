@@ -23,7 +23,7 @@ use pokerkit::Probability;
 /// for safety-only, or [`crate::depth::DepthSolver`] for depth-limiting only.
 pub struct SubGameSolver<'blueprint, const W: usize, const L: usize, N, I, Y>
 where
-    N: DepthSampler<L, Blueprint: CfrSolution<I = I>>,
+    N: DepthSampler<L, Blueprint: CfrFlow<I = I>>,
     N: WorldRestrict<W, I = I>,
     I: CfrInfo<E = N::E, T = N::T, Y = Y>,
     Y: CfrSecret,
@@ -40,7 +40,7 @@ where
 
 impl<'blueprint, const W: usize, const L: usize, N, I, Y> SubGameSolver<'blueprint, W, L, N, I, Y>
 where
-    N: WorldRestrict<W, I = I> + DepthSampler<L, Blueprint: CfrSolution<I = I>>,
+    N: WorldRestrict<W, I = I> + DepthSampler<L, Blueprint: CfrFlow<I = I>>,
     I: CfrInfo<E = N::E, T = N::T, Y = Y>,
     Y: CfrSecret,
 {
@@ -87,6 +87,13 @@ where
         }
     }
 
+    /// Pins the opponent at the lock's nodes; see [`Lock`]. Pins are
+    /// lifted onto the depth-limited game's nodes.
+    pub fn with_lock(mut self, lock: Lock<I::X, N::E>) -> Self {
+        self.profile = self.profile.with_lock(DepthView::<N::Blueprint, L>::lift(lock));
+        self
+    }
+
     pub fn into_profile(self) -> WorldProfile<'blueprint, DepthView<'blueprint, N::Blueprint, L>> {
         self.profile
     }
@@ -102,7 +109,7 @@ where
 
 impl<'blueprint, const W: usize, const L: usize, N, I, Y> Solver for SubGameSolver<'blueprint, W, L, N, I, Y>
 where
-    N: WorldRestrict<W, I = I> + DepthSampler<L, Blueprint: CfrSolution<I = I>> + Sync,
+    N: WorldRestrict<W, I = I> + DepthSampler<L, Blueprint: CfrFlow<I = I>> + Sync,
     N::Blueprint: Sync,
     I: CfrInfo<E = N::E, T = N::T, Y = Y>,
     Y: CfrSecret,
@@ -153,8 +160,11 @@ where
         self.entry = DepthGame::<_, L>::new(inner, self.internal, self.origin);
         let updates = self.batch();
         let n = updates.len();
+        let frozen = self.profile.frozen(self.external);
         for ref update in updates {
-            self.update_regret(update);
+            if !frozen {
+                self.update_regret(update);
+            }
             self.update_weight(update);
             self.update_payoff(update);
             self.update_visits(update);
@@ -173,7 +183,7 @@ where
 
 impl<const W: usize, const L: usize, N, I, Y> Harvest for SubGameSolver<'_, W, L, N, I, Y>
 where
-    N: WorldRestrict<W, I = I> + DepthSampler<L, Blueprint: CfrSolution<I = I>> + Sync,
+    N: WorldRestrict<W, I = I> + DepthSampler<L, Blueprint: CfrFlow<I = I>> + Sync,
     N::Blueprint: Sync,
     I: CfrInfo<E = N::E, T = N::T, Y = Y>,
     Y: CfrSecret,
@@ -0,0 +1,120 @@
+//! Blueprint with resolved subgame policies spliced in.
+//!
+//! A resolve only changes play at the info sets it was run for. To judge
+//! whether it helped or hurt, the resolved policies are overlaid on the
+//! blueprint and the combined strategy is measured on the full game, so
+//! that every solver (world partition or gadget) is scored the same way.
+use mccfr::*;
+use pokerkit::*;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+/// Read-only profile that serves resolved policies where present and
+/// falls through to the blueprint everywhere else.
+pub struct Splice<'blueprint, P>
+where
+    P: CfrFlow,
+{
+    blueprint: &'blueprint P,
+    resolved: HashMap<P::I, BTreeMap<P::E, Probability>>,
+}
+
+impl<'blueprint, P> Splice<'blueprint, P>
+where
+    P: CfrFlow,
+{
+    pub fn new(blueprint: &'blueprint P) -> Self {
+        Self {
+            blueprint,
+            resolved: HashMap::new(),
+        }
+    }
+
+    /// Overrides the averaged policy at `info`.
+    pub fn splice(&mut self, info: P::I, policy: BTreeMap<P::E, Probability>) {
+        self.resolved.insert(info, policy);
+    }
+
+    /// Splices the refined policy a solver harvested at `info`.
+    pub fn harvest<S>(&mut self, solver: &S, info: P::I)
+    where
+        S: Harvest<Base = P::I, Edge = P::E>,
+    {
+        self.splice(info, solver.harvest(info).refined);
+    }
+
+    /// Full-game exploitability of the spliced strategy.
+    pub fn measure<N>(&self, encoder: &N) -> Utility
+    where
+        N: CfrEncoder<T = P::T, E = P::E, G = P::G, I = P::I>,
+    {
+        self.exploitability(
+            TreeBuilder::<_, _, _, _, _, _, VanillaSampling>::new(encoder, self, P::G::exploitability_root(), 0)
+                .build(),
+        )
+    }
+}
+
+impl<P> CfrRule for Splice<'_, P>
+where
+    P: CfrFlow,
+{
+    type T = P::T;
+    type E = P::E;
+    type G = P::G;
+    type I = P::I;
+}
+
+impl<P> RefProf for Splice<'_, P>
+where
+    P: CfrFlow,
+{
+    fn t(&self) -> usize {
+        self.blueprint.t()
+    }
+
+    fn sum_regret(&self) -> Utility {
+        self.blueprint.sum_regret()
+    }
+
+    fn cum_weight(&self, info: &Self::I, edge: &Self::E) -> Probability {
+        self.resolved
+            .get(info)
+            .map_or_else(|| self.blueprint.cum_weight(info, edge), |p| p.get(edge).copied().unwrap_or(0.0))
+    }
+
+    fn cum_regret(&self, info: &Self::I, edge: &Self::E) -> Utility {
+        self.blueprint.cum_regret(info, edge)
+    }
+
+    fn cum_payoff(&self, info: &Self::I, edge: &Self::E) -> Utility {
+        self.blueprint.cum_payoff(info, edge)
+    }
+
+    fn cum_visits(&self, info: &Self::I, edge: &Self::E) -> u32 {
+        self.blueprint.cum_visits(info, edge)
+    }
+}
+
+impl<P> CfrSampling for Splice<'_, P>
+where
+    P: CfrFlow,
+{
+    fn increment(&mut self) {}
+
+    fn walker(&self) -> Self::T {
+        CfrSampling::walker(self.blueprint)
+    }
+
+    fn temperature(&self) -> Entropy {
+        CfrSampling::temperature(self.blueprint)
+    }
+
+    fn smoothing(&self) -> Energy {
+        CfrSampling::smoothing(self.blueprint)
+    }
+
+    fn curiosity(&self) -> Probability {
+        CfrSampling::curiosity(self.blueprint)
+    }
+}
@@ -0,0 +1,168 @@
+//! Node locking: opponent policies pinned at chosen public nodes.
+//!
+//! A [`Lock`] maps public nodes to user-supplied action frequencies.
+//! [`WorldProfile`] reads a pinned node's policy from the lock instead of
+//! its own regrets, so CFR re-solves everything else against it. Public
+//! states may repeat across betting rounds, so each pin names its round
+//! (see [`CfrSecret::round`]). A [`Pin::Policy`] fixes the same
+//! frequencies for every private state and may be partial: the edges left
+//! out share whatever mass remains in proportion to the blueprint, so
+//! "never folds" is `{fold: 0}`. A [`Pin::Tilt`] instead reweights each
+//! private state's blueprint policy, keeping how it plays different hands
+//! apart.
+use mccfr::*;
+use pokerkit::*;
+use std::collections::BTreeMap;
+
+/// What the rest of the opponent's strategy does around the pins.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Response {
+    /// Unpinned opponent nodes keep re-solving: a restricted equilibrium.
+    #[default]
+    Restricted,
+    /// Unpinned opponent nodes stay at the blueprint, so hero's solve is
+    /// a best response to the locked opponent.
+    Best,
+}
+
+/// How a pinned node's policy is fixed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pin<E>
+where
+    E: CfrEdge,
+{
+    /// The same frequencies at every private state.
+    Policy(BTreeMap<E, Probability>),
+    /// Each private state's blueprint policy, multiplied edge by edge by
+    /// these weights and renormalized. Edges left out keep weight one.
+    Tilt(BTreeMap<E, Probability>),
+}
+
+impl<E> Pin<E>
+where
+    E: CfrEdge,
+{
+    /// The pin's frequencies or weights, by edge.
+    pub fn values(&self) -> &BTreeMap<E, Probability> {
+        match self {
+            Self::Policy(values) | Self::Tilt(values) => values,
+        }
+    }
+
+    /// The same pin over edges relabeled by `f`.
+    pub fn map<F>(&self, f: impl Fn(E) -> F) -> Pin<F>
+    where
+        F: CfrEdge,
+    {
+        let values = self.values().iter().map(|(e, p)| (f(*e), *p)).collect();
+        match self {
+            Self::Policy(_) => Pin::Policy(values),
+            Self::Tilt(_) => Pin::Tilt(values),
+        }
+    }
+}
+
+/// Pinned opponent policies keyed by betting round and public node.
+#[derive(Debug, Clone)]
+pub struct Lock<X, E>
+where
+    X: CfrPublic<E = E>,
+    E: CfrEdge,
+{
+    pins: BTreeMap<(usize, X), Pin<E>>,
+    response: Response,
+}
+
+impl<X, E> Default for Lock<X, E>
+where
+    X: CfrPublic<E = E>,
+    E: CfrEdge,
+{
+    fn default() -> Self {
+        Self::new(Response::default())
+    }
+}
+
+impl<X, E> Lock<X, E>
+where
+    X: CfrPublic<E = E>,
+    E: CfrEdge,
+{
+    pub fn new(response: Response) -> Self {
+        Self {
+            pins: BTreeMap::new(),
+            response,
+        }
+    }
+
+    /// Pins `policy` at `public` on `round`, replacing any earlier pin
+    /// there. Frequencies are clamped to `[0, 1]`.
+    pub fn pin(self, round: usize, public: X, policy: impl IntoIterator<Item = (E, Probability)>) -> Self {
+        let policy = policy.into_iter().map(|(e, p)| (e, p.clamp(0., 1.))).collect();
+        self.place(round, public, Pin::Policy(policy))
+    }
+
+    /// Tilts the blueprint by `weights` at `public` on `round`, replacing
+    /// any earlier pin there. Negative weights are clamped to zero.
+    pub fn tilt(self, round: usize, public: X, weights: impl IntoIterator<Item = (E, Probability)>) -> Self {
+        let weights = weights.into_iter().map(|(e, w)| (e, w.max(0.))).collect();
+        self.place(round, public, Pin::Tilt(weights))
+    }
+
+    /// Places `pin` at `public` on `round`, replacing any earlier pin there.
+    pub fn place(mut self, round: usize, public: X, pin: Pin<E>) -> Self {
+        self.pins.insert((round, public), pin);
+        self
+    }
+
+    pub fn response(&self) -> Response {
+        self.response
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pins.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pins.len()
+    }
+
+    /// Whether `info`'s public node is pinned on its round.
+    pub fn is_pinned<I>(&self, info: &I) -> bool
+    where
+        I: CfrInfo<X = X, E = E>,
+    {
+        self.pins.contains_key(&(info.secret().round(), info.public()))
+    }
+
+    pub fn pins(&self) -> impl Iterator<Item = (usize, &X, &Pin<E>)> {
+        self.pins.iter().map(|((round, public), pin)| (*round, public, pin))
+    }
+
+    /// Locked probability of `edge` at `info`, or `None` if its public
+    /// node isn't pinned on its round. `prior` is the blueprint at `info`:
+    /// a policy pin's omitted edges split the remaining mass by it, and a
+    /// tilt reweights it.
+    pub fn density<I, F>(&self, info: &I, edge: &E, prior: F) -> Option<Probability>
+    where
+        I: CfrInfo<X = X, E = E>,
+        F: Fn(&E) -> Probability,
+    {
+        let pinned = match self.pins.get(&(info.secret().round(), info.public()))? {
+            Pin::Policy(pinned) => pinned,
+            Pin::Tilt(weights) => {
+                let tilted = |e: &E| prior(e) * weights.get(e).copied().unwrap_or(1.);
+                let total = info.choices().map(|ref e| tilted(e)).sum::<Probability>();
+                return Some(tilted(edge) / total.max(EPSILON));
+            }
+        };
+        if let Some(p) = pinned.get(edge) {
+            return Some(*p);
+        }
+        let (fixed, free) = info.choices().fold((0., 0.), |(fixed, free), e| match pinned.get(&e) {
+            Some(p) => (fixed + p, free),
+            None => (fixed, free + prior(&e)),
+        });
+        Some((1. - fixed).max(0.) * prior(edge) / free.max(EPSILON))
+    }
+}
@@ -21,6 +21,7 @@
 //! - `belief` — `Belief`
 //! - `world` — `World` (primitive index)
 //! - `info` — `WorldInfo`
+//! - `lock` — `Lock`, `Response`
 //! - `profile` — `WorldProfile`
 //! - `encoder` — `WorldEncoder`
 //! - `partition` — `Partition` trait
@@ -37,6 +38,7 @@
 mod belief;
 mod encoder;
 mod info;
+mod lock;
 mod partition;
 mod profile;
 mod recall;
@@ -48,6 +50,7 @@ mod world;
 pub use belief::*;
 pub use encoder::*;
 pub use info::*;
+pub use lock::*;
 pub use partition::*;
 pub use profile::*;
 pub use recall::*;
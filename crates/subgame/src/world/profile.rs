@@ -11,6 +11,10 @@
 //! mechanism (different worlds = different card deals = different game
 //! trees), NOT from policy perturbation. This is the safe subgame
 //! solving approach from Brown & Sandholm 2017.
+//!
+//! A [`Lock`] overrides both in the policy: pinned public nodes play the
+//! lock's frequencies whatever the local or blueprint values say, while
+//! their accumulators keep their own values.
 use super::*;
 use mccfr::*;
 use pokerkit::*;
@@ -31,6 +35,7 @@ where
 {
     global: &'blueprint P,
     local: HashMap<WorldInfo<P::I>, HashMap<P::E, Encounter>>,
+    lock: Lock<<P::I as CfrInfo>::X, P::E>,
     t: usize,
 }
 
@@ -42,13 +47,40 @@ where
         Self {
             local: HashMap::new(),
             global: blueprint,
+            lock: Lock::default(),
             t: 0,
         }
     }
 
+    /// Pins the opponent's policy at the lock's public nodes.
+    pub fn with_lock(mut self, lock: Lock<<P::I as CfrInfo>::X, P::E>) -> Self {
+        self.lock = lock;
+        self
+    }
+
     pub fn blueprint(&self) -> &P {
         self.global
     }
+
+    pub fn lock(&self) -> &Lock<<P::I as CfrInfo>::X, P::E> {
+        &self.lock
+    }
+
+    /// Whether the current walker's regrets stay put: under
+    /// [`Response::Best`] the `external` player keeps its blueprint
+    /// regrets everywhere it isn't pinned.
+    pub fn frozen(&self, external: P::T) -> bool
+    where
+        P: CfrSampling,
+    {
+        self.lock.response() == Response::Best && self.walker() == external
+    }
+
+    /// The lock's probability for `edge` at `info`, if its node is pinned.
+    fn locked(&self, info: &WorldInfo<P::I>, edge: &P::E) -> Option<Probability> {
+        let inner = info.inner();
+        self.lock.density(&inner, edge, |e| self.global.weight(&inner, e))
+    }
 }
 
 impl<P> CfrRule for WorldProfile<'_, P>
@@ -145,10 +177,26 @@ where
             .map_or_else(|| self.global.cum_visits(&info.inner(), edge), |e| e.visits)
     }
 
+    /// Regret-matching numerator: the lock's frequency at pinned nodes,
+    /// so the current policy and every reach through it play the pin.
+    fn regret(&self, info: &Self::I, edge: &Self::E) -> Utility {
+        self.locked(info, edge)
+            .unwrap_or_else(|| self.cum_regret(info, edge))
+            .max(EPSILON)
+    }
+
+    /// Average-policy numerator, locked the same way as [`Self::regret`].
+    fn weight(&self, info: &Self::I, edge: &Self::E) -> Probability {
+        self.locked(info, edge)
+            .unwrap_or_else(|| self.cum_weight(info, edge))
+            .max(EPSILON)
+    }
+
     fn sum_regret(&self) -> Utility {
         self.local
-            .values()
-            .flat_map(|edges| edges.values())
+            .iter()
+            .filter(|(info, _)| !self.lock.is_pinned(*info))
+            .flat_map(|(_, edges)| edges.values())
             .map(|e| e.regret.max(0.))
             .sum::<Utility>()
             / self.t.max(1) as Utility
@@ -19,7 +19,7 @@ use pokerkit::Probability;
 /// full (non-depth-limited) subgame tree with per-world info sets.
 pub struct WorldSolver<'blueprint, const W: usize, P, N, I, Y>
 where
-    P: CfrSolution<I = I>,
+    P: CfrFlow<I = I>,
     N: WorldRestrict<W, T = P::T, E = P::E, G = P::G, I = I>,
     I: CfrInfo<E = P::E, T = P::T, Y = Y>,
     Y: CfrSecret,
@@ -34,7 +34,7 @@ where
 
 impl<'blueprint, const W: usize, P, N, I, Y> WorldSolver<'blueprint, W, P, N, I, Y>
 where
-    P: CfrSolution<I = I>,
+    P: CfrFlow<I = I>,
     N: WorldRestrict<W, T = P::T, E = P::E, G = P::G, I = I>,
     I: CfrInfo<E = P::E, T = P::T, Y = Y>,
     Y: CfrSecret,
@@ -59,6 +59,12 @@ where
         }
     }
 
+    /// Pins the opponent at the lock's nodes; see [`Lock`].
+    pub fn with_lock(mut self, lock: Lock<I::X, P::E>) -> Self {
+        self.profile = self.profile.with_lock(lock);
+        self
+    }
+
     pub fn into_profile(self) -> WorldProfile<'blueprint, P> {
         self.profile
     }
@@ -74,7 +80,7 @@ where
 
 impl<'blueprint, const W: usize, P, N, I, Y> Solver for WorldSolver<'blueprint, W, P, N, I, Y>
 where
-    P: CfrSolution<I = I> + Sync,
+    P: CfrFlow<I = I> + Sync,
     N: WorldRestrict<W, T = P::T, E = P::E, G = P::G, I = I> + Sync,
     I: CfrInfo<E = P::E, T = P::T, Y = Y>,
     Y: CfrSecret,
@@ -124,8 +130,11 @@ where
             .restrict(self.external, world, &self.belief, &self.recall.game());
         let updates = self.batch();
         let n = updates.len();
+        let frozen = self.profile.frozen(self.external);
         for ref update in updates {
-            self.update_regret(update);
+            if !frozen {
+                self.update_regret(update);
+            }
             self.update_weight(update);
             self.update_payoff(update);
             self.update_visits(update);
@@ -144,7 +153,7 @@ where
 
 impl<const W: usize, P, N, I, Y> Harvest for WorldSolver<'_, W, P, N, I, Y>
 where
-    P: CfrSolution<I = I> + Sync,
+    P: CfrFlow<I = I> + Sync,
     N: WorldRestrict<W, T = P::T, E = P::E, G = P::G, I = I> + Sync,
     I: CfrInfo<E = P::E, T = P::T, Y = Y>,
     Y: CfrSecret,
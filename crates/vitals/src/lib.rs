@@ -157,6 +157,8 @@ fn histogram_views() -> Vec<Box<dyn View>> {
         // SubgameHyperParams::timeout_ms (default 5000). 2^0 (1ms) → 2^14
         // (~16s).
         log2_view("rbp.subgame.decision_ms", 0, 14),
+        // Pondered budget — at most one decision's timeout_ms; same range.
+        log2_view("rbp.subgame.ponder_saved_ms", 0, 14),
         // K-means iteration wall-clock — single iter typically <60s, full
         // street caps ~30min. 2^7 (128ms) → 2^21 (~2M = ~35min).
         log2_view("rbp.kmeans.iteration_ms", 7, 21),
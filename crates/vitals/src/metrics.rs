@@ -40,6 +40,8 @@ pub struct Handles {
     pub kmeans_early_terminated:    Counter   <u64>,
     pub kmeans_drift_max:           Gauge     <f64>,
     pub kmeans_reassignment:        Gauge     <f64>,
+    pub kmeans_inertia:             Gauge     <f64>,
+    pub kmeans_inertia_delta:       Gauge     <f64>,
     pub kmeans_iteration_ms:        Histogram <f64>,
     pub kmeans_phase_ms:            Histogram <f64>,
     pub kmeans_cluster_size:        Histogram <u64>,
@@ -50,6 +52,9 @@ pub struct Handles {
     pub subgame_iterations:         Histogram <u64>,
     pub subgame_relative_regret:    Histogram <f64>,
     pub subgame_policy_deviation:   Histogram <f64>,
+    pub subgame_ponder_saved_ms:    Histogram <f64>,
+    pub subgame_cache_hits:         Counter   <u64>,
+    pub subgame_cache_misses:       Counter   <u64>,
     // ── HTTP server ─────────────────────────────────────────────────────
     pub http_requests:              Counter   <u64>,
     pub http_duration_ms:           Histogram <f64>,
@@ -162,6 +167,20 @@ fn build(meter: &Meter) -> Handles {
                  Complementary convergence signal to drift_max (centroid motion).",
             )
             .build(),
+        kmeans_inertia: meter
+            .f64_gauge("rbp.kmeans.inertia")
+            .with_description(
+                "Mean within-cluster EMD at convergence, labeled by street and \
+                 centroid rule (mean, barycenter).",
+            )
+            .build(),
+        kmeans_inertia_delta: meter
+            .f64_gauge("rbp.kmeans.inertia_delta")
+            .with_description(
+                "Barycenter minus arithmetic-mean within-cluster EMD over the same \
+                 final assignments. Negative = barycenter centroids fit tighter.",
+            )
+            .build(),
         kmeans_drift_dist: meter
             .f64_histogram("rbp.kmeans.drift_dist")
             .with_description(
@@ -194,6 +213,24 @@ fn build(meter: &Meter) -> Handles {
                  Zero = subgame agreed with blueprint; 2 = fully disjoint.",
             )
             .build(),
+        subgame_ponder_saved_ms: meter
+            .f64_histogram("rbp.subgame.ponder_saved_ms")
+            .with_description(
+                "Solve budget already spent pondering when the decision arrived. \
+                 Only recorded when a pondered line matched the real action.",
+            )
+            .build(),
+        subgame_cache_hits: meter
+            .u64_counter("rbp.subgame.cache_hits")
+            .with_description(
+                "Subgame decisions served from the solution cache. `outcome` = \
+                 replace (no solve) or pool (shorter solve merged into the entry).",
+            )
+            .build(),
+        subgame_cache_misses: meter
+            .u64_counter("rbp.subgame.cache_misses")
+            .with_description("Subgame decisions with no cached solution for their spot")
+            .build(),
         http_requests: meter
             .u64_counter("rbp.http.requests")
             .with_description("HTTP requests served")